@@ -17,6 +17,23 @@ use crate::{custom_emoji::CustomEmoji, AccountId, RoleId};
 ///
 /// See also [the API reference](https://docs.joinmastodon.org/entities/Account/)
 ///
+/// Accounts can be built with [`Account::builder`], e.g. for tests, with the
+/// fields which aren't set taking their [`Default`] values.
+///
+/// ```
+/// use mastodon_async_entities::{account::Account, AccountId};
+///
+/// let account = Account::builder()
+///     .id("1")
+///     .username("alice")
+///     .acct("alice")
+///     .bot(true)
+///     .build()
+///     .unwrap();
+/// assert_eq!(account.id, AccountId::new("1"));
+/// assert_eq!(account.followers_count, 0);
+/// ```
+///
 /// ## Example
 /// ```rust
 /// use mastodon_async_entities::account::Account;
@@ -84,7 +101,14 @@ use crate::{custom_emoji::CustomEmoji, AccountId, RoleId};
 /// let subject: Account = serde_json::from_str(example).unwrap();
 /// assert_eq!(subject.username, "noiob")
 /// ```
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Builder, Deserialize, Serialize, PartialEq)]
+#[builder(
+    name = "AccountEntityBuilder",
+    default,
+    setter(into),
+    build_fn(error = "crate::error::Error")
+)]
+#[non_exhaustive]
 pub struct Account {
     /// The Webfinger account URI. Equal to [`Account::username`] for local users, or
     /// `username@domain` for remote users. See also [the API reference](https://docs.joinmastodon.org/entities/Account/#acct)
@@ -157,6 +181,86 @@ pub struct Account {
     pub url: Url,
     /// The username of the account, not including domain. See also [the API reference](https://docs.joinmastodon.org/entities/Account/#username)
     pub username: String,
+    /// The highlighted roles of the account, shown as badges on its profile. See also [the API reference](https://docs.joinmastodon.org/entities/Account/#roles)
+    #[serde(default)]
+    pub roles: Vec<AccountRole>,
+    /// The extensions sent by Pleroma and Akkoma, such as the other accounts
+    /// of the same person. Requires the `pleroma` feature.
+    #[cfg(feature = "pleroma")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pleroma: Option<crate::pleroma::Account>,
+    /// Any other fields sent with the account, e.g. `enable_rss` from
+    /// GoToSocial or the `akkoma` object from Akkoma. Requires the
+    /// `extra-fields` feature.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+impl Default for Account {
+    /// An account with an empty ID and names, created at the Unix epoch,
+    /// whose URLs point to `https://mastodon.example/`.
+    fn default() -> Self {
+        let url = crate::helpers::placeholder_url();
+        Self {
+            acct: String::new(),
+            avatar: url.clone(),
+            avatar_static: url.clone(),
+            bot: false,
+            created_at: OffsetDateTime::UNIX_EPOCH,
+            discoverable: None,
+            display_name: String::new(),
+            emojis: vec![],
+            fields: vec![],
+            followers_count: 0,
+            following_count: 0,
+            group: false,
+            header: url.clone(),
+            header_static: url.clone(),
+            id: AccountId::new(""),
+            last_status_at: None,
+            limited: false,
+            locked: false,
+            moved: None,
+            no_index: None,
+            note: String::new(),
+            source: None,
+            statuses_count: 0,
+            suspended: false,
+            url,
+            username: String::new(),
+            roles: vec![],
+            #[cfg(feature = "pleroma")]
+            pleroma: None,
+            #[cfg(feature = "extra-fields")]
+            extra: Default::default(),
+        }
+    }
+}
+
+impl Account {
+    /// Build an account, starting from the [`Default`] one.
+    pub fn builder() -> AccountEntityBuilder {
+        AccountEntityBuilder::default()
+    }
+
+    /// The account's bio as plain text, e.g. for bots reading the
+    /// instructions people leave in theirs. See
+    /// [`html::to_plain_text`](crate::html::to_plain_text).
+    pub fn plain_note(&self) -> String {
+        crate::html::to_plain_text(&self.note)
+    }
+
+    /// The account its user moved to last, following [`moved`](Self::moved)
+    /// through accounts which were moved again, or `None` if the account
+    /// wasn't moved.
+    pub fn moved_to(&self) -> Option<&Account> {
+        let mut moved = self.moved.as_deref()?;
+        while let Some(next) = moved.moved.as_deref() {
+            moved = next;
+        }
+        Some(moved)
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -168,6 +272,17 @@ pub struct CredentialAccount {
     /// methods that [verify credentials](https://docs.joinmastodon.org/methods/accounts/#verify_credentials)
     /// and [update credentials](https://docs.joinmastodon.org/methods/accounts/#update_credentials). See also [the API reference](https://docs.joinmastodon.org/entities/Account/#source)
     pub source: Source,
+    /// The role assigned to the currently authorized user. See also [the API reference](https://docs.joinmastodon.org/entities/Account/#role)
+    #[serde(default)]
+    pub role: Option<Role>,
+}
+
+impl std::ops::Deref for CredentialAccount {
+    type Target = Account;
+
+    fn deref(&self) -> &Self::Target {
+        &self.account
+    }
 }
 
 /// A single name: value pair from a user's profile
@@ -336,6 +451,7 @@ impl CredentialsBuilder {
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
 pub struct Role {
     /// The ID of the Role in the database See also [the API reference](https://docs.joinmastodon.org/entities/Role/#id)
+    #[serde(deserialize_with = "role_id")]
     pub id: RoleId,
     /// The name of the role. See also [the API reference](https://docs.joinmastodon.org/entities/Role/#name)
     pub name: String,
@@ -343,21 +459,104 @@ pub struct Role {
     /// string will be empty. See also [the API reference](https://docs.joinmastodon.org/entities/Role/#color)
     pub color: Color,
     /// An index for the role’s position. The higher the position, the more
-    /// priority the role has over other roles. See also [the API reference](https://docs.joinmastodon.org/entities/Role/#position)
-    pub position: i64,
+    /// priority the role has over other roles. Not included when the role is
+    /// part of an account's credentials. See also [the API reference](https://docs.joinmastodon.org/entities/Role/#position)
+    #[serde(default)]
+    pub position: Option<i64>,
     /// A bitmask that represents the sum of all permissions granted to the
     /// role. See also [the API reference](https://docs.joinmastodon.org/entities/Role/#permissions) and
     /// <https://docs.joinmastodon.org/entities/Role/#permission-flags>
-    #[serde(with = "role_permissions_serde::numeric_representation")]
+    #[serde(
+        serialize_with = "role_permissions_serde::numeric_representation::serialize",
+        deserialize_with = "role_permissions"
+    )]
     pub permissions: RolePermissions,
     /// Whether the role is publicly visible as a badge on user profiles. See also [the API reference](https://docs.joinmastodon.org/entities/Role/#highlighted)
     pub highlighted: bool,
-    /// The date that the role was created See also [the API reference](https://docs.joinmastodon.org/entities/Role/#created_at)
-    #[serde(with = "iso8601")]
-    pub created_at: OffsetDateTime,
-    /// The date that the role was updated. See also [the API reference](https://docs.joinmastodon.org/entities/Role/#updated_at)
-    #[serde(with = "iso8601")]
-    pub updated_at: OffsetDateTime,
+    /// The date that the role was created. Not included when the role is
+    /// part of an account's credentials. See also [the API reference](https://docs.joinmastodon.org/entities/Role/#created_at)
+    #[serde(default, with = "iso8601::option")]
+    pub created_at: Option<OffsetDateTime>,
+    /// The date that the role was updated. Not included when the role is
+    /// part of an account's credentials. See also [the API reference](https://docs.joinmastodon.org/entities/Role/#updated_at)
+    #[serde(default, with = "iso8601::option")]
+    pub updated_at: Option<OffsetDateTime>,
+}
+
+impl Role {
+    /// Whether this role grants all of the given permissions, either directly
+    /// or because it grants [`RolePermissions::ADMINISTRATOR`], which
+    /// bypasses all permission checks.
+    ///
+    /// ```
+    /// use mastodon_async_entities::account::{Role, RolePermissions};
+    /// let example = r##"{
+    ///     "id": "2",
+    ///     "name": "Moderator",
+    ///     "color": "",
+    ///     "permissions": "1040",
+    ///     "highlighted": true
+    /// }"##;
+    /// let moderator: Role = serde_json::from_str(example).unwrap();
+    /// assert!(moderator.permissions.has_manage_reports());
+    /// // MANAGE_REPORTS | MANAGE_USERS
+    /// assert!(moderator.grants(RolePermissions::from(0x410)));
+    /// // MANAGE_SETTINGS
+    /// assert!(!moderator.grants(RolePermissions::from(0x40)));
+    /// ```
+    pub fn grants(&self, permissions: RolePermissions) -> bool {
+        self.permissions.has_administrator() || self.permissions & permissions == permissions
+    }
+}
+
+/// A role shown as a badge on an account's profile. Only highlighted roles
+/// are included, and only with the details needed to display them.
+///
+/// See also [the API reference](https://docs.joinmastodon.org/entities/Account/#roles)
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct AccountRole {
+    /// The ID of the Role in the database
+    #[serde(deserialize_with = "role_id")]
+    pub id: RoleId,
+    /// The name of the role.
+    pub name: String,
+    /// The hex code assigned to this role, or `Unspecified` if none is
+    /// assigned.
+    pub color: Color,
+}
+
+/// Deserialize a [`RoleId`], which the API sends as either a number or a
+/// string depending on the server version.
+fn role_id<'de, D: Deserializer<'de>>(val: D) -> ::std::result::Result<RoleId, D::Error> {
+    number_or_string::<_, i64>(val).map(RoleId::new)
+}
+
+/// Deserialize [`RolePermissions`], which the API sends as either a number
+/// or a string depending on the server version.
+fn role_permissions<'de, D: Deserializer<'de>>(
+    val: D,
+) -> ::std::result::Result<RolePermissions, D::Error> {
+    number_or_string::<_, u64>(val).map(RolePermissions::from)
+}
+
+fn number_or_string<'de, D, N>(val: D) -> ::std::result::Result<N, D::Error>
+where
+    D: Deserializer<'de>,
+    N: FromStr + Deserialize<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum NumberOrString<N> {
+        Number(N),
+        Str(String),
+    }
+
+    match NumberOrString::deserialize(val)? {
+        NumberOrString::Number(n) => Ok(n),
+        NumberOrString::Str(s) => s
+            .parse()
+            .map_err(|_| de::Error::invalid_value(Unexpected::Str(&s), &"a number")),
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, is_enum_variant)]
@@ -420,12 +619,12 @@ impl<'de> Deserialize<'de> for Color {
     }
 }
 
-impl ToString for Color {
-    fn to_string(&self) -> String {
+impl std::fmt::Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if let Color::Value { red, green, blue } = self {
-            format!("#{red:x}{green:x}{blue:x}")
+            write!(f, "#{red:x}{green:x}{blue:x}")
         } else {
-            String::new()
+            Ok(())
         }
     }
 }
@@ -499,6 +698,28 @@ mod fields_attributes_ser {
     }
 }
 
+/// The minimal subset of an account returned alongside grouped
+/// notifications when `expand_accounts=partial_avatars` is requested.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/methods/grouped_notifications/#PartialAccountWithAvatar)
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PartialAccountWithAvatar {
+    /// The account id.
+    pub id: AccountId,
+    /// The Webfinger account URI.
+    pub acct: String,
+    /// The location of the user's profile page.
+    pub url: Url,
+    /// An image icon that is shown next to statuses and in the profile.
+    pub avatar: Url,
+    /// A static version of the avatar.
+    pub avatar_static: Url,
+    /// Whether the account manually approves follow requests.
+    pub locked: bool,
+    /// Indicates that the account may perform automated actions.
+    pub bot: bool,
+}
+
 /// Represents a subset of your follows who also follow some other user.
 ///
 /// See also [the API documentation](https://docs.joinmastodon.org/entities/FamiliarFollowers/)
@@ -512,18 +733,42 @@ pub struct FamiliarFollowers {
 
 /// Represents a suggested account to follow and an associated reason for the
 /// suggestion.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/entities/Suggestion/)
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct Suggestion {
-    source: SuggestionSource,
-    account: Account,
+    /// The reason this account is being suggested. Deprecated in favour of
+    /// [`sources`](Suggestion::sources).
+    pub source: SuggestionSource,
+    /// All of the reasons this account is being suggested.
+    #[serde(default)]
+    pub sources: Vec<SuggestionSource>,
+    /// The account being suggested.
+    pub account: Account,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, is_enum_variant)]
+/// A reason an account is being suggested.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, is_enum_variant)]
 #[serde(rename_all = "snake_case")]
 pub enum SuggestionSource {
+    /// The account was manually recommended by staff.
     Staff,
+    /// The user has interacted with the account previously.
     PastInteractions,
+    /// The account has many reblogs, favourites, and active local followers.
     Global,
+    /// The account is featured by the instance's administrators. This is
+    /// what [`Staff`](SuggestionSource::Staff) is called in
+    /// [`sources`](Suggestion::sources).
+    Featured,
+    /// The account has many active local followers.
+    MostFollowed,
+    /// The account had many reblogs and favourites within the last 30 days.
+    MostInteractions,
+    /// The account's profile is similar to recently followed accounts.
+    SimilarToRecentlyFollowed,
+    /// The account is followed by accounts the user follows.
+    FriendsOfFriends,
 }
 
 #[cfg(test)]
@@ -590,12 +835,40 @@ mod tests {
         assert_eq!(red, 0xFF);
         assert_eq!(green, 0x38);
         assert_eq!(blue, 0x38);
-        assert_eq!(subject.position, 1000);
+        assert_eq!(subject.position, Some(1000));
         assert!(subject.permissions.has_administrator());
         assert_eq!(subject.permissions, 1);
         assert!(subject.highlighted);
     }
 
+    #[test]
+    fn test_credential_role_example() {
+        let example = r##"{
+            "id": "3",
+            "name": "Moderator",
+            "color": "",
+            "permissions": "1040",
+            "highlighted": true
+        }"##;
+        let subject: Role = serde_json::from_str(example).unwrap();
+        assert_eq!(subject.id, RoleId::new(3));
+        assert!(subject.color.is_unspecified());
+        assert_eq!(subject.position, None);
+        assert_eq!(subject.created_at, None);
+        assert!(subject.grants(RolePermissions::MANAGE_REPORTS));
+        assert!(subject.grants(RolePermissions::MANAGE_USERS));
+        assert!(!subject.grants(RolePermissions::MANAGE_ROLES));
+    }
+
+    #[test]
+    fn test_account_role_example() {
+        let example = r##"{"id": "3", "name": "Moderator", "color": "#ff3838"}"##;
+        let subject: AccountRole = serde_json::from_str(example).unwrap();
+        assert_eq!(subject.id, RoleId::new(3));
+        assert_eq!(subject.name, "Moderator");
+        assert!(!subject.color.is_unspecified());
+    }
+
     #[test]
     fn test_account_example() {
         let example = r#"{
@@ -667,5 +940,83 @@ mod tests {
         assert!(!subject.locked);
         assert!(!subject.bot);
         assert_eq!(subject.followers_count, 547);
+        assert!(subject.moved_to().is_none());
+    }
+
+    #[test]
+    fn test_moved() {
+        let account = |id: &str, moved: serde_json::Value| {
+            serde_json::json!({
+                "id": id,
+                "username": format!("user{id}"),
+                "acct": format!("user{id}@example.com"),
+                "display_name": "",
+                "locked": false,
+                "bot": false,
+                "created_at": "2017-02-08T02:00:53.274Z",
+                "note": "",
+                "url": format!("https://example.com/@user{id}"),
+                "avatar": "https://example.com/avatar.png",
+                "avatar_static": "https://example.com/avatar.png",
+                "header": "https://example.com/header.png",
+                "header_static": "https://example.com/header.png",
+                "followers_count": 0,
+                "following_count": 0,
+                "statuses_count": 0,
+                "last_status_at": null,
+                "emojis": [],
+                "fields": [],
+                "moved": moved,
+                "pleroma": {"also_known_as": ["https://example.com/users/user0"]}
+            })
+        };
+        let example = account("1", account("2", account("3", serde_json::Value::Null)));
+        let subject: Account = serde_json::from_value(example).unwrap();
+        assert_eq!(subject.moved.as_ref().unwrap().id, AccountId::new("2"));
+        assert_eq!(subject.moved_to().unwrap().id, AccountId::new("3"));
+        #[cfg(feature = "pleroma")]
+        assert_eq!(
+            subject.pleroma.unwrap().also_known_as[0].as_str(),
+            "https://example.com/users/user0"
+        );
+    }
+
+    #[test]
+    fn test_suggestion_example() {
+        let example = r#"{
+          "source": "past_interactions",
+          "sources": ["similar_to_recently_followed", "friends_of_friends"],
+          "account": {
+            "id": "784058",
+            "username": "katie",
+            "acct": "katie@example.social",
+            "display_name": "Katie",
+            "locked": false,
+            "bot": false,
+            "created_at": "2019-06-06T00:00:00.000Z",
+            "note": "",
+            "url": "https://example.social/@katie",
+            "avatar": "https://example.social/avatars/original/missing.png",
+            "avatar_static": "https://example.social/avatars/original/missing.png",
+            "header": "https://example.social/headers/original/missing.png",
+            "header_static": "https://example.social/headers/original/missing.png",
+            "followers_count": 1,
+            "following_count": 2,
+            "statuses_count": 3,
+            "last_status_at": "2023-03-18",
+            "emojis": [],
+            "fields": []
+          }
+        }"#;
+        let subject: Suggestion = serde_json::from_str(example).unwrap();
+        assert!(subject.source.is_past_interactions());
+        assert_eq!(
+            subject.sources,
+            vec![
+                SuggestionSource::SimilarToRecentlyFollowed,
+                SuggestionSource::FriendsOfFriends,
+            ]
+        );
+        assert_eq!(subject.account.id, AccountId::new("784058"));
     }
 }
@@ -0,0 +1,119 @@
+//! Module containing the warnings moderators give to accounts.
+
+use derive_is_enum_variant::is_enum_variant;
+use serde::{Deserialize, Serialize};
+use time::{serde::iso8601, OffsetDateTime};
+
+use crate::{account::Account, AccountWarningId, StatusId};
+
+/// A moderation action taken against the user's account, as attached to
+/// `moderation_warning` notifications.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/entities/AccountWarning/)
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct AccountWarning {
+    /// The ID of the warning.
+    pub id: AccountWarningId,
+    /// The action taken against the account.
+    pub action: Action,
+    /// The message the moderator attached to the warning.
+    pub text: String,
+    /// The statuses the action was taken against, if any.
+    #[serde(default)]
+    pub status_ids: Option<Vec<StatusId>>,
+    /// The account the action was taken against.
+    pub target_account: Account,
+    /// The user's appeal against the warning, if they made one.
+    #[serde(default)]
+    pub appeal: Option<Appeal>,
+    /// When the warning was given.
+    #[serde(with = "iso8601")]
+    pub created_at: OffsetDateTime,
+}
+
+/// The action taken against an account with an [`AccountWarning`].
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, is_enum_variant)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    /// No action, only a warning.
+    None,
+    /// The account was disabled, so that it can't log in.
+    Disable,
+    /// Some of the account's statuses were marked as sensitive.
+    MarkStatusesAsSensitive,
+    /// Some of the account's statuses were deleted.
+    DeleteStatuses,
+    /// All of the account's media will be marked as sensitive.
+    Sensitive,
+    /// The account was limited, hiding it from people who don't follow it.
+    Silence,
+    /// The account was suspended.
+    Suspend,
+}
+
+/// An appeal against an [`AccountWarning`].
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/entities/Appeal/)
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Appeal {
+    /// The reason the user gave for appealing.
+    pub text: String,
+    /// Whether the appeal has been decided on.
+    pub state: AppealState,
+}
+
+/// The state of an [`Appeal`].
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, is_enum_variant)]
+#[serde(rename_all = "lowercase")]
+pub enum AppealState {
+    /// The appeal hasn't been decided on yet.
+    Pending,
+    /// The appeal was approved, and the action reverted.
+    Approved,
+    /// The appeal was rejected.
+    Rejected,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize() {
+        let example = r#"{
+            "id": "3",
+            "action": "mark_statuses_as_sensitive",
+            "text": "Please mark your media as sensitive",
+            "status_ids": ["109348238547925380"],
+            "target_account": {
+                "id": "1",
+                "username": "alice",
+                "acct": "alice",
+                "display_name": "Alice",
+                "locked": false,
+                "bot": false,
+                "created_at": "2022-01-01T00:00:00.000Z",
+                "note": "",
+                "url": "https://mastodon.example/@alice",
+                "avatar": "https://mastodon.example/avatar.png",
+                "avatar_static": "https://mastodon.example/avatar.png",
+                "header": "https://mastodon.example/header.png",
+                "header_static": "https://mastodon.example/header.png",
+                "followers_count": 0,
+                "following_count": 0,
+                "statuses_count": 0,
+                "last_status_at": null
+            },
+            "appeal": {"text": "It was a cat", "state": "pending"},
+            "created_at": "2024-09-30T12:00:00.000Z"
+        }"#;
+        let subject: AccountWarning = serde_json::from_str(example).expect("deserialize");
+        assert!(subject.action.is_mark_statuses_as_sensitive());
+        assert_eq!(
+            subject.status_ids,
+            Some(vec![StatusId::new("109348238547925380")])
+        );
+        assert_eq!(subject.target_account.username, "alice");
+        assert!(subject.appeal.expect("appeal").state.is_pending());
+    }
+}
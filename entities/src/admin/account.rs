@@ -19,23 +19,29 @@ pub struct Account {
     /// When the account was first discovered.
     #[serde(with = "iso8601")]
     pub created_at: OffsetDateTime,
-    /// The email address associated with the account.
-    pub email: String,
+    /// The email address associated with the account. Only set for local
+    /// accounts.
+    pub email: Option<String>,
     /// The reason given when requesting an invite (for instances that require
     /// manual approval of registrations)
     pub invite_request: Option<String>,
     /// The IP address last used to login to this account.
     pub ip: Option<String>,
     /// All known IP addresses associated with this account.
+    #[serde(default, deserialize_with = "crate::conversion::null_as_default")]
     pub ips: Vec<Ip>,
-    /// The current role of the account.
-    pub role: Role,
+    /// The current role of the account. Only set for local accounts.
+    pub role: Option<Role>,
     /// Whether the account has confirmed their email address.
     pub confirmed: bool,
     /// Whether the account is currently suspended.
     pub suspended: bool,
     /// Whether the account is currently silenced.
     pub silenced: bool,
+    /// Whether the account's media is currently forced to be marked as
+    /// sensitive.
+    #[serde(default)]
+    pub sensitized: bool,
     /// Whether the account is currently disabled.
     pub disabled: bool,
     /// Whether the account is currently approved.
@@ -127,4 +133,55 @@ mod tests {
         let ip = &account.ips[0];
         assert_eq!(ip.ip.to_string(), "192.168.42.1");
     }
+
+    #[test]
+    fn test_deserialize_remote() {
+        let data = r##"{
+  "id": "108267695853695427",
+  "username": "Gargron",
+  "domain": "mastodon.social",
+  "created_at": "2022-05-08T00:00:00.000Z",
+  "email": null,
+  "ip": null,
+  "role": null,
+  "confirmed": false,
+  "suspended": false,
+  "silenced": false,
+  "sensitized": true,
+  "disabled": false,
+  "approved": false,
+  "locale": null,
+  "invite_request": null,
+  "ips": null,
+  "account": {
+    "id": "108267695853695427",
+    "username": "Gargron",
+    "acct": "Gargron@mastodon.social",
+    "display_name": "Eugen",
+    "locked": false,
+    "bot": false,
+    "discoverable": true,
+    "group": false,
+    "created_at": "2016-03-16T00:00:00.000Z",
+    "note": "",
+    "url": "https://mastodon.social/@Gargron",
+    "avatar": "https://files.mastodon.social/accounts/avatars/000/000/001/original/dc4286ceb8fab734.jpg",
+    "avatar_static": "https://files.mastodon.social/accounts/avatars/000/000/001/original/dc4286ceb8fab734.jpg",
+    "header": "https://files.mastodon.social/accounts/headers/000/000/001/original/3b91c9965d00888b.jpeg",
+    "header_static": "https://files.mastodon.social/accounts/headers/000/000/001/original/3b91c9965d00888b.jpeg",
+    "followers_count": 0,
+    "following_count": 0,
+    "statuses_count": 0,
+    "last_status_at": null,
+    "emojis": [],
+    "fields": []
+  }
+}"##;
+        let account: Account = serde_json::from_str(data).expect("deserialize");
+        assert_eq!(account.domain.as_deref(), Some("mastodon.social"));
+        assert!(account.email.is_none());
+        assert!(account.role.is_none());
+        assert!(account.ips.is_empty());
+        assert!(account.sensitized);
+    }
 }
@@ -25,7 +25,9 @@ pub struct Cohort {
 )]
 #[serde(rename_all = "lowercase")]
 pub enum CohortFrequency {
+    /// Daily buckets
     Day,
+    /// Monthly buckets
     Month,
 }
 
@@ -22,6 +22,7 @@ pub struct EmailDomainBlock {
 /// Usage history for a given day
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct History {
+    /// The day these statistics were counted for.
     #[serde(with = "conversion::date_from_timestamp")]
     pub day: Date,
     /// The counted accounts signup attempts using that email domain within that day.
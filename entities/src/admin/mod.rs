@@ -8,6 +8,7 @@ pub mod ip_block;
 pub mod measure;
 pub mod report;
 pub mod tag;
+pub mod trends;
 
 pub use account::Account;
 pub use canonical_email_block::*;
@@ -21,7 +22,7 @@ pub use tag::Tag;
 
 pub mod prelude {
     pub use super::{
-        account, dimension, domain, email_domain_block, ip_block, measure, Account,
+        account, dimension, domain, email_domain_block, ip_block, measure, trends, Account,
         CanonicalEmailBlock, Cohort, CohortFrequency, Dimension, EmailDomainBlock, IpBlock,
         Measure, Report, Tag,
     };
@@ -12,8 +12,8 @@ pub struct Report {
     /// Whether an action was taken to resolve this report.
     pub action_taken: bool,
     /// When an action was taken, if this report is currently resolved.
-    #[serde(with = "iso8601")]
-    pub action_taken_at: OffsetDateTime,
+    #[serde(with = "iso8601::option")]
+    pub action_taken_at: Option<OffsetDateTime>,
     /// The category under which the report is classified.
     pub category: report::Category,
     /// An optional reason for reporting.
@@ -39,3 +39,113 @@ pub struct Report {
     /// Rules attached to the report, for context.
     pub rules: Vec<Rule>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACCOUNT: &str = r#"{
+      "id": "108965278956942133",
+      "username": "admin",
+      "domain": null,
+      "created_at": "2022-09-08T23:03:26.762Z",
+      "email": "admin@mastodon.local",
+      "ip": null,
+      "role": {
+        "id": 3,
+        "name": "Owner",
+        "color": "",
+        "position": 1000,
+        "permissions": 1,
+        "highlighted": true,
+        "created_at": "2022-09-08T22:48:07.983Z",
+        "updated_at": "2022-09-08T22:48:07.983Z"
+      },
+      "confirmed": true,
+      "suspended": false,
+      "silenced": false,
+      "disabled": false,
+      "approved": true,
+      "locale": null,
+      "invite_request": null,
+      "ips": [],
+      "account": {
+        "id": "108965278956942133",
+        "username": "admin",
+        "acct": "admin",
+        "display_name": "",
+        "locked": false,
+        "bot": false,
+        "discoverable": null,
+        "group": false,
+        "created_at": "2022-09-08T00:00:00.000Z",
+        "note": "",
+        "url": "http://mastodon.local/@admin",
+        "avatar": "http://mastodon.local/avatars/original/missing.png",
+        "avatar_static": "http://mastodon.local/avatars/original/missing.png",
+        "header": "http://mastodon.local/headers/original/missing.png",
+        "header_static": "http://mastodon.local/headers/original/missing.png",
+        "followers_count": 0,
+        "following_count": 0,
+        "statuses_count": 0,
+        "last_status_at": null,
+        "emojis": [],
+        "fields": []
+      }
+    }"#;
+
+    #[test]
+    fn test_deserialize_unresolved() {
+        let example = format!(
+            r#"{{
+  "id": "1",
+  "action_taken": false,
+  "action_taken_at": null,
+  "category": "spam",
+  "comment": "",
+  "forwarded": false,
+  "created_at": "2022-09-09T21:19:23.085Z",
+  "updated_at": "2022-09-09T21:19:23.085Z",
+  "account": {ACCOUNT},
+  "target_account": {ACCOUNT},
+  "assigned_account": null,
+  "action_taken_by_account": null,
+  "statuses": [],
+  "rules": []
+}}"#
+        );
+        let subject: Report = serde_json::from_str(&example).expect("deserialize");
+        assert_eq!(subject.id, ReportId::new("1"));
+        assert!(!subject.action_taken);
+        assert!(subject.action_taken_at.is_none());
+        assert!(subject.assigned_account.is_none());
+    }
+
+    #[test]
+    fn test_deserialize_resolved() {
+        let example = format!(
+            r#"{{
+  "id": "2",
+  "action_taken": true,
+  "action_taken_at": "2022-09-10T08:00:00.000Z",
+  "category": "violation",
+  "comment": "",
+  "forwarded": true,
+  "created_at": "2022-09-09T21:19:23.085Z",
+  "updated_at": "2022-09-10T08:00:00.000Z",
+  "account": {ACCOUNT},
+  "target_account": {ACCOUNT},
+  "assigned_account": {ACCOUNT},
+  "action_taken_by_account": {ACCOUNT},
+  "statuses": [],
+  "rules": []
+}}"#
+        );
+        let subject: Report = serde_json::from_str(&example).expect("deserialize");
+        assert_eq!(
+            subject.action_taken_at.map(|at| at.unix_timestamp()),
+            Some(1662796800)
+        );
+        assert!(subject.assigned_account.is_some());
+    }
+}
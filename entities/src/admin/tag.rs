@@ -2,6 +2,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::{tag, TagId};
 
+/// Admin-level information about a hashtag, including whether it may trend.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/entities/Tag/#admin)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Tag {
     /// Non-admin data related to this hashtag
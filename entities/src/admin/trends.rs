@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{card::TrendsLink, status, PreviewCardId};
+
+/// A trending link, with the moderation details needed to review it.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/methods/admin/trends/#links)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Link {
+    /// The public data about this trending link.
+    #[serde(flatten)]
+    pub link: TrendsLink,
+    /// The ID of the link's preview card in the database.
+    pub id: PreviewCardId,
+    /// Whether the link has not been reviewed yet to approve or reject its
+    /// trending.
+    #[serde(default)]
+    pub requires_review: bool,
+}
+
+/// A trending status, with the moderation details needed to review it.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/methods/admin/trends/#statuses)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Status {
+    /// The public data about this trending status.
+    #[serde(flatten)]
+    pub status: status::Status,
+    /// Whether the status has not been reviewed yet to approve or reject its
+    /// trending.
+    #[serde(default)]
+    pub requires_review: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_link_example() {
+        let example = r#"{
+          "id": "15",
+          "url": "https://www.nbcnews.com/specials/plan-your-vote-2022-elections/index.html",
+          "title": "Plan Your Vote: 2022 Elections",
+          "description": "Everything you need to know about the voting rules where you live.",
+          "type": "link",
+          "author_name": "NBC News",
+          "author_url": "",
+          "provider_name": "NBC News",
+          "provider_url": "",
+          "html": "",
+          "width": 400,
+          "height": 225,
+          "image": "https://files.mastodon.social/cache/preview_cards/images/045/027/070/original/0c1e6f8dfa74df4d.jpeg",
+          "embed_url": "",
+          "blurhash": "UcQmF#ay~qofj[WBj[j[~qof9Fayofofayay",
+          "requires_review": true,
+          "history": [
+            {
+              "day": "1667174400",
+              "accounts": "15",
+              "uses": "15"
+            }
+          ]
+        }"#;
+        let subject: Link = serde_json::from_str(example).unwrap();
+        assert_eq!(subject.id, PreviewCardId::new("15"));
+        assert!(subject.requires_review);
+        assert_eq!(subject.link.card.title, "Plan Your Vote: 2022 Elections");
+        assert_eq!(subject.link.history.len(), 1);
+    }
+}
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::VapidKey;
+use crate::{auth::Scopes, VapidKey};
 
 /// Represents an application that interfaces with the REST API to access
 /// accounts or post statuses.
@@ -17,6 +17,14 @@ pub struct Application {
     pub client_id: Option<String>,
     ///  Client secret key, to be used for obtaining OAuth tokens
     pub client_secret: Option<String>,
+    /// The scopes the application may request. Only returned by newer
+    /// servers.
+    #[serde(default)]
+    pub scopes: Option<Scopes>,
+    /// The URIs users may be redirected to after authorizing the
+    /// application. Only returned by newer servers.
+    #[serde(default)]
+    pub redirect_uris: Vec<String>,
 }
 
 #[cfg(test)]
@@ -33,4 +41,24 @@ mod tests {
         let app: Application = serde_json::from_str(example).expect("deserialize");
         assert_eq!(app.name, "test app")
     }
+
+    #[test]
+    fn test_deserialize_verify_credentials() {
+        let example = r#"{
+  "name": "tootctl",
+  "website": "https://example.com",
+  "scopes": ["read", "write:statuses", "push"],
+  "redirect_uri": "urn:ietf:wg:oauth:2.0:oob",
+  "redirect_uris": ["urn:ietf:wg:oauth:2.0:oob"],
+  "vapid_key": "BCk-QqERU0q-CfYZjcuB6lnyyOYfJ2AifKqfeGIm7Z-HiTU5T9eTG5GxVA0_OH5mMlI4UkkDTpaZwozy0TzdZ2M="
+}"#;
+        let app: Application = serde_json::from_str(example).expect("deserialize");
+        assert_eq!(app.website.as_deref(), Some("https://example.com"));
+        assert_eq!(
+            app.scopes.map(|scopes| scopes.to_string()).as_deref(),
+            Some("read write:statuses push")
+        );
+        assert_eq!(app.redirect_uris, ["urn:ietf:wg:oauth:2.0:oob"]);
+        assert!(app.client_id.is_none());
+    }
 }
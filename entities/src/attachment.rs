@@ -1,6 +1,6 @@
 //! Module containing everything related to media attachements.
 
-use crate::AttachmentId;
+use crate::{conversion::lenient, AttachmentId};
 use derive_is_enum_variant::is_enum_variant;
 use serde::{Deserialize, Serialize};
 use url::Url;
@@ -29,7 +29,11 @@ pub struct Attachment {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub text_url: Option<Url>,
     /// Meta information about the attachment.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "lenient::option_object"
+    )]
     pub meta: Option<Meta>,
     /// Noop will be removed.
     pub description: Option<String>,
@@ -50,39 +54,192 @@ impl Attachment {
     pub fn is_done_processing(&self) -> bool {
         self.url.is_some()
     }
+
+    /// Decode the attachment's [`blurhash`](Self::blurhash) into a
+    /// placeholder image of `width` by `height` pixels, to show while the
+    /// media loads. The pixels are returned row by row, as four bytes of
+    /// RGBA each.
+    ///
+    /// Returns `None` if the attachment has no blurhash. The placeholder is
+    /// blurry at any size, so a small one, e.g. 32 pixels wide, can be
+    /// decoded and scaled up.
+    ///
+    /// ```
+    /// use mastodon_async_entities::attachment::Attachment;
+    ///
+    /// let attachment: Attachment = serde_json::from_str(r#"{
+    ///     "id": "1",
+    ///     "type": "image",
+    ///     "url": "https://files.mastodon.example/original/1.png",
+    ///     "preview_url": "https://files.mastodon.example/small/1.png",
+    ///     "remote_url": null,
+    ///     "description": null,
+    ///     "blurhash": "LEHV6nWB2yk8pyo0adR*.7kCMdnj"
+    /// }"#).unwrap();
+    /// let pixels = attachment.decode_blurhash(32, 24).unwrap().unwrap();
+    /// assert_eq!(pixels.len(), 32 * 24 * 4);
+    /// ```
+    #[cfg(feature = "blurhash")]
+    pub fn decode_blurhash(
+        &self,
+        width: u32,
+        height: u32,
+    ) -> Result<Option<Vec<u8>>, crate::Error> {
+        let Some(hash) = &self.blurhash else {
+            return Ok(None);
+        };
+        Ok(Some(blurhash::decode(hash, width, height, 1.0)?))
+    }
 }
 
 /// Metadata about some attachment.
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+///
+/// Which fields are set depends on the type of the media and on the server.
+/// Numbers which a server sends as strings are parsed, and values which
+/// can't be read are left unset rather than failing to deserialize the
+/// attachment.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/entities/MediaAttachment/#meta)
+#[derive(Debug, Default, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(default)]
 pub struct Meta {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The length of a video or audio track, formatted like `0:01:28.65`.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "lenient::option_string"
+    )]
     pub length: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The length of a video or audio track, in seconds.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "lenient::option_f64"
+    )]
     pub duration: Option<f64>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The frame rate of a video, rounded to frames per second.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "lenient::option_i64"
+    )]
     pub fps: Option<i64>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The size of a video, expressed like `WIDTHxHEIGHT`.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "lenient::option_string"
+    )]
     pub size: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// How many pixels wide a video is.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "lenient::option_i64"
+    )]
     pub width: Option<i64>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// How many pixels tall a video is.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "lenient::option_i64"
+    )]
     pub height: Option<i64>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The aspect ratio of a video, its width divided by its height.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "lenient::option_f64"
+    )]
     pub aspect: Option<f64>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The codec of the audio track, e.g. `mp3`.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "lenient::option_string"
+    )]
     pub audio_encode: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The sample rate of the audio track, e.g. `44100 Hz`.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "lenient::option_string"
+    )]
     pub audio_bitrate: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The channel layout of the audio track, e.g. `stereo`.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "lenient::option_string"
+    )]
     pub audio_channels: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Details about the original media.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "lenient::option_object"
+    )]
     pub original: Option<SizeSpecificDetails>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Details about the preview.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "lenient::option_object"
+    )]
     pub small: Option<SizeSpecificDetails>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The point of an image which should remain in view when it's cropped.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "lenient::option_object"
+    )]
     pub focus: Option<FocalPoint>,
 }
 
+impl Meta {
+    /// The width and height of the original media, in pixels.
+    pub fn dimensions(&self) -> Option<(i64, i64)> {
+        self.original
+            .as_ref()
+            .and_then(SizeSpecificDetails::dimensions)
+            .or_else(|| dimensions(self.width, self.height, self.size.as_deref()))
+    }
+
+    /// The aspect ratio of the original media, its width divided by its
+    /// height.
+    pub fn aspect_ratio(&self) -> Option<f64> {
+        self.original
+            .as_ref()
+            .and_then(SizeSpecificDetails::aspect_ratio)
+            .or(self.aspect)
+            .or_else(|| aspect_ratio(self.dimensions()?))
+    }
+
+    /// The length of a video or audio track, in seconds.
+    pub fn duration(&self) -> Option<f64> {
+        self.original
+            .as_ref()
+            .and_then(|original| original.duration)
+            .or(self.duration)
+    }
+
+    /// The frame rate of a video, in frames per second.
+    pub fn frame_rate(&self) -> Option<f64> {
+        self.original
+            .as_ref()
+            .and_then(SizeSpecificDetails::frames_per_second)
+            .or_else(|| self.fps.map(|fps| fps as f64))
+    }
+
+    /// The bitrate of the original video or audio track, in bits per
+    /// second.
+    pub fn bitrate(&self) -> Option<i64> {
+        self.original.as_ref()?.bitrate
+    }
+}
+
+/// The dimensions given by `width` and `height`, or else by `size`.
+fn dimensions(width: Option<i64>, height: Option<i64>, size: Option<&str>) -> Option<(i64, i64)> {
+    match (width, height) {
+        (Some(width), Some(height)) => Some((width, height)),
+        _ => {
+            let (width, height) = size?.split_once('x')?;
+            Some((width.trim().parse().ok()?, height.trim().parse().ok()?))
+        }
+    }
+}
+
+fn aspect_ratio((width, height): (i64, i64)) -> Option<f64> {
+    (height != 0).then(|| width as f64 / height as f64)
+}
+
 /// The type of media attachment.
 ///
 /// See also [the API documentation](https://docs.joinmastodon.org/entities/MediaAttachment/#type)
@@ -101,32 +258,80 @@ pub enum MediaType {
     Unknown,
 }
 
-/// Metadata about a video attachment
+/// Details about one size of an attachment's media: the original, or
+/// the preview.
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SizeSpecificDetails {
     /// How many pixels wide the video or image is.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "lenient::option_i64"
+    )]
     pub width: Option<i64>,
     /// How many pixels tall the video is.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "lenient::option_i64"
+    )]
     pub height: Option<i64>,
-    /// The frame rate of the video.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The frame rate of the video, as a fraction like `100/3`.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "lenient::option_string"
+    )]
     pub frame_rate: Option<String>,
     /// The duration of the video, in seconds.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "lenient::option_f64"
+    )]
     pub duration: Option<f64>,
     /// The bitrate of the video.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "lenient::option_i64"
+    )]
     pub bitrate: Option<i64>,
     /// The aspect ratio of the video
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "lenient::option_f64"
+    )]
     pub aspect: Option<f64>,
     /// The size of the video, expressed like WIDTHxHEIGHT.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "lenient::option_string"
+    )]
     pub size: Option<String>,
 }
 
+impl SizeSpecificDetails {
+    /// The width and height, in pixels, from `width` and `height`, or else
+    /// parsed from `size`.
+    pub fn dimensions(&self) -> Option<(i64, i64)> {
+        dimensions(self.width, self.height, self.size.as_deref())
+    }
+
+    /// The aspect ratio, or else the width divided by the height.
+    pub fn aspect_ratio(&self) -> Option<f64> {
+        self.aspect.or_else(|| aspect_ratio(self.dimensions()?))
+    }
+
+    /// The frame rate in frames per second, parsed from `frame_rate`.
+    pub fn frames_per_second(&self) -> Option<f64> {
+        let frame_rate = self.frame_rate.as_deref()?.trim();
+        let fps = match frame_rate.split_once('/') {
+            Some((frames, seconds)) => {
+                frames.trim().parse::<f64>().ok()? / seconds.trim().parse::<f64>().ok()?
+            }
+            None => frame_rate.parse().ok()?,
+        };
+        fps.is_finite().then_some(fps)
+    }
+}
+
 /// A point on an image which should remain in view when cropped for previews.
 ///
 /// There is some more information [here](https://docs.joinmastodon.org/api/guidelines/#focal-points)
@@ -135,11 +340,36 @@ pub struct SizeSpecificDetails {
 #[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
 pub struct FocalPoint {
     /// The point on the horizontal plane which should remain in focus.
+    #[serde(default, deserialize_with = "focal_coordinate")]
     pub x: f64,
     /// The point on the vertical plane which should remain in focus.
+    #[serde(default, deserialize_with = "focal_coordinate")]
     pub y: f64,
 }
 
+/// A coordinate of a focal point, which is centered if it's missing.
+fn focal_coordinate<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    Ok(lenient::option_f64(deserializer)?.unwrap_or_default())
+}
+
+impl FocalPoint {
+    /// A new focal point. Both coordinates should fall within `-1.0..=1.0`,
+    /// with `(0.0, 0.0)` being the center of the image.
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+}
+
+/// Formats the focal point as the API expects it in form data, e.g. `"-0.5,0.25"`.
+impl std::fmt::Display for FocalPoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// The focal point of an image, as it is named by the `focus` form parameter.
+pub type Focus = FocalPoint;
+
 /// A media attachment which has been processed and has a URL.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct ProcessedAttachment {
@@ -160,7 +390,11 @@ pub struct ProcessedAttachment {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub text_url: Option<Url>,
     /// Meta information about the attachment.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "lenient::option_object"
+    )]
     pub meta: Option<Meta>,
     /// Noop will be removed.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -202,6 +436,93 @@ mod tests {
   "blurhash": "UFBWY:8_0Jxv4mx]t8t64.%M-:IUWGWAt6M}"
 }"##);
 
+    #[cfg(feature = "blurhash")]
+    #[test]
+    fn test_decode_blurhash() {
+        let mut attachment: Attachment = serde_json::from_str(
+            r#"{
+                "id": "1",
+                "type": "image",
+                "url": null,
+                "preview_url": "https://files.mastodon.example/small/1.png",
+                "remote_url": null,
+                "description": null,
+                "blurhash": "UFBWY:8_0Jxv4mx]t8t64.%M-:IUWGWAt6M}"
+            }"#,
+        )
+        .expect("deserialize");
+        let pixels = attachment
+            .decode_blurhash(4, 3)
+            .expect("decode")
+            .expect("blurhash");
+        assert_eq!(pixels.len(), 4 * 3 * 4);
+        assert!(pixels.chunks(4).all(|pixel| pixel[3] == 255));
+
+        attachment.blurhash = Some("not a blurhash".to_string());
+        assert!(attachment.decode_blurhash(4, 3).is_err());
+        attachment.blurhash = None;
+        assert_eq!(attachment.decode_blurhash(4, 3).expect("decode"), None);
+    }
+
+    #[test]
+    fn test_deserialize_inconsistent_meta() {
+        let attachment: Attachment = serde_json::from_str(
+            r#"{
+                "id": "1",
+                "type": "video",
+                "url": null,
+                "preview_url": "https://files.mastodon.example/small/1.png",
+                "remote_url": null,
+                "meta": {
+                    "fps": 29.97,
+                    "duration": "12.5",
+                    "width": "1280",
+                    "height": null,
+                    "size": "1280x720",
+                    "audio_bitrate": 44100,
+                    "original": {
+                        "frame_rate": "30000/1001",
+                        "bitrate": "862056",
+                        "aspect": "unknown"
+                    },
+                    "small": [],
+                    "focus": {"x": "-0.5"}
+                },
+                "description": null,
+                "blurhash": null
+            }"#,
+        )
+        .expect("deserialize");
+        let meta = attachment.meta.expect("meta");
+        assert_eq!(meta.fps, Some(30));
+        assert_eq!(meta.duration(), Some(12.5));
+        assert_eq!(meta.width, Some(1280));
+        assert_eq!(meta.height, None);
+        assert_eq!(meta.audio_bitrate.as_deref(), Some("44100"));
+        assert_eq!(meta.dimensions(), Some((1280, 720)));
+        assert_eq!(meta.aspect_ratio(), Some(1280.0 / 720.0));
+        assert_eq!(meta.bitrate(), Some(862056));
+        let frame_rate = meta.frame_rate().expect("frame rate");
+        assert!((frame_rate - 29.97).abs() < 0.01);
+        assert_eq!(meta.small, None);
+        assert_eq!(meta.focus, Some(FocalPoint::new(-0.5, 0.0)));
+
+        let attachment: Attachment = serde_json::from_str(
+            r#"{
+                "id": "2",
+                "type": "unknown",
+                "url": null,
+                "preview_url": "https://files.mastodon.example/small/2.png",
+                "remote_url": null,
+                "meta": [],
+                "description": null,
+                "blurhash": null
+            }"#,
+        )
+        .expect("deserialize");
+        assert_eq!(attachment.meta, None);
+    }
+
     serde_value_test!(test_deserialize_with_audio(Attachment): r##"{
   "id": "21165404",
   "type": "audio",
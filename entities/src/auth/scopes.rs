@@ -9,6 +9,7 @@ use std::{
 use crate::error::Error;
 
 use derive_is_enum_variant::is_enum_variant;
+use log::debug;
 use serde::{
     de::{self, Visitor},
     ser::Serializer,
@@ -27,6 +28,26 @@ use serde::{
 /// let follow = Scopes::follow();
 /// let all = read | write | follow;
 /// ```
+///
+/// Granular scopes can be combined the same way, and a set of scopes returned
+/// by the server can be checked for the permission a method needs:
+///
+/// ```rust
+/// use mastodon_async_entities::auth::scopes::{Admin, Read, Scope, Scopes, Write};
+///
+/// let scopes = Scopes::read(Read::Notifications)
+///     | Scopes::write(Write::Bookmarks)
+///     | Scopes::admin_read(Admin::Accounts)
+///     | Scopes::profile();
+/// assert_eq!(
+///     scopes.to_string(),
+///     "read:notifications write:bookmarks profile admin:read:accounts"
+/// );
+///
+/// let granted: Scopes = "read write:statuses".parse().unwrap();
+/// assert!(granted.allows(Scope::Read(Some(Read::Notifications))));
+/// assert!(!granted.allows(Scope::Write(Some(Write::Bookmarks))));
+/// ```
 #[derive(Clone)]
 pub struct Scopes {
     scopes: HashSet<Scope>,
@@ -61,23 +82,37 @@ impl<'de> Visitor<'de> for DeserializeScopesVisitor {
     type Value = Scopes;
 
     fn expecting(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(formatter, "space separated scopes")
+        write!(formatter, "space separated scopes or a list of scopes")
     }
 
     fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
     where
         E: de::Error,
     {
-        Scopes::from_str(v).map_err(de::Error::custom)
+        Ok(Scopes::lenient(v.split_whitespace()))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let mut names = vec![];
+        while let Some(name) = seq.next_element::<String>()? {
+            names.push(name);
+        }
+        Ok(Scopes::lenient(names.iter().map(String::as_str)))
     }
 }
 
+/// Scopes are deserialized leniently: scopes which aren't known, e.g. ones
+/// added by a newer server or specific to another server software, are
+/// skipped rather than failing to deserialize the whole response.
 impl<'de> Deserialize<'de> for Scopes {
     fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_str(DeserializeScopesVisitor)
+        deserializer.deserialize_any(DeserializeScopesVisitor)
     }
 }
 
@@ -166,6 +201,84 @@ impl Scopes {
         Scopes::new(Scope::Push)
     }
 
+    /// Represents the "profile" scope, which only grants access to the
+    /// `verify_credentials` endpoint
+    ///
+    /// ```
+    /// use mastodon_async_entities::prelude::*;
+    ///
+    /// let scope = Scopes::profile();
+    /// assert_eq!(&format!("{}", scope), "profile");
+    /// ```
+    pub fn profile() -> Scopes {
+        Scopes::new(Scope::Profile)
+    }
+
+    /// Represents the full "admin:read" scope
+    ///
+    /// ```
+    /// use mastodon_async_entities::prelude::*;
+    ///
+    /// let scope = Scopes::admin_read_all();
+    /// assert_eq!(&format!("{}", scope), "admin:read");
+    /// ```
+    pub fn admin_read_all() -> Scopes {
+        Scopes::new(Scope::AdminRead(None))
+    }
+
+    /// Represents a specific "admin:read:___" scope
+    ///
+    /// ```
+    /// use mastodon_async_entities::auth::scopes::{Admin, Scopes};
+    ///
+    /// let scope = Scopes::admin_read(Admin::Accounts);
+    /// assert_eq!(&format!("{}", scope), "admin:read:accounts");
+    /// ```
+    pub fn admin_read(subscope: Admin) -> Scopes {
+        Scopes::new(Scope::AdminRead(Some(subscope)))
+    }
+
+    /// Represents the full "admin:write" scope
+    ///
+    /// ```
+    /// use mastodon_async_entities::prelude::*;
+    ///
+    /// let scope = Scopes::admin_write_all();
+    /// assert_eq!(&format!("{}", scope), "admin:write");
+    /// ```
+    pub fn admin_write_all() -> Scopes {
+        Scopes::new(Scope::AdminWrite(None))
+    }
+
+    /// Represents a specific "admin:write:___" scope
+    ///
+    /// ```
+    /// use mastodon_async_entities::auth::scopes::{Admin, Scopes};
+    ///
+    /// let scope = Scopes::admin_write(Admin::Reports);
+    /// assert_eq!(&format!("{}", scope), "admin:write:reports");
+    /// ```
+    pub fn admin_write(subscope: Admin) -> Scopes {
+        Scopes::new(Scope::AdminWrite(Some(subscope)))
+    }
+
+    /// Whether this set of scopes grants the given scope, either directly or
+    /// through a broader scope which includes it. For example, "read" allows
+    /// "read:accounts", and the legacy "follow" scope allows
+    /// "write:follows".
+    ///
+    /// ```
+    /// use mastodon_async_entities::auth::scopes::{Read, Scope, Scopes, Write};
+    ///
+    /// let scopes = Scopes::read_all() | Scopes::follow();
+    /// assert!(scopes.allows(Scope::Read(Some(Read::Accounts))));
+    /// assert!(scopes.allows(Scope::Write(Some(Write::Blocks))));
+    /// assert!(!scopes.allows(Scope::Write(Some(Write::Statuses))));
+    /// ```
+    pub fn allows(&self, scope: Scope) -> bool {
+        self.scopes.iter().any(|granted| granted.includes(scope))
+    }
+
     /// Combines 2 scopes together
     ///
     /// // Example
@@ -182,6 +295,19 @@ impl Scopes {
         Scopes { scopes: new_set }
     }
 
+    /// The known scopes among `names`, skipping the others.
+    fn lenient<'a>(names: impl IntoIterator<Item = &'a str>) -> Scopes {
+        let scopes = names
+            .into_iter()
+            .filter_map(|name| {
+                Scope::from_str(name)
+                    .map_err(|_| debug!(scope = name; "skipping unknown scope"))
+                    .ok()
+            })
+            .collect();
+        Scopes { scopes }
+    }
+
     fn _write(subscope: Option<Write>) -> Scopes {
         Scopes::new(Scope::Write(subscope))
     }
@@ -214,6 +340,8 @@ impl PartialEq for Scopes {
     }
 }
 
+impl Eq for Scopes {}
+
 impl Default for Scopes {
     fn default() -> Scopes {
         Scopes::read_all()
@@ -270,6 +398,45 @@ pub enum Scope {
     Follow,
     /// Push permissions
     Push,
+    /// Only permission to read the authenticated user's own profile.
+    Profile,
+    /// Read only permissions for the admin API.
+    AdminRead(Option<Admin>),
+    /// Write only permissions for the admin API.
+    AdminWrite(Option<Admin>),
+}
+
+impl Scope {
+    /// Whether having this scope also grants `other`.
+    fn includes(self, other: Scope) -> bool {
+        use self::Scope::*;
+        match (self, other) {
+            (a, b) if a == b => true,
+            (Read(None), Read(_)) | (Write(None), Write(_)) => true,
+            (AdminRead(None), AdminRead(_)) | (AdminWrite(None), AdminWrite(_)) => true,
+            (
+                Follow,
+                Read(Some(self::Read::Follows | self::Read::Blocks | self::Read::Mutes))
+                | Write(Some(self::Write::Follows | self::Write::Blocks | self::Write::Mutes)),
+            ) => true,
+            // the profile is also readable with "read:accounts"
+            (Read(None | Some(self::Read::Accounts)), Profile) => true,
+            _ => false,
+        }
+    }
+
+    /// Sort key for the top-level kind of scope
+    fn rank(&self) -> u8 {
+        match self {
+            Scope::Read(..) => 0,
+            Scope::Write(..) => 1,
+            Scope::Follow => 2,
+            Scope::Push => 3,
+            Scope::Profile => 4,
+            Scope::AdminRead(..) => 5,
+            Scope::AdminWrite(..) => 6,
+        }
+    }
 }
 
 impl FromStr for Scope {
@@ -281,6 +448,17 @@ impl FromStr for Scope {
             "write" => Scope::Write(None),
             "follow" => Scope::Follow,
             "push" => Scope::Push,
+            "profile" => Scope::Profile,
+            "admin:read" => Scope::AdminRead(None),
+            "admin:write" => Scope::AdminWrite(None),
+            admin if admin.starts_with("admin:read:") => {
+                let a: Admin = Admin::from_str(&admin[11..])?;
+                Scope::AdminRead(Some(a))
+            }
+            admin if admin.starts_with("admin:write:") => {
+                let a: Admin = Admin::from_str(&admin[12..])?;
+                Scope::AdminWrite(Some(a))
+            }
             read if read.starts_with("read:") => {
                 let r: Read = Read::from_str(&read[5..])?;
                 Scope::Read(Some(r))
@@ -303,31 +481,11 @@ impl PartialOrd for Scope {
 impl Ord for Scope {
     fn cmp(&self, other: &Scope) -> Ordering {
         match (*self, *other) {
-            (Scope::Read(None), Scope::Read(None)) => Ordering::Equal,
-            (Scope::Read(None), Scope::Read(Some(..))) => Ordering::Less,
-            (Scope::Read(Some(..)), Scope::Read(None)) => Ordering::Greater,
-            (Scope::Read(Some(ref a)), Scope::Read(Some(ref b))) => a.cmp(b),
-
-            (Scope::Write(None), Scope::Write(None)) => Ordering::Equal,
-            (Scope::Write(None), Scope::Write(Some(..))) => Ordering::Less,
-            (Scope::Write(Some(..)), Scope::Write(None)) => Ordering::Greater,
-            (Scope::Write(Some(ref a)), Scope::Write(Some(ref b))) => a.cmp(b),
-
-            (Scope::Read(..), Scope::Write(..)) => Ordering::Less,
-            (Scope::Read(..), Scope::Follow) => Ordering::Less,
-            (Scope::Read(..), Scope::Push) => Ordering::Less,
-
-            (Scope::Write(..), Scope::Read(..)) => Ordering::Greater,
-            (Scope::Write(..), Scope::Follow) => Ordering::Less,
-            (Scope::Write(..), Scope::Push) => Ordering::Less,
-
-            (Scope::Follow, Scope::Read(..)) => Ordering::Greater,
-            (Scope::Follow, Scope::Write(..)) => Ordering::Greater,
-            (Scope::Follow, Scope::Follow) => Ordering::Equal,
-            (Scope::Follow, Scope::Push) => Ordering::Less,
-
-            (Scope::Push, Scope::Push) => Ordering::Equal,
-            (Scope::Push, _) => Ordering::Greater,
+            (Scope::Read(a), Scope::Read(b)) => a.cmp(&b),
+            (Scope::Write(a), Scope::Write(b)) => a.cmp(&b),
+            (Scope::AdminRead(a), Scope::AdminRead(b)) => a.cmp(&b),
+            (Scope::AdminWrite(a), Scope::AdminWrite(b)) => a.cmp(&b),
+            (a, b) => a.rank().cmp(&b.rank()),
         }
     }
 }
@@ -342,6 +500,11 @@ impl fmt::Display for Scope {
             Write(None) => "write",
             Follow => "follow",
             Push => "push",
+            Profile => "profile",
+            AdminRead(Some(ref a)) => return write!(f, "admin:read:{}", a),
+            AdminRead(None) => "admin:read",
+            AdminWrite(Some(ref a)) => return write!(f, "admin:write:{}", a),
+            AdminWrite(None) => "admin:write",
         };
         write!(f, "{}", s)
     }
@@ -361,6 +524,8 @@ pub enum Read {
     Accounts,
     /// Blocks
     Blocks,
+    /// Bookmarks
+    Bookmarks,
     /// Favourites
     Favourites,
     /// Filters
@@ -388,6 +553,7 @@ impl FromStr for Read {
         Ok(match s {
             "accounts" => Read::Accounts,
             "blocks" => Read::Blocks,
+            "bookmarks" => Read::Bookmarks,
             "favourites" => Read::Favourites,
             "filters" => Read::Filters,
             "follows" => Read::Follows,
@@ -424,6 +590,7 @@ impl fmt::Display for Read {
             match *self {
                 Read::Accounts => "accounts",
                 Read::Blocks => "blocks",
+                Read::Bookmarks => "bookmarks",
                 Read::Favourites => "favourites",
                 Read::Filters => "filters",
                 Read::Follows => "follows",
@@ -446,6 +613,10 @@ pub enum Write {
     Accounts,
     /// Blocks
     Blocks,
+    /// Bookmarks
+    Bookmarks,
+    /// Conversations
+    Conversations,
     /// Favourites
     Favourites,
     /// Filters
@@ -473,6 +644,8 @@ impl FromStr for Write {
         Ok(match s {
             "accounts" => Write::Accounts,
             "blocks" => Write::Blocks,
+            "bookmarks" => Write::Bookmarks,
+            "conversations" => Write::Conversations,
             "favourites" => Write::Favourites,
             "filters" => Write::Filters,
             "follows" => Write::Follows,
@@ -509,6 +682,8 @@ impl fmt::Display for Write {
             match *self {
                 Write::Accounts => "accounts",
                 Write::Blocks => "blocks",
+                Write::Bookmarks => "bookmarks",
+                Write::Conversations => "conversations",
                 Write::Favourites => "favourites",
                 Write::Filters => "filters",
                 Write::Follows => "follows",
@@ -523,6 +698,76 @@ impl fmt::Display for Write {
     }
 }
 
+/// Represents the granular "admin:read:___" and "admin:write:___" oauth
+/// scopes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, is_enum_variant)]
+#[serde(rename_all = "snake_case")]
+pub enum Admin {
+    /// Accounts
+    Accounts,
+    /// Reports
+    Reports,
+    /// Domain allows
+    DomainAllows,
+    /// Domain blocks
+    DomainBlocks,
+    /// IP blocks
+    IpBlocks,
+    /// Email domain blocks
+    EmailDomainBlocks,
+    /// Canonical email blocks
+    CanonicalEmailBlocks,
+}
+
+impl FromStr for Admin {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Admin, Self::Err> {
+        Ok(match s {
+            "accounts" => Admin::Accounts,
+            "reports" => Admin::Reports,
+            "domain_allows" => Admin::DomainAllows,
+            "domain_blocks" => Admin::DomainBlocks,
+            "ip_blocks" => Admin::IpBlocks,
+            "email_domain_blocks" => Admin::EmailDomainBlocks,
+            "canonical_email_blocks" => Admin::CanonicalEmailBlocks,
+            _ => return Err(Error::UnknownScope(s.to_owned())),
+        })
+    }
+}
+
+impl PartialOrd for Admin {
+    fn partial_cmp(&self, other: &Admin) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Admin {
+    fn cmp(&self, other: &Admin) -> Ordering {
+        let a = format!("{:?}", self);
+        let b = format!("{:?}", other);
+        a.cmp(&b)
+    }
+}
+
+impl fmt::Display for Admin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match *self {
+                Admin::Accounts => "accounts",
+                Admin::Reports => "reports",
+                Admin::DomainAllows => "domain_allows",
+                Admin::DomainBlocks => "domain_blocks",
+                Admin::IpBlocks => "ip_blocks",
+                Admin::EmailDomainBlocks => "email_domain_blocks",
+                Admin::CanonicalEmailBlocks => "canonical_email_blocks",
+            }
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -718,6 +963,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_scopes_deserialize_unknown() {
+        let des: Scopes =
+            serde_json::from_str(r#""read write:bites profile""#).expect("Couldn't deserialize");
+        assert_eq!(des, Scopes::read_all() | Scopes::profile());
+        let des: Scopes = serde_json::from_str(r#"["push", "admin:read:widgets", "follow"]"#)
+            .expect("Couldn't deserialize");
+        assert_eq!(des, Scopes::push() | Scopes::follow());
+        assert!(Scopes::from_str("read write:bites").is_err());
+    }
+
     #[test]
     fn test_scope_from_str() {
         let tests = [
@@ -765,4 +1021,48 @@ mod tests {
         let result = format!("{}", scopes);
         assert_eq!(original, result);
     }
+
+    #[test]
+    fn test_granular_scopes_round_trip() {
+        let original = "read:bookmarks write:conversations push profile admin:read \
+                        admin:write:canonical_email_blocks admin:write:reports";
+        let scopes = Scopes::from_str(original).expect("Couldn't convert to Scopes");
+        assert!(scopes.contains(&Scope::Read(Some(Read::Bookmarks))));
+        assert!(scopes.contains(&Scope::Write(Some(Write::Conversations))));
+        assert!(scopes.contains(&Scope::AdminRead(None)));
+        assert!(scopes.contains(&Scope::AdminWrite(Some(Admin::CanonicalEmailBlocks))));
+        assert_eq!(
+            original.split_whitespace().collect::<Vec<_>>().join(" "),
+            scopes.to_string()
+        );
+    }
+
+    #[test]
+    fn test_scopes_allows() {
+        let scopes = Scopes::read_all()
+            | Scopes::write(Write::Statuses)
+            | Scopes::follow()
+            | Scopes::admin_read_all();
+        let allowed = [
+            Scope::Read(None),
+            Scope::Read(Some(Read::Notifications)),
+            Scope::Write(Some(Write::Statuses)),
+            Scope::Write(Some(Write::Follows)),
+            Scope::Write(Some(Write::Mutes)),
+            Scope::Profile,
+            Scope::AdminRead(Some(Admin::Reports)),
+        ];
+        let denied = [
+            Scope::Write(None),
+            Scope::Write(Some(Write::Bookmarks)),
+            Scope::Push,
+            Scope::AdminWrite(Some(Admin::Reports)),
+        ];
+        for scope in allowed {
+            assert!(scopes.allows(scope), "{scope} should be allowed");
+        }
+        for scope in denied {
+            assert!(!scopes.allows(scope), "{scope} should not be allowed");
+        }
+    }
 }
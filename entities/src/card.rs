@@ -1,14 +1,15 @@
 //! Module representing cards of statuses.
 
-use crate::{conversion, tag};
+use crate::{account::Account, conversion, tag};
 use derive_is_enum_variant::is_enum_variant;
 use serde::{Deserialize, Serialize};
+use time::{serde::iso8601, OffsetDateTime};
 use url::Url;
 
 /// Represents a rich preview card that is generated using OpenGraph tags from a URL.
 ///
 /// See also [the API documentation](https://docs.joinmastodon.org/entities/PreviewCard/)
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct Card {
     /// Location of linked resource.
     pub url: Url,
@@ -21,11 +22,19 @@ pub struct Card {
     pub card_type: Type,
     /// Preview thumbnail.
     pub image: Option<String>,
-    /// The author of the original resource.
+    /// Alternative text for the preview thumbnail.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_description: Option<String>,
+    /// The author of the original resource. If there are several authors,
+    /// see [`authors`](Card::authors).
     pub author_name: String,
     /// A link to the author of the original resource.
     #[serde(with = "conversion::maybe_empty_url")]
     pub author_url: Option<Url>,
+    /// The authors of the original resource, with their fediverse accounts
+    /// if they're known. Empty on servers older than Mastodon 4.3.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<Author>,
     /// The provider of the original resource.
     pub provider_name: String,
     /// A link to the provider of the original resource.
@@ -44,6 +53,32 @@ pub struct Card {
     /// for generating colorful preview thumbnails when media has not been
     /// downloaded yet.
     pub blurhash: Option<String>,
+    /// When the original resource was published.
+    #[serde(
+        with = "iso8601::option",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    pub published_at: Option<OffsetDateTime>,
+    /// The language of the original resource, as the code the server gave,
+    /// e.g. `en`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+/// An author of the resource a preview card links to.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/entities/PreviewCardAuthor/)
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct Author {
+    /// The original resource author's name.
+    pub name: String,
+    /// A link to the author of the original resource.
+    #[serde(with = "conversion::maybe_empty_url")]
+    pub url: Option<Url>,
+    /// The fediverse account of the author, if they've attributed the
+    /// resource to it, e.g. with a `fediverse:creator` meta tag.
+    pub account: Option<Account>,
 }
 
 /// The type of the preview card.
@@ -61,7 +96,7 @@ pub enum Type {
 }
 
 /// A preview card which holds a trending link
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct TrendsLink {
     /// The preview card associated with this trending link
     #[serde(flatten)]
@@ -127,6 +162,54 @@ mod tests {
         "blurhash": null
     }"#);
 
+    #[test]
+    fn test_authors() {
+        let card: Card = serde_json::from_str(
+            r#"{
+                "url": "https://blog.example/posts/hello",
+                "title": "Hello",
+                "description": "A first post",
+                "language": "en",
+                "type": "link",
+                "author_name": "Alice",
+                "author_url": "https://blog.example/alice",
+                "provider_name": "Blog",
+                "provider_url": "",
+                "html": "",
+                "width": 400,
+                "height": 200,
+                "image": "https://files.mastodon.example/cache/preview_cards/images/1.png",
+                "image_description": "A waving hand",
+                "embed_url": "",
+                "blurhash": null,
+                "published_at": "2024-10-08T12:00:00.000Z",
+                "authors": [
+                    {
+                        "name": "Alice",
+                        "url": "https://blog.example/alice",
+                        "account": null
+                    },
+                    {
+                        "name": "Bob",
+                        "url": "",
+                        "account": null
+                    }
+                ]
+            }"#,
+        )
+        .expect("deserialize");
+        assert_eq!(card.authors.len(), 2);
+        assert_eq!(card.authors[0].name, "Alice");
+        assert_eq!(card.authors[1].url, None);
+        assert_eq!(card.image_description.as_deref(), Some("A waving hand"));
+        assert_eq!(card.language.as_deref(), Some("en"));
+        assert_eq!(
+            card.published_at
+                .map(|published_at| published_at.unix_timestamp()),
+            Some(1728388800)
+        );
+    }
+
     serde_value_test!(test_trending_link(TrendsLink): r#"{
         "url": "https://www.nbcnews.com/specials/plan-your-vote-2022-elections/index.html",
         "title": "Plan Your Vote: 2022 Elections",
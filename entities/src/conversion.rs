@@ -192,3 +192,197 @@ pub mod date_from_timestamp {
         deserializer.deserialize_str(Vizitor)
     }
 }
+
+/// Deserialize `null` as the default value of `T`, for fields which the API
+/// omits by sending `null` rather than an empty value.
+pub(crate) fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Default + serde::Deserialize<'de>,
+{
+    let value: Option<T> = serde::Deserialize::deserialize(deserializer)?;
+    Ok(value.unwrap_or_default())
+}
+
+/// Deserializers for fields which servers don't send consistently, e.g.
+/// numbers which are sometimes sent as strings. A value which can't be read
+/// as the expected type is deserialized as `None`, rather than failing to
+/// deserialize the whole entity.
+pub(crate) mod lenient {
+    use serde::{
+        de::{self, value::MapAccessDeserializer, IgnoredAny, Visitor},
+        Deserialize, Deserializer,
+    };
+
+    enum Scalar {
+        Int(i64),
+        Float(f64),
+        Str(String),
+        Other,
+    }
+
+    struct ScalarVisitor;
+
+    impl<'v> Visitor<'v> for ScalarVisitor {
+        type Value = Scalar;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(formatter, "a number, a string or null")
+        }
+
+        fn visit_bool<E: de::Error>(self, _: bool) -> Result<Self::Value, E> {
+            Ok(Scalar::Other)
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+            Ok(Scalar::Int(v))
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            Ok(i64::try_from(v).map_or(Scalar::Float(v as f64), Scalar::Int))
+        }
+
+        fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+            Ok(Scalar::Float(v))
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            Ok(Scalar::Str(v.to_string()))
+        }
+
+        fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(Scalar::Other)
+        }
+
+        fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(Scalar::Other)
+        }
+
+        fn visit_some<D: Deserializer<'v>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+            deserializer.deserialize_any(self)
+        }
+
+        fn visit_seq<A: de::SeqAccess<'v>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            while seq.next_element::<IgnoredAny>()?.is_some() {}
+            Ok(Scalar::Other)
+        }
+
+        fn visit_map<A: de::MapAccess<'v>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+            Ok(Scalar::Other)
+        }
+    }
+
+    fn finite(value: f64) -> Option<f64> {
+        value.is_finite().then_some(value)
+    }
+
+    /// An integer, a float rounded to the nearest integer, or a string
+    /// holding either.
+    pub(crate) fn option_i64<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(match deserializer.deserialize_any(ScalarVisitor)? {
+            Scalar::Int(v) => Some(v),
+            Scalar::Float(v) => finite(v).map(|v| v.round() as i64),
+            Scalar::Str(v) => {
+                let v = v.trim();
+                v.parse()
+                    .ok()
+                    .or_else(|| v.parse().ok().and_then(finite).map(|v| v.round() as i64))
+            }
+            Scalar::Other => None,
+        })
+    }
+
+    /// A number, or a string holding one.
+    pub(crate) fn option_f64<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(match deserializer.deserialize_any(ScalarVisitor)? {
+            Scalar::Int(v) => Some(v as f64),
+            Scalar::Float(v) => finite(v),
+            Scalar::Str(v) => v.trim().parse().ok().and_then(finite),
+            Scalar::Other => None,
+        })
+    }
+
+    /// A string, or a number formatted as one.
+    pub(crate) fn option_string<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(match deserializer.deserialize_any(ScalarVisitor)? {
+            Scalar::Int(v) => Some(v.to_string()),
+            Scalar::Float(v) => Some(v.to_string()),
+            Scalar::Str(v) => Some(v),
+            Scalar::Other => None,
+        })
+    }
+
+    /// An object, or `None` for anything else, such as the empty array some
+    /// servers send in place of an empty object.
+    pub(crate) fn option_object<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de>,
+    {
+        struct ObjectVisitor<T>(std::marker::PhantomData<T>);
+
+        impl<'v, T: Deserialize<'v>> Visitor<'v> for ObjectVisitor<T> {
+            type Value = Option<T>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(formatter, "an object or null")
+            }
+
+            fn visit_map<A: de::MapAccess<'v>>(self, map: A) -> Result<Self::Value, A::Error> {
+                T::deserialize(MapAccessDeserializer::new(map)).map(Some)
+            }
+
+            fn visit_some<D: Deserializer<'v>>(
+                self,
+                deserializer: D,
+            ) -> Result<Self::Value, D::Error> {
+                deserializer.deserialize_any(self)
+            }
+
+            fn visit_seq<A: de::SeqAccess<'v>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                while seq.next_element::<IgnoredAny>()?.is_some() {}
+                Ok(None)
+            }
+
+            fn visit_bool<E: de::Error>(self, _: bool) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_i64<E: de::Error>(self, _: i64) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_u64<E: de::Error>(self, _: u64) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_f64<E: de::Error>(self, _: f64) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_str<E: de::Error>(self, _: &str) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+        }
+
+        deserializer.deserialize_any(ObjectVisitor(std::marker::PhantomData))
+    }
+}
@@ -1,7 +1,11 @@
 use derive_is_enum_variant::is_enum_variant;
 
 /// Error type
+///
+/// Variants may be added in later versions, or depending on which features
+/// are enabled, e.g. `Blurhash` with the `blurhash` feature.
 #[derive(Debug, thiserror::Error, is_enum_variant)]
+#[non_exhaustive]
 pub enum Error {
     #[error("unrecognized visibility '{invalid}'")]
     VisibilityParsingError { invalid: String },
@@ -9,4 +13,7 @@ pub enum Error {
     UnknownScope(String),
     #[error(transparent)]
     Builder(#[from] derive_builder::UninitializedFieldError),
+    #[cfg(feature = "blurhash")]
+    #[error("invalid blurhash: {0}")]
+    Blurhash(#[from] blurhash::Error),
 }
@@ -1,4 +1,8 @@
-use crate::{notification::Notification, status::Status};
+#![allow(clippy::large_enum_variant)]
+use crate::{
+    announcement::Announcement, conversation::Conversation, notification::Notification,
+    status::Status, AnnouncementId,
+};
 use derive_is_enum_variant::is_enum_variant;
 use serde::{Deserialize, Serialize};
 
@@ -13,4 +17,46 @@ pub enum Event {
     Delete(String),
     /// FiltersChanged event
     FiltersChanged,
+    /// A status has been edited
+    StatusUpdate(Status),
+    /// A direct conversation has been updated
+    Conversation(Conversation),
+    /// An announcement has been published
+    Announcement(Announcement),
+    /// An announcement has received an emoji reaction
+    AnnouncementReaction(AnnouncementReaction),
+    /// An announcement has been deleted
+    AnnouncementDelete(AnnouncementId),
+    /// A status has been reacted to with an emoji, or a reaction has been
+    /// removed. Carries the status with its updated reactions. Only sent by
+    /// Pleroma and Akkoma.
+    EmojiReaction(Status),
+}
+
+/// The payload of an `announcement.reaction` event.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/methods/streaming/#announcement.reaction)
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct AnnouncementReaction {
+    /// The emoji used for the reaction. Either a unicode emoji, or a custom
+    /// emoji's shortcode.
+    pub name: String,
+    /// The total number of users who have added this reaction.
+    pub count: i64,
+    /// The ID of the announcement which was reacted to.
+    pub announcement_id: AnnouncementId,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_announcement_reaction_example() {
+        let example = r#"{"name":"bongoCat","count":9,"announcement_id":"8"}"#;
+        let subject: AnnouncementReaction = serde_json::from_str(example).unwrap();
+        assert_eq!(subject.name, "bongoCat");
+        assert_eq!(subject.count, 9);
+        assert_eq!(subject.announcement_id, AnnouncementId::new("8"));
+    }
 }
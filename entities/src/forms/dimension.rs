@@ -0,0 +1,191 @@
+use serde::{ser::SerializeMap, Serialize, Serializer};
+use time::OffsetDateTime;
+
+use super::KeyParam;
+use crate::{DimensionKey, TagId};
+
+/// A dimension of qualitative server data which can be requested from the
+/// admin API.
+///
+/// See also [the API reference](https://docs.joinmastodon.org/methods/admin/dimensions/#available-dimensions)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Key {
+    /// The most-used languages on the server
+    Languages,
+    /// The most-used sign-up sources
+    Sources,
+    /// The remote servers with the most statuses
+    Servers,
+    /// Disk space used by the database, cache and media storage
+    SpaceUsage,
+    /// Versions of the software used by the server
+    SoftwareVersions,
+    /// The servers with the most statuses using the hashtag with this ID
+    TagServers(TagId),
+    /// The most-used languages in statuses using the hashtag with this ID
+    TagLanguages(TagId),
+    /// The accounts from the given domain with the most statuses
+    InstanceAccounts(String),
+    /// The most-used languages in statuses from the given domain
+    InstanceLanguages(String),
+}
+
+impl Key {
+    /// The keystring for this dimension, as used in the request and in
+    /// [`Dimension::key`](crate::admin::Dimension::key).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Key::Languages => "languages",
+            Key::Sources => "sources",
+            Key::Servers => "servers",
+            Key::SpaceUsage => "space_usage",
+            Key::SoftwareVersions => "software_versions",
+            Key::TagServers(_) => "tag_servers",
+            Key::TagLanguages(_) => "tag_languages",
+            Key::InstanceAccounts(_) => "instance_accounts",
+            Key::InstanceLanguages(_) => "instance_languages",
+        }
+    }
+
+    /// The extra parameter this dimension requires, if any.
+    fn param(&self) -> Option<KeyParam<'_>> {
+        match self {
+            Key::TagServers(id) | Key::TagLanguages(id) => Some(KeyParam {
+                id: Some(id.as_ref()),
+                domain: None,
+            }),
+            Key::InstanceAccounts(domain) | Key::InstanceLanguages(domain) => Some(KeyParam {
+                id: None,
+                domain: Some(domain),
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl PartialEq<Key> for DimensionKey {
+    fn eq(&self, other: &Key) -> bool {
+        self.as_ref() == other.as_str()
+    }
+}
+
+/// Form for requesting qualitative data about the server over a period of
+/// time.
+///
+/// ```
+/// use mastodon_async_entities::forms::dimension::{Key, Query};
+/// use time::{Date, Month};
+///
+/// let day = |day| {
+///     Date::from_calendar_date(2022, Month::September, day)
+///         .unwrap()
+///         .midnight()
+///         .assume_utc()
+/// };
+/// let mut form = Query::new(day(14), day(21));
+/// form.key(Key::Languages)
+///     .key(Key::InstanceLanguages("example.com".into()))
+///     .limit(5);
+/// assert_eq!(
+///     serde_json::to_string(&form).unwrap(),
+///     r#"{"keys":["languages","instance_languages"],"start_at":"2022-09-14T00:00:00Z","end_at":"2022-09-21T00:00:00Z","limit":5,"instance_languages":{"domain":"example.com"}}"#
+/// );
+/// ```
+///
+/// See also [the API reference](https://docs.joinmastodon.org/methods/admin/dimensions/#get)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    keys: Vec<Key>,
+    start_at: OffsetDateTime,
+    end_at: OffsetDateTime,
+    limit: Option<u64>,
+}
+
+impl Query {
+    /// Construct a new form for the period from `start_at` to `end_at`, with
+    /// no dimensions selected.
+    pub fn new(start_at: OffsetDateTime, end_at: OffsetDateTime) -> Self {
+        Self {
+            keys: vec![],
+            start_at,
+            end_at,
+            limit: None,
+        }
+    }
+
+    /// Request the given dimension.
+    pub fn key(&mut self, key: Key) -> &mut Self {
+        self.keys.push(key);
+        self
+    }
+
+    /// Request each of the given dimensions.
+    pub fn keys(&mut self, keys: impl IntoIterator<Item = Key>) -> &mut Self {
+        self.keys.extend(keys);
+        self
+    }
+
+    /// The maximum number of data items to return for each dimension.
+    pub fn limit(&mut self, limit: u64) -> &mut Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+impl Serialize for Query {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct Timestamp<'a>(#[serde(serialize_with = "super::rfc3339")] &'a OffsetDateTime);
+
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry(
+            "keys",
+            &self.keys.iter().map(Key::as_str).collect::<Vec<_>>(),
+        )?;
+        map.serialize_entry("start_at", &Timestamp(&self.start_at))?;
+        map.serialize_entry("end_at", &Timestamp(&self.end_at))?;
+        if let Some(limit) = self.limit {
+            map.serialize_entry("limit", &limit)?;
+        }
+        for key in &self.keys {
+            if let Some(param) = key.param() {
+                map.serialize_entry(key.as_str(), &param)?;
+            }
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::{Date, Month};
+
+    use super::*;
+
+    #[test]
+    fn test_serialize_tag_keys() {
+        let start_at = Date::from_calendar_date(2022, Month::September, 14)
+            .unwrap()
+            .midnight()
+            .assume_utc();
+        let mut form = Query::new(start_at, start_at);
+        form.keys([
+            Key::SpaceUsage,
+            Key::TagServers(TagId::new("802")),
+            Key::TagLanguages(TagId::new("802")),
+        ]);
+        assert_eq!(
+            serde_json::to_value(&form).unwrap(),
+            serde_json::json!({
+                "keys": ["space_usage", "tag_servers", "tag_languages"],
+                "start_at": "2022-09-14T00:00:00Z",
+                "end_at": "2022-09-14T00:00:00Z",
+                "tag_servers": { "id": "802" },
+                "tag_languages": { "id": "802" },
+            })
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use derive_builder::Builder;
+use serde::Serialize;
+
+use crate::list::RepliesPolicy;
+
+/// Form for creating or updating a list.
+///
+/// ```
+/// use mastodon_async_entities::prelude::*;
+///
+/// let form = forms::list::List::builder("friends")
+///     .replies_policy(list::RepliesPolicy::Followed)
+///     .exclusive(true)
+///     .build()
+///     .unwrap();
+/// assert_eq!(
+///     serde_json::to_string(&form).unwrap(),
+///     r#"{"title":"friends","replies_policy":"followed","exclusive":true}"#
+/// );
+/// ```
+///
+/// See also [the API reference](https://docs.joinmastodon.org/methods/lists/#create)
+#[derive(Builder, Debug, Default, Clone, Serialize, PartialEq, Eq)]
+#[builder(derive(Debug), build_fn(error = "crate::Error"))]
+pub struct List {
+    /// The title of the list.
+    #[builder(setter(custom), default)]
+    title: String,
+    /// Which replies should be shown in the list.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    replies_policy: Option<RepliesPolicy>,
+    /// Whether members of the list should be removed from the home timeline.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    exclusive: Option<bool>,
+}
+
+impl List {
+    /// Get a ListBuilder object for a list with the given title
+    pub fn builder(title: impl Into<String>) -> ListBuilder {
+        ListBuilder {
+            title: Some(title.into()),
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use serde::Serialize;
+
+use crate::{NotificationId, StatusId};
+
+/// Form for saving the read position in one or both timelines.
+///
+/// ```
+/// use mastodon_async_entities::prelude::*;
+///
+/// let mut form = forms::marker::Update::new();
+/// form.home(&StatusId::new("103194548672408537"))
+///     .notifications(&NotificationId::new("35098814"));
+/// assert_eq!(
+///     serde_json::to_string(&form).unwrap(),
+///     r#"{"home":{"last_read_id":"103194548672408537"},"notifications":{"last_read_id":"35098814"}}"#
+/// );
+/// ```
+///
+/// See also [the API reference](https://docs.joinmastodon.org/methods/markers/#create)
+#[derive(Debug, Default, Clone, Serialize, PartialEq, Eq)]
+pub struct Update {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    home: Option<Position>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notifications: Option<Position>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+struct Position {
+    last_read_id: String,
+}
+
+impl Update {
+    /// Construct a new, empty `Update` form.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the ID of the last read status in the home timeline.
+    pub fn home(&mut self, last_read_id: &StatusId) -> &mut Self {
+        self.home = Some(Position {
+            last_read_id: last_read_id.to_string(),
+        });
+        self
+    }
+
+    /// Set the ID of the last read notification.
+    pub fn notifications(&mut self, last_read_id: &NotificationId) -> &mut Self {
+        self.notifications = Some(Position {
+            last_read_id: last_read_id.to_string(),
+        });
+        self
+    }
+}
@@ -0,0 +1,205 @@
+use serde::{ser::SerializeMap, Serialize, Serializer};
+use time::OffsetDateTime;
+
+use super::KeyParam;
+use crate::{MeasureKey, TagId};
+
+/// A measure of server activity which can be requested from the admin API.
+///
+/// See also [the API reference](https://docs.joinmastodon.org/methods/admin/measures/#available-measures)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Key {
+    /// Users active during the time period
+    ActiveUsers,
+    /// Users who signed up during the time period
+    NewUsers,
+    /// Favourites, boosts and replies received by local statuses
+    Interactions,
+    /// Reports filed during the time period
+    OpenedReports,
+    /// Reports resolved during the time period
+    ResolvedReports,
+    /// Accounts which used the hashtag with this ID
+    TagAccounts(TagId),
+    /// Statuses which used the hashtag with this ID
+    TagUses(TagId),
+    /// Servers with statuses which used the hashtag with this ID
+    TagServers(TagId),
+    /// Accounts discovered from the given domain
+    InstanceAccounts(String),
+    /// Space used by media attachments from the given domain
+    InstanceMediaAttachments(String),
+    /// Reports filed against accounts from the given domain
+    InstanceReports(String),
+    /// Statuses received from the given domain
+    InstanceStatuses(String),
+    /// Local accounts followed by accounts from the given domain
+    InstanceFollows(String),
+    /// Accounts from the given domain followed by local accounts
+    InstanceFollowers(String),
+}
+
+impl Key {
+    /// The keystring for this measure, as used in the request and in
+    /// [`Measure::key`](crate::admin::Measure::key).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Key::ActiveUsers => "active_users",
+            Key::NewUsers => "new_users",
+            Key::Interactions => "interactions",
+            Key::OpenedReports => "opened_reports",
+            Key::ResolvedReports => "resolved_reports",
+            Key::TagAccounts(_) => "tag_accounts",
+            Key::TagUses(_) => "tag_uses",
+            Key::TagServers(_) => "tag_servers",
+            Key::InstanceAccounts(_) => "instance_accounts",
+            Key::InstanceMediaAttachments(_) => "instance_media_attachments",
+            Key::InstanceReports(_) => "instance_reports",
+            Key::InstanceStatuses(_) => "instance_statuses",
+            Key::InstanceFollows(_) => "instance_follows",
+            Key::InstanceFollowers(_) => "instance_followers",
+        }
+    }
+
+    /// The extra parameter this measure requires, if any.
+    fn param(&self) -> Option<KeyParam<'_>> {
+        match self {
+            Key::TagAccounts(id) | Key::TagUses(id) | Key::TagServers(id) => Some(KeyParam {
+                id: Some(id.as_ref()),
+                domain: None,
+            }),
+            Key::InstanceAccounts(domain)
+            | Key::InstanceMediaAttachments(domain)
+            | Key::InstanceReports(domain)
+            | Key::InstanceStatuses(domain)
+            | Key::InstanceFollows(domain)
+            | Key::InstanceFollowers(domain) => Some(KeyParam {
+                id: None,
+                domain: Some(domain),
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl PartialEq<Key> for MeasureKey {
+    fn eq(&self, other: &Key) -> bool {
+        self.as_ref() == other.as_str()
+    }
+}
+
+/// Form for requesting quantitative measures of server activity over a
+/// period of time.
+///
+/// ```
+/// use mastodon_async_entities::{forms::measure::{Key, Query}, TagId};
+/// use time::{Date, Month};
+///
+/// let day = |day| {
+///     Date::from_calendar_date(2022, Month::September, day)
+///         .unwrap()
+///         .midnight()
+///         .assume_utc()
+/// };
+/// let mut form = Query::new(day(14), day(21));
+/// form.key(Key::ActiveUsers)
+///     .key(Key::TagUses(TagId::new("25")));
+/// assert_eq!(
+///     serde_json::to_string(&form).unwrap(),
+///     r#"{"keys":["active_users","tag_uses"],"start_at":"2022-09-14T00:00:00Z","end_at":"2022-09-21T00:00:00Z","tag_uses":{"id":"25"}}"#
+/// );
+/// ```
+///
+/// See also [the API reference](https://docs.joinmastodon.org/methods/admin/measures/#get)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    keys: Vec<Key>,
+    start_at: OffsetDateTime,
+    end_at: OffsetDateTime,
+}
+
+impl Query {
+    /// Construct a new form for the period from `start_at` to `end_at`, with
+    /// no measures selected.
+    pub fn new(start_at: OffsetDateTime, end_at: OffsetDateTime) -> Self {
+        Self {
+            keys: vec![],
+            start_at,
+            end_at,
+        }
+    }
+
+    /// Request the given measure.
+    pub fn key(&mut self, key: Key) -> &mut Self {
+        self.keys.push(key);
+        self
+    }
+
+    /// Request each of the given measures.
+    pub fn keys(&mut self, keys: impl IntoIterator<Item = Key>) -> &mut Self {
+        self.keys.extend(keys);
+        self
+    }
+}
+
+impl Serialize for Query {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct Timestamp<'a>(#[serde(serialize_with = "super::rfc3339")] &'a OffsetDateTime);
+
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry(
+            "keys",
+            &self.keys.iter().map(Key::as_str).collect::<Vec<_>>(),
+        )?;
+        map.serialize_entry("start_at", &Timestamp(&self.start_at))?;
+        map.serialize_entry("end_at", &Timestamp(&self.end_at))?;
+        for key in &self.keys {
+            if let Some(param) = key.param() {
+                map.serialize_entry(key.as_str(), &param)?;
+            }
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::{Date, Month};
+
+    use super::*;
+
+    fn day(day: u8) -> OffsetDateTime {
+        Date::from_calendar_date(2022, Month::September, day)
+            .unwrap()
+            .midnight()
+            .assume_utc()
+    }
+
+    #[test]
+    fn test_serialize_instance_keys() {
+        let mut form = Query::new(day(14), day(21));
+        form.keys([Key::NewUsers, Key::InstanceStatuses("example.com".into())]);
+        assert_eq!(
+            serde_json::to_value(&form).unwrap(),
+            serde_json::json!({
+                "keys": ["new_users", "instance_statuses"],
+                "start_at": "2022-09-14T00:00:00Z",
+                "end_at": "2022-09-21T00:00:00Z",
+                "instance_statuses": { "domain": "example.com" },
+            })
+        );
+    }
+
+    #[test]
+    fn test_measure_key_eq() {
+        assert_eq!(
+            MeasureKey::new("tag_servers"),
+            Key::TagServers(TagId::new("1"))
+        );
+        assert_ne!(MeasureKey::new("new_users"), Key::ActiveUsers);
+    }
+}
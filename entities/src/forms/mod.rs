@@ -1,4 +1,34 @@
 pub mod application;
+pub mod dimension;
 pub mod filter;
+pub mod list;
+pub mod marker;
+pub mod measure;
+pub mod notification;
+pub mod retention;
 
 pub use application::{Application, ApplicationBuilder};
+
+/// The extra parameter sent alongside a measure or dimension key which is
+/// scoped to a particular hashtag or domain.
+#[derive(serde::Serialize)]
+struct KeyParam<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    domain: Option<&'a str>,
+}
+
+/// Serialize a timestamp in the RFC 3339 format expected by the admin
+/// metrics endpoints.
+fn rfc3339<S>(value: &time::OffsetDateTime, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    use serde::ser::Error;
+
+    let value = value
+        .format(&time::format_description::well_known::Rfc3339)
+        .map_err(S::Error::custom)?;
+    serializer.serialize_str(&value)
+}
@@ -0,0 +1,54 @@
+use derive_builder::Builder;
+use serde::Serialize;
+
+use crate::notification::PolicyAction;
+
+/// Form for updating the notification filtering policy. Fields which aren't
+/// set are left unchanged.
+///
+/// ```
+/// use mastodon_async_entities::prelude::*;
+///
+/// let form = forms::notification::Policy::builder()
+///     .for_new_accounts(notification::PolicyAction::Filter)
+///     .for_private_mentions(notification::PolicyAction::Accept)
+///     .build()
+///     .unwrap();
+/// assert_eq!(
+///     serde_json::to_string(&form).unwrap(),
+///     r#"{"for_new_accounts":"filter","for_private_mentions":"accept"}"#
+/// );
+/// ```
+///
+/// See also [the API reference](https://docs.joinmastodon.org/methods/notifications/#update-the-filtering-policy-for-notifications)
+#[derive(Builder, Debug, Default, Clone, Serialize, PartialEq, Eq)]
+#[builder(derive(Debug), build_fn(error = "crate::Error"))]
+pub struct Policy {
+    /// How to handle notifications from accounts the user doesn't follow.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    for_not_following: Option<PolicyAction>,
+    /// How to handle notifications from accounts which don't follow the user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    for_not_followers: Option<PolicyAction>,
+    /// How to handle notifications from accounts created in the past 30 days.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    for_new_accounts: Option<PolicyAction>,
+    /// How to handle private mentions from accounts the user doesn't follow.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    for_private_mentions: Option<PolicyAction>,
+    /// How to handle notifications from accounts limited by moderators.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    for_limited_accounts: Option<PolicyAction>,
+}
+
+impl Policy {
+    /// Get a PolicyBuilder object
+    pub fn builder() -> PolicyBuilder {
+        PolicyBuilder::default()
+    }
+}
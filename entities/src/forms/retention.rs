@@ -0,0 +1,50 @@
+use serde::Serialize;
+use time::OffsetDateTime;
+
+use crate::admin::CohortFrequency;
+
+/// Form for requesting user retention data, grouping users by when they
+/// signed up and showing how many stayed active afterwards.
+///
+/// ```
+/// use mastodon_async_entities::{admin::CohortFrequency, forms::retention::Query};
+/// use time::{Date, Month};
+///
+/// let month = |month| {
+///     Date::from_calendar_date(2022, month, 1)
+///         .unwrap()
+///         .midnight()
+///         .assume_utc()
+/// };
+/// let form = Query::new(month(Month::July), month(Month::September), CohortFrequency::Month);
+/// assert_eq!(
+///     serde_json::to_string(&form).unwrap(),
+///     r#"{"start_at":"2022-07-01T00:00:00Z","end_at":"2022-09-01T00:00:00Z","frequency":"month"}"#
+/// );
+/// ```
+///
+/// See also [the API reference](https://docs.joinmastodon.org/methods/admin/retention/#create)
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct Query {
+    #[serde(serialize_with = "super::rfc3339")]
+    start_at: OffsetDateTime,
+    #[serde(serialize_with = "super::rfc3339")]
+    end_at: OffsetDateTime,
+    frequency: CohortFrequency,
+}
+
+impl Query {
+    /// Construct a new form for users who signed up between `start_at` and
+    /// `end_at`, grouped into daily or monthly cohorts.
+    pub fn new(
+        start_at: OffsetDateTime,
+        end_at: OffsetDateTime,
+        frequency: CohortFrequency,
+    ) -> Self {
+        Self {
+            start_at,
+            end_at,
+            frequency,
+        }
+    }
+}
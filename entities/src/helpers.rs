@@ -1,3 +1,8 @@
+/// The URL which the [`Default`] values of entities point to.
+pub(crate) fn placeholder_url() -> url::Url {
+    url::Url::parse("https://mastodon.example/").expect("valid URL")
+}
+
 /// Returns true if the given value refers to "false"
 pub fn is_false(value: &bool) -> bool {
     !*value
@@ -0,0 +1,360 @@
+/// Convert HTML, as found in the content of statuses and the notes of
+/// accounts, to plain text.
+///
+/// Paragraphs are separated by blank lines, line breaks and list items start
+/// new lines, and tags are otherwise dropped, keeping the text inside them:
+/// mentions, hashtags and links become the text they're displayed as, e.g.
+/// `@alice` and `#rust`, with the whole URL for links the server shortened.
+/// Custom emoji given as images, as some servers other than Mastodon do,
+/// become their `:shortcode:`, as Mastodon writes them. Character references
+/// such as `&amp;` are decoded.
+///
+/// This is meant for the restricted HTML servers produce after sanitizing it,
+/// rather than for arbitrary documents.
+///
+/// // Example
+///
+/// ```
+/// use mastodon_async_entities::html::to_plain_text;
+///
+/// let html = concat!(
+///     r#"<p><span class="h-card"><a href="https://example.com/@alice" class="u-url mention">"#,
+///     r#"@<span>alice</span></a></span> hi &amp; welcome!</p>"#,
+///     r#"<p>See <a href="https://example.com/tags/rust" class="mention hashtag">#<span>rust</span></a>"#,
+///     r#"<br>:wave:</p>"#,
+/// );
+/// assert_eq!(to_plain_text(html), "@alice hi & welcome!\n\nSee #rust\n:wave:");
+/// ```
+pub fn to_plain_text(html: &str) -> String {
+    let mut text = Text::default();
+    let mut rest = html;
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            text.push_str(&decode(rest));
+            break;
+        };
+        text.push_str(&decode(&rest[..start]));
+        rest = &rest[start..];
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let Some(end) = rest.find('>') else {
+            // not a tag after all
+            text.push_str(&decode(rest));
+            break;
+        };
+        let tag = Tag::parse(&rest[1..end]);
+        rest = &rest[end + 1..];
+        if matches!(tag.name.as_str(), "script" | "style") && !tag.closing {
+            let close = format!("</{}", tag.name);
+            rest = find_ignoring_case(rest, &close).map_or("", |end| &rest[end..]);
+            continue;
+        }
+        text.tag(&tag);
+    }
+    let mut output = text.output;
+    output.truncate(output.trim_end().len());
+    output
+}
+
+/// The text being written, and how the elements it's in affect it.
+#[derive(Default)]
+struct Text {
+    output: String,
+    /// The break to write before any more text.
+    pending: Option<Break>,
+    /// The lists the text is in, innermost last, with the number of the
+    /// next item of ordered lists.
+    lists: Vec<Option<u32>>,
+    preformatted: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Break {
+    Line,
+    Paragraph,
+}
+
+impl Text {
+    fn tag(&mut self, tag: &Tag) {
+        match (tag.name.as_str(), tag.closing) {
+            ("br", _) => {
+                // consecutive line breaks are kept, unlike paragraph breaks
+                if let Some(pending) = self.pending.take() {
+                    self.write_break(pending);
+                }
+                self.pending = Some(Break::Line);
+            }
+            ("p" | "div" | "blockquote" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6", _) => {
+                self.break_with(Break::Paragraph)
+            }
+            ("pre", closing) => {
+                self.break_with(Break::Paragraph);
+                self.preformatted = !closing;
+            }
+            ("ul", false) => {
+                self.break_with(Break::Paragraph);
+                self.lists.push(None);
+            }
+            ("ol", false) => {
+                self.break_with(Break::Paragraph);
+                let start = tag.attribute("start").and_then(|start| start.parse().ok());
+                self.lists.push(Some(start.unwrap_or(1)));
+            }
+            ("ul" | "ol", true) => {
+                self.lists.pop();
+                self.break_with(Break::Paragraph);
+            }
+            ("li", false) => {
+                self.break_with(Break::Line);
+                let depth = self.lists.len().saturating_sub(1);
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number = number.saturating_add(1);
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => "- ".to_string(),
+                };
+                self.write(&format!("{}{marker}", "  ".repeat(depth)));
+            }
+            ("li", true) => self.break_with(Break::Line),
+            ("img", false) => {
+                if let Some(alt) = tag.attribute("alt") {
+                    self.write(&alt);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Break the text before any more is written, with at least `kind`.
+    fn break_with(&mut self, kind: Break) {
+        self.pending = self.pending.max(Some(kind));
+    }
+
+    fn write_break(&mut self, kind: Break) {
+        let trimmed = self.output.trim_end_matches(' ').len();
+        self.output.truncate(trimmed);
+        if self.output.is_empty() {
+            return;
+        }
+        self.output.push_str(match kind {
+            Break::Line => "\n",
+            Break::Paragraph => "\n\n",
+        });
+    }
+
+    /// Write text from between tags, collapsing whitespace unless it's
+    /// preformatted.
+    fn push_str(&mut self, text: &str) {
+        if self.preformatted {
+            self.write(text);
+            return;
+        }
+        let at_line_start =
+            self.pending.is_some() || self.output.is_empty() || self.output.ends_with('\n');
+        let space_before = text.starts_with(|c: char| c.is_ascii_whitespace())
+            && !at_line_start
+            && !self.output.ends_with(' ');
+        let mut collapsed = text.split_ascii_whitespace().collect::<Vec<_>>().join(" ");
+        if collapsed.is_empty() {
+            if space_before {
+                self.output.push(' ');
+            }
+            return;
+        }
+        if space_before {
+            collapsed.insert(0, ' ');
+        }
+        if text.ends_with(|c: char| c.is_ascii_whitespace()) {
+            collapsed.push(' ');
+        }
+        self.write(&collapsed);
+    }
+
+    fn write(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        if let Some(pending) = self.pending.take() {
+            self.write_break(pending);
+        }
+        self.output.push_str(text);
+    }
+}
+
+/// An opening or closing tag, with its attributes.
+struct Tag<'a> {
+    name: String,
+    closing: bool,
+    attributes: Vec<(String, &'a str)>,
+}
+
+impl<'a> Tag<'a> {
+    /// Parse the inside of a tag, between `<` and `>`.
+    fn parse(inside: &'a str) -> Self {
+        let inside = inside.trim().trim_end_matches('/');
+        let (closing, inside) = match inside.strip_prefix('/') {
+            Some(inside) => (true, inside),
+            None => (false, inside),
+        };
+        let name_end = inside
+            .find(|c: char| c.is_ascii_whitespace())
+            .unwrap_or(inside.len());
+        let name = inside[..name_end].to_ascii_lowercase();
+        let mut attributes = vec![];
+        let mut rest = inside[name_end..].trim_start();
+        while !rest.is_empty() {
+            let key_end = rest
+                .find(|c: char| c == '=' || c.is_ascii_whitespace())
+                .unwrap_or(rest.len());
+            let key = rest[..key_end].to_ascii_lowercase();
+            rest = rest[key_end..].trim_start();
+            let value = match rest.strip_prefix('=') {
+                Some(value) => {
+                    let value = value.trim_start();
+                    let (value, after) = match value.chars().next() {
+                        Some(quote @ ('"' | '\'')) => {
+                            let value = &value[1..];
+                            let end = value.find(quote).unwrap_or(value.len());
+                            (&value[..end], value.get(end + 1..).unwrap_or(""))
+                        }
+                        _ => {
+                            let end = value
+                                .find(|c: char| c.is_ascii_whitespace())
+                                .unwrap_or(value.len());
+                            (&value[..end], &value[end..])
+                        }
+                    };
+                    rest = after.trim_start();
+                    value
+                }
+                None => "",
+            };
+            attributes.push((key, value));
+        }
+        Self {
+            name,
+            closing,
+            attributes,
+        }
+    }
+
+    /// The decoded value of the attribute named `name`.
+    fn attribute(&self, name: &str) -> Option<String> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| decode(value))
+    }
+}
+
+fn find_ignoring_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack
+        .char_indices()
+        .map(|(index, _)| index)
+        .find(|&index| {
+            haystack
+                .get(index..index + needle.len())
+                .is_some_and(|candidate| candidate.eq_ignore_ascii_case(needle))
+        })
+}
+
+/// Decode the character references in `text`, leaving ones which aren't
+/// recognized as they are.
+fn decode(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let reference = rest[1..]
+            .find(';')
+            .filter(|&end| end <= 32)
+            .and_then(|end| Some((character(&rest[1..1 + end])?, end + 2)));
+        match reference {
+            Some((character, len)) => {
+                decoded.push(character);
+                rest = &rest[len..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// The character a reference stands for, given what's between `&` and `;`.
+fn character(reference: &str) -> Option<char> {
+    if let Some(number) = reference.strip_prefix('#') {
+        let code = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => number.parse().ok()?,
+        };
+        return char::from_u32(code);
+    }
+    Some(match reference {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mastodon_content() {
+        let html = concat!(
+            r#"<p>Hello <span class="h-card" translate="no"><a href="https://example.com/@bob" "#,
+            r#"class="u-url mention">@<span>bob</span></a></span>, look:</p>"#,
+            r#"<p><a href="https://example.com/a/very/long/path" target="_blank" rel="nofollow noopener">"#,
+            r#"<span class="invisible">https://</span><span class="ellipsis">example.com/a/very/</span>"#,
+            r#"<span class="invisible">long/path</span></a></p>"#,
+            r#"<p>line one<br />line two<br><br>after a blank line &lt;3 &#x1F980; &#38;</p>"#,
+        );
+        assert_eq!(
+            to_plain_text(html),
+            "Hello @bob, look:\n\nhttps://example.com/a/very/long/path\n\n\
+             line one\nline two\n\nafter a blank line <3 🦀 &"
+        );
+    }
+
+    #[test]
+    fn test_lists_and_quotes() {
+        let html = concat!(
+            "<p>Steps:</p><ol start=\"3\"><li>one</li><li>two <ul><li>nested</li></ul></li></ol>",
+            "<blockquote><p>quoted</p></blockquote><pre><code>let x  =\n  1;</code></pre>",
+        );
+        assert_eq!(
+            to_plain_text(html),
+            "Steps:\n\n3. one\n4. two\n\n  - nested\n\nquoted\n\nlet x  =\n  1;"
+        );
+    }
+
+    #[test]
+    fn test_whitespace_and_emoji() {
+        let html = "\n<p>  spaced\n   out  </p>\n<p>blob <img class=\"emoji\" alt=\":blobcat:\" src=\"x.png\"/> cat</p>";
+        assert_eq!(to_plain_text(html), "spaced out\n\nblob :blobcat: cat");
+    }
+
+    #[test]
+    fn test_malformed() {
+        assert_eq!(to_plain_text("a < b &bogus; & c"), "a < b &bogus; & c");
+        assert_eq!(
+            to_plain_text("<p>x<!-- hidden --></p><script>y</script>z"),
+            "x\n\nz"
+        );
+        assert_eq!(to_plain_text("plain"), "plain");
+        assert_eq!(to_plain_text("<ol start=0><li>zero"), "0. zero");
+    }
+}
@@ -12,7 +12,7 @@ macro_rules! define_ids {
     };
     ($doc:literal as $name:ident(from $from_t:ty, as $ref_t:ident ref),) => {
         #[doc = concat!("Wrapper type for ", $doc)]
-        #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+        #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
         #[serde(transparent)]
         pub struct $name($from_t);
 
@@ -39,6 +39,18 @@ macro_rules! define_ids {
                 $name(it)
             }
         }
+
+        impl From<&$ref_t> for $name {
+            fn from(it: &$ref_t) -> $name {
+                $name(it.to_owned())
+            }
+        }
+
+        impl From<&$name> for $name {
+            fn from(it: &$name) -> $name {
+                it.clone()
+            }
+        }
     };
     () => {}
 }
@@ -52,6 +64,7 @@ define_ids!(
     "a list ID" as ListId(from String, as str ref),
     "a mention ID" as MentionId(from String, as str ref),
     "a notification ID" as NotificationId(from String, as str ref),
+    "a notification request ID" as NotificationRequestId(from String, as str ref),
     "a subscription ID" as SubscriptionId(from String, as str ref),
     "a relationship ID" as RelationshipId(from String, as str ref),
     "a report ID" as ReportId(from String, as str ref),
@@ -69,8 +82,25 @@ define_ids!(
     "a conversation ID" as ConversationId(from String, as str ref),
     "a poll ID" as PollId(from String, as str ref),
     "a hashtag ID" as TagId(from String, as str ref),
+    "a preview card ID" as PreviewCardId(from String, as str ref),
+    "the ID of an event which severed relationships" as RelationshipSeveranceEventId(from String, as str ref),
+    "an account warning ID" as AccountWarningId(from String, as str ref),
     "the ID of an application.
 
 As [`Application`](crate::application::Application) doesn't have an ID, I'm not sure what you're supposed to compare this to." as ApplicationId(from i64, as i64 ref),
     "a role ID" as RoleId(from i64, as i64 ref),
 );
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversions() {
+        let id = StatusId::new("109345671283569234");
+        assert_eq!(StatusId::from("109345671283569234"), id);
+        assert_eq!(StatusId::from(&id), id);
+        assert_eq!(StatusId::from("109345671283569234".to_string()), id);
+        assert_eq!(RoleId::from(&3), RoleId::new(3));
+    }
+}
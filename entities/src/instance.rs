@@ -1,7 +1,7 @@
 //! Module containing everything related to an instance.
 use isolang::Language;
 use serde::{Deserialize, Serialize};
-use time::{serde::iso8601, OffsetDateTime};
+use time::{serde::iso8601, Date, OffsetDateTime};
 use url::Url;
 
 use crate::{account::Account, admin, conversion, RuleId};
@@ -35,6 +35,18 @@ pub struct Instance {
     pub contact: Contact,
     /// An itemized list of rules for this website.
     pub rules: Vec<Rule>,
+    /// The versions of each API supported by this website. Not present
+    /// before Mastodon 4.3.
+    #[serde(default)]
+    pub api_versions: ApiVersions,
+}
+
+/// The versions of each API supported by an instance.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiVersions {
+    /// The version of the Mastodon API, if known.
+    #[serde(default)]
+    pub mastodon: Option<u64>,
 }
 
 /// Usage data for this instance.
@@ -57,9 +69,11 @@ pub struct Thumbnail {
     /// A hash computed by [the BlurHash algorithm](https://github.com/woltapp/blurhash),
     /// for generating colorful preview thumbnails when media has not been
     /// downloaded yet.
-    pub blurhash: String,
+    #[serde(default)]
+    pub blurhash: Option<String>,
     /// Links to scaled resolution images, for high DPI screens.
-    pub versions: ThumbnailVersions,
+    #[serde(default)]
+    pub versions: Option<ThumbnailVersions>,
 }
 
 /// Links to scaled resolution images, for high DPI screens.
@@ -79,7 +93,7 @@ pub struct Contact {
     /// An email address that can be messaged regarding inquiries or issues.
     pub email: String,
     /// An account that can be contacted natively over the network regarding inquiries or issues.
-    pub account: Account,
+    pub account: Option<Account>,
 }
 
 /// Information about registering for this website.
@@ -101,6 +115,9 @@ pub struct Rule {
     pub id: RuleId,
     /// The rule to be followed.
     pub text: String,
+    /// Longer-form description of the rule. Empty before Mastodon 4.3.
+    #[serde(default)]
+    pub hint: String,
 }
 
 /// Represents a domain that is blocked by the instance.
@@ -121,8 +138,9 @@ pub struct DomainBlock {
 /// Weekly activity on an instance
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
 pub struct Activity {
-    /// UNIX Timestamp at midnight at the first day of the week.
-    pub week: String,
+    /// Midnight at the first day of the week.
+    #[serde(with = "conversion::date_from_timestamp")]
+    pub week: Date,
     /// The number of Statuses created since the week began (cast from an integer)
     #[serde(with = "conversion::string_to::u64")]
     pub statuses: u64,
@@ -163,6 +181,63 @@ pub struct ExtendedDescription {
     pub content: String,
 }
 
+/// Represents the privacy policy of the instance.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/entities/PrivacyPolicy/)
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PrivacyPolicy {
+    /// A timestamp of when the privacy policy was last updated.
+    #[serde(with = "iso8601")]
+    pub updated_at: OffsetDateTime,
+    /// The rendered HTML content of the privacy policy.
+    pub content: String,
+}
+
+/// Represents the terms of service of the instance.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/entities/TermsOfService/)
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct TermsOfService {
+    /// The date these terms of service came or will come into effect, in
+    /// `YYYY-MM-DD` format.
+    pub effective_date: String,
+    /// Whether these terms of service are currently in effect.
+    pub effective: bool,
+    /// The rendered HTML content of the terms of service.
+    pub content: String,
+    /// The date of the next version of the terms of service, if there is one
+    /// which isn't in effect yet, in `YYYY-MM-DD` format.
+    pub succeeded_by: Option<String>,
+}
+
+/// The languages the instance can translate statuses between, as returned by
+/// `/api/v1/instance/translation_languages`: the target languages for each
+/// source language, as language codes such as `en` or `pt-BR`. Empty if
+/// translation isn't enabled on the instance.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/methods/instance/#translation_languages)
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct TranslationLanguages {
+    /// The target languages, by source language.
+    pub languages: std::collections::HashMap<String, Vec<String>>,
+}
+
+impl TranslationLanguages {
+    /// Whether statuses in `from` can be translated into `to`, e.g. to
+    /// decide whether to offer translating a status into the user's
+    /// language. Regional variants, such as `pt-BR`, count as their
+    /// language.
+    pub fn can_translate(&self, from: Language, to: Language) -> bool {
+        let code = |lang: Language| lang.to_639_1().unwrap_or_else(|| lang.to_639_3());
+        let matches =
+            |candidate: &str, lang: Language| candidate.split('-').next() == Some(code(lang));
+        self.languages.iter().any(|(source, targets)| {
+            matches(source, from) && targets.iter().any(|target| matches(target, to))
+        })
+    }
+}
+
 pub mod configuration {
     use serde::{Deserialize, Serialize};
     use url::Url;
@@ -598,6 +673,13 @@ mod tests {
 }"##;
         let subject: Instance = serde_json::from_str(example).expect("deserialize");
         assert_eq!(subject.domain, "mastodon.social");
+        assert_eq!(subject.configuration.statuses.max_characters, 500);
+        assert_eq!(
+            subject.thumbnail.blurhash.as_deref(),
+            Some("UeKUpFxuo~R%0nW;WCnhF6RjaJt757oJodS$")
+        );
+        assert!(subject.contact.account.is_some());
+        assert_eq!(subject.api_versions.mastodon, None);
         let rule = &subject.rules[0];
         assert_eq!(rule.id, RuleId::new("1"));
         assert_eq!(
@@ -606,6 +688,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_minimal_thumbnail_and_contact() {
+        let thumbnail: Thumbnail = serde_json::from_str(
+            r#"{"url": "https://example.social/packs/media/images/preview.png"}"#,
+        )
+        .expect("deserialize thumbnail");
+        assert!(thumbnail.blurhash.is_none());
+        assert!(thumbnail.versions.is_none());
+        let contact: Contact =
+            serde_json::from_str(r#"{"email": "", "account": null}"#).expect("deserialize contact");
+        assert!(contact.account.is_none());
+        let versions: ApiVersions =
+            serde_json::from_str(r#"{"mastodon": 2}"#).expect("deserialize api_versions");
+        assert_eq!(versions.mastodon, Some(2));
+        let versions: ApiVersions = serde_json::from_str("{}").expect("deserialize api_versions");
+        assert_eq!(versions.mastodon, None);
+    }
+
     #[test]
     fn test_extended_description() {
         let example = r#"{
@@ -620,6 +720,76 @@ mod tests {
         );
         assert_eq!(subject.content, "<p>For inquiries not related specifically to the operation of this server, such as press inquiries, please contact <a href=\"mailto:press@joinmastodon.org\">press@joinmastodon.org</a>.</p>\n\n<h2>Funding</h2>\n\n<p>This server is crowdfunded by <a href=\"https://patreon.com/mastodon\">Patreon donations</a>. For a list of sponsors, see <a href=\"https://joinmastodon.org/sponsors\">joinmastodon.org</a>.</p>\n\n<h2>Reporting and moderation</h2>\n\n<p>When reporting accounts, please make sure to include at least a few posts that show rule-breaking behaviour, when applicable. If there is any additional context that might help make a decision, please also include it in the comment. This is especially important when the content is in a language nobody on the moderation team speaks.</p>\n\n<p>We usually handle reports within 24 hours. Please mind that you are not notified when a report you have made has led to a punitive action, and that not all punitive actions are externally visible. For first time offenses, we may opt to delete offending content, escalating to harsher measures on repeat offenses.</p>\n\n<h2>Impressum</h2>\n\n<p>Mastodon gGmbH<br>\nMühlenstraße 8a<br>\n14167 Berlin<br>\nGermany</p>\n\n<p>E-Mail-Adresse: hello@joinmastodon.org</p>\n\n<p>Vertretungsberechtigt: Eugen Rochko (Geschäftsführer)</p>\n\n<p>Umsatzsteuer Identifikationsnummer (USt-ID): DE344258260</p>\n\n<p>Handelsregister<br>\nGeführt bei: Amtsgericht Charlottenburg<br>\nNummer: HRB 230086 B</p>\n");
     }
+    #[test]
+    fn test_privacy_policy() {
+        let example = r#"{
+          "updated_at": "2022-10-07T00:00:00.000+00:00",
+          "content": "<p>This privacy policy describes how example.com collects, protects and uses the personally identifiable information you may provide through the example.com website or its API.</p>"
+        }"#;
+        let subject: PrivacyPolicy = serde_json::from_str(example).expect("deserialize");
+        assert_eq!(subject.updated_at.year(), 2022);
+        assert!(subject.content.starts_with("<p>This privacy policy"));
+    }
+
+    #[test]
+    fn test_terms_of_service() {
+        let example = r#"{
+          "effective_date": "2025-04-15",
+          "effective": true,
+          "content": "<p>Foo bar newer</p>",
+          "succeeded_by": null
+        }"#;
+        let subject: TermsOfService = serde_json::from_str(example).expect("deserialize");
+        assert_eq!(subject.effective_date, "2025-04-15");
+        assert!(subject.effective);
+        assert!(subject.succeeded_by.is_none());
+    }
+
+    #[test]
+    fn test_translation_languages() {
+        let example = r#"{
+          "en": ["de", "es", "pt-BR"],
+          "de": ["en"]
+        }"#;
+        let subject: TranslationLanguages = serde_json::from_str(example).expect("deserialize");
+        assert!(subject.can_translate(Language::Eng, Language::Por));
+        assert!(subject.can_translate(Language::Deu, Language::Eng));
+        assert!(!subject.can_translate(Language::Deu, Language::Spa));
+        assert!(!TranslationLanguages::default().can_translate(Language::Eng, Language::Deu));
+    }
+
+    #[test]
+    fn test_rule_with_hint() {
+        let example = r#"{
+          "id": "2",
+          "text": "No racism, sexism, homophobia, transphobia, ableism, xenophobia, or casteism.",
+          "hint": "Transphobic behavior such as intentional misgendering and deadnaming is strictly prohibited."
+        }"#;
+        let subject: Rule = serde_json::from_str(example).expect("deserialize");
+        assert_eq!(subject.id, RuleId::new("2"));
+        assert!(subject.hint.starts_with("Transphobic behavior"));
+    }
+
+    #[test]
+    fn test_activity() {
+        let example = r#"[
+          {
+            "week": "1574640000",
+            "statuses": "37125",
+            "logins": "14239",
+            "registrations": "542"
+          }
+        ]"#;
+        let subject: Vec<Activity> = serde_json::from_str(example).expect("deserialize");
+        let week = &subject[0];
+        assert_eq!(week.week.year(), 2019);
+        assert_eq!(week.week.month(), time::Month::November);
+        assert_eq!(week.week.day(), 25);
+        assert_eq!(week.statuses, 37125);
+        assert_eq!(week.logins, 14239);
+        assert_eq!(week.registrations, 542);
+    }
+
     #[test]
     fn test_domain_block_example() {
         let example = r#"{
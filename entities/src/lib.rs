@@ -9,6 +9,8 @@ pub use error::Error;
 
 /// Data structures for ser/de of account-related resources
 pub mod account;
+/// Data structures for ser/de of the warnings moderators give to accounts
+pub mod account_warning;
 /// Data structures for ser/de of admin-related resources
 pub mod admin;
 /// Data structures for ser/de of announcement-related resources
@@ -35,6 +37,8 @@ pub mod event;
 pub mod filter;
 /// Builders for form submissions
 pub mod forms;
+/// Converting the HTML of statuses and profiles to plain text
+pub mod html;
 /// Type-safe ID values
 pub mod ids;
 pub use ids::*;
@@ -48,6 +52,9 @@ pub mod marker;
 pub mod mention;
 /// Data structures for ser/de of notification-related resources
 pub mod notification;
+/// Data structures for ser/de of the extensions made by Pleroma and Akkoma
+#[cfg(feature = "pleroma")]
+pub mod pleroma;
 /// Data structures for working with user preferences.
 pub mod preferences;
 /// Data structures for ser/de of push-subscription-related resources
@@ -79,9 +86,10 @@ pub mod prelude {
             self, /* for
                   SuggestionSource, Suggestion, FamiliarFollowers, Color, Credentials,
                   CredentialsBuilder */
-            Account, CredentialAccount, Role, RolePermissions, Source,
+            Account, AccountRole, CredentialAccount, Role, RolePermissions, Source,
         },
-        admin::prelude::*,
+        account_warning::{self /* for Action, Appeal, AppealState */, AccountWarning},
+        admin::{self, prelude::*},
         announcement::{self /* for Status, Account, Reaction */, Announcement},
         application::Application,
         attachment::{
@@ -89,34 +97,42 @@ pub mod prelude {
             Attachment, MediaType, ProcessedAttachment,
         },
         auth::{self, prelude::*},
-        card::{self /* for Type */, Card, TrendsLink},
+        card::{self /* for Type, Author */, Card, TrendsLink},
         context::Context,
         conversation::Conversation,
         custom_emoji::CustomEmoji,
-        event::Event,
+        event::{AnnouncementReaction, Event},
         filter::{self /* for Action, Keyword, Status, v1, Result, Context */, Filter},
         forms,
         ids::*,
         instance::{
             self, /* for
                   Usage, Users, Thumbnail, ThumbnailVersions, Contact, Registrations,
-                  Rule, Activity, Configuration, ExtendedDescription */
+                  Rule, Activity, Configuration, ExtendedDescription, ApiVersions,
+                  PrivacyPolicy, TermsOfService */
             DomainBlock, Instance,
         },
         list::{self /* for RepliesPolicy */, List},
-        marker::Marker,
+        marker::{self /* for Timeline, Markers */, Marker},
         mention::Mention,
-        notification::{self /* for Type */, Notification},
+        notification::{
+            self, /* for Type, Group, GroupedResults, Policy, PolicyAction, PolicySummary,
+                  Request, RequestsMerged, AnnualReportEvent */
+            Notification,
+        },
         preferences::Preferences,
         push::{
-            self, /* for Alerts, AdminAlerts, add_subscription, update_data */
+            self, /* for Alerts, AdminAlerts, Notification, Policy, add_subscription, update_data */
             Subscription,
         },
-        relationship::Relationship,
+        relationship::{
+            self, /* for SeveranceType */
+            Relationship, RelationshipSeveranceEvent,
+        },
         report::{self /* for Category */, Report},
         search_result::SearchResult,
         status::{
-            self, /* for Scheduled, Source, Tag, Application, FeaturedTag, Mention*/
+            self, /* for Scheduled, Source, Tag, Application, FeaturedTag, Mention, Translation */
             NewStatus, NewStatusBuilder, Poll, PollBuilder, Status,
         },
         tag::{self /* for History */, Tag},
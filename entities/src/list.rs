@@ -14,10 +14,13 @@ pub struct List {
     pub title: String,
     /// Which replies should be shown in the list.
     pub replies_policy: RepliesPolicy,
+    /// Whether members of the list are removed from the home timeline.
+    #[serde(default)]
+    pub exclusive: bool,
 }
 
 /// Which replies should be shown in the list.
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, is_enum_variant)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, is_enum_variant)]
 #[serde(rename_all = "lowercase")]
 pub enum RepliesPolicy {
     /// Show replies to any followed user
@@ -43,5 +46,19 @@ mod tests {
         assert_eq!(subject.id, ListId::new("13585"));
         assert_eq!(subject.title, "test");
         assert!(subject.replies_policy.is_list());
+        assert!(!subject.exclusive);
+    }
+
+    #[test]
+    fn test_deserialize_exclusive() {
+        let example = r#"{
+          "id": "13585",
+          "title": "test",
+          "replies_policy": "none",
+          "exclusive": true
+        }"#;
+        let subject: List = serde_json::from_str(example).expect("deserialize");
+        assert!(subject.replies_policy.is_none());
+        assert!(subject.exclusive);
     }
 }
@@ -1,4 +1,5 @@
 use crate::StatusId;
+use derive_is_enum_variant::is_enum_variant;
 use serde::{Deserialize, Serialize};
 use time::{serde::iso8601, OffsetDateTime};
 
@@ -11,10 +12,32 @@ pub struct Marker {
     pub last_read_id: StatusId,
     /// An incrementing counter, used for locking to prevent write conflicts.
     pub version: i64,
+    /// When the marker was set.
     #[serde(with = "iso8601")]
     pub updated_at: OffsetDateTime,
 }
 
+/// A timeline which can have a read position saved with a [`Marker`].
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, is_enum_variant)]
+#[serde(rename_all = "lowercase")]
+pub enum Timeline {
+    /// The home timeline.
+    Home,
+    /// The notifications list.
+    Notifications,
+}
+
+/// The saved read positions of each requested timeline.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Markers {
+    /// The read position in the home timeline, if requested and set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub home: Option<Marker>,
+    /// The read position in the notifications list, if requested and set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notifications: Option<Marker>,
+}
+
 #[cfg(test)]
 mod tests {
     use time::format_description::well_known::Iso8601;
@@ -37,4 +60,19 @@ mod tests {
                 .expect("parse updated time")
         );
     }
+
+    #[test]
+    fn test_deserialize_markers() {
+        let example = r#"{
+          "notifications": {
+            "last_read_id": "35098814",
+            "version": 361,
+            "updated_at": "2019-11-26T22:37:25.239Z"
+          }
+        }"#;
+        let subject: Markers = serde_json::from_str(example).expect("deserialize");
+        assert!(subject.home.is_none());
+        let notifications = subject.notifications.expect("notifications marker");
+        assert_eq!(notifications.version, 361);
+    }
 }
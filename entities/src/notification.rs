@@ -1,8 +1,13 @@
 //! Module containing all info about notifications.
 
-use crate::{admin::Report, NotificationId};
+use crate::{admin::Report, AccountId, NotificationId, NotificationRequestId, StatusId};
 
-use super::{account::Account, status::Status};
+use super::{
+    account::{Account, PartialAccountWithAvatar},
+    account_warning::AccountWarning,
+    relationship::RelationshipSeveranceEvent,
+    status::Status,
+};
 use derive_is_enum_variant::is_enum_variant;
 use serde::{Deserialize, Serialize};
 use time::{serde::iso8601, OffsetDateTime};
@@ -30,6 +35,27 @@ pub struct Notification {
     /// Report that was the object of the notification. Attached when type of
     /// the notification is `admin.report`.
     pub report: Option<Report>,
+    /// The event which removed some of the user's relationships. Attached
+    /// when type of the notification is `severed_relationships`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event: Option<RelationshipSeveranceEvent>,
+    /// The moderation action taken against the user's account. Attached
+    /// when type of the notification is `moderation_warning`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub moderation_warning: Option<AccountWarning>,
+    /// The year the user's annual report was generated for. Attached when
+    /// type of the notification is `annual_report`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub annual_report: Option<AnnualReportEvent>,
+    /// The emoji someone reacted with. Attached when type of the
+    /// notification is `pleroma:emoji_reaction`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub emoji: Option<String>,
+    /// Any other fields sent with the notification, e.g. the `pleroma`
+    /// object with its `is_seen` flag. Requires the `extra-fields` feature.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
 }
 
 /// The type of notification.
@@ -58,4 +84,316 @@ pub enum Type {
     /// A new report has been filed
     #[serde(rename = "admin.report")]
     Report,
+    /// Some of your follow relationships have been severed by a moderation
+    /// or block event
+    SeveredRelationships,
+    /// A moderator has taken action against your account
+    ModerationWarning,
+    /// Your annual report, a summary of your year on the server, is ready
+    AnnualReport,
+    /// Someone reacted to one of your statuses with an emoji (Pleroma and
+    /// Akkoma only)
+    #[serde(rename = "pleroma:emoji_reaction")]
+    EmojiReaction,
+}
+
+/// The annual report attached to an `annual_report` notification.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct AnnualReportEvent {
+    /// The year the report is for, e.g. `"2024"`.
+    pub year: String,
+}
+
+/// The notification filtering policy of the authenticated user.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/entities/NotificationPolicy/)
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Policy {
+    /// How to handle notifications from accounts the user doesn't follow.
+    pub for_not_following: PolicyAction,
+    /// How to handle notifications from accounts which don't follow the user.
+    pub for_not_followers: PolicyAction,
+    /// How to handle notifications from accounts created in the past 30 days.
+    pub for_new_accounts: PolicyAction,
+    /// How to handle private mentions from accounts the user doesn't follow.
+    pub for_private_mentions: PolicyAction,
+    /// How to handle notifications from accounts limited by moderators.
+    pub for_limited_accounts: PolicyAction,
+    /// Counts of notifications currently held back by this policy.
+    pub summary: PolicySummary,
+}
+
+/// What to do with a notification matched by a [`Policy`].
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, is_enum_variant)]
+#[serde(rename_all = "lowercase")]
+pub enum PolicyAction {
+    /// Show the notification normally.
+    Accept,
+    /// File the notification under a notification request.
+    Filter,
+    /// Drop the notification entirely.
+    Drop,
+}
+
+/// Summary of the notifications held back by a [`Policy`].
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PolicySummary {
+    /// Number of distinct accounts with filtered notifications.
+    pub pending_requests_count: u64,
+    /// Total number of filtered notifications.
+    pub pending_notifications_count: u64,
+}
+
+/// A group of filtered notifications from a single account.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/entities/NotificationRequest/)
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct Request {
+    /// The id of the notification request in the database.
+    pub id: NotificationRequestId,
+    /// When the first filtered notification from that account was created.
+    #[serde(with = "iso8601")]
+    pub created_at: OffsetDateTime,
+    /// When the notification request was last updated.
+    #[serde(with = "iso8601")]
+    pub updated_at: OffsetDateTime,
+    /// The account that performed the actions that generated the filtered
+    /// notifications.
+    pub account: Account,
+    /// How many of this account's notifications were filtered, as a string.
+    pub notifications_count: String,
+    /// The most recent status associated with a filtered notification.
+    pub last_status: Option<Status>,
+}
+
+/// Whether the notification requests accepted so far have been merged into
+/// the regular notifications list.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub struct RequestsMerged {
+    /// `true` once every accepted request has been merged.
+    pub merged: bool,
+}
+
+/// A group of notifications of the same type about the same thing,
+/// e.g. all the favourites of one status.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/entities/GroupedNotificationsResults/#NotificationGroup)
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct Group {
+    /// Identifies the group. Ungrouped notifications have a key of the form
+    /// `ungrouped-{notification_id}`.
+    pub group_key: String,
+    /// Total number of notifications in the group.
+    pub notifications_count: u64,
+    /// The type of every notification in the group.
+    #[serde(rename = "type")]
+    pub notification_type: Type,
+    /// ID of the most recent notification in the group.
+    pub most_recent_notification_id: NotificationId,
+    /// ID of the oldest notification from this group represented in the
+    /// current page, for use with `max_id` when paginating.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_min_id: Option<NotificationId>,
+    /// ID of the newest notification from this group represented in the
+    /// current page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_max_id: Option<NotificationId>,
+    /// When the newest notification from this group in the current page was
+    /// created.
+    #[serde(
+        with = "iso8601::option",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub latest_page_notification_at: Option<OffsetDateTime>,
+    /// IDs of some of the accounts who most recently triggered notifications
+    /// in this group. Look them up in [`GroupedResults::accounts`] or
+    /// [`GroupedResults::partial_accounts`].
+    pub sample_account_ids: Vec<AccountId>,
+    /// ID of the status the notifications are about, found in
+    /// [`GroupedResults::statuses`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_id: Option<StatusId>,
+    /// The report the notification is about, for `admin.report` groups.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub report: Option<Report>,
+    /// The event the notification is about, for `severed_relationships`
+    /// groups.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event: Option<RelationshipSeveranceEvent>,
+    /// The moderation action the notification is about, for
+    /// `moderation_warning` groups.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub moderation_warning: Option<AccountWarning>,
+}
+
+/// The response of `GET /api/v2/notifications`. Accounts and statuses are
+/// deduplicated into top-level lists and referenced by ID from each group.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/entities/GroupedNotificationsResults/)
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct GroupedResults {
+    /// Accounts referenced by the notification groups.
+    pub accounts: Vec<Account>,
+    /// Partial accounts referenced by the notification groups, when
+    /// `expand_accounts=partial_avatars` was requested.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub partial_accounts: Vec<PartialAccountWithAvatar>,
+    /// Statuses referenced by the notification groups.
+    pub statuses: Vec<Status>,
+    /// The notification groups themselves.
+    pub notification_groups: Vec<Group>,
+}
+
+impl GroupedResults {
+    /// Find a full account referenced by a group.
+    pub fn account(&self, id: &AccountId) -> Option<&Account> {
+        self.accounts.iter().find(|account| &account.id == id)
+    }
+
+    /// Find a status referenced by a group.
+    pub fn status(&self, id: &StatusId) -> Option<&Status> {
+        self.statuses.iter().find(|status| &status.id == id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serde_value_test;
+
+    serde_value_test!(test_policy(Policy): r#"{
+        "for_not_following": "accept",
+        "for_not_followers": "filter",
+        "for_new_accounts": "drop",
+        "for_private_mentions": "filter",
+        "for_limited_accounts": "filter",
+        "summary": {
+            "pending_requests_count": 1,
+            "pending_notifications_count": 3
+        }
+    }"#);
+
+    serde_value_test!(test_merged(RequestsMerged): r#"{"merged": false}"#);
+
+    #[test]
+    fn test_deserialize_group() {
+        let example = r#"{
+            "group_key": "favourite-113010503322889311-479000",
+            "notifications_count": 2,
+            "type": "favourite",
+            "most_recent_notification_id": "296",
+            "page_min_id": "295",
+            "page_max_id": "296",
+            "latest_page_notification_at": "2024-08-23T08:10:03.000Z",
+            "sample_account_ids": ["1", "2"],
+            "status_id": "113010503322889311"
+        }"#;
+        let subject: Group = serde_json::from_str(example).expect("deserialize");
+        assert_eq!(subject.notifications_count, 2);
+        assert!(subject.notification_type.is_favourite());
+        assert_eq!(subject.page_min_id, Some(NotificationId::new("295")));
+        assert_eq!(
+            subject.sample_account_ids,
+            vec![AccountId::new("1"), AccountId::new("2")]
+        );
+        assert_eq!(subject.status_id, Some(StatusId::new("113010503322889311")));
+        assert!(subject.latest_page_notification_at.is_some());
+        assert!(subject.report.is_none());
+    }
+
+    fn account() -> &'static str {
+        r#"{
+            "id": "1",
+            "username": "alice",
+            "acct": "alice",
+            "display_name": "Alice",
+            "locked": false,
+            "bot": false,
+            "created_at": "2022-01-01T00:00:00.000Z",
+            "note": "",
+            "url": "https://mastodon.example/@alice",
+            "avatar": "https://mastodon.example/avatar.png",
+            "avatar_static": "https://mastodon.example/avatar.png",
+            "header": "https://mastodon.example/header.png",
+            "header_static": "https://mastodon.example/header.png",
+            "followers_count": 0,
+            "following_count": 0,
+            "statuses_count": 0,
+            "last_status_at": null
+        }"#
+    }
+
+    #[test]
+    fn test_deserialize_severed_relationships() {
+        let example = format!(
+            r#"{{
+                "id": "38",
+                "type": "severed_relationships",
+                "created_at": "2024-09-30T12:00:00.000Z",
+                "account": {account},
+                "event": {{
+                    "id": "2",
+                    "type": "domain_block",
+                    "purged": false,
+                    "target_name": "spam.example",
+                    "followers_count": 0,
+                    "following_count": 5,
+                    "created_at": "2024-09-30T12:00:00.000Z"
+                }}
+            }}"#,
+            account = account()
+        );
+        let subject: Notification = serde_json::from_str(&example).expect("deserialize");
+        assert!(subject.notification_type.is_severed_relationships());
+        let event = subject.event.expect("event");
+        assert!(event.event_type.is_domain_block());
+        assert_eq!(event.following_count, 5);
+    }
+
+    #[test]
+    fn test_deserialize_moderation_warning() {
+        let example = format!(
+            r#"{{
+                "id": "39",
+                "type": "moderation_warning",
+                "created_at": "2024-09-30T12:00:00.000Z",
+                "account": {account},
+                "moderation_warning": {{
+                    "id": "3",
+                    "action": "silence",
+                    "text": "",
+                    "status_ids": null,
+                    "target_account": {account},
+                    "appeal": null,
+                    "created_at": "2024-09-30T12:00:00.000Z"
+                }}
+            }}"#,
+            account = account()
+        );
+        let subject: Notification = serde_json::from_str(&example).expect("deserialize");
+        assert!(subject.notification_type.is_moderation_warning());
+        let warning = subject.moderation_warning.expect("moderation warning");
+        assert!(warning.action.is_silence());
+        assert_eq!(warning.status_ids, None);
+    }
+
+    #[test]
+    fn test_deserialize_annual_report() {
+        let example = format!(
+            r#"{{
+                "id": "40",
+                "type": "annual_report",
+                "created_at": "2024-12-10T12:00:00.000Z",
+                "account": {account},
+                "annual_report": {{"year": "2024"}}
+            }}"#,
+            account = account()
+        );
+        let subject: Notification = serde_json::from_str(&example).expect("deserialize");
+        assert!(subject.notification_type.is_annual_report());
+        assert_eq!(subject.annual_report.expect("annual report").year, "2024");
+    }
 }
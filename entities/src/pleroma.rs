@@ -0,0 +1,77 @@
+//! Extensions to the Mastodon API made by Pleroma and Akkoma.
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::account;
+
+/// The `pleroma` object of a status.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Status {
+    /// The emoji reactions to the status, one per emoji.
+    #[serde(default)]
+    pub emoji_reactions: Vec<EmojiReaction>,
+}
+
+/// The `pleroma` object of an account.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Account {
+    /// The ActivityPub IDs of the other accounts of the same person, which
+    /// the account can be moved from or to.
+    #[serde(default)]
+    pub also_known_as: Vec<Url>,
+}
+
+/// The aliases of the authenticated user's account, as listed by
+/// `GET /api/pleroma/aliases`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Aliases {
+    /// The accounts, as `username@domain`.
+    pub aliases: Vec<String>,
+}
+
+/// Everyone who reacted to a status with the same emoji.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmojiReaction {
+    /// The emoji: either a unicode emoji, or the shortcode of a custom emoji.
+    pub name: String,
+    /// How many accounts reacted with this emoji.
+    pub count: u64,
+    /// Whether the current user reacted with this emoji.
+    #[serde(default)]
+    pub me: bool,
+    /// The image of a custom emoji. Only sent by Akkoma.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<Url>,
+    /// The accounts which reacted. Only included when listing the reactions
+    /// to a status.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub accounts: Vec<account::Account>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_emoji_reactions() {
+        let example = r#"{
+          "emoji_reactions": [
+            {"name": "👍", "count": 2, "me": true},
+            {"name": "blobcat", "count": 1, "me": false, "url": "https://akkoma.example/emoji/blobcat.png"}
+          ],
+          "local": true
+        }"#;
+        let subject: Status = serde_json::from_str(example).unwrap();
+        assert_eq!(subject.emoji_reactions.len(), 2);
+        assert_eq!(subject.emoji_reactions[0].name, "👍");
+        assert_eq!(subject.emoji_reactions[0].count, 2);
+        assert!(subject.emoji_reactions[0].me);
+        assert!(subject.emoji_reactions[0].url.is_none());
+        assert_eq!(
+            subject.emoji_reactions[1].url.as_ref().unwrap().as_str(),
+            "https://akkoma.example/emoji/blobcat.png"
+        );
+        assert!(subject.emoji_reactions[1].accounts.is_empty());
+    }
+}
@@ -1,7 +1,7 @@
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
-use crate::SubscriptionId;
+use crate::{notification::Type, NotificationId, SubscriptionId};
 
 /// Represents the `alerts` key of the `Subscription` object
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, Builder)]
@@ -55,6 +55,21 @@ pub struct AdminAlerts {
     pub report: Option<bool>,
 }
 
+impl AlertsBuilder {
+    /// Receive a push notification when a new user has signed up? Only
+    /// delivered to moderators.
+    pub fn sign_up(&mut self, v: bool) -> &mut Self {
+        self.admin.get_or_insert_with(AdminAlerts::default).sign_up = Some(v);
+        self
+    }
+    /// Receive a push notification when a new report has been filed? Only
+    /// delivered to moderators.
+    pub fn report(&mut self, v: bool) -> &mut Self {
+        self.admin.get_or_insert_with(AdminAlerts::default).report = Some(v);
+        self
+    }
+}
+
 impl Alerts {
     pub fn sign_up(&mut self, v: bool) -> &mut Self {
         self.admin.sign_up = Some(v);
@@ -83,6 +98,21 @@ impl Alerts {
         !self.is_none()
     }
 }
+/// Whose notifications should be pushed to a subscription.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Policy {
+    /// Notifications from anyone. The default.
+    #[default]
+    All,
+    /// Only notifications from accounts the user follows.
+    Followed,
+    /// Only notifications from accounts which follow the user.
+    Follower,
+    /// No notifications.
+    None,
+}
+
 /// Represents a subscription to the push streaming server.
 ///
 /// See also [the API documentation](https://docs.joinmastodon.org/entities/WebPushSubscription/)
@@ -96,14 +126,43 @@ pub struct Subscription {
     pub server_key: String,
     /// Which alerts should be delivered to the endpoint.
     pub alerts: Alerts,
+    /// Whose notifications are delivered to the endpoint. Added in 3.5.0.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy: Option<Policy>,
+    /// Whether the notifications are encrypted as standardized by RFC 8291,
+    /// rather than by an earlier draft of it. Added in 4.4.0.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub standard: Option<bool>,
+}
+
+/// The payload of a Web Push message sent to a subscription, once decrypted.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/methods/push/)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Notification {
+    /// The access token of the subscription the notification was pushed to,
+    /// to tell which account it's for.
+    pub access_token: String,
+    /// The locale the title and body are written in.
+    pub preferred_locale: String,
+    /// The ID of the notification, to fetch it from the API.
+    pub notification_id: NotificationId,
+    /// The type of event the notification is for.
+    pub notification_type: Type,
+    /// The avatar of the account which caused the notification.
+    pub icon: String,
+    /// A summary of the notification, ready to be displayed.
+    pub title: String,
+    /// The text of the status or report, if any, as plain text.
+    pub body: String,
 }
 
 pub mod add_subscription {
     use serde::Serialize;
 
-    use super::Alerts;
+    use super::{Alerts, Policy};
 
-    #[derive(Debug, Clone, PartialEq, Eq, Serialize, Default)]
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
     pub struct Form {
         pub subscription: Subscription,
         pub data: Option<Data>,
@@ -113,6 +172,8 @@ pub mod add_subscription {
     pub struct Subscription {
         pub endpoint: String,
         pub keys: Keys,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub standard: Option<bool>,
     }
 
     #[derive(Debug, Clone, PartialEq, Eq, Serialize, Default)]
@@ -124,22 +185,27 @@ pub mod add_subscription {
     #[derive(Debug, Clone, PartialEq, Eq, Serialize, Default)]
     pub struct Data {
         pub alerts: Option<Alerts>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub policy: Option<Policy>,
     }
 }
 
 pub mod update_data {
     use serde::Serialize;
 
-    use super::Alerts;
+    use super::{Alerts, Policy};
+    use crate::SubscriptionId;
 
     #[derive(Debug, Clone, PartialEq, Eq, Serialize, Default)]
     pub struct Data {
         pub alerts: Option<Alerts>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub policy: Option<Policy>,
     }
 
-    #[derive(Debug, Clone, PartialEq, Eq, Serialize, Default)]
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
     pub struct Form {
-        pub id: String,
+        pub id: SubscriptionId,
         pub data: Data,
     }
 }
@@ -175,6 +241,59 @@ mod tests {
         assert!(subject.alerts.status.is_none());
         assert!(subject.alerts.follow_request.is_none());
         assert!(subject.alerts.update.is_none());
+        assert!(subject.policy.is_none());
+        assert!(subject.standard.is_none());
         assert_eq!(subject.server_key, "BCk-QqERU0q-CfYZjcuB6lnyyOYfJ2AifKqfeGIm7Z-HiTU5T9eTG5GxVA0_OH5mMlI4UkkDTpaZwozy0TzdZ2M=");
     }
+
+    #[test]
+    fn test_deserialize_notification() {
+        let example = r#"{
+          "access_token": "ZA-Yj3aBD8U8Cm7lKUp-lm9O9BmDgdhHzDeqsY8tlL0",
+          "preferred_locale": "en",
+          "notification_id": "12",
+          "notification_type": "admin.sign_up",
+          "icon": "https://mastodon.example/avatars/original/missing.png",
+          "title": "Alice signed up",
+          "body": ""
+        }"#;
+        let subject: Notification = serde_json::from_str(example).unwrap();
+        assert_eq!(subject.notification_id, NotificationId::new("12"));
+        assert_eq!(subject.notification_type, Type::SignUp);
+        assert_eq!(subject.title, "Alice signed up");
+    }
+
+    #[test]
+    fn test_deserialize_subscription_with_policy() {
+        let example = r#"{
+          "id": "328184",
+          "endpoint": "https://yourdomain.example/listener",
+          "standard": true,
+          "alerts": {
+            "mention": true,
+            "status": true,
+            "update": false,
+            "follow_request": true,
+            "admin.sign_up": true,
+            "admin.report": false
+          },
+          "policy": "followed",
+          "server_key": "BCk-QqERU0q-CfYZjcuB6lnyyOYfJ2AifKqfeGIm7Z-HiTU5T9eTG5GxVA0_OH5mMlI4UkkDTpaZwozy0TzdZ2M="
+        }"#;
+        let subject: Subscription = serde_json::from_str(example).unwrap();
+        assert_eq!(subject.policy, Some(Policy::Followed));
+        assert_eq!(subject.standard, Some(true));
+        assert_eq!(
+            subject.alerts,
+            AlertsBuilder::default()
+                .mention(true)
+                .status(true)
+                .update(false)
+                .follow_request(true)
+                .sign_up(true)
+                .report(false)
+                .build()
+                .unwrap()
+        );
+    }
 }
@@ -1,9 +1,11 @@
 //! module containing everything relating to a relationship with
 //! another account.
+use derive_is_enum_variant::is_enum_variant;
 use isolang::Language;
 use serde::{Deserialize, Serialize};
+use time::{serde::iso8601, OffsetDateTime};
 
-use crate::RelationshipId;
+use crate::{RelationshipId, RelationshipSeveranceEventId};
 
 /// Represents the relationship between accounts, such as following / blocking / muting / etc.
 ///
@@ -38,10 +40,51 @@ pub struct Relationship {
     /// Are you featuring this user on your profile?
     #[serde(default)]
     pub endorsed: bool,
-    /// This user’s profile bio
+    /// The private note you have set on this user, see
+    /// `Mastodon::set_account_note`.
     pub note: String,
 }
 
+/// A moderation or block event which removed follow relationships of the
+/// user, as attached to `severed_relationships` notifications.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/entities/RelationshipSeveranceEvent/)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RelationshipSeveranceEvent {
+    /// The ID of the event.
+    pub id: RelationshipSeveranceEventId,
+    /// What removed the relationships.
+    #[serde(rename = "type")]
+    pub event_type: SeveranceType,
+    /// Whether the list of severed relationships is unavailable because the
+    /// underlying issue has been purged.
+    pub purged: bool,
+    /// The name of the target of the moderation or block event, i.e. a
+    /// domain or an account's handle.
+    pub target_name: String,
+    /// How many of the user's followers were removed.
+    #[serde(default)]
+    pub followers_count: u64,
+    /// How many accounts the user followed were removed.
+    #[serde(default)]
+    pub following_count: u64,
+    /// When the event happened.
+    #[serde(with = "iso8601")]
+    pub created_at: OffsetDateTime,
+}
+
+/// What removed the relationships of a [`RelationshipSeveranceEvent`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, is_enum_variant)]
+#[serde(rename_all = "snake_case")]
+pub enum SeveranceType {
+    /// A moderator suspended a whole domain.
+    DomainBlock,
+    /// The user blocked a whole domain.
+    UserDomainBlock,
+    /// A moderator suspended a specific account.
+    AccountSuspension,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,4 +121,23 @@ mod tests {
         assert!(!subject.endorsed);
         assert!(subject.note.is_empty());
     }
+
+    #[test]
+    fn test_deserialize_severance_event() {
+        let example = r#"{
+          "id": "1",
+          "type": "user_domain_block",
+          "purged": false,
+          "target_name": "spam.example",
+          "followers_count": 3,
+          "following_count": 1,
+          "created_at": "2024-09-30T12:00:00.000Z"
+        }"#;
+        let subject: RelationshipSeveranceEvent =
+            serde_json::from_str(example).expect("deserialize");
+        assert!(subject.event_type.is_user_domain_block());
+        assert_eq!(subject.target_name, "spam.example");
+        assert_eq!(subject.followers_count, 3);
+        assert_eq!(subject.following_count, 1);
+    }
 }
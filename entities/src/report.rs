@@ -36,23 +36,21 @@ pub struct Report {
     pub target_account: Account,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, is_enum_variant)]
+/// The generic reason for a report.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, is_enum_variant)]
 #[serde(rename_all = "lowercase")]
 pub enum Category {
     /// Malicious, fake, or repetitive content
     Spam,
     /// Violates one or more specific rules
     Violation,
+    /// Illegal content
+    Legal,
     /// The default (catch-all) category
+    #[default]
     Other,
 }
 
-impl Default for Category {
-    fn default() -> Self {
-        Self::Other
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use time::format_description::well_known::Iso8601;
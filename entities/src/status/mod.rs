@@ -6,15 +6,18 @@ pub mod new;
 pub mod poll;
 pub mod scheduled;
 pub mod source;
+pub mod translation;
 
 pub use edit::Edit;
 use isolang::Language;
-pub use new::{NewStatus, NewStatusBuilder};
+pub use new::{ContentType, NewStatus, NewStatusBuilder};
 pub use poll::{Poll, PollBuilder};
 pub use scheduled::Status as Scheduled;
 pub use source::Source;
+pub use translation::Translation;
 
 use crate::{custom_emoji::CustomEmoji, filter};
+use derive_builder::Builder;
 
 use super::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -22,7 +25,32 @@ use time::{serde::iso8601, OffsetDateTime};
 use url::Url;
 
 /// Represents a status posted by an account.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+///
+/// Statuses can be built with [`Status::builder`], e.g. for tests, with the
+/// fields which aren't set taking their [`Default`] values.
+///
+/// ```
+/// use mastodon_async_entities::{account::Account, prelude::*};
+///
+/// let author = Account::builder().id("1").acct("alice").build().unwrap();
+/// let status = Status::builder()
+///     .id("100")
+///     .account(author)
+///     .content("<p>hello</p>")
+///     .visibility(Visibility::Unlisted)
+///     .build()
+///     .unwrap();
+/// assert_eq!(status.plain_content(), "hello");
+/// assert!(status.reblog.is_none());
+/// ```
+#[derive(Debug, Clone, Builder, Serialize, Deserialize, PartialEq)]
+#[builder(
+    name = "StatusEntityBuilder",
+    default,
+    setter(into),
+    build_fn(error = "crate::error::Error")
+)]
+#[non_exhaustive]
 pub struct Status {
     /// The ID of the status.
     pub id: StatusId,
@@ -104,6 +132,85 @@ pub struct Status {
     /// that matched this status.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub filtered: Vec<filter::Result>,
+    /// Whether the status was posted to the local instance only. Only sent
+    /// by the glitch-soc and Hometown forks of Mastodon.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_only: Option<bool>,
+    /// The extensions sent by Pleroma and Akkoma, such as emoji reactions.
+    /// Requires the `pleroma` feature.
+    #[cfg(feature = "pleroma")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pleroma: Option<crate::pleroma::Status>,
+    /// Fields of the status this type doesn't know about, such as the
+    /// `pleroma` object sent by Pleroma and Akkoma (unless the `pleroma`
+    /// feature is enabled), or ones added in a newer version of Mastodon.
+    /// Requires the `extra-fields` feature.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+impl Default for Status {
+    /// A public status with an empty ID and content, by the [`Default`]
+    /// account, posted at the Unix epoch, whose URI is
+    /// `https://mastodon.example/`.
+    fn default() -> Self {
+        Self {
+            id: StatusId::new(""),
+            uri: crate::helpers::placeholder_url(),
+            url: None,
+            account: Account::default(),
+            in_reply_to_id: None,
+            in_reply_to_account_id: None,
+            reblog: None,
+            content: String::new(),
+            created_at: OffsetDateTime::UNIX_EPOCH,
+            edited_at: None,
+            emojis: vec![],
+            replies_count: 0,
+            reblogs_count: 0,
+            favourites_count: 0,
+            reblogged: None,
+            favourited: None,
+            muted: None,
+            bookmarked: None,
+            pinned: None,
+            sensitive: false,
+            spoiler_text: String::new(),
+            visibility: Visibility::default(),
+            media_attachments: vec![],
+            mentions: vec![],
+            tags: vec![],
+            application: None,
+            language: None,
+            poll: None,
+            card: None,
+            text: None,
+            filtered: vec![],
+            local_only: None,
+            #[cfg(feature = "pleroma")]
+            pleroma: None,
+            #[cfg(feature = "extra-fields")]
+            extra: Default::default(),
+        }
+    }
+}
+
+impl Status {
+    /// Build a status, starting from the [`Default`] one.
+    pub fn builder() -> StatusEntityBuilder {
+        StatusEntityBuilder::default()
+    }
+
+    /// The content of the status as plain text, with paragraph breaks kept
+    /// and mentions, hashtags and links written as they're displayed. See
+    /// [`html::to_plain_text`](crate::html::to_plain_text).
+    ///
+    /// A reblog has no content of its own; the reblogged status's is in
+    /// [`reblog`](Self::reblog).
+    pub fn plain_content(&self) -> String {
+        crate::html::to_plain_text(&self.content)
+    }
 }
 
 /// Represents a hashtag used within the content of a status.
@@ -139,9 +246,10 @@ pub struct FeaturedTag {
     pub url: Url,
     /// The number of authored statuses containing this hashtag.
     pub statuses_count: u64,
-    /// The timestamp of the last authored status containing this hashtag.
-    #[serde(with = "iso8601")]
-    pub last_status_at: OffsetDateTime,
+    /// The timestamp of the last authored status containing this hashtag,
+    /// or `None` if no status has used it yet.
+    #[serde(default, with = "iso8601::option")]
+    pub last_status_at: Option<OffsetDateTime>,
 }
 
 #[cfg(test)]
@@ -150,9 +258,7 @@ mod tests {
 
     use super::*;
 
-    #[test]
-    fn test_deserialize_example() {
-        let example = r#"{
+    pub(crate) const EXAMPLE: &str = r#"{
             "id": "103270115826048975",
             "created_at": "2019-12-08T03:48:33.901Z",
             "in_reply_to_id": null,
@@ -231,6 +337,10 @@ mod tests {
             },
             "poll": null
         }"#;
+
+    #[test]
+    fn test_deserialize_example() {
+        let example = EXAMPLE;
         let status: Status = serde_json::from_str(example).expect("deserialize");
         assert_eq!(status.id, StatusId::new("103270115826048975"));
         assert_eq!(
@@ -297,7 +407,7 @@ mod tests {
         assert_eq!(acct.statuses_count, 61323);
         assert_eq!(acct.last_status_at, Some("2019-12-10T08:14:44.811Z".into()));
         assert!(acct.emojis.is_empty());
-        let field = acct.fields.get(0).expect("first field");
+        let field = acct.fields.first().expect("first field");
         assert_eq!(field.name, "Patreon");
         assert_eq!(field.value, "<a href=\"https://www.patreon.com/mastodon\" rel=\"me nofollow noopener noreferrer\" target=\"_blank\"><span class=\"invisible\">https://www.</span><span class=\"\">patreon.com/mastodon</span><span class=\"invisible\"></span}");
         assert!(field.verified_at.is_none());
@@ -354,8 +464,47 @@ mod tests {
         assert_eq!(subject.statuses_count, 70);
         assert_eq!(
             subject.last_status_at,
-            OffsetDateTime::parse("2022-08-29T12:03:35.061Z", &Iso8601::PARSING)
-                .expect("parse test time")
+            Some(
+                OffsetDateTime::parse("2022-08-29T12:03:35.061Z", &Iso8601::PARSING)
+                    .expect("parse test time")
+            )
+        );
+        let example = r#"{
+            "id": "628",
+            "name": "unused",
+            "url": "https://mastodon.social/@trwnh/tagged/unused",
+            "statuses_count": 0
+        }"#;
+        let subject: FeaturedTag = serde_json::from_str(example).expect("deserialize");
+        assert!(subject.last_status_at.is_none());
+    }
+
+    #[cfg(feature = "extra-fields")]
+    #[test]
+    fn test_extra_fields() {
+        let example = EXAMPLE.replacen('{', r#"{"akkoma": {"source": null},"#, 1);
+        let status: Status = serde_json::from_str(&example).expect("deserialize");
+        assert_eq!(status.extra.len(), 1);
+        assert!(status.extra["akkoma"]["source"].is_null());
+        assert_eq!(status.account.extra, Default::default());
+        let reserialized = serde_json::to_value(&status).expect("serialize");
+        assert!(reserialized["akkoma"].is_object());
+    }
+
+    #[cfg(feature = "pleroma")]
+    #[test]
+    fn test_pleroma_emoji_reactions() {
+        let status: Status = serde_json::from_str(EXAMPLE).expect("deserialize");
+        assert!(status.pleroma.is_none());
+        let example = EXAMPLE.replacen(
+            '{',
+            r#"{"pleroma": {"emoji_reactions": [{"name": "👍", "count": 1, "me": false}]},"#,
+            1,
         );
+        let status: Status = serde_json::from_str(&example).expect("deserialize");
+        let reactions = status.pleroma.expect("pleroma object").emoji_reactions;
+        assert_eq!(reactions.len(), 1);
+        assert_eq!(reactions[0].name, "👍");
+        assert_eq!(reactions[0].count, 1);
     }
 }
@@ -1,13 +1,16 @@
 use derive_builder::Builder;
 use isolang::Language;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{prelude::Visibility, AttachmentId};
+use crate::{
+    prelude::{Preferences, Status, Visibility},
+    AccountId, AttachmentId, StatusId,
+};
 
 /// Represents a post that can be sent to the POST /api/v1/status endpoint
 ///
 /// See also [the API documentation](https://docs.joinmastodon.org/methods/statuses/#form-data-parameters)
-#[derive(Debug, Builder, Default, Clone, Serialize, PartialEq, Eq)]
+#[derive(Debug, Builder, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[builder(build_fn(error = "crate::error::Error"))]
 pub struct NewStatus {
     /// The text content of the status. If media_ids is provided, this becomes
@@ -23,7 +26,7 @@ pub struct NewStatus {
     /// ID of the status being replied to, if status is a reply.
     #[serde(skip_serializing_if = "Option::is_none")]
     #[builder(default, setter(into, strip_option))]
-    pub in_reply_to_id: Option<String>,
+    pub in_reply_to_id: Option<StatusId>,
     /// Include Attachment IDs to be attached as media. If provided, status
     /// becomes optional, and poll cannot be used.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -46,11 +49,193 @@ pub struct NewStatus {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[builder(default, setter(strip_option))]
     pub language: Option<Language>,
-    /// Ignored by Mastodon servers, sets the content type for the status.
-    /// Mastodon "toots" are always `text/plain`, regardless of this value.
+    /// The format of the status text, e.g. [`ContentType::Markdown`], on
+    /// servers which support formats other than plain text, such as
+    /// Pleroma, Akkoma, GoToSocial and glitch-soc. Ignored by Mastodon,
+    /// whose statuses are always `text/plain`.
     #[serde(skip_serializing_if = "Option::is_none")]
     #[builder(default, setter(into, strip_option))]
     pub content_type: Option<String>,
+    /// Post the status to the local instance only, without federating it.
+    /// Only supported by the glitch-soc and Hometown forks of Mastodon.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    pub local_only: Option<bool>,
+}
+
+/// The format of a status's text.
+///
+/// Converts to the MIME type the API expects, so it can be passed to
+/// [`NewStatusBuilder::content_type`].
+///
+/// ```
+/// use mastodon_async_entities::{prelude::*, status::ContentType};
+///
+/// let status = NewStatusBuilder::default()
+///     .status("*emphasis*")
+///     .content_type(ContentType::Markdown)
+///     .build()
+///     .unwrap();
+/// assert_eq!(status.content_type.as_deref(), Some("text/markdown"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ContentType {
+    /// `text/plain`, the default
+    PlainText,
+    /// `text/markdown`
+    Markdown,
+    /// `text/html`
+    Html,
+    /// `text/bbcode`, supported by Pleroma and Akkoma
+    BbCode,
+    /// Any other MIME type
+    Other(String),
+}
+
+impl ContentType {
+    /// The MIME type of the format.
+    pub fn as_str(&self) -> &str {
+        match self {
+            ContentType::PlainText => "text/plain",
+            ContentType::Markdown => "text/markdown",
+            ContentType::Html => "text/html",
+            ContentType::BbCode => "text/bbcode",
+            ContentType::Other(mime) => mime,
+        }
+    }
+}
+
+impl std::fmt::Display for ContentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&str> for ContentType {
+    fn from(mime: &str) -> Self {
+        match mime {
+            "text/plain" => ContentType::PlainText,
+            "text/markdown" => ContentType::Markdown,
+            "text/html" => ContentType::Html,
+            "text/bbcode" => ContentType::BbCode,
+            other => ContentType::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<ContentType> for String {
+    fn from(content_type: ContentType) -> Self {
+        match content_type {
+            ContentType::Other(mime) => mime,
+            known => known.as_str().to_string(),
+        }
+    }
+}
+
+impl Serialize for ContentType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ContentType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mime = String::deserialize(deserializer)?;
+        Ok(ContentType::from(mime.as_str()))
+    }
+}
+
+impl NewStatusBuilder {
+    /// Fill in the visibility, sensitivity, and language of the status from
+    /// the user's posting defaults, unless they've already been set.
+    ///
+    /// ```
+    /// use mastodon_async_entities::prelude::*;
+    ///
+    /// let preferences: Preferences = serde_json::from_str(r#"{
+    ///     "posting:default:visibility": "unlisted",
+    ///     "posting:default:sensitive": false,
+    ///     "posting:default:language": null,
+    ///     "reading:expand:media": "default",
+    ///     "reading:expand:spoilers": false
+    /// }"#).unwrap();
+    /// let status = NewStatusBuilder::default()
+    ///     .status("a status")
+    ///     .sensitive(true)
+    ///     .defaults_from(&preferences)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(status.visibility, Some(Visibility::Unlisted));
+    /// assert_eq!(status.sensitive, Some(true));
+    /// assert_eq!(status.language, None);
+    /// ```
+    pub fn defaults_from(&mut self, preferences: &Preferences) -> &mut Self {
+        let defaults = preferences.posting.default;
+        self.visibility.get_or_insert(Some(defaults.visibility));
+        self.sensitive.get_or_insert(Some(defaults.sensitive));
+        if defaults.language.is_some() {
+            self.language.get_or_insert(defaults.language);
+        }
+        self
+    }
+
+    /// Make the status a reply to `status`, as Mastodon's web interface
+    /// does, `me` being the ID of the account posting it:
+    ///
+    /// - the author of `status` and the accounts it mentions are mentioned,
+    ///   except for `me`, before the text set so far, if any.
+    /// - the visibility is that of `status`, or the one set so far if it's
+    ///   less public.
+    /// - the content warning of `status` is kept, unless one was set.
+    ///
+    /// Replying to a boost replies to the boosted status.
+    ///
+    /// ```
+    /// use mastodon_async_entities::{prelude::*, AccountId};
+    ///
+    /// fn thank(status: &Status, me: &AccountId) -> NewStatus {
+    ///     NewStatusBuilder::default()
+    ///         .status("thanks!")
+    ///         .reply_to(status, me)
+    ///         .build()
+    ///         .unwrap()
+    /// }
+    /// ```
+    pub fn reply_to(&mut self, status: &Status, me: &AccountId) -> &mut Self {
+        let status = status.reblog.as_deref().unwrap_or(status);
+        self.in_reply_to_id = Some(Some(status.id.clone()));
+
+        let mut mentioned: Vec<&str> = vec![];
+        let accounts = std::iter::once((&status.account.id, status.account.acct.as_str())).chain(
+            status
+                .mentions
+                .iter()
+                .map(|mention| (&mention.id, mention.acct.as_str())),
+        );
+        for (id, acct) in accounts {
+            if id != me && !mentioned.contains(&acct) {
+                mentioned.push(acct);
+            }
+        }
+        let mut text: String = mentioned.iter().map(|acct| format!("@{acct} ")).collect();
+        if let Some(Some(existing)) = &self.status {
+            text.push_str(existing);
+        }
+        if !text.is_empty() {
+            self.status = Some(Some(text));
+        }
+
+        let visibility = match self.visibility {
+            Some(Some(visibility)) => visibility.min(status.visibility),
+            _ => status.visibility,
+        };
+        self.visibility = Some(Some(visibility));
+        if !status.spoiler_text.is_empty() {
+            self.spoiler_text
+                .get_or_insert(Some(status.spoiler_text.clone()));
+        }
+        self
+    }
 }
 
 #[cfg(test)]
@@ -74,10 +259,31 @@ mod tests {
             visibility: None,
             language: None,
             content_type: None,
+            local_only: None,
         };
         assert_eq!(s, expected);
     }
 
+    #[test]
+    fn test_content_type() {
+        let status = NewStatusBuilder::default()
+            .status("[b]bold[/b]")
+            .content_type(ContentType::BbCode)
+            .build()
+            .expect("Couldn't build status");
+        assert_eq!(
+            serde_json::to_string(&status).expect("Couldn't serialize status"),
+            r#"{"status":"[b]bold[/b]","content_type":"text/bbcode"}"#
+        );
+        assert_eq!(ContentType::from("text/html"), ContentType::Html);
+        assert_eq!(
+            ContentType::from("text/x.misskeymarkdown"),
+            ContentType::Other("text/x.misskeymarkdown".to_string())
+        );
+        let parsed: ContentType = serde_json::from_str(r#""text/markdown""#).unwrap();
+        assert_eq!(parsed, ContentType::Markdown);
+    }
+
     #[test]
     fn test_default_visibility() {
         let v: Visibility = Default::default();
@@ -125,4 +331,44 @@ mod tests {
             "{\"status\":\"a status\",\"language\":\"eng\"}"
         );
     }
+
+    #[test]
+    fn test_reply_to() {
+        let mut status: crate::status::Status =
+            serde_json::from_str(crate::status::tests::EXAMPLE).expect("deserialize");
+        status.visibility = Visibility::Unlisted;
+        status.spoiler_text = "cw".to_string();
+        for (id, acct) in [("42", "me"), ("7", "alice@example.com"), ("1", "Gargron")] {
+            status.mentions.push(crate::mention::Mention {
+                url: format!("https://example.com/@{acct}"),
+                username: acct.to_string(),
+                acct: acct.to_string(),
+                id: AccountId::new(id),
+            });
+        }
+        let me = AccountId::new("42");
+
+        let reply = NewStatusBuilder::default()
+            .status("hi")
+            .reply_to(&status, &me)
+            .build()
+            .expect("build");
+        assert_eq!(reply.in_reply_to_id, Some(status.id.clone()));
+        assert_eq!(
+            reply.status.as_deref(),
+            Some("@Gargron @alice@example.com hi")
+        );
+        assert_eq!(reply.visibility, Some(Visibility::Unlisted));
+        assert_eq!(reply.spoiler_text.as_deref(), Some("cw"));
+
+        let reply = NewStatusBuilder::default()
+            .visibility(Visibility::Direct)
+            .spoiler_text("mine")
+            .reply_to(&status, &AccountId::new("1"))
+            .build()
+            .expect("build");
+        assert_eq!(reply.status.as_deref(), Some("@me @alice@example.com "));
+        assert_eq!(reply.visibility, Some(Visibility::Direct));
+        assert_eq!(reply.spoiler_text.as_deref(), Some("mine"));
+    }
 }
@@ -14,7 +14,7 @@ use time::{serde::iso8601, OffsetDateTime};
 pub struct Status {
     /// ID of the scheduled status in the database.
     pub id: StatusId,
-    ///
+    /// The timestamp for when the status will be posted.
     #[serde(with = "iso8601")]
     pub scheduled_at: OffsetDateTime,
     /// The parameters that were used when scheduling the status, to be used when the status is posted.
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 
+use super::ContentType;
 use crate::StatusId;
 
 /// Represents a status's source as plain text.
@@ -13,6 +14,10 @@ pub struct Source {
     pub text: String,
     /// The plain text used to compose the status’s subject or content warning.
     pub spoiler_text: String,
+    /// The format the status was composed in, on servers which support
+    /// formats other than plain text, such as GoToSocial and glitch-soc.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<ContentType>,
 }
 
 #[cfg(test)]
@@ -30,5 +35,18 @@ mod tests {
         assert_eq!(subject.id, StatusId::new("108942703571991143"));
         assert_eq!(subject.text, "this is a status that will be edited");
         assert!(subject.spoiler_text.is_empty());
+        assert!(subject.content_type.is_none());
+    }
+
+    #[test]
+    fn test_deserialize_content_type() {
+        let example = r#"{
+          "id": "01HQ3TJPB1C4AE7TCNDN8Y8ZTZ",
+          "text": "some **markdown**",
+          "spoiler_text": "",
+          "content_type": "text/markdown"
+        }"#;
+        let subject: Source = serde_json::from_str(example).expect("deserialize");
+        assert_eq!(subject.content_type, Some(ContentType::Markdown));
     }
 }
@@ -0,0 +1,89 @@
+use isolang::Language;
+use serde::{Deserialize, Serialize};
+
+use crate::{AttachmentId, PollId};
+
+/// Represents the result of machine translating some status content.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/entities/Translation/)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Translation {
+    /// HTML-encoded translated content of the status.
+    pub content: String,
+    /// The translated spoiler warning of the status.
+    #[serde(default)]
+    pub spoiler_text: String,
+    /// The translated poll options of the status, if it has a poll.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub poll: Option<Poll>,
+    /// The translated descriptions of the media attachments of the status.
+    #[serde(default)]
+    pub media_attachments: Vec<Attachment>,
+    /// The language of the source text, as auto-detected by the machine
+    /// translation provider.
+    pub detected_source_language: Language,
+    /// The target language of the translation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<Language>,
+    /// The service that provided the machine translation.
+    pub provider: String,
+}
+
+/// The translated options of a poll.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Poll {
+    /// The ID of the poll.
+    pub id: PollId,
+    /// The translated poll options, in the same order as on the poll.
+    pub options: Vec<PollOption>,
+}
+
+/// A single translated poll option.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PollOption {
+    /// The translated title of the poll option.
+    pub title: String,
+}
+
+/// The translated description of a media attachment.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Attachment {
+    /// The ID of the media attachment.
+    pub id: AttachmentId,
+    /// The translated description of the media attachment.
+    pub description: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize() {
+        let example = r#"{
+            "content": "<p>Hello world</p>",
+            "spoiler_text": "Greetings ahead",
+            "media_attachments": [
+                {"id": "22345792", "description": "Status author waving at the camera"}
+            ],
+            "poll": {
+                "id": "34858",
+                "options": [{"title": "Red"}, {"title": "Blue"}]
+            },
+            "detected_source_language": "es",
+            "language": "en",
+            "provider": "DeepL.com"
+        }"#;
+        let subject: Translation = serde_json::from_str(example).expect("deserialize");
+        assert_eq!(subject.detected_source_language, Language::Spa);
+        assert_eq!(subject.language, Some(Language::Eng));
+        assert_eq!(subject.provider, "DeepL.com");
+        assert_eq!(
+            subject.media_attachments[0].id,
+            AttachmentId::new("22345792")
+        );
+        let poll = subject.poll.expect("poll");
+        assert_eq!(poll.id, PollId::new("34858"));
+        assert_eq!(poll.options[1].title, "Blue");
+    }
+}
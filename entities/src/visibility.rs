@@ -2,8 +2,20 @@ use derive_is_enum_variant::is_enum_variant;
 use serde::Deserialize;
 use serde::Serialize;
 
-/// The visibility of a status.
-#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq, is_enum_variant)]
+/// The visibility of a status, ordered from the least to the most public.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deserialize,
+    Serialize,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    is_enum_variant,
+)]
 #[serde(rename_all = "lowercase")]
 pub enum Visibility {
     /// A Direct message to a user
@@ -17,6 +29,17 @@ pub enum Visibility {
     Public,
 }
 
+impl std::fmt::Display for Visibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Visibility::Direct => "direct",
+            Visibility::Private => "private",
+            Visibility::Unlisted => "unlisted",
+            Visibility::Public => "public",
+        })
+    }
+}
+
 impl std::str::FromStr for Visibility {
     type Err = crate::error::Error;
 
@@ -45,4 +68,17 @@ mod tests {
             .expect_err("parsed invalid?")
             .is_visibility_parsing_error());
     }
+
+    #[test]
+    fn test_display_round_trip() {
+        for visibility in [
+            Visibility::Direct,
+            Visibility::Private,
+            Visibility::Unlisted,
+            Visibility::Public,
+        ] {
+            let parsed = Visibility::from_str(&visibility.to_string()).expect("parse");
+            assert_eq!(parsed, visibility);
+        }
+    }
 }
@@ -12,7 +12,7 @@ async fn run() -> Result<()> {
     let you = mastodon.verify_credentials().await?;
 
     mastodon
-        .following(you.id)
+        .following(&you.id)
         .await?
         .items_iter()
         .for_each(|acct| async move {
@@ -2,7 +2,6 @@
 #![cfg_attr(not(feature = "toml"), allow(unused_imports))]
 mod register;
 
-use futures_util::StreamExt;
 use mastodon_async::Result;
 use std::{
     io::Write,
@@ -14,12 +13,7 @@ async fn run() -> Result<()> {
     use register::bool_input;
     let mastodon = register::get_mastodon_data().await?;
 
-    let peers: Vec<_> = mastodon
-        .instance_peers()
-        .await?
-        .items_iter()
-        .collect()
-        .await;
+    let peers = mastodon.instance_peers().await?;
 
     if bool_input(format!("print {} peers?", peers.len()), false)? {
         let mut process = Command::new("less")
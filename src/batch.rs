@@ -0,0 +1,218 @@
+use futures::{Stream, StreamExt};
+use log::debug;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    entities::{account::Account, status::Status, AccountId, StatusId},
+    errors::Result,
+    helpers::read_response::read_response,
+    rate_limit::Throttle,
+    retry::Retry,
+    Mastodon,
+};
+
+/// Fetches many statuses or accounts by their IDs, a few at a time, as
+/// returned by [`Mastodon::batch`].
+///
+/// Requests are throttled so that the batch as a whole stays within the
+/// rate limit the client last saw, and requests which fail because the
+/// server was overloaded or unreachable are retried. Unless they're
+/// overridden, the client's [`Throttle`] and [`Retry`] options are used, or
+/// the defaults if the client wasn't built with them.
+///
+/// Results are returned in the order of the IDs, with an error in place of
+/// each entity which couldn't be fetched.
+///
+/// // Example
+///
+/// ```no_run
+/// use mastodon_async::prelude::*;
+///
+/// tokio_test::block_on(async {
+///     let client = Mastodon::from(Data::default());
+///     let mut batch = client.batch();
+///     batch.concurrency(8);
+///     let ids = ["109348238547925380", "109348238547925381"];
+///     for (id, status) in ids.iter().zip(batch.statuses(ids).await) {
+///         match status {
+///             Ok(status) => println!("{id}: {}", status.content),
+///             Err(err) => eprintln!("{id}: {err}"),
+///         }
+///     }
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct Batch {
+    client: Mastodon,
+    concurrency: usize,
+    throttle: Throttle,
+    retry: Retry,
+}
+
+impl Batch {
+    /// Fetch with `client`, four requests at a time.
+    pub fn new(client: Mastodon) -> Self {
+        Self {
+            concurrency: 4,
+            throttle: client.throttle.unwrap_or_default(),
+            retry: client.retry.unwrap_or_default(),
+            client,
+        }
+    }
+
+    /// How many requests to have in flight at once. At least one is.
+    pub fn concurrency(&mut self, concurrency: usize) -> &mut Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// How to keep the batch within the rate limit.
+    pub fn throttle(&mut self, throttle: Throttle) -> &mut Self {
+        self.throttle = throttle;
+        self
+    }
+
+    /// How to retry requests which failed.
+    pub fn retry(&mut self, retry: Retry) -> &mut Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Fetch the statuses with the given IDs.
+    pub async fn statuses<I>(&self, ids: impl IntoIterator<Item = I>) -> Vec<Result<Status>>
+    where
+        I: Into<StatusId>,
+    {
+        self.statuses_stream(ids).collect().await
+    }
+
+    /// Fetch the statuses with the given IDs, yielding each as soon as it
+    /// and the ones before it have been fetched.
+    pub fn statuses_stream<I>(
+        &self,
+        ids: impl IntoIterator<Item = I>,
+    ) -> impl Stream<Item = Result<Status>> + '_
+    where
+        I: Into<StatusId>,
+    {
+        let paths = ids
+            .into_iter()
+            .map(|id| format!("/api/v1/statuses/{}", id.into()));
+        self.fetch(paths.collect())
+    }
+
+    /// Fetch the accounts with the given IDs.
+    pub async fn accounts<I>(&self, ids: impl IntoIterator<Item = I>) -> Vec<Result<Account>>
+    where
+        I: Into<AccountId>,
+    {
+        self.accounts_stream(ids).collect().await
+    }
+
+    /// Fetch the accounts with the given IDs, yielding each as soon as it
+    /// and the ones before it have been fetched.
+    pub fn accounts_stream<I>(
+        &self,
+        ids: impl IntoIterator<Item = I>,
+    ) -> impl Stream<Item = Result<Account>> + '_
+    where
+        I: Into<AccountId>,
+    {
+        let paths = ids
+            .into_iter()
+            .map(|id| format!("/api/v1/accounts/{}", id.into()));
+        self.fetch(paths.collect())
+    }
+
+    fn fetch<T>(&self, paths: Vec<String>) -> impl Stream<Item = Result<T>> + '_
+    where
+        T: for<'de> Deserialize<'de> + Serialize,
+    {
+        futures::stream::iter(paths)
+            .map(move |path| self.fetch_one(path))
+            .buffered(self.concurrency)
+    }
+
+    async fn fetch_one<T>(&self, path: String) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de> + Serialize,
+    {
+        let call_id = Uuid::new_v4();
+        let url = self.client.route(path);
+        debug!(url = url, method = "get", call_id:? = call_id; "making batched API request");
+        let mut request = self
+            .client
+            .authenticated(self.client.client.get(&url))
+            .header("Accept", "application/json")
+            .build()?;
+        self.client.add_default_headers(request.headers_mut());
+        let response = self
+            .client
+            .execute_with(request, Some(&self.throttle), Some(self.retry))
+            .await?;
+        read_response(response).await
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use reqwest::StatusCode;
+
+    use super::*;
+    use crate::{
+        helpers::fixtures::{self, Fake, Reply},
+        thread::tests::status,
+        Error,
+    };
+
+    /// A fake answering `/api/v1/statuses/{id}` with a status, after a
+    /// delay which is shorter for later IDs, and with a 503 the first time
+    /// `flaky` is requested.
+    fn statuses() -> Arc<Fake> {
+        let attempts = Mutex::new(HashMap::<String, usize>::new());
+        Fake::new(move |request| {
+            let id = request.url().path().trim_start_matches("/api/v1/statuses/");
+            let attempt = {
+                let mut attempts = attempts.lock().unwrap();
+                let attempt = attempts.entry(id.to_string()).or_default();
+                *attempt += 1;
+                *attempt
+            };
+            let delay = 40u64.saturating_sub(id.parse::<u64>().unwrap_or(0) * 10);
+            let reply = match id {
+                "flaky" if attempt == 1 => Reply::status(StatusCode::SERVICE_UNAVAILABLE, "{}"),
+                "missing" => Reply::not_found(),
+                _ => Reply::ok(serde_json::to_string(&status(id, None, 0)).unwrap()),
+            };
+            reply.delay(Duration::from_millis(delay))
+        })
+    }
+
+    #[tokio::test]
+    async fn test_statuses_in_order() {
+        let statuses = statuses();
+        let client = fixtures::client(statuses.clone());
+        let mut retry = Retry::new();
+        retry.initial_delay(Duration::from_millis(1));
+        let mut batch = client.batch();
+        batch.concurrency(3).retry(retry);
+        let results = batch.statuses(["1", "2", "flaky", "missing", "3"]).await;
+        let ids: Vec<Option<&str>> = results
+            .iter()
+            .map(|result| result.as_ref().ok().map(|status| status.id.as_ref()))
+            .collect();
+        assert_eq!(ids, [Some("1"), Some("2"), Some("flaky"), None, Some("3")]);
+        assert!(matches!(results[3], Err(Error::Api { .. })));
+        let requested = statuses.paths();
+        let count = |path| requested.iter().filter(|p| *p == path).count();
+        assert_eq!(count("/api/v1/statuses/flaky"), 2);
+        assert_eq!(count("/api/v1/statuses/missing"), 1);
+    }
+}
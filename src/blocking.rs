@@ -0,0 +1,150 @@
+use std::future::Future;
+
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::runtime::{Builder, Runtime};
+
+use crate::{entities::prelude::*, errors::Result, Data, NewStatus, Page};
+
+/// A client whose methods block the current thread until the response is
+/// received, for programs which don't otherwise use async.
+///
+/// Only a handful of the most common methods, e.g.
+/// [`verify_credentials`](Mastodon::verify_credentials) and
+/// [`new_status`](Mastodon::new_status), are mirrored here. The rest of the
+/// API is reached by passing a closure calling the method of the async
+/// [`Mastodon`](crate::Mastodon) client to [`Mastodon::call`], which blocks
+/// until it completes, and paged results and event streams are iterated over
+/// with [`Mastodon::items`] and [`Mastodon::iter`].
+///
+/// Must not be used from within an async runtime.
+///
+/// // Example
+///
+/// ```no_run
+/// use mastodon_async::{blocking::Mastodon, prelude::Data, NewStatusBuilder};
+///
+/// let client = Mastodon::from_data(Data::default()).unwrap();
+/// let me = client.verify_credentials().unwrap();
+/// println!("logged in as {}", me.acct);
+/// let status = NewStatusBuilder::default().status("hello").build().unwrap();
+/// client.new_status(status).unwrap();
+/// for follower in client.items(client.call(|client| client.followers(&me.id)).unwrap()) {
+///     println!("followed by {}", follower.unwrap().acct);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Mastodon {
+    client: crate::Mastodon,
+    runtime: Runtime,
+}
+
+macro_rules! blocking {
+    ($($(#[$meta:meta])* fn $name:ident($($arg:ident: $ty:ty),*) -> $ret:ty;)*) => {
+        $(
+            $(#[$meta])*
+            pub fn $name(&self, $($arg: $ty),*) -> Result<$ret> {
+                self.runtime.block_on(self.client.$name($($arg),*))
+            }
+        )*
+    };
+}
+
+impl Mastodon {
+    /// Wrap an async client.
+    pub fn new(client: crate::Mastodon) -> Result<Self> {
+        let runtime = Builder::new_current_thread().enable_all().build()?;
+        Ok(Self { client, runtime })
+    }
+
+    /// A client with the default settings for the instance and access token
+    /// in `data`.
+    pub fn from_data(data: Data) -> Result<Self> {
+        Self::new(crate::Mastodon::from(data))
+    }
+
+    /// The async client this wraps.
+    pub fn inner(&self) -> &crate::Mastodon {
+        &self.client
+    }
+
+    /// Call any method of the async client, blocking until it completes.
+    ///
+    /// ```no_run
+    /// use mastodon_async::{blocking::Mastodon, prelude::*};
+    ///
+    /// let client = Mastodon::from_data(Data::default()).unwrap();
+    /// let id = StatusId::new("109345671283569234");
+    /// let reblogged_by = client.call(|client| client.reblogged_by(&id)).unwrap();
+    /// ```
+    pub fn call<'a, F>(&'a self, method: impl FnOnce(&'a crate::Mastodon) -> F) -> F::Output
+    where
+        F: Future + 'a,
+    {
+        self.runtime.block_on(method(&self.client))
+    }
+
+    /// Iterate over every item of `page` and the pages after it, fetching
+    /// each page as it's reached. Once an error is returned, the iterator
+    /// ends.
+    pub fn items<T>(&self, page: Page<T>) -> impl Iterator<Item = Result<T>> + '_
+    where
+        T: for<'de> Deserialize<'de> + Serialize + 'static,
+    {
+        self.iter(page.items_stream())
+    }
+
+    /// Iterate over the items of an async `stream`, e.g. one of the event
+    /// streams, blocking while waiting for each one.
+    pub fn iter<S>(&self, stream: S) -> impl Iterator<Item = S::Item> + '_
+    where
+        S: Stream + 'static,
+    {
+        let mut stream = Box::pin(stream);
+        std::iter::from_fn(move || self.runtime.block_on(stream.next()))
+    }
+
+    // Methods mirrored from the async client. Others should only be added
+    // here if they're commonly used; `call` covers everything else.
+    blocking! {
+        /// Get the account of the access token's user.
+        fn verify_credentials() -> CredentialAccount;
+        /// Post a new status to the account.
+        fn new_status(status: NewStatus) -> Status;
+        /// Get a single status.
+        fn get_status(id: impl Into<StatusId>) -> Status;
+        /// Delete a status.
+        fn delete_status(id: impl Into<StatusId>) -> Empty;
+        /// Get a single account.
+        fn get_account(id: impl Into<AccountId>) -> Account;
+        /// Get the context (ancestors and descendants) of a status.
+        fn get_context(id: impl Into<StatusId>) -> Context;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::fixtures::Server;
+    use std::thread;
+
+    #[test]
+    fn test_call() {
+        let runtime = Builder::new_current_thread().enable_all().build().unwrap();
+        let server = runtime.block_on(Server::bind());
+        let client = Mastodon::from_data(server.data()).unwrap();
+        let server = thread::spawn(move || {
+            runtime.block_on(server.respond("200 OK", r#"["example.com"]"#));
+        });
+        let peers = client.call(|client| client.instance_peers()).unwrap();
+        assert_eq!(peers, vec!["example.com".to_string()]);
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_iter() {
+        let client = Mastodon::from_data(Data::default()).unwrap();
+        let items: Vec<_> = client.iter(futures::stream::iter([1, 2, 3])).collect();
+        assert_eq!(items, vec![1, 2, 3]);
+    }
+}
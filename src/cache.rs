@@ -0,0 +1,193 @@
+use std::collections::{HashMap, VecDeque};
+
+use reqwest::{
+    header::{HeaderMap, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+    Method, Request, Response, StatusCode,
+};
+
+use crate::errors::Result;
+
+/// The bodies of recent responses to GET requests which carried an `ETag` or
+/// `Last-Modified` header, so that requests for the same URL can be made
+/// conditional, and a `304 Not Modified` answered from the cache.
+#[derive(Debug)]
+pub(crate) struct ResponseCache {
+    capacity: usize,
+    entries: HashMap<String, Entry>,
+    /// URLs in the order they were cached, oldest first.
+    order: VecDeque<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Vec<u8>,
+}
+
+impl ResponseCache {
+    /// A cache holding the responses for up to `capacity` URLs.
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    /// Whether responses to `request` can be cached.
+    pub(crate) fn applies_to(request: &Request) -> bool {
+        request.method() == Method::GET
+    }
+
+    /// Make `request` conditional on the cached response for its URL having
+    /// changed, if there is one.
+    pub(crate) fn prepare(&self, request: &mut Request) {
+        let Some(entry) = self.entries.get(request.url().as_str()) else {
+            return;
+        };
+        if let Some(etag) = entry.headers.get(ETAG) {
+            request.headers_mut().insert(IF_NONE_MATCH, etag.clone());
+        }
+        if let Some(last_modified) = entry.headers.get(LAST_MODIFIED) {
+            request
+                .headers_mut()
+                .insert(IF_MODIFIED_SINCE, last_modified.clone());
+        }
+    }
+
+    /// A copy of the cached response for `url`, if there is one.
+    pub(crate) fn get(&self, url: &str) -> Option<Response> {
+        let entry = self.entries.get(url)?;
+        let mut response = http::Response::new(entry.body.clone());
+        *response.status_mut() = entry.status;
+        *response.headers_mut() = entry.headers.clone();
+        Some(response.into())
+    }
+
+    /// Cache the body of a successful `response` to a request for `url` if it
+    /// can be validated later, evicting the oldest entry if the cache is
+    /// full. Returns a response equivalent to the one passed in.
+    pub(crate) async fn store(
+        cache: &std::sync::Mutex<Self>,
+        url: &str,
+        response: Response,
+    ) -> Result<Response> {
+        let validated =
+            response.headers().contains_key(ETAG) || response.headers().contains_key(LAST_MODIFIED);
+        if !response.status().is_success() || !validated {
+            return Ok(response);
+        }
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.bytes().await?.to_vec();
+        let mut cache = cache.lock().unwrap_or_else(|err| err.into_inner());
+        cache.insert(
+            url,
+            Entry {
+                status,
+                headers,
+                body,
+            },
+        );
+        Ok(cache.get(url).expect("just inserted"))
+    }
+
+    fn insert(&mut self, url: &str, entry: Entry) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.insert(url.to_string(), entry).is_some() {
+            self.order.retain(|cached| cached != url);
+        }
+        self.order.push_back(url.to_string());
+        while self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{helpers::fixtures::Server, Mastodon};
+    use std::sync::Mutex;
+
+    fn entry(etag: &str) -> Entry {
+        let mut headers = HeaderMap::new();
+        headers.insert(ETAG, etag.parse().unwrap());
+        Entry {
+            status: StatusCode::OK,
+            headers,
+            body: b"[]".to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_prepare() {
+        let client = reqwest::Client::new();
+        let mut cache = ResponseCache::new(2);
+        cache.insert("https://example.com/api/v1/instance", entry("W/\"abc\""));
+        let mut request = client
+            .get("https://example.com/api/v1/instance")
+            .build()
+            .unwrap();
+        cache.prepare(&mut request);
+        assert_eq!(request.headers()[IF_NONE_MATCH], "W/\"abc\"");
+        let mut request = client
+            .get("https://example.com/api/v1/lists")
+            .build()
+            .unwrap();
+        cache.prepare(&mut request);
+        assert!(!request.headers().contains_key(IF_NONE_MATCH));
+    }
+
+    #[test]
+    fn test_eviction() {
+        let mut cache = ResponseCache::new(2);
+        cache.insert("a", entry("1"));
+        cache.insert("b", entry("2"));
+        cache.insert("a", entry("3"));
+        cache.insert("c", entry("4"));
+        assert!(cache.get("b").is_none());
+        assert_eq!(cache.get("a").unwrap().headers()[ETAG], "3");
+        assert!(cache.get("c").is_some());
+        let mut cache = ResponseCache::new(0);
+        cache.insert("a", entry("1"));
+        assert!(cache.get("a").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_not_modified() {
+        let server = Server::bind().await;
+        let client = Mastodon::builder(server.data()).cache(10).build().unwrap();
+        let body = r#"["example.com"]"#;
+        let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nETag: \"v1\"\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        let (peers, _) = tokio::join!(
+            client.instance_peers(),
+            server.respond_raw(response.as_bytes())
+        );
+        assert_eq!(peers.unwrap(), vec!["example.com".to_string()]);
+        let (peers, request) = tokio::join!(
+            client.instance_peers(),
+            server.respond_raw(
+                b"HTTP/1.1 304 Not Modified\r\nETag: \"v1\"\r\nConnection: close\r\n\r\n"
+            )
+        );
+        assert!(request.contains("if-none-match: \"v1\""), "{request}");
+        assert_eq!(peers.unwrap(), vec!["example.com".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_store_unvalidated() {
+        let cache = Mutex::new(ResponseCache::new(1));
+        let response = Response::from(http::Response::new("[]"));
+        ResponseCache::store(&cache, "a", response).await.unwrap();
+        assert!(cache.lock().unwrap().get("a").is_none());
+    }
+}
@@ -0,0 +1,498 @@
+use std::fmt;
+
+use serde_json::Value;
+use url::Url;
+
+use crate::entities::{
+    filter::{self, Filter},
+    instance::{self, Instance},
+    status::ContentType,
+};
+
+/// The maximum length of a status, in characters, on instances which don't
+/// say otherwise.
+const DEFAULT_MAX_STATUS_CHARS: u64 = 500;
+
+/// The server software an instance runs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Software {
+    /// [Mastodon](https://joinmastodon.org/), or one of its close forks, such
+    /// as glitch-soc
+    Mastodon,
+    /// [Pleroma](https://pleroma.social/)
+    Pleroma,
+    /// [Akkoma](https://akkoma.social/), a fork of Pleroma
+    Akkoma,
+    /// [GoToSocial](https://gotosocial.org/)
+    GoToSocial,
+    /// Anything else, by the name it reports through NodeInfo
+    Other(String),
+}
+
+impl Software {
+    /// The software called `name`, as NodeInfo or the `(compatible; ...)`
+    /// part of an instance's version name it.
+    fn from_name(name: &str) -> Self {
+        match name.to_lowercase().as_str() {
+            "mastodon" | "hometown" => Software::Mastodon,
+            "pleroma" => Software::Pleroma,
+            "akkoma" => Software::Akkoma,
+            "gotosocial" => Software::GoToSocial,
+            _ => Software::Other(name.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Software {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Software::Mastodon => f.write_str("Mastodon"),
+            Software::Pleroma => f.write_str("Pleroma"),
+            Software::Akkoma => f.write_str("Akkoma"),
+            Software::GoToSocial => f.write_str("GoToSocial"),
+            Software::Other(name) => f.write_str(name),
+        }
+    }
+}
+
+/// A `major.minor.patch` version number, ignoring any pre-release or build
+/// suffix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    /// The major version
+    pub major: u64,
+    /// The minor version
+    pub minor: u64,
+    /// The patch version
+    pub patch: u64,
+}
+
+impl Version {
+    /// The version `major.minor.patch`.
+    pub const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Parse the version at the start of `version`, e.g. `4.2.0` from
+    /// `4.2.0-beta1+glitch`. Missing minor or patch versions are `0`.
+    pub fn parse(version: &str) -> Option<Self> {
+        let end = version
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(version.len());
+        let mut parts = version[..end].split('.').map(str::parse::<u64>);
+        let major = parts.next()?.ok()?;
+        let minor = parts.next().and_then(Result::ok).unwrap_or(0);
+        let patch = parts.next().and_then(Result::ok).unwrap_or(0);
+        Some(Self::new(major, minor, patch))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// What an instance's server software supports, as determined by
+/// [`Mastodon::capabilities`](crate::Mastodon::capabilities).
+///
+/// Mastodon reports its own version, Pleroma and Akkoma report the version
+/// of Mastodon they're compatible with alongside their own (e.g.
+/// `2.7.2 (compatible; Pleroma 2.5.0)`), and GoToSocial is recognized by its
+/// source URL. If neither version of the instance endpoint can be read,
+/// the software is looked up through NodeInfo instead.
+///
+/// // Example
+///
+/// ```no_run
+/// use mastodon_async::prelude::*;
+///
+/// tokio_test::block_on(async {
+///     let client = Mastodon::from(Data::default());
+///     let capabilities = client.capabilities().await.unwrap();
+///     println!("{} {:?}", capabilities.software(), capabilities.version());
+///     if capabilities.supports_editing() {
+///         // ...
+///     }
+/// });
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capabilities {
+    software: Software,
+    version: Option<Version>,
+    mastodon_version: Option<Version>,
+    api_version: u64,
+    max_status_chars: Option<u64>,
+    instance_v2: bool,
+    local_only: bool,
+    content_types: Vec<String>,
+    streaming_url: Option<Url>,
+}
+
+impl Capabilities {
+    /// Determine the capabilities from the body of a response to
+    /// `/api/v1/instance`, or `/api/v2/instance` if `v2`.
+    pub(crate) fn from_instance(instance: &Value, v2: bool) -> Self {
+        let version = instance["version"].as_str().unwrap_or_default();
+        let source_url = instance["source_url"].as_str().unwrap_or_default();
+        let (software, own_version, mastodon_version) =
+            if let Some((compatible, fork)) = parse_compatible(version) {
+                let (name, own_version) = fork.split_once(' ').unwrap_or((fork, ""));
+                (
+                    Software::from_name(name),
+                    Version::parse(own_version),
+                    Version::parse(compatible),
+                )
+            } else if source_url.to_lowercase().contains("gotosocial") {
+                (Software::GoToSocial, Version::parse(version), None)
+            } else {
+                let version = Version::parse(version);
+                (Software::Mastodon, version, version)
+            };
+        let max_status_chars = instance
+            .pointer("/configuration/statuses/max_characters")
+            .or_else(|| instance.get("max_toot_chars"))
+            .and_then(Value::as_u64);
+        Self {
+            software,
+            version: own_version,
+            mastodon_version,
+            api_version: instance
+                .pointer("/api_versions/mastodon")
+                .and_then(Value::as_u64)
+                .unwrap_or(0),
+            max_status_chars,
+            instance_v2: v2,
+            local_only: is_local_only_fork(version),
+            content_types: strings(
+                instance
+                    .pointer("/configuration/statuses/supported_mime_types")
+                    .or_else(|| instance.pointer("/pleroma/metadata/post_formats")),
+            ),
+            streaming_url: instance
+                .pointer("/configuration/urls/streaming")
+                .or_else(|| instance.pointer("/urls/streaming_api"))
+                .and_then(Value::as_str)
+                .and_then(|url| url.parse().ok()),
+        }
+    }
+
+    /// Determine the capabilities from a NodeInfo document.
+    pub(crate) fn from_nodeinfo(nodeinfo: &Value) -> Self {
+        let name = nodeinfo["software"]["name"].as_str().unwrap_or_default();
+        let raw_version = nodeinfo["software"]["version"].as_str().unwrap_or_default();
+        let software = Software::from_name(name);
+        let version = Version::parse(raw_version);
+        Self {
+            mastodon_version: (software == Software::Mastodon)
+                .then_some(version)
+                .flatten(),
+            software,
+            version,
+            api_version: 0,
+            max_status_chars: None,
+            instance_v2: false,
+            local_only: is_local_only_fork(name) || is_local_only_fork(raw_version),
+            content_types: strings(nodeinfo.pointer("/metadata/postFormats")),
+            streaming_url: None,
+        }
+    }
+
+    /// The server software the instance runs.
+    pub fn software(&self) -> &Software {
+        &self.software
+    }
+
+    /// The version of the server software, if it could be determined.
+    pub fn version(&self) -> Option<Version> {
+        self.version
+    }
+
+    /// The version of Mastodon whose API the instance implements, if it
+    /// reports one.
+    pub fn mastodon_version(&self) -> Option<Version> {
+        self.mastodon_version
+    }
+
+    /// The version of the Mastodon API reported by Mastodon 4.3 and later,
+    /// which increases as features are added, or `0` if not reported.
+    pub fn api_version(&self) -> u64 {
+        self.api_version
+    }
+
+    /// Whether `/api/v2/instance` is supported, i.e.
+    /// [`Mastodon::instance`](crate::Mastodon::instance) can be used rather
+    /// than [`Mastodon::instance_v1`](crate::Mastodon::instance_v1).
+    pub fn supports_instance_v2(&self) -> bool {
+        self.instance_v2
+    }
+
+    /// Whether statuses can be edited once posted.
+    pub fn supports_editing(&self) -> bool {
+        match self.software {
+            Software::Pleroma => self.version_at_least(2, 5),
+            Software::Akkoma => true,
+            Software::GoToSocial => self.version_at_least(0, 18),
+            _ => self.mastodon_at_least(3, 5),
+        }
+    }
+
+    /// Whether filters with several keywords and actions are supported,
+    /// i.e. [`Mastodon::filters`](crate::Mastodon::filters) can be used
+    /// rather than [`Mastodon::filters_v1`](crate::Mastodon::filters_v1).
+    pub fn supports_filters_v2(&self) -> bool {
+        match self.software {
+            Software::Pleroma | Software::Akkoma => false,
+            Software::GoToSocial => self.version_at_least(0, 17),
+            _ => self.mastodon_at_least(4, 0),
+        }
+    }
+
+    /// Whether statuses can be posted to the local instance only, i.e.
+    /// [`NewStatus::local_only`](crate::NewStatus::local_only) is
+    /// supported. Only the glitch-soc and Hometown forks of Mastodon support
+    /// this.
+    pub fn supports_local_only(&self) -> bool {
+        self.local_only
+    }
+
+    /// The formats statuses can be written in, as MIME types, if the
+    /// instance lists them. Mastodon doesn't, as it only supports plain
+    /// text.
+    pub fn content_types(&self) -> &[String] {
+        &self.content_types
+    }
+
+    /// Whether statuses can be written in `content_type`, i.e. it can be
+    /// passed to [`NewStatusBuilder::content_type`](crate::NewStatusBuilder::content_type).
+    pub fn supports_content_type(&self, content_type: &ContentType) -> bool {
+        *content_type == ContentType::PlainText
+            || self
+                .content_types
+                .iter()
+                .any(|supported| supported == content_type.as_str())
+    }
+
+    /// The URL of the streaming API, if the instance advertises it. It may
+    /// be on another host than the instance itself.
+    pub fn streaming_url(&self) -> Option<&Url> {
+        self.streaming_url.as_ref()
+    }
+
+    /// The maximum length of a status, in characters. Mastodon's default of
+    /// 500 is assumed if the instance doesn't say.
+    pub fn max_status_chars(&self) -> u64 {
+        self.max_status_chars.unwrap_or(DEFAULT_MAX_STATUS_CHARS)
+    }
+
+    fn version_at_least(&self, major: u64, minor: u64) -> bool {
+        self.version
+            .is_some_and(|version| version >= Version::new(major, minor, 0))
+    }
+
+    fn mastodon_at_least(&self, major: u64, minor: u64) -> bool {
+        self.mastodon_version
+            .is_some_and(|version| version >= Version::new(major, minor, 0))
+    }
+}
+
+impl fmt::Display for Capabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.version {
+            Some(version) => write!(f, "{} {version}", self.software),
+            None => write!(f, "{}", self.software),
+        }
+    }
+}
+
+/// The strings in `value`, if it's an array.
+fn strings(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|value| Some(value.as_str()?.to_string()))
+        .collect()
+}
+
+/// Whether a version (e.g. `4.2.0+glitch`) or software name is that of a
+/// Mastodon fork supporting local-only statuses.
+fn is_local_only_fork(version: &str) -> bool {
+    let version = version.to_lowercase();
+    version.contains("glitch") || version.contains("hometown")
+}
+
+/// Split a version like `2.7.2 (compatible; Pleroma 2.5.0)` into the
+/// Mastodon version and the name and version of the actual software.
+fn parse_compatible(version: &str) -> Option<(&str, &str)> {
+    let (compatible, rest) = version.split_once("(compatible;")?;
+    let fork = rest.split(')').next().unwrap_or(rest);
+    Some((compatible.trim(), fork.trim()))
+}
+
+/// The information about an instance, from whichever version of the
+/// instance endpoint it supports.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnyInstance {
+    /// From `/api/v1/instance`
+    V1(Box<instance::v1::Instance>),
+    /// From `/api/v2/instance`
+    V2(Box<Instance>),
+}
+
+/// The filters of the current user, from whichever version of the filters
+/// API the instance supports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnyFilters {
+    /// From `/api/v1/filters`, one phrase per filter
+    V1(Vec<filter::v1::Filter>),
+    /// From `/api/v2/filters`
+    V2(Vec<Filter>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(Version::parse("4.2.0"), Some(Version::new(4, 2, 0)));
+        assert_eq!(
+            Version::parse("4.3.0-beta.1+glitch"),
+            Some(Version::new(4, 3, 0))
+        );
+        assert_eq!(
+            Version::parse("0.16.0 git-ccd5b34"),
+            Some(Version::new(0, 16, 0))
+        );
+        assert_eq!(Version::parse("3"), Some(Version::new(3, 0, 0)));
+        assert_eq!(Version::parse("unknown"), None);
+        assert_eq!(Version::new(4, 2, 1).to_string(), "4.2.1");
+    }
+
+    #[test]
+    fn test_mastodon() {
+        let capabilities = Capabilities::from_instance(
+            &json!({
+                "version": "4.3.0",
+                "source_url": "https://github.com/mastodon/mastodon",
+                "configuration": {
+                    "statuses": {"max_characters": 1000},
+                    "urls": {"streaming": "wss://streaming.mastodon.example"}
+                },
+                "api_versions": {"mastodon": 2}
+            }),
+            true,
+        );
+        assert_eq!(capabilities.software(), &Software::Mastodon);
+        assert_eq!(capabilities.version(), Some(Version::new(4, 3, 0)));
+        assert_eq!(capabilities.api_version(), 2);
+        assert!(capabilities.supports_instance_v2());
+        assert!(capabilities.supports_editing());
+        assert!(capabilities.supports_filters_v2());
+        assert!(!capabilities.supports_local_only());
+        assert!(capabilities.supports_content_type(&ContentType::PlainText));
+        assert!(!capabilities.supports_content_type(&ContentType::Markdown));
+        assert_eq!(capabilities.max_status_chars(), 1000);
+        assert_eq!(capabilities.to_string(), "Mastodon 4.3.0");
+        assert_eq!(
+            capabilities.streaming_url().map(Url::as_str),
+            Some("wss://streaming.mastodon.example/")
+        );
+
+        let capabilities = Capabilities::from_instance(
+            &json!({
+                "version": "3.4.6",
+                "uri": "old.example",
+                "urls": {"streaming_api": "wss://old.example"}
+            }),
+            false,
+        );
+        assert_eq!(
+            capabilities.streaming_url().map(Url::as_str),
+            Some("wss://old.example/")
+        );
+        assert!(!capabilities.supports_editing());
+        assert!(!capabilities.supports_filters_v2());
+        assert_eq!(capabilities.max_status_chars(), 500);
+
+        for version in ["4.2.8+glitch", "4.0.2+hometown-1.1.1"] {
+            let capabilities = Capabilities::from_instance(&json!({ "version": version }), true);
+            assert_eq!(capabilities.software(), &Software::Mastodon);
+            assert!(capabilities.supports_local_only(), "{version}");
+        }
+    }
+
+    #[test]
+    fn test_forks() {
+        let capabilities = Capabilities::from_instance(
+            &json!({
+                "version": "2.7.2 (compatible; Pleroma 2.5.0)",
+                "max_toot_chars": 5000,
+                "pleroma": {"metadata": {"post_formats": ["text/plain", "text/html", "text/markdown", "text/bbcode"]}}
+            }),
+            false,
+        );
+        assert!(capabilities.supports_content_type(&ContentType::BbCode));
+        assert_eq!(capabilities.software(), &Software::Pleroma);
+        assert_eq!(capabilities.version(), Some(Version::new(2, 5, 0)));
+        assert_eq!(capabilities.mastodon_version(), Some(Version::new(2, 7, 2)));
+        assert!(capabilities.supports_editing());
+        assert!(!capabilities.supports_filters_v2());
+        assert_eq!(capabilities.max_status_chars(), 5000);
+
+        let capabilities = Capabilities::from_instance(
+            &json!({"version": "3.5.0 (compatible; Akkoma 3.10.4)"}),
+            true,
+        );
+        assert_eq!(capabilities.software(), &Software::Akkoma);
+        assert_eq!(capabilities.version(), Some(Version::new(3, 10, 4)));
+
+        let capabilities = Capabilities::from_instance(
+            &json!({
+                "version": "0.17.3 git-1fe3a2e",
+                "source_url": "https://github.com/superseriousbusiness/gotosocial",
+                "configuration": {"statuses": {"supported_mime_types": ["text/plain", "text/markdown"]}}
+            }),
+            true,
+        );
+        assert_eq!(capabilities.software(), &Software::GoToSocial);
+        assert_eq!(capabilities.version(), Some(Version::new(0, 17, 3)));
+        assert_eq!(capabilities.mastodon_version(), None);
+        assert!(!capabilities.supports_editing());
+        assert!(capabilities.supports_filters_v2());
+        assert_eq!(
+            capabilities.content_types(),
+            ["text/plain", "text/markdown"]
+        );
+        assert!(capabilities.supports_content_type(&ContentType::Markdown));
+        assert!(!capabilities.supports_content_type(&ContentType::Html));
+    }
+
+    #[test]
+    fn test_nodeinfo() {
+        let capabilities = Capabilities::from_nodeinfo(&json!({
+            "version": "2.0",
+            "software": {"name": "misskey", "version": "13.14.2"}
+        }));
+        assert_eq!(
+            capabilities.software(),
+            &Software::Other("misskey".to_string())
+        );
+        assert_eq!(capabilities.version(), Some(Version::new(13, 14, 2)));
+        assert!(!capabilities.supports_editing());
+        assert!(!capabilities.supports_instance_v2());
+
+        let capabilities = Capabilities::from_nodeinfo(&json!({
+            "software": {"name": "hometown", "version": "1.1.1"}
+        }));
+        assert_eq!(capabilities.software(), &Software::Mastodon);
+        assert!(capabilities.supports_local_only());
+    }
+}
@@ -1,6 +1,10 @@
 use std::borrow::Cow;
 
+use mastodon_async_entities::auth::Scopes;
 use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::registration::Registered;
 
 /// Raw data about mastodon app. Save `Data` using `serde` to prevent needing
 /// to authenticate on every run.
@@ -16,4 +20,379 @@ pub struct Data {
     pub redirect: Cow<'static, str>,
     /// The client's access token.
     pub token: Cow<'static, str>,
+    /// The scopes granted to the access token, if known. When set, methods
+    /// which require a scope that wasn't granted fail early with
+    /// [`Error::MissingScope`](crate::Error::MissingScope).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<Scopes>,
+}
+
+/// The version of the [`Accounts`] format written by this version of the
+/// crate.
+const ACCOUNTS_VERSION: u32 = 1;
+
+/// Several accounts, and the apps they were authorized through, to be saved
+/// in one file, e.g. with [`helpers::toml::accounts_to_file`], so that an
+/// account can be added or re-authorized without overwriting the others.
+///
+/// A file holding a single [`Data`], as saved by earlier versions, is read
+/// as one account named after its instance.
+///
+/// [`helpers::toml::accounts_to_file`]: crate::helpers::toml::accounts_to_file
+///
+/// // Example
+///
+/// ```
+/// use mastodon_async::{data::Accounts, Data};
+///
+/// let mut accounts = Accounts::new();
+/// let data = Data {
+///     base: "https://botsin.space".into(),
+///     token: "the-token".into(),
+///     ..Data::default()
+/// };
+/// accounts.insert("bot@botsin.space", &data);
+/// assert_eq!(accounts.get("bot@botsin.space"), Some(data));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(try_from = "AccountsFile", into = "AccountsFile")]
+pub struct Accounts {
+    apps: Vec<App>,
+    accounts: Vec<Account>,
+    default: Option<String>,
+}
+
+/// An app registered on an instance.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct App {
+    /// Base url of the instance.
+    pub base: Cow<'static, str>,
+    /// The client's id given by the instance.
+    pub client_id: Cow<'static, str>,
+    /// The client's secret given by the instance.
+    pub client_secret: Cow<'static, str>,
+    /// Url to redirect back to the app from the instance.
+    pub redirect: Cow<'static, str>,
+    /// The scopes the app was registered with, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<Scopes>,
+}
+
+/// An account authorized through an [`App`] registered on its instance.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Account {
+    /// The name the account is saved as, e.g. `user@instance.example`.
+    pub name: String,
+    /// Base url of the instance.
+    pub base: Cow<'static, str>,
+    /// The id of the app the account was authorized through, or `None` for
+    /// accounts saved before several apps could be saved for an instance,
+    /// which use the first app saved for it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<Cow<'static, str>>,
+    /// The account's access token.
+    pub token: Cow<'static, str>,
+    /// The scopes granted to the access token, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<Scopes>,
+}
+
+impl Accounts {
+    /// No accounts.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Save an account as `name`, along with the app it was authorized
+    /// through, replacing an account saved with the same name. Apps are told
+    /// apart by their instance and client id, so that accounts authorized
+    /// through different apps on the same instance can be saved side by
+    /// side. The first account saved becomes the default.
+    pub fn insert(&mut self, name: impl Into<String>, data: &Data) {
+        let name = name.into();
+        let app = App {
+            base: data.base.clone(),
+            client_id: data.client_id.clone(),
+            client_secret: data.client_secret.clone(),
+            redirect: data.redirect.clone(),
+            scopes: None,
+        };
+        match self
+            .apps
+            .iter_mut()
+            .find(|saved| saved.base == app.base && saved.client_id == app.client_id)
+        {
+            Some(saved) => {
+                saved.client_secret = app.client_secret;
+                saved.redirect = app.redirect;
+            }
+            None => self.apps.push(app),
+        }
+        let account = Account {
+            name: name.clone(),
+            base: data.base.clone(),
+            client_id: Some(data.client_id.clone()),
+            token: data.token.clone(),
+            scopes: data.scopes.clone(),
+        };
+        match self.accounts.iter_mut().find(|saved| saved.name == name) {
+            Some(saved) => *saved = account,
+            None => self.accounts.push(account),
+        }
+        self.default.get_or_insert(name);
+    }
+
+    /// The account saved as `name`.
+    pub fn get(&self, name: &str) -> Option<Data> {
+        let account = self.accounts.iter().find(|account| account.name == name)?;
+        let app = self.apps.iter().find(|app| {
+            app.base == account.base
+                && account
+                    .client_id
+                    .as_ref()
+                    .map_or(true, |client_id| app.client_id == *client_id)
+        })?;
+        Some(Data {
+            base: account.base.clone(),
+            client_id: app.client_id.clone(),
+            client_secret: app.client_secret.clone(),
+            redirect: app.redirect.clone(),
+            token: account.token.clone(),
+            scopes: account.scopes.clone(),
+        })
+    }
+
+    /// Forget the account saved as `name`, returning it. The app it was
+    /// authorized through is kept, to add other accounts with.
+    pub fn remove(&mut self, name: &str) -> Option<Data> {
+        let data = self.get(name)?;
+        self.accounts.retain(|account| account.name != name);
+        if self.default.as_deref() == Some(name) {
+            self.default = self.accounts.first().map(|account| account.name.clone());
+        }
+        Some(data)
+    }
+
+    /// The names of the saved accounts, in the order they were added.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.accounts.iter().map(|account| account.name.as_str())
+    }
+
+    /// The default account, if any account is saved.
+    pub fn default_account(&self) -> Option<Data> {
+        self.get(self.default.as_deref()?)
+    }
+
+    /// Make the account saved as `name` the default. Returns whether there
+    /// is such an account.
+    pub fn set_default(&mut self, name: &str) -> bool {
+        let exists = self.accounts.iter().any(|account| account.name == name);
+        if exists {
+            self.default = Some(name.to_string());
+        }
+        exists
+    }
+
+    /// The app most recently saved for the instance at `base`.
+    pub fn app(&self, base: &str) -> Option<&App> {
+        self.apps.iter().rev().find(|app| app.base == base)
+    }
+
+    /// Save the app registered on an instance, e.g. with the scopes it was
+    /// registered with, before any account has been authorized through it,
+    /// replacing an app saved with the same instance and client id.
+    pub fn insert_app(&mut self, app: App) {
+        match self
+            .apps
+            .iter_mut()
+            .find(|saved| saved.base == app.base && saved.client_id == app.client_id)
+        {
+            Some(saved) => *saved = app,
+            None => self.apps.push(app),
+        }
+    }
+
+    /// The app most recently saved for the instance at `base`, ready to
+    /// authorize an account through it without registering another app,
+    /// e.g. to add another account or to re-authorize one whose token was
+    /// revoked.
+    ///
+    /// The user is asked to log in, even if they're already logged in to
+    /// the instance, so that they can choose which account to authorize.
+    pub fn registered(&self, base: &str) -> Option<Registered> {
+        let app = self.app(base)?;
+        Some(Registered::from_parts(
+            &app.base,
+            &app.client_id,
+            &app.client_secret,
+            &app.redirect,
+            app.scopes.clone().unwrap_or_else(Scopes::read_all),
+            true,
+        ))
+    }
+}
+
+impl From<Data> for Accounts {
+    /// Migrate a single account, naming it after the host of its instance.
+    fn from(data: Data) -> Self {
+        let name = Url::parse(&data.base)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_else(|| data.base.to_string());
+        let mut accounts = Accounts::new();
+        accounts.insert(name, &data);
+        accounts
+    }
+}
+
+/// The formats `Accounts` can be read from.
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+enum AccountsFile {
+    Versioned {
+        version: u32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        default: Option<String>,
+        #[serde(default)]
+        apps: Vec<App>,
+        #[serde(default)]
+        accounts: Vec<Account>,
+    },
+    Single(Data),
+}
+
+impl TryFrom<AccountsFile> for Accounts {
+    type Error = String;
+
+    fn try_from(file: AccountsFile) -> Result<Self, Self::Error> {
+        match file {
+            AccountsFile::Versioned {
+                version: ACCOUNTS_VERSION,
+                apps,
+                accounts,
+                default,
+            } => Ok(Accounts {
+                apps,
+                accounts,
+                default,
+            }),
+            AccountsFile::Versioned { version, .. } => Err(format!(
+                "unsupported accounts file version {version}, expected {ACCOUNTS_VERSION}"
+            )),
+            AccountsFile::Single(data) => Ok(data.into()),
+        }
+    }
+}
+
+impl From<Accounts> for AccountsFile {
+    fn from(accounts: Accounts) -> Self {
+        AccountsFile::Versioned {
+            version: ACCOUNTS_VERSION,
+            apps: accounts.apps,
+            accounts: accounts.accounts,
+            default: accounts.default,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(base: &'static str, token: &'static str) -> Data {
+        Data {
+            base: base.into(),
+            client_id: "adbc01234".into(),
+            client_secret: "0987dcba".into(),
+            redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
+            token: token.into(),
+            scopes: None,
+        }
+    }
+
+    #[test]
+    fn test_accounts() {
+        let mut accounts = Accounts::new();
+        accounts.insert("alice@example.com", &data("https://example.com", "a"));
+        accounts.insert("bob@example.com", &data("https://example.com", "b"));
+        accounts.insert("bob@example.org", &data("https://example.org", "c"));
+        assert_eq!(accounts.apps.len(), 2);
+        assert_eq!(
+            accounts.names().collect::<Vec<_>>(),
+            ["alice@example.com", "bob@example.com", "bob@example.org"]
+        );
+        assert_eq!(
+            accounts.get("bob@example.com"),
+            Some(data("https://example.com", "b"))
+        );
+        assert_eq!(
+            accounts.default_account(),
+            Some(data("https://example.com", "a"))
+        );
+
+        accounts.insert("alice@example.com", &data("https://example.com", "d"));
+        assert_eq!(accounts.get("alice@example.com").unwrap().token, "d");
+        assert!(accounts.set_default("bob@example.org"));
+        assert!(!accounts.set_default("carol@example.org"));
+        assert_eq!(accounts.remove("bob@example.org").unwrap().token, "c");
+        assert_eq!(accounts.default_account().unwrap().token, "d");
+        assert!(accounts.registered("https://example.org").is_some());
+        assert!(accounts.registered("https://example.net").is_none());
+    }
+
+    #[test]
+    fn test_apps_with_different_client_ids() {
+        let mut accounts = Accounts::new();
+        accounts.insert("alice@example.com", &data("https://example.com", "a"));
+        let other_app = Data {
+            client_id: "ffff9999".into(),
+            ..data("https://example.com", "b")
+        };
+        accounts.insert("bob@example.com", &other_app);
+        assert_eq!(accounts.apps.len(), 2);
+        assert_eq!(
+            accounts.get("alice@example.com"),
+            Some(data("https://example.com", "a"))
+        );
+        assert_eq!(accounts.get("bob@example.com"), Some(other_app));
+        assert_eq!(
+            accounts.app("https://example.com").unwrap().client_id,
+            "ffff9999"
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut accounts = Accounts::new();
+        accounts.insert("alice@example.com", &data("https://example.com", "a"));
+        let json = serde_json::to_value(&accounts).unwrap();
+        assert_eq!(json["version"], 1);
+        assert_eq!(json["default"], "alice@example.com");
+        let parsed: Accounts = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, accounts);
+    }
+
+    #[test]
+    fn test_migrate_single_account() {
+        let parsed: Accounts = serde_json::from_str(
+            r#"{
+                "base": "https://example.com",
+                "client_id": "adbc01234",
+                "client_secret": "0987dcba",
+                "redirect": "urn:ietf:wg:oauth:2.0:oob",
+                "token": "fedc5678"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            parsed.get("example.com"),
+            Some(data("https://example.com", "fedc5678"))
+        );
+    }
+
+    #[test]
+    fn test_unsupported_version() {
+        let err = serde_json::from_str::<Accounts>(r#"{"version": 2, "accounts": []}"#);
+        assert!(err.is_err());
+    }
 }
@@ -0,0 +1,351 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    hash::Hash,
+    time::Duration,
+};
+
+use futures::{stream::unfold, Stream, StreamExt, TryStream, TryStreamExt};
+use log::trace;
+use time::OffsetDateTime;
+
+use crate::entities::event::Event;
+
+/// Cleans up events received from several, possibly overlapping, streams,
+/// e.g. the home timeline, a list and a hashtag, whether they're streamed
+/// separately or over
+/// [`Mastodon::stream_multiplexed`](crate::Mastodon::stream_multiplexed).
+///
+/// Events are held for a short window before being passed on, during which:
+///
+/// - an event which was already received is dropped. Events are the same if
+///   they're of the same type, about the same status, notification or
+///   announcement, and received on the same stream, or on any stream if
+///   [`across_streams`](Self::across_streams) is set. Edits are only the
+///   same if they have the same edit time. Other events, such as filter or
+///   conversation updates, are never dropped.
+/// - new statuses are put in the order they were posted.
+/// - a new status which is deleted before it was passed on is dropped,
+///   leaving only the deletion.
+///
+/// Errors are passed on as soon as the events received before them have
+/// been.
+///
+/// // Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use futures_util::{pin_mut, stream, StreamExt};
+/// use mastodon_async::{
+///     dedup::Dedup,
+///     prelude::*,
+///     streaming::{Channel, StreamEvent, StreamOptions},
+///     Error,
+/// };
+///
+/// tokio_test::block_on(async {
+///     let client = Mastodon::from(Data::default());
+///     let home = client.stream_reconnecting(&StreamOptions::new(Channel::User));
+///     let rust = client.stream_reconnecting(&StreamOptions::new(Channel::Hashtag("rust".into())));
+///     let events = stream::select(home.map(|item| ("home", item)), rust.map(|item| ("#rust", item)))
+///         .filter_map(|(stream, item)| async move {
+///             match item {
+///                 StreamEvent::Event(event) => Some(Ok::<_, Error>((stream, event))),
+///                 _ => None,
+///             }
+///         });
+///     let events = Dedup::new()
+///         .window(Duration::from_millis(500))
+///         .across_streams()
+///         .dedup(events);
+///     pin_mut!(events);
+///     while let Some(Ok((stream, event))) = events.next().await {
+///         println!("{stream}: {event:?}");
+///     }
+/// });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dedup {
+    window: Duration,
+    across_streams: bool,
+    capacity: usize,
+}
+
+impl Default for Dedup {
+    fn default() -> Self {
+        Self {
+            window: Duration::from_millis(250),
+            across_streams: false,
+            capacity: 1000,
+        }
+    }
+}
+
+impl Dedup {
+    /// Hold events for a quarter of a second, and remember the last
+    /// thousand to drop the ones received again on the same stream.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How long to hold each event for.
+    pub fn window(&mut self, window: Duration) -> &mut Self {
+        self.window = window;
+        self
+    }
+
+    /// Drop events which were already received on any stream, rather than
+    /// only on the same one.
+    pub fn across_streams(&mut self) -> &mut Self {
+        self.across_streams = true;
+        self
+    }
+
+    /// How many events to remember to recognize the ones received again.
+    pub fn capacity(&mut self, capacity: usize) -> &mut Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Clean up `events`, tagged with the stream they were received on.
+    pub fn dedup<S, K>(&self, events: S) -> impl Stream<Item = Result<(K, Event), S::Error>>
+    where
+        S: TryStream<Ok = (K, Event)>,
+        K: Clone + Eq + Hash,
+    {
+        let state = State {
+            events: Box::pin(events.into_stream()),
+            options: *self,
+            buffer: VecDeque::new(),
+            seen: Seen::new(self.capacity),
+            error: None,
+            ended: false,
+        };
+        unfold(state, |mut state| async move {
+            loop {
+                if let Some(item) = state.release(OffsetDateTime::now_utc()) {
+                    return Some((Ok(item), state));
+                }
+                if let Some(err) = state.error.take() {
+                    return Some((Err(err), state));
+                }
+                if state.ended {
+                    return None;
+                }
+                let next = match state.buffer.front() {
+                    Some(pending) => {
+                        let wait = (pending.due - OffsetDateTime::now_utc())
+                            .try_into()
+                            .unwrap_or_default();
+                        match crate::helpers::time::timeout(wait, state.events.next()).await {
+                            Ok(next) => next,
+                            Err(_) => continue,
+                        }
+                    }
+                    None => state.events.next().await,
+                };
+                match next {
+                    Some(Ok((stream, event))) => state.receive(stream, event),
+                    Some(Err(err)) => state.error = Some(err),
+                    None => state.ended = true,
+                }
+            }
+        })
+    }
+}
+
+struct Pending<K> {
+    stream: K,
+    event: Event,
+    due: OffsetDateTime,
+}
+
+/// What makes an event the same as another.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Key<K> {
+    stream: Option<K>,
+    kind: &'static str,
+    id: String,
+}
+
+struct State<S, K, E> {
+    events: S,
+    options: Dedup,
+    buffer: VecDeque<Pending<K>>,
+    seen: Seen<Key<K>>,
+    error: Option<E>,
+    ended: bool,
+}
+
+impl<S, K: Clone + Eq + Hash, E> State<S, K, E> {
+    fn receive(&mut self, stream: K, event: Event) {
+        if let Some((kind, id)) = identify(&event) {
+            let key = Key {
+                stream: (!self.options.across_streams).then(|| stream.clone()),
+                kind,
+                id,
+            };
+            if !self.seen.insert(key) {
+                trace!(kind = kind; "dropped duplicate event");
+                return;
+            }
+        }
+        if let Event::Delete(id) = &event {
+            self.buffer.retain(|pending| match &pending.event {
+                Event::Update(status) | Event::StatusUpdate(status) => status.id.as_ref() != id,
+                _ => true,
+            });
+        }
+        self.buffer.push_back(Pending {
+            stream,
+            event,
+            due: OffsetDateTime::now_utc() + self.options.window,
+        });
+    }
+
+    /// The next event to pass on, if the oldest one held is due, or all of
+    /// them are because the stream ended. A new status is preceded by the
+    /// new statuses held which were posted before it.
+    fn release(&mut self, now: OffsetDateTime) -> Option<(K, Event)> {
+        let front = self.buffer.front()?;
+        if front.due > now && !self.ended && self.error.is_none() {
+            return None;
+        }
+        let index = match front.event {
+            Event::Update(_) => self
+                .buffer
+                .iter()
+                .enumerate()
+                .filter_map(|(index, pending)| match &pending.event {
+                    Event::Update(status) => Some((index, status.id.as_ref())),
+                    _ => None,
+                })
+                .min_by_key(|(_, id)| (id.len(), *id))
+                .map_or(0, |(index, _)| index),
+            _ => 0,
+        };
+        let pending = self.buffer.remove(index)?;
+        Some((pending.stream, pending.event))
+    }
+}
+
+/// The type of an event and the ID of what it's about, if events about the
+/// same thing can be told apart from each other.
+fn identify(event: &Event) -> Option<(&'static str, String)> {
+    Some(match event {
+        Event::Update(status) => ("update", status.id.to_string()),
+        Event::StatusUpdate(status) => {
+            let edited_at = status.edited_at.map(OffsetDateTime::unix_timestamp_nanos);
+            ("status.update", format!("{}@{edited_at:?}", status.id))
+        }
+        Event::Delete(id) => ("delete", id.clone()),
+        Event::Notification(notification) => ("notification", notification.id.to_string()),
+        Event::AnnouncementDelete(id) => ("announcement.delete", id.to_string()),
+        _ => return None,
+    })
+}
+
+/// The most recent items seen, up to a fixed number of them.
+#[derive(Debug)]
+pub(crate) struct Seen<T> {
+    items: HashSet<T>,
+    order: VecDeque<T>,
+    capacity: usize,
+}
+
+impl<T: Clone + Eq + Hash> Seen<T> {
+    /// Remember up to `capacity` items.
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            items: HashSet::new(),
+            order: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    /// Remember `item`, forgetting the oldest item if there are too many,
+    /// and return whether it wasn't seen before.
+    pub(crate) fn insert(&mut self, item: T) -> bool {
+        if !self.items.insert(item.clone()) {
+            return false;
+        }
+        self.order.push_back(item);
+        if self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.items.remove(&oldest);
+            }
+        }
+        true
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use std::convert::Infallible;
+
+    use futures::stream;
+
+    use super::*;
+    use crate::thread::tests::status;
+
+    fn update(stream: &'static str, id: &str) -> Result<(&'static str, Event), Infallible> {
+        Ok((stream, Event::Update(status(id, None, 0))))
+    }
+
+    fn describe(item: Result<(&str, Event), Infallible>) -> String {
+        match item {
+            Ok((stream, Event::Update(status))) => format!("{stream} {}", status.id),
+            Ok((stream, Event::Delete(id))) => format!("{stream} delete {id}"),
+            Ok((stream, event)) => format!("{stream} {event:?}"),
+            Err(err) => match err {},
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dedup_and_order() {
+        let events = stream::iter([
+            update("home", "10"),
+            update("home", "9"),
+            update("list", "10"),
+            update("home", "10"),
+            update("home", "11"),
+            Ok(("home", Event::Delete("11".into()))),
+            update("home", "8"),
+        ]);
+        let per_stream: Vec<String> = Dedup::new()
+            .dedup(events.clone())
+            .map(describe)
+            .collect()
+            .await;
+        assert_eq!(
+            per_stream,
+            ["home 8", "home 9", "home 10", "list 10", "home delete 11"]
+        );
+        let across: Vec<String> = Dedup::new()
+            .across_streams()
+            .dedup(events)
+            .map(describe)
+            .collect()
+            .await;
+        assert_eq!(across, ["home 8", "home 9", "home 10", "home delete 11"]);
+    }
+
+    #[tokio::test]
+    async fn test_released_after_window() {
+        let events =
+            stream::iter([update("home", "2"), update("home", "1")]).chain(stream::pending());
+        let events = Dedup::new().window(Duration::from_millis(10)).dedup(events);
+        let released: Vec<String> = events.take(2).map(describe).collect().await;
+        assert_eq!(released, ["home 1", "home 2"]);
+    }
+
+    #[test]
+    fn test_seen_is_bounded() {
+        let mut seen = Seen::new(2);
+        assert!(seen.insert(1));
+        assert!(seen.insert(2));
+        assert!(!seen.insert(2));
+        assert!(seen.insert(3));
+        assert!(seen.insert(1));
+        assert!(!seen.insert(3));
+    }
+}
@@ -30,7 +30,7 @@ pub(crate) struct ItemsIter<T: Clone + for<'de> Deserialize<'de> + Serialize> {
     use_initial: bool,
 }
 
-impl<'a, T: Clone + for<'de> Deserialize<'de> + Serialize> ItemsIter<T> {
+impl<T: Clone + for<'de> Deserialize<'de> + Serialize> ItemsIter<T> {
     pub(crate) fn new(page: Page<T>) -> ItemsIter<T> {
         ItemsIter {
             page,
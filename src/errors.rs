@@ -1,11 +1,15 @@
 use std::string::FromUtf8Error;
-use std::{error, fmt, io::Error as IoError, num::TryFromIntError};
+use std::{collections::BTreeMap, error, fmt, io::Error as IoError, num::TryFromIntError};
 
 use derive_builder::UninitializedFieldError;
 use derive_is_enum_variant::is_enum_variant;
 #[cfg(feature = "env")]
 use envy::Error as EnvyError;
-use reqwest::{header::ToStrError as HeaderStrError, Error as HttpError, StatusCode};
+use mastodon_async_entities::auth::{Scope, Scopes};
+use reqwest::{
+    header::{HeaderMap, InvalidHeaderValue, ToStrError as HeaderStrError},
+    Error as HttpError, Response, StatusCode,
+};
 use serde::Deserialize;
 use serde_json::Error as SerdeError;
 use serde_urlencoded::ser::Error as UrlEncodedError;
@@ -15,6 +19,8 @@ use tomlcrate::de::Error as TomlDeError;
 use tomlcrate::ser::Error as TomlSerError;
 use url::ParseError as UrlError;
 
+use crate::rate_limit::RateLimit;
+
 /// Convience type over `std::result::Result` with `Error` as the error type.
 pub type Result<T> = ::std::result::Result<T, Error>;
 
@@ -30,6 +36,14 @@ pub enum Error {
         /// The JSON-decoded error response from the server.
         response: ApiError,
     },
+    /// The server refused the request because a rate limit was exceeded.
+    #[error("rate limited: {response}, rate limit: {rate_limit:?}")]
+    RateLimited {
+        /// The JSON-decoded error response from the server.
+        response: ApiError,
+        /// The rate limit which was exceeded, if the server reported it.
+        rate_limit: Option<RateLimit>,
+    },
     /// Error deserialising to json. Typically represents a breaking change in
     /// the Mastodon API
     #[error("error from serde")]
@@ -64,6 +78,20 @@ pub enum Error {
     #[error("Error deserializing from toml")]
     TomlDe(#[from] TomlDeError),
 
+    /// Error encrypting with age
+    #[cfg(all(feature = "encrypted", not(target_arch = "wasm32")))]
+    #[error("Error encrypting")]
+    AgeEncrypt(#[from] age::EncryptError),
+    /// Error decrypting with age, e.g. because the passphrase is wrong
+    #[cfg(all(feature = "encrypted", not(target_arch = "wasm32")))]
+    #[error("Error decrypting")]
+    AgeDecrypt(#[from] age::DecryptError),
+
+    /// Error from the platform's secret store
+    #[cfg(all(feature = "keyring", not(target_arch = "wasm32")))]
+    #[error("Error from the platform's secret store")]
+    Keyring(#[from] keyringcrate::Error),
+
     /// Error raised in the helpers::json::to_writer or helpers::toml::to_writer function if not
     /// all bytes were written to the writer
     #[cfg(any(feature = "toml", feature = "json"))]
@@ -73,6 +101,10 @@ pub enum Error {
     /// Error converting an http header to a string
     #[error("Error converting an http header to a string")]
     HeaderStrError(#[from] HeaderStrError),
+    /// A string which isn't valid in an http header was used as a header
+    /// value
+    #[error("invalid http header value")]
+    InvalidHeaderValue(#[from] InvalidHeaderValue),
     /// Error parsing the http Link header
     #[error("error parsing http link header")]
     LinkHeaderParse(#[from] parse_link_header::Error),
@@ -104,18 +136,139 @@ pub enum Error {
     /// Error constructing type from its builder
     #[error(transparent)]
     Builder(#[from] UninitializedFieldError),
+    /// The access token was not granted the scope required by a method.
+    #[error(
+        "the access token is missing the required scope {required}, granted scopes are {granted}"
+    )]
+    MissingScope {
+        /// The scope required by the method
+        required: Scope,
+        /// The scopes the access token was granted
+        granted: Scopes,
+    },
+    /// The instance's server software doesn't support something that was
+    /// asked for, as determined by
+    /// [`Mastodon::capabilities`](crate::Mastodon::capabilities).
+    #[error("{feature} isn't supported by {software}")]
+    Unsupported {
+        /// What isn't supported
+        feature: &'static str,
+        /// The server software and its version
+        software: String,
+    },
+    /// A pushed Web Push message couldn't be decrypted
+    #[cfg(feature = "web-push")]
+    #[error("couldn't decrypt the push message: {0}")]
+    WebPush(&'static str),
+    /// Error from the WebSocket connection to the streaming API
+    #[cfg(feature = "websocket")]
+    #[error("websocket error")]
+    WebSocket(#[source] Box<tokio_tungstenite::tungstenite::Error>),
     /// Other errors
     #[error("other error: {0:?}")]
     Other(String),
 }
 
+#[cfg(feature = "websocket")]
+impl From<tokio_tungstenite::tungstenite::Error> for Error {
+    fn from(err: tokio_tungstenite::tungstenite::Error) -> Self {
+        Error::WebSocket(Box::new(err))
+    }
+}
+
+impl Error {
+    /// The error for an unsuccessful response with the given status, headers
+    /// and decoded body.
+    pub(crate) fn from_response(
+        status: StatusCode,
+        headers: &HeaderMap,
+        response: ApiError,
+    ) -> Self {
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            Error::RateLimited {
+                response,
+                rate_limit: RateLimit::from_headers(headers),
+            }
+        } else {
+            Error::Api { status, response }
+        }
+    }
+
+    /// The error for an unsuccessful `response`, reading its body.
+    pub(crate) async fn from_error_response(response: Response) -> Self {
+        let status = response.status();
+        let headers = response.headers().clone();
+        match response.bytes().await {
+            Ok(body) => Error::from_response(status, &headers, ApiError::parse(status, &body)),
+            Err(err) => err.into(),
+        }
+    }
+
+    /// The error returned by the API, if this is one.
+    pub fn api_error(&self) -> Option<&ApiError> {
+        match self {
+            Error::Api { response, .. } | Error::RateLimited { response, .. } => Some(response),
+            _ => None,
+        }
+    }
+
+    /// What kind of error the API returned, if this is an API error, e.g.
+    /// to tell a record which wasn't found from a duplicate one.
+    pub fn api_error_kind(&self) -> Option<ApiErrorKind> {
+        match self {
+            Error::Api { status, response } => Some(ApiErrorKind::of(*status, response)),
+            Error::RateLimited { .. } => Some(ApiErrorKind::RateLimited),
+            _ => None,
+        }
+    }
+}
+
 /// Error returned from the Mastodon API.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
 pub struct ApiError {
     /// The error message.
     pub error: String,
     /// A longer description of the error, mainly provided with the OAuth API.
     pub error_description: Option<String>,
+    /// Why each invalid attribute of a record was rejected, keyed by the
+    /// attribute's name, for `422 Unprocessable Entity` responses to
+    /// requests which create or update a record.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub details: BTreeMap<String, Vec<ValidationError>>,
+}
+
+impl ApiError {
+    /// Parse the body of an unsuccessful response. Bodies which aren't a
+    /// JSON error, e.g. the HTML error page of a proxy in front of the
+    /// instance, are kept as the error message, or replaced with the
+    /// status's reason if empty.
+    pub(crate) fn parse(status: StatusCode, body: &[u8]) -> Self {
+        if let Ok(error) = serde_json::from_slice(body) {
+            return error;
+        }
+        let body = String::from_utf8_lossy(body);
+        let error = match body.trim() {
+            "" => status
+                .canonical_reason()
+                .unwrap_or("unknown error")
+                .to_string(),
+            body => body.to_string(),
+        };
+        ApiError {
+            error,
+            error_description: None,
+            details: BTreeMap::new(),
+        }
+    }
+
+    /// Whether any attribute was rejected with the validation error `code`,
+    /// e.g. `ERR_TAKEN`.
+    pub fn has_validation_error(&self, code: &str) -> bool {
+        self.details
+            .values()
+            .flatten()
+            .any(|detail| detail.error == code)
+    }
 }
 
 impl fmt::Display for ApiError {
@@ -126,6 +279,59 @@ impl fmt::Display for ApiError {
 
 impl error::Error for ApiError {}
 
+/// Why one attribute of a record was rejected.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ValidationError {
+    /// A code for the error, e.g. `ERR_BLANK`, `ERR_TAKEN` or `ERR_INVALID`.
+    pub error: String,
+    /// A human-readable description of the error, e.g. "is already taken".
+    pub description: String,
+}
+
+/// The kind of an error returned by the API, for handling errors without
+/// matching on status codes and messages.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ApiErrorKind {
+    /// The access token is missing, invalid or revoked (401).
+    Unauthorized,
+    /// The access token lacks the required scope, or the user isn't allowed
+    /// to do this (403).
+    Forbidden,
+    /// The record doesn't exist, or isn't visible to the user (404).
+    NotFound,
+    /// The record, or one of its unique attributes, already exists (422).
+    Duplicate,
+    /// The request was invalid, e.g. a required attribute was blank (422).
+    Validation,
+    /// A rate limit was exceeded (429).
+    RateLimited,
+    /// The server failed, or is unavailable (5xx).
+    Server,
+    /// Any other error.
+    Other,
+}
+
+impl ApiErrorKind {
+    /// The kind of `error`, returned with `status`.
+    pub fn of(status: StatusCode, error: &ApiError) -> Self {
+        match status {
+            StatusCode::UNAUTHORIZED => ApiErrorKind::Unauthorized,
+            StatusCode::FORBIDDEN => ApiErrorKind::Forbidden,
+            StatusCode::NOT_FOUND => ApiErrorKind::NotFound,
+            StatusCode::UNPROCESSABLE_ENTITY
+                if error.error == "Duplicate record" || error.has_validation_error("ERR_TAKEN") =>
+            {
+                ApiErrorKind::Duplicate
+            }
+            StatusCode::UNPROCESSABLE_ENTITY => ApiErrorKind::Validation,
+            StatusCode::TOO_MANY_REQUESTS => ApiErrorKind::RateLimited,
+            status if status.is_server_error() => ApiErrorKind::Server,
+            _ => ApiErrorKind::Other,
+        }
+    }
+}
+
 #[macro_export]
 /// Used to easily create errors from strings
 macro_rules! format_err {
@@ -185,6 +391,49 @@ mod tests {
         assert_is!(err, Error::Url(..));
     }
 
+    #[test]
+    fn from_rate_limited_response() {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-RateLimit-Limit", "300".parse().unwrap());
+        headers.insert("X-RateLimit-Remaining", "0".parse().unwrap());
+        let response = ApiError::parse(
+            StatusCode::TOO_MANY_REQUESTS,
+            br#"{"error":"Too many requests"}"#,
+        );
+        let err = Error::from_response(StatusCode::TOO_MANY_REQUESTS, &headers, response.clone());
+        let Error::RateLimited { rate_limit, .. } = err else {
+            panic!("expected a rate limit error, got {err:?}")
+        };
+        assert_eq!(rate_limit.unwrap().remaining, 0);
+        let err = Error::from_response(StatusCode::NOT_FOUND, &headers, response);
+        assert_is!(err, Error::Api { .. });
+    }
+
+    #[test]
+    fn parse_api_error() {
+        let body = br#"{"error":"Validation failed: Username has already been taken","details":{"username":[{"error":"ERR_TAKEN","description":"is already taken"}]}}"#;
+        let error = ApiError::parse(StatusCode::UNPROCESSABLE_ENTITY, body);
+        assert_eq!(error.details["username"][0].description, "is already taken");
+        let err = Error::from_response(StatusCode::UNPROCESSABLE_ENTITY, &HeaderMap::new(), error);
+        assert_eq!(err.api_error_kind(), Some(ApiErrorKind::Duplicate));
+
+        let error = ApiError::parse(StatusCode::NOT_FOUND, br#"{"error":"Record not found"}"#);
+        assert!(error.details.is_empty());
+        let err = Error::from_response(StatusCode::NOT_FOUND, &HeaderMap::new(), error);
+        assert_eq!(err.api_error_kind(), Some(ApiErrorKind::NotFound));
+        assert_eq!(err.api_error().unwrap().error, "Record not found");
+
+        let error = ApiError::parse(StatusCode::BAD_GATEWAY, b"<html>Bad Gateway</html>\n");
+        assert_eq!(error.error, "<html>Bad Gateway</html>");
+        assert_eq!(
+            ApiErrorKind::of(StatusCode::BAD_GATEWAY, &error),
+            ApiErrorKind::Server
+        );
+        let error = ApiError::parse(StatusCode::SERVICE_UNAVAILABLE, b"");
+        assert_eq!(error.error, "Service Unavailable");
+        assert_eq!(Error::from(io::Error::other("")).api_error_kind(), None);
+    }
+
     #[cfg(feature = "toml")]
     #[test]
     fn from_toml_de_error() {
@@ -1,7 +1,7 @@
 use std::io;
 
 use crate::{errors::Result, prelude::*, Error};
-use futures::{stream::try_unfold, TryStream, TryStreamExt};
+use futures::{future::ready, stream::try_unfold, TryStream, TryStreamExt};
 use log::{debug, error, info, trace};
 use reqwest::Response;
 use tokio::io::AsyncBufReadExt;
@@ -16,25 +16,40 @@ pub fn event_stream(
     location: String,
     client: &Mastodon,
 ) -> impl TryStream<Ok = (Event, Mastodon), Error = Error> + '_ {
+    events_and_heartbeats(response, location)
+        .try_filter_map(move |event| ready(Ok(event.map(|event| (event, client.clone())))))
+}
+
+/// Like [`event_stream`], but also yields `None` for each heartbeat (comment)
+/// the server sends to keep the connection alive.
+pub(crate) fn events_and_heartbeats(
+    response: Response,
+    location: String,
+) -> impl TryStream<Ok = Option<Event>, Error = Error> {
     let stream = StreamReader::new(response.bytes_stream().map_err(|err| {
         error!(err:? = err; "error reading stream");
         io::Error::new(io::ErrorKind::BrokenPipe, format!("{err:?}"))
     }));
     let lines_iter = stream.lines();
-    try_unfold((lines_iter, location, client), |mut this| async move {
-        let (ref mut lines_iter, ref location, client) = this;
-        let mut lines = vec![];
+    try_unfold((lines_iter, location, vec![]), |mut this| async move {
+        let (ref mut lines_iter, ref location, ref mut lines) = this;
         while let Some(line) = lines_iter.next_line().await? {
             debug!(message = line, location = &location; "received message");
             let line = line.trim().to_string();
-            if line.starts_with(':') || line.is_empty() {
+            if line.is_empty() {
+                // a blank line ends the message; drop whatever wasn't a
+                // recognized event so that it can't shadow the next one.
+                lines.clear();
                 continue;
             }
+            if line.starts_with(':') {
+                return Ok(Some((None, this)));
+            }
             lines.push(line);
-            if let Ok(event) = make_event(&lines) {
+            if let Ok(event) = make_event(lines) {
                 info!(event:serde = event, location = location; "received event");
                 lines.clear();
-                return Ok(Some(((event, client.clone()), this)));
+                return Ok(Some((Some(event), this)));
             } else {
                 continue;
             }
@@ -43,6 +58,11 @@ pub fn event_stream(
     })
 }
 
+/// The `data` of an `event`, which is an error if it's missing.
+fn payload(data: Option<String>, event: &str) -> Result<String> {
+    data.ok_or_else(|| Error::Other(format!("Missing `data` line for {event}")))
+}
+
 pub(crate) fn make_event(lines: &[String]) -> Result<Event> {
     let event;
     let data;
@@ -66,23 +86,102 @@ pub(crate) fn make_event(lines: &[String]) -> Result<Event> {
     trace!(event = event, payload = data; "SSE message parsed");
     Ok(match event {
         "notification" => {
-            let data = data
-                .ok_or_else(|| Error::Other("Missing `data` line for notification".to_string()))?;
+            let data = payload(data, event)?;
             let notification = serde_json::from_str::<Notification>(&data)?;
             Event::Notification(notification)
         }
         "update" => {
-            let data =
-                data.ok_or_else(|| Error::Other("Missing `data` line for update".to_string()))?;
+            let data = payload(data, event)?;
             let status = serde_json::from_str::<Status>(&data)?;
             Event::Update(status)
         }
         "delete" => {
-            let data =
-                data.ok_or_else(|| Error::Other("Missing `data` line for delete".to_string()))?;
+            let data = payload(data, event)?;
             Event::Delete(data)
         }
         "filters_changed" => Event::FiltersChanged,
+        "status.update" => {
+            let data = payload(data, event)?;
+            let status = serde_json::from_str::<Status>(&data)?;
+            Event::StatusUpdate(status)
+        }
+        "conversation" => {
+            let data = payload(data, event)?;
+            let conversation = serde_json::from_str::<Conversation>(&data)?;
+            Event::Conversation(conversation)
+        }
+        "announcement" => {
+            let data = payload(data, event)?;
+            let announcement = serde_json::from_str::<Announcement>(&data)?;
+            Event::Announcement(announcement)
+        }
+        "announcement.reaction" => {
+            let data = payload(data, event)?;
+            let reaction = serde_json::from_str::<AnnouncementReaction>(&data)?;
+            Event::AnnouncementReaction(reaction)
+        }
+        "announcement.delete" => {
+            let data = payload(data, event)?;
+            Event::AnnouncementDelete(AnnouncementId::new(data))
+        }
+        "pleroma:emoji_reaction" => {
+            let data = payload(data, event)?;
+            let status = serde_json::from_str::<Status>(&data)?;
+            Event::EmojiReaction(status)
+        }
         _ => return Err(Error::Other(format!("Unknown event `{event}`"))),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sse(event: &str, data: &str) -> Vec<String> {
+        vec![format!("event: {event}"), format!("data: {data}")]
+    }
+
+    #[test]
+    fn test_announcement_events() {
+        let event = make_event(&sse(
+            "announcement.reaction",
+            r#"{"name":"bongoCat","count":9,"announcement_id":"8"}"#,
+        ))
+        .expect("reaction");
+        let Event::AnnouncementReaction(reaction) = event else {
+            panic!("expected an announcement reaction, got {event:?}")
+        };
+        assert_eq!(reaction.announcement_id, AnnouncementId::new("8"));
+        let event = make_event(&sse("announcement.delete", "8")).expect("delete");
+        let Event::AnnouncementDelete(id) = event else {
+            panic!("expected an announcement deletion, got {event:?}")
+        };
+        assert_eq!(id, AnnouncementId::new("8"));
+    }
+
+    #[test]
+    fn test_conversation_event() {
+        let message = json!({
+            "event": "conversation",
+            "payload": json!({
+                "id": "418450",
+                "unread": true,
+                "accounts": [],
+                "last_status": null,
+            }).to_string(),
+        });
+        let event = make_event(&[message.to_string()]).expect("conversation");
+        let Event::Conversation(conversation) = event else {
+            panic!("expected a conversation, got {event:?}")
+        };
+        assert_eq!(conversation.id, ConversationId::new("418450"));
+        assert!(conversation.unread);
+    }
+
+    #[test]
+    fn test_missing_data() {
+        let err = make_event(&["event: status.update".to_string()]).unwrap_err();
+        assert!(matches!(err, Error::Other(message) if message.ends_with("status.update")));
+        assert!(make_event(&sse("unknown", "{}")).is_err());
+    }
+}
@@ -1,6 +1,12 @@
 use std::io::{self, BufRead, Write};
 
-use crate::{errors::Result, registration::Registered, Mastodon};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::TcpListener,
+};
+use url::Url;
+
+use crate::{data::Accounts, errors::Result, format_err, registration::Registered, Mastodon};
 
 /// Finishes the authentication process for the given `Registered` object,
 /// using the command-line
@@ -27,9 +33,136 @@ pub async fn authenticate(registration: Registered) -> Result<Mastodon> {
     registration.complete(code).await
 }
 
+/// Finishes the authentication process for the given `Registered` object
+/// like [`authenticate`], and saves the account in `accounts`, next to the
+/// accounts saved before, as `username@instance`.
+///
+/// To authorize another account through an app which is already saved,
+/// rather than registering another one, use [`Accounts::registered`].
+///
+/// ```no_run
+/// use mastodon_async::{data::Accounts, helpers::cli, prelude::*};
+///
+/// tokio_test::block_on(async {
+///     // e.g. read with helpers::toml::accounts_from_file
+///     let mut accounts = Accounts::new();
+///     let registration = match accounts.registered("https://botsin.space") {
+///         Some(registration) => registration,
+///         None => Registration::new("https://botsin.space")
+///             .client_name("mastodon-async_test")
+///             .build()
+///             .await
+///             .unwrap(),
+///     };
+///     cli::add_account(&mut accounts, registration).await.unwrap();
+///     // and saved with helpers::toml::accounts_to_file
+/// });
+/// ```
+pub async fn add_account(accounts: &mut Accounts, registration: Registered) -> Result<Mastodon> {
+    let mastodon = authenticate(registration).await?;
+    let account = mastodon.verify_credentials().await?;
+    let base = Url::parse(&mastodon.data.base)?;
+    let host = base.host_str().unwrap_or(&mastodon.data.base);
+    accounts.insert(format!("{}@{host}", account.username), &mastodon.data);
+    Ok(mastodon)
+}
+
+/// Finishes the authentication process for the given `Registered` object,
+/// capturing the authorization code with a temporary HTTP server on
+/// localhost instead of asking the user to paste it.
+///
+/// The app must have been registered with a loopback redirect URI which
+/// includes a port, such as `http://127.0.0.1:7777/callback`. The server
+/// listens on that address until the browser is redirected back to it.
+///
+/// ```no_run
+/// use mastodon_async::{helpers::cli, prelude::*};
+///
+/// tokio_test::block_on(async {
+///     let registration = Registration::new("https://botsin.space")
+///         .client_name("mastodon-async_test")
+///         .redirect_uris("http://127.0.0.1:7777/callback")
+///         .build()
+///         .await
+///         .unwrap();
+///     let mastodon = cli::authenticate_with_loopback(registration).await.unwrap();
+/// });
+/// ```
+pub async fn authenticate_with_loopback(registration: Registered) -> Result<Mastodon> {
+    let redirect = Url::parse(registration.redirect())?;
+    let (host, port) = match (redirect.host_str(), redirect.port()) {
+        (Some(host @ ("127.0.0.1" | "localhost" | "[::1]")), Some(port)) => {
+            (host.trim_matches(|c| c == '[' || c == ']'), port)
+        }
+        _ => {
+            return Err(format_err!(
+                "redirect URI {redirect} is not a loopback address with a port"
+            ))
+        }
+    };
+    let listener = TcpListener::bind((host, port)).await?;
+
+    let url = registration.authorize_url()?;
+    println!("Click this link to authorize: {url}");
+
+    let code = receive_code(&listener, &redirect).await?;
+    registration.complete(code).await
+}
+
+/// Accept connections on `listener` until one is a redirect carrying an
+/// authorization code or an error, and return the code.
+async fn receive_code(listener: &TcpListener, redirect: &Url) -> Result<String> {
+    loop {
+        let (mut stream, _) = listener.accept().await?;
+        let mut request_line = String::new();
+        BufReader::new(&mut stream)
+            .read_line(&mut request_line)
+            .await?;
+        // e.g. "GET /callback?code=... HTTP/1.1"
+        let Some(target) = request_line.split_whitespace().nth(1) else {
+            continue;
+        };
+        let target = redirect.join(target)?;
+        if target.path() != redirect.path() {
+            stream
+                .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n")
+                .await?;
+            continue;
+        }
+
+        let mut code = None;
+        let mut error = None;
+        for (key, value) in target.query_pairs() {
+            match key.as_ref() {
+                "code" => code = Some(value.into_owned()),
+                "error" => error = Some(value.into_owned()),
+                _ => {}
+            }
+        }
+        let body = if code.is_some() {
+            "Authorization complete. You can close this window."
+        } else {
+            "Authorization failed. You can close this window."
+        };
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; charset=utf-8\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(response.as_bytes()).await?;
+
+        match (code, error) {
+            (Some(code), _) => return Ok(code),
+            (None, Some(error)) => return Err(format_err!("authorization failed: {error}")),
+            (None, None) => continue,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tokio::{io::AsyncReadExt, net::TcpStream};
 
     #[test]
     fn send_sync() {
@@ -40,5 +173,49 @@ mod tests {
             let _ = authenticate(mock_reg()).await;
         };
         assert_send_sync(no_run());
+        let no_run = || async move {
+            let _ = authenticate_with_loopback(mock_reg()).await;
+        };
+        assert_send_sync(no_run());
+    }
+
+    async fn send(port: u16, target: &str) -> String {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+        stream
+            .write_all(format!("GET {target} HTTP/1.1\r\nHost: 127.0.0.1\r\n\r\n").as_bytes())
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[test]
+    fn test_receive_code() {
+        tokio_test::block_on(async {
+            let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+            let port = listener.local_addr().unwrap().port();
+            let redirect = Url::parse(&format!("http://127.0.0.1:{port}/callback")).unwrap();
+            let client = async {
+                assert!(send(port, "/favicon.ico").await.starts_with("HTTP/1.1 404"));
+                assert!(send(port, "/callback?code=the-code")
+                    .await
+                    .starts_with("HTTP/1.1 200"));
+            };
+            let (code, ()) = futures::join!(receive_code(&listener, &redirect), client);
+            assert_eq!(code.unwrap(), "the-code");
+        });
+    }
+
+    #[test]
+    fn test_receive_error() {
+        tokio_test::block_on(async {
+            let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+            let port = listener.local_addr().unwrap().port();
+            let redirect = Url::parse(&format!("http://127.0.0.1:{port}/")).unwrap();
+            let client = send(port, "/?error=access_denied");
+            let (code, _) = futures::join!(receive_code(&listener, &redirect), client);
+            assert!(code.is_err());
+        });
     }
 }
@@ -0,0 +1,138 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{Read, Write},
+    iter,
+    path::Path,
+};
+
+use age::{scrypt, Decryptor, Encryptor};
+
+use crate::{Data, Result};
+
+/// Attempts to decrypt and deserialize a Data struct from a slice of bytes
+pub fn from_slice(s: &[u8], passphrase: &str) -> Result<Data> {
+    from_reader(s, passphrase)
+}
+
+/// Attempts to decrypt and deserialize a Data struct from something that
+/// implements the std::io::Read trait
+pub fn from_reader<R: Read>(r: R, passphrase: &str) -> Result<Data> {
+    let identity: &dyn age::Identity = &scrypt::Identity::new(passphrase.into());
+    let reader = Decryptor::new(r)?.decrypt(iter::once(identity))?;
+    Ok(serde_json::from_reader(reader)?)
+}
+
+/// Attempts to decrypt and deserialize a Data struct from a file
+pub fn from_file<P: AsRef<Path>>(path: P, passphrase: &str) -> Result<Data> {
+    let path = path.as_ref();
+    let file = File::open(path)?;
+    from_reader(file, passphrase)
+}
+
+/// Attempts to serialize and encrypt a Data struct to a Vec of bytes
+pub fn to_vec(data: &Data, passphrase: &str) -> Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+    to_writer(data, &mut buffer, passphrase)?;
+    Ok(buffer)
+}
+
+/// Attempts to serialize and encrypt a Data struct to something that
+/// implements the std::io::Write trait
+pub fn to_writer<W: Write>(data: &Data, writer: W, passphrase: &str) -> Result<()> {
+    encrypt(data, writer, scrypt::Recipient::new(passphrase.into()))
+}
+
+fn encrypt<W: Write>(data: &Data, writer: W, recipient: scrypt::Recipient) -> Result<()> {
+    let recipient: &dyn age::Recipient = &recipient;
+    let encryptor = Encryptor::with_recipients(iter::once(recipient))?;
+    let mut writer = encryptor.wrap_output(writer)?;
+    serde_json::to_writer(&mut writer, data)?;
+    writer.finish()?;
+    Ok(())
+}
+
+/// Attempts to serialize and encrypt a Data struct to a file
+///
+/// When opening the file, this will set the `.write(true)` and
+/// `.truncate(true)` options, and on Unix make the file only readable by
+/// its owner, use the next method for more fine-grained control
+pub fn to_file<P: AsRef<Path>>(data: &Data, path: P, passphrase: &str) -> Result<()> {
+    let mut options = OpenOptions::new();
+    options.create(true).write(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    to_file_with_options(data, path, options, passphrase)
+}
+
+/// Attempts to serialize and encrypt a Data struct to a file
+pub fn to_file_with_options<P: AsRef<Path>>(
+    data: &Data,
+    path: P,
+    options: OpenOptions,
+    passphrase: &str,
+) -> Result<()> {
+    let path = path.as_ref();
+    let file = options.open(path)?;
+    to_writer(data, file, passphrase)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+    use tempfile::tempdir;
+
+    fn data() -> Data {
+        Data {
+            base: "https://example.com".into(),
+            client_id: "adbc01234".into(),
+            client_secret: "0987dcba".into(),
+            redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
+            token: "fedc5678".into(),
+            scopes: None,
+        }
+    }
+
+    /// Encrypt with a low work factor, as the default takes about a second
+    fn encrypt_quickly(passphrase: &str) -> Vec<u8> {
+        let mut recipient = scrypt::Recipient::new(passphrase.into());
+        recipient.set_work_factor(4);
+        let mut buffer = Vec::new();
+        encrypt(&data(), &mut buffer, recipient).expect("Couldn't encrypt Data");
+        buffer
+    }
+
+    #[test]
+    fn test_from_slice() {
+        let encrypted = encrypt_quickly("correct horse battery staple");
+        assert!(!String::from_utf8_lossy(&encrypted).contains("fedc5678"));
+        let decrypted =
+            from_slice(&encrypted, "correct horse battery staple").expect("Couldn't decrypt Data");
+        assert_eq!(decrypted, data());
+    }
+
+    #[test]
+    fn test_wrong_passphrase() {
+        let encrypted = encrypt_quickly("correct horse battery staple");
+        let err = from_slice(&encrypted, "Tr0ub4dor&3").unwrap_err();
+        assert!(matches!(err, Error::AgeDecrypt(_)), "{err:?}");
+    }
+
+    #[test]
+    fn test_to_file() {
+        let dir = tempdir().expect("Couldn't create tempdir");
+        let path = dir.path().join("mastodon-data.age");
+        to_file(&data(), &path, "hunter2").expect("Couldn't write to file");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        assert_eq!(
+            from_file(&path, "hunter2").expect("Couldn't read file"),
+            data()
+        );
+    }
+}
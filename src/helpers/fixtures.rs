@@ -0,0 +1,247 @@
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use reqwest::{header::HeaderMap, Method, Request, StatusCode};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+use url::Url;
+
+use crate::{
+    builder::MastodonBuilder,
+    transport::{ResponseFuture, Transport},
+    Data, Mastodon,
+};
+
+/// A local HTTP server which answers requests one at a time, for testing
+/// what the client actually sends over the network.
+#[derive(Debug)]
+pub(crate) struct Server {
+    listener: TcpListener,
+}
+
+impl Server {
+    /// Listen on a free port of the loopback interface.
+    pub(crate) async fn bind() -> Self {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        Self { listener }
+    }
+
+    /// The base URL of the server.
+    pub(crate) fn base(&self) -> String {
+        format!("http://{}", self.listener.local_addr().unwrap())
+    }
+
+    /// Data for a client of the server.
+    pub(crate) fn data(&self) -> Data {
+        Data {
+            base: self.base().into(),
+            ..Data::default()
+        }
+    }
+
+    /// Answer the next request with `status` and a JSON `body`, returning
+    /// the request.
+    pub(crate) async fn respond(&self, status: &str, body: &str) -> String {
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        self.respond_raw(response.as_bytes()).await
+    }
+
+    /// Answer the next request with `response` as it is, status line and
+    /// headers included, returning the request.
+    pub(crate) async fn respond_raw(&self, response: &[u8]) -> String {
+        let (mut stream, request) = self.accept().await;
+        stream.write_all(response).await.unwrap();
+        request
+    }
+
+    /// Receive the next request and send the start of a response, but
+    /// nothing more, returning the connection to keep it open until the
+    /// client gives up.
+    pub(crate) async fn hang(&self, partial: &[u8]) -> TcpStream {
+        let (mut stream, _) = self.accept().await;
+        stream.write_all(partial).await.unwrap();
+        stream
+    }
+
+    async fn accept(&self) -> (TcpStream, String) {
+        let (mut stream, _) = self.listener.accept().await.unwrap();
+        let mut request = vec![0; 4096];
+        let len = stream.read(&mut request).await.unwrap();
+        let request = String::from_utf8_lossy(&request[..len]).to_string();
+        (stream, request)
+    }
+}
+
+/// A client for `https://mastodon.example` which sends its requests to
+/// `transport`.
+pub(crate) fn client(transport: impl Transport + 'static) -> Mastodon {
+    let data = Data {
+        base: "https://mastodon.example".into(),
+        ..Data::default()
+    };
+    client_with(data, transport)
+}
+
+/// A client for the instance in `data` which sends its requests to
+/// `transport`.
+pub(crate) fn client_with(data: Data, transport: impl Transport + 'static) -> Mastodon {
+    MastodonBuilder::new(data)
+        .transport(transport)
+        .build()
+        .unwrap()
+}
+
+/// A fake instance which answers each request with the [`Reply`] a function
+/// gives for it, without a network, and records the requests it received.
+pub(crate) struct Fake {
+    respond: Box<dyn Fn(&Request) -> Reply + Send + Sync>,
+    requests: Mutex<Vec<Recorded>>,
+}
+
+impl Fake {
+    /// A fake answering requests with `respond`.
+    pub(crate) fn new(respond: impl Fn(&Request) -> Reply + Send + Sync + 'static) -> Arc<Self> {
+        Arc::new(Self {
+            respond: Box::new(respond),
+            requests: Mutex::default(),
+        })
+    }
+
+    /// A fake answering requests for each path with the JSON body given for
+    /// it, and `404 Not Found` for paths it doesn't know.
+    pub(crate) fn routes<B: Into<String>>(
+        routes: impl IntoIterator<Item = (&'static str, B)>,
+    ) -> Arc<Self> {
+        let routes: Vec<(&str, String)> = routes
+            .into_iter()
+            .map(|(path, body)| (path, body.into()))
+            .collect();
+        Self::new(move |request| {
+            routes
+                .iter()
+                .find(|(path, _)| *path == request.url().path())
+                .map_or_else(Reply::not_found, |(_, body)| Reply::ok(body.clone()))
+        })
+    }
+
+    /// Every request received so far, oldest first.
+    pub(crate) fn requests(&self) -> Vec<Recorded> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// The paths of the requests received so far, oldest first.
+    pub(crate) fn paths(&self) -> Vec<String> {
+        self.requests()
+            .iter()
+            .map(|request| request.url.path().to_string())
+            .collect()
+    }
+
+    /// The full URLs of the requests received so far, oldest first.
+    pub(crate) fn urls(&self) -> Vec<String> {
+        self.requests()
+            .iter()
+            .map(|request| request.url.to_string())
+            .collect()
+    }
+}
+
+impl fmt::Debug for Fake {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Fake")
+            .field("requests", &self.requests)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Transport for Arc<Fake> {
+    fn execute(&self, request: Request) -> ResponseFuture<'_> {
+        self.requests.lock().unwrap().push(Recorded {
+            method: request.method().clone(),
+            url: request.url().clone(),
+            headers: request.headers().clone(),
+            body: request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .map(<[u8]>::to_vec),
+        });
+        let reply = (self.respond)(&request);
+        Box::pin(async move {
+            if !reply.delay.is_zero() {
+                tokio::time::sleep(reply.delay).await;
+            }
+            let mut response = http::Response::builder().status(reply.status);
+            for (name, value) in reply.headers {
+                response = response.header(name, value);
+            }
+            Ok(response.body(reply.body).unwrap().into())
+        })
+    }
+}
+
+/// A request received by a [`Fake`] instance.
+#[derive(Debug, Clone)]
+pub(crate) struct Recorded {
+    pub(crate) method: Method,
+    pub(crate) url: Url,
+    pub(crate) headers: HeaderMap,
+    pub(crate) body: Option<Vec<u8>>,
+}
+
+impl Recorded {
+    /// The request body parsed as JSON.
+    pub(crate) fn json<T: serde::de::DeserializeOwned>(&self) -> T {
+        serde_json::from_slice(self.body.as_deref().unwrap()).unwrap()
+    }
+}
+
+/// The response a [`Fake`] instance gives to a request.
+#[derive(Debug)]
+pub(crate) struct Reply {
+    status: StatusCode,
+    headers: Vec<(&'static str, String)>,
+    body: String,
+    delay: Duration,
+}
+
+impl Reply {
+    /// A response with `status` and a JSON `body`.
+    pub(crate) fn status(status: StatusCode, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            headers: vec![],
+            body: body.into(),
+            delay: Duration::ZERO,
+        }
+    }
+
+    /// A `200 OK` response with a JSON `body`.
+    pub(crate) fn ok(body: impl Into<String>) -> Self {
+        Self::status(StatusCode::OK, body)
+    }
+
+    /// A `404 Not Found` response.
+    pub(crate) fn not_found() -> Self {
+        Self::status(StatusCode::NOT_FOUND, r#"{"error":"Not Found"}"#)
+    }
+
+    /// Add a header to the response.
+    pub(crate) fn header(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.headers.push((name, value.into()));
+        self
+    }
+
+    /// Wait for `delay` before responding.
+    pub(crate) fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
@@ -0,0 +1,65 @@
+use keyringcrate::Entry;
+
+use crate::{Data, Result};
+
+/// Attempts to deserialize a Data struct from the platform's secret store,
+/// where it was saved with `to_keyring` for the same `service` and `user`
+pub fn from_keyring(service: &str, user: &str) -> Result<Data> {
+    from_entry(&Entry::new(service, user)?)
+}
+
+/// Attempts to deserialize a Data struct from an entry in the platform's
+/// secret store
+pub fn from_entry(entry: &Entry) -> Result<Data> {
+    Ok(serde_json::from_str(&entry.get_password()?)?)
+}
+
+/// Attempts to save a Data struct in the platform's secret store, as the
+/// password for `user` of `service`, replacing anything saved there before
+pub fn to_keyring(data: &Data, service: &str, user: &str) -> Result<()> {
+    to_entry(data, &Entry::new(service, user)?)
+}
+
+/// Attempts to save a Data struct to an entry in the platform's secret
+/// store
+pub fn to_entry(data: &Data, entry: &Entry) -> Result<()> {
+    entry.set_password(&serde_json::to_string(data)?)?;
+    Ok(())
+}
+
+/// Removes a Data struct saved with `to_keyring` from the platform's secret
+/// store, e.g. when logging out
+pub fn delete_from_keyring(service: &str, user: &str) -> Result<()> {
+    Entry::new(service, user)?.delete_credential()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+    use keyringcrate::{mock, Error as KeyringError};
+
+    #[test]
+    fn test_entry_round_trip() {
+        keyringcrate::set_default_credential_builder(mock::default_credential_builder());
+        let data = Data {
+            base: "https://example.com".into(),
+            client_id: "adbc01234".into(),
+            client_secret: "0987dcba".into(),
+            redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
+            token: "fedc5678".into(),
+            ..Data::default()
+        };
+        let entry = Entry::new("mastodon-async-test", "user@example.com").unwrap();
+        assert!(matches!(
+            from_entry(&entry),
+            Err(Error::Keyring(KeyringError::NoEntry))
+        ));
+        to_entry(&data, &entry).expect("Couldn't save to the keyring");
+        assert_eq!(
+            from_entry(&entry).expect("Couldn't load from the keyring"),
+            data
+        );
+    }
+}
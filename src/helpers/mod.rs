@@ -22,6 +22,37 @@ pub mod toml;
 /// ```
 pub mod json;
 
+#[cfg(all(feature = "encrypted", not(target_arch = "wasm32")))]
+/// Helpers for saving a `Data` struct to, and loading it from, a file
+/// encrypted with a passphrase, in the [age](https://age-encryption.org)
+/// format, so it can also be decrypted with `age --decrypt`
+///
+/// In order to use this module, set the "encrypted" feature in your
+/// Cargo.toml:
+///
+/// ```toml,ignore
+/// [dependencies.mastodon-async]
+/// version = "1"
+/// features = ["encrypted"]
+/// ```
+pub mod encrypted;
+
+#[cfg(all(feature = "keyring", not(target_arch = "wasm32")))]
+/// Helpers for saving a `Data` struct in, and loading it from, the
+/// platform's secret store: the Secret Service on Linux and the BSDs, the
+/// Keychain on macOS and the Credential Manager on Windows, so the access
+/// token isn't kept in a plaintext file
+///
+/// In order to use this module, set the "keyring" feature in your
+/// Cargo.toml:
+///
+/// ```toml,ignore
+/// [dependencies.mastodon-async]
+/// version = "1"
+/// features = ["keyring"]
+/// ```
+pub mod keyring;
+
 #[cfg(feature = "env")]
 /// Helpers for deserializing a `Data` struct from the environment
 ///
@@ -35,9 +66,15 @@ pub mod json;
 pub mod env;
 
 /// Helpers for working with the command line
+#[cfg(not(target_arch = "wasm32"))]
 pub mod cli;
+/// Fixtures shared by the tests
+#[cfg(all(test, not(target_arch = "wasm32")))]
+pub(crate) mod fixtures;
 /// Helpers for serializing data for logging
 pub mod log;
 /// Adapter for reading JSON data from a response with better logging and a
 /// fail-safe timeout.
 pub mod read_response;
+/// Timers which work on every supported platform, including WebAssembly
+pub(crate) mod time;
@@ -1,12 +1,15 @@
 use std::time::Duration;
 
-use crate::{errors::Result, Error};
+use crate::{
+    errors::{ApiError, Result},
+    helpers::time::timeout,
+    Error,
+};
 use futures::pin_mut;
 use futures_util::StreamExt;
 use log::{debug, trace, warn};
 use reqwest::Response;
 use serde::{Deserialize, Serialize};
-use tokio::time::timeout;
 
 /// Adapter for reading JSON data from a response with better logging and a
 /// fail-safe timeout.
@@ -21,6 +24,7 @@ where
     let mut bytes = vec![];
     let url = response.url().clone();
     let status = response.status();
+    let headers = response.headers().clone();
     trace!(status:serde = crate::helpers::log::Status::from(&response), headers:serde = crate::helpers::log::Headers::from(&response); "attempting to stream response");
     let stream = response.bytes_stream();
     pin_mut!(stream);
@@ -66,8 +70,8 @@ where
         Ok(result)
     } else {
         // we've received an error message, let's deserialize that instead.
-        let response = serde_json::from_slice(bytes)?;
+        let response = ApiError::parse(status, bytes);
         debug!(status:? = status, response:serde = response; "error received from API");
-        Err(Error::Api { status, response })
+        Err(Error::from_response(status, &headers, response))
     }
 }
@@ -0,0 +1,35 @@
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use tokio::time::{sleep, timeout};
+#[cfg(target_arch = "wasm32")]
+pub(crate) use wasm::{sleep, timeout};
+
+/// Timers backed by the browser's `setTimeout`, since tokio's need a runtime
+/// which isn't available on WebAssembly.
+#[cfg(target_arch = "wasm32")]
+mod wasm {
+    use std::{future::Future, time::Duration};
+
+    use futures::future::{select, Either};
+
+    /// Wait until `duration` has elapsed.
+    pub(crate) async fn sleep(duration: Duration) {
+        gloo_timers::future::sleep(duration).await
+    }
+
+    /// Wait for `future` to complete, giving up after `duration`.
+    pub(crate) async fn timeout<F: Future>(
+        duration: Duration,
+        future: F,
+    ) -> Result<F::Output, Elapsed> {
+        futures::pin_mut!(future);
+        match select(future, Box::pin(sleep(duration))).await {
+            Either::Left((output, _)) => Ok(output),
+            Either::Right(_) => Err(Elapsed),
+        }
+    }
+
+    /// The error returned by [`timeout`] when the future didn't complete in
+    /// time.
+    #[derive(Debug)]
+    pub(crate) struct Elapsed;
+}
@@ -6,7 +6,7 @@ use std::{
 
 use tomlcrate;
 
-use crate::{Data, Result};
+use crate::{data::Accounts, Data, Result};
 
 /// Attempts to deserialize a Data struct from a string
 pub fn from_str(s: &str) -> Result<Data> {
@@ -73,6 +73,40 @@ pub fn to_file_with_options<P: AsRef<Path>>(
     Ok(())
 }
 
+/// Attempts to deserialize saved accounts from a string, which may also
+/// hold a single Data struct
+pub fn accounts_from_str(s: &str) -> Result<Accounts> {
+    Ok(tomlcrate::from_str(s)?)
+}
+
+/// Attempts to deserialize saved accounts from a file, which may also hold
+/// a single Data struct. A file which doesn't exist holds no accounts.
+pub fn accounts_from_file<P: AsRef<Path>>(path: P) -> Result<Accounts> {
+    match std::fs::read_to_string(path) {
+        Ok(s) => accounts_from_str(&s),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Accounts::new()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Attempts to serialize saved accounts to a String
+pub fn accounts_to_string(accounts: &Accounts) -> Result<String> {
+    Ok(tomlcrate::to_string_pretty(accounts)?)
+}
+
+/// Attempts to serialize saved accounts to a file, replacing its contents.
+/// On Unix, a new file is made only readable by its owner, as it holds
+/// access tokens.
+pub fn accounts_to_file<P: AsRef<Path>>(accounts: &Accounts, path: P) -> Result<()> {
+    let mut options = OpenOptions::new();
+    options.create(true).write(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(path)?;
+    file.write_all(accounts_to_string(accounts)?.as_bytes())?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,6 +134,7 @@ mod tests {
                 client_secret: "0987dcba".into(),
                 redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
                 token: "fedc5678".into(),
+                scopes: None,
             }
         );
     }
@@ -115,6 +150,7 @@ mod tests {
                 client_secret: "0987dcba".into(),
                 redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
                 token: "fedc5678".into(),
+                scopes: None,
             }
         );
     }
@@ -131,6 +167,7 @@ mod tests {
                 client_secret: "0987dcba".into(),
                 redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
                 token: "fedc5678".into(),
+                scopes: None,
             }
         );
     }
@@ -147,6 +184,7 @@ mod tests {
                 client_secret: "0987dcba".into(),
                 redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
                 token: "fedc5678".into(),
+                scopes: None,
             }
         );
     }
@@ -158,6 +196,7 @@ mod tests {
             client_secret: "0987dcba".into(),
             redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
             token: "fedc5678".into(),
+            scopes: None,
         };
         let s = to_string(&data).expect("Couldn't serialize Data");
         let desered = from_str(&s).expect("Couldn't deserialize Data");
@@ -171,6 +210,7 @@ mod tests {
             client_secret: "0987dcba".into(),
             redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
             token: "fedc5678".into(),
+            scopes: None,
         };
         let v = to_vec(&data).expect("Couldn't write to vec");
         let desered = from_slice(&v).expect("Couldn't deserialize data");
@@ -184,6 +224,7 @@ mod tests {
             client_secret: "0987dcba".into(),
             redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
             token: "fedc5678".into(),
+            scopes: None,
         };
         let mut buffer = Vec::new();
         to_writer(&data, &mut buffer).expect("Couldn't write to writer");
@@ -199,6 +240,7 @@ mod tests {
             client_secret: "0987dcba".into(),
             redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
             token: "fedc5678".into(),
+            scopes: None,
         };
         let tempdir = tempdir().expect("Couldn't create tempdir");
         let filename = tempdir.path().join("mastodon-data.toml");
@@ -214,6 +256,7 @@ mod tests {
             client_secret: "0987dcba".into(),
             redirect: "urn:ietf:wg:oauth:2.0:oob".into(),
             token: "fedc5678".into(),
+            scopes: None,
         };
         let file = NamedTempFile::new().expect("Couldn't create tempfile");
         let mut options = OpenOptions::new();
@@ -222,4 +265,29 @@ mod tests {
         let desered = from_file(file.path()).expect("Couldn't deserialize Data");
         assert_eq!(data, desered);
     }
+
+    #[test]
+    fn test_accounts_file() {
+        let dir = tempdir().expect("Couldn't create tempdir");
+        let path = dir.path().join("accounts.toml");
+        let mut accounts = accounts_from_file(&path).expect("Couldn't read missing file");
+        assert_eq!(accounts, Accounts::new());
+
+        let data = from_str(DOC).expect("Couldn't deserialize Data");
+        accounts.insert("alice@example.com", &data);
+        accounts.insert("bob@example.com", &data);
+        accounts_to_file(&accounts, &path).expect("Couldn't write to file");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        let read = accounts_from_file(&path).expect("Couldn't read from file");
+        assert_eq!(read, accounts);
+        assert_eq!(read.get("bob@example.com"), Some(data.clone()));
+
+        let migrated = accounts_from_str(DOC).expect("Couldn't migrate Data");
+        assert_eq!(migrated.default_account(), Some(data));
+    }
 }
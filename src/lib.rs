@@ -46,6 +46,7 @@
 //!             Event::Notification(ref notification) => { /* .. */ },
 //!             Event::Delete(ref id) => { /* .. */ },
 //!             Event::FiltersChanged => { /* .. */ },
+//!             _ => { /* .. */ },
 //!         }
 //!         Ok(())
 //!     }).await.unwrap();
@@ -78,6 +79,9 @@ extern crate envy;
 #[cfg(feature = "toml")]
 extern crate toml as tomlcrate;
 
+#[cfg(all(feature = "keyring", not(target_arch = "wasm32")))]
+extern crate keyring as keyringcrate;
+
 #[cfg(test)]
 extern crate tempfile;
 
@@ -87,8 +91,9 @@ extern crate indoc;
 
 use page::Page;
 
+pub use builder::MastodonBuilder;
 pub use data::Data;
-pub use errors::{ApiError, Error, Result};
+pub use errors::{ApiError, ApiErrorKind, Error, Result};
 pub use isolang::Language;
 pub use mastodon::{Mastodon, MastodonUnauthenticated};
 // pub use mastodon_client::{MastodonClient, MastodonUnauthenticated};
@@ -98,8 +103,19 @@ pub use mastodon_async_entities::{
 pub use registration::Registration;
 pub use requests::{AddPushRequest, StatusesRequest, UpdatePushRequest};
 
+/// Fetching many entities by ID at once
+pub mod batch;
+/// A client which blocks instead of being async
+#[cfg(feature = "blocking")]
+pub mod blocking;
+/// Configuring the client
+pub mod builder;
+/// Detecting the server software of an instance and what it supports
+pub mod capabilities;
 /// Contains the struct that holds the client auth data
 pub mod data;
+/// Dropping repeated events and ordering the rest
+pub mod dedup;
 /// Entities returned from the API
 pub mod entities;
 /// Errors
@@ -108,17 +124,45 @@ pub mod errors;
 pub mod event_stream;
 /// Collection of helpers for serializing/deserializing `Data` objects
 pub mod helpers;
+/// Hooks around the requests sent by the client
+pub mod middleware;
+/// Posting statuses which can wait until the instance can be reached
+#[cfg(all(feature = "outbox", not(target_arch = "wasm32")))]
+pub mod outbox;
 /// Handling multiple pages of entities.
 pub mod page;
+/// Posting statuses with media attached in one go
+pub mod post;
+/// Rate limits reported by the server
+pub mod rate_limit;
 /// Registering your app.
 pub mod registration;
 /// Requests
 pub mod requests;
+/// Retrying failed requests
+pub mod retry;
+/// A fake instance for testing applications
+#[cfg(all(feature = "testing", not(target_arch = "wasm32")))]
+pub mod testing;
+/// Arranging conversations into trees of replies
+pub mod thread;
+/// Following a timeline from its recent history to the statuses streamed
+pub mod timeline;
+/// Pluggable HTTP transports
+pub mod transport;
+/// Recording and replaying HTTP interactions in tests
+#[cfg(all(feature = "vcr", not(target_arch = "wasm32")))]
+pub mod vcr;
 
+/// Caching responses to conditional requests
+#[cfg(not(target_arch = "wasm32"))]
+mod cache;
 #[macro_use]
 mod macros;
 /// How much time to wait before checking an endpoint again.
 pub mod polling_time;
+/// The OAuth scopes required by the API endpoints
+mod scopes;
 /// Automatically import the things you need
 pub mod prelude {
     pub use crate::{
@@ -130,11 +174,19 @@ pub mod prelude {
 }
 /// The mastodon client
 pub mod mastodon;
+/// Streaming channels and automatically reconnecting streams
+pub mod streaming;
+/// Decrypting Web Push notifications
+#[cfg(feature = "web-push")]
+pub mod web_push;
+/// Multiplexed streaming over a single WebSocket connection
+#[cfg(feature = "websocket")]
+pub mod websocket;
 
 /// Legacy aliases. TODO remove for 2.0
 pub mod status_builder {
     pub use super::entities::{
-        status::{NewStatus, NewStatusBuilder as StatusBuilder},
+        status::{ContentType, NewStatus, NewStatusBuilder as StatusBuilder},
         visibility::Visibility,
     };
 }
@@ -21,7 +21,7 @@ macro_rules! methods {
 
                 let url = url.as_ref();
                 debug!(url = url, method = stringify!($method), call_id:? = call_id; "making API request");
-                let response = self.authenticated(self.client.$method(url)).header("Accept", "application/json").send().await?;
+                let response = self.send(self.authenticated(self.client.$method(url)).header("Accept", "application/json")).await?;
                 read_response(response).await
             }
          )+
@@ -49,7 +49,7 @@ macro_rules! paged_routes {
                 let url = self.route(concat!("/api/v1/", $url));
                 let call_id = uuid::Uuid::new_v4();
                 debug!(url = url, method = stringify!($method), call_id:? = call_id; "making API request");
-                let response = self.authenticated(self.client.$method(&url)).header("Accept", "application/json").send().await?;
+                let response = self.send(self.authenticated(self.client.$method(&url)).header("Accept", "application/json")).await?;
 
                 Page::new(self.clone(), response, call_id).await
             }
@@ -66,6 +66,7 @@ macro_rules! paged_routes {
                 $url,
                 "`\n# Errors\nIf `access_token` is not set."
             ),
+            #[allow(clippy::extra_unused_lifetimes)]
             pub async fn $name<'a>(&self, $($param: $typ,)*) -> Result<Page<$ret>> {
                 use serde_urlencoded;
                 use log::debug;
@@ -99,7 +100,7 @@ macro_rules! paged_routes {
 
                 debug!(url = url, method = "get", call_id:? = call_id; "making API request");
 
-                let response = self.authenticated(self.client.get(&url)).header("Accept", "application/json").send().await?;
+                let response = self.send(self.authenticated(self.client.get(&url)).header("Accept", "application/json")).await?;
 
                 Page::new(self.clone(), response, call_id).await
             }
@@ -119,6 +120,7 @@ macro_rules! route_v2 {
                 $url,
                 "`\n# Errors\nIf `access_token` is not set."
             ),
+            #[allow(clippy::extra_unused_lifetimes)]
             pub async fn $name<'a>(&self, $($param: $typ,)*) -> Result<$ret> {
                 use serde_urlencoded;
                 use log::debug;
@@ -187,11 +189,10 @@ macro_rules! route_v2 {
                     "making API request"
                 );
 
-                let response = self.authenticated(self.client.post(url))
+                let request = self.authenticated(self.client.post(url))
                     .multipart(form_data)
-                    .header("Accept", "application/json")
-                    .send()
-                    .await?;
+                    .header("Accept", "application/json");
+                let response = self.send(request).await?;
 
                 read_response(response).await
             }
@@ -227,11 +228,10 @@ macro_rules! route_v2 {
                     "making API request"
                 );
 
-                let response = self.authenticated(self.client.post(url))
+                let request = self.authenticated(self.client.post(url))
                     .multipart(form_data)
-                    .header("Accept", "application/json")
-                    .send()
-                    .await?;
+                    .header("Accept", "application/json");
+                let response = self.send(request).await?;
 
                 read_response(response).await
             }
@@ -275,9 +275,7 @@ macro_rules! route_v2 {
 
                 let call_id = Uuid::new_v4();
 
-                let form_data = serde_urlencoded::to_string(&form)?;
-
-                let url = &self.route(format!("/api/v2/{}?{form_data}", $url));
+                let url = &self.route(concat!("/api/v2/", $url));
                 debug!(
                     url = url.as_str(), method = stringify!($method),
                     call_id:? = call_id,
@@ -285,10 +283,10 @@ macro_rules! route_v2 {
                     "making API request"
                 );
 
-                let response = self.authenticated(self.client.$method(url))
-                    .header("Accept", "application/json")
-                    .send()
-                    .await?;
+                let request = self.authenticated(self.client.$method(url))
+                    .json(&form)
+                    .header("Accept", "application/json");
+                let response = self.send(request).await?;
 
                 read_response(response).await
             }
@@ -328,11 +326,10 @@ macro_rules! route {
                     "making API request"
                 );
 
-                let response = self.authenticated(self.client.post(url))
+                let request = self.authenticated(self.client.post(url))
                     .multipart(form_data)
-                    .header("Accept", "application/json")
-                    .send()
-                    .await?;
+                    .header("Accept", "application/json");
+                let response = self.send(request).await?;
 
                 read_response(response).await
             }
@@ -373,11 +370,10 @@ macro_rules! route {
                     "making API request"
                 );
 
-                let response = self.authenticated(self.client.post(url))
+                let request = self.authenticated(self.client.post(url))
                     .multipart(form_data)
-                    .header("Accept", "application/json")
-                    .send()
-                    .await?;
+                    .header("Accept", "application/json");
+                let response = self.send(request).await?;
 
                 read_response(response).await
             }
@@ -391,6 +387,7 @@ macro_rules! route {
                 $url,
                 "`\n# Errors\nIf `access_token` is not set."
             ),
+            #[allow(clippy::extra_unused_lifetimes)]
             pub async fn $name<'a>(&self, $($param: $typ,)*) -> Result<$ret> {
                 use serde_urlencoded;
                 use log::debug;
@@ -429,40 +426,72 @@ macro_rules! route {
         route!{$($rest)*}
     };
 
-    (($method:ident ($($param:ident: $typ:ty,)*)) $name:ident: $url:expr => $ret:ty, $($rest:tt)*) => {
-        doc_comment! {
-            concat!(
-                "Equivalent to `", stringify!($method), " /api/v1/",
-                $url,
-                "`\n# Errors\nIf `access_token` is not set.",
-            ),
-            pub async fn $name(&self, $($param: $typ,)*) -> Result<$ret> {
-                use log::debug;
-                use uuid::Uuid;
-
-                let call_id = Uuid::new_v4();
-
-                let form_data = json!({
-                    $(
-                        stringify!($param): $param,
-                    )*
-                });
-                let url = &self.route(concat!("/api/v1/", $url));
-                debug!(
-                    url = url.as_str(), method = stringify!($method),
-                    call_id:? = call_id,
-                    form_data:serde = &form_data;
-                    "making API request"
-                );
+    ($(#[$m:meta])* ($method:ident<-$typ:ty) $name:ident: $url:expr => $ret:ty, $($rest:tt)*) => {
+        $(#[$m])*
+        #[doc = ""]
+        #[doc = concat!(
+            "Equivalent to `", stringify!($method), " /api/v1/",
+            $url,
+            "`\n# Errors\nIf `access_token` is not set.",
+        )]
+        pub async fn $name(&self, form: $typ) -> Result<$ret> {
+            use log::debug;
+            use uuid::Uuid;
+
+            let call_id = Uuid::new_v4();
+
+            let url = &self.route(concat!("/api/v1/", $url));
+            debug!(
+                url = url.as_str(), method = stringify!($method),
+                call_id:? = call_id,
+                form_data:serde = &form;
+                "making API request"
+            );
+
+            let request = self.authenticated(self.client.$method(url))
+                .json(&form)
+                .header("Accept", "application/json");
+            let response = self.send(request).await?;
+
+            read_response(response).await
+        }
 
-                let response = self.authenticated(self.client.$method(url))
-                    .json(&form_data)
-                    .header("Accept", "application/json")
-                    .send()
-                    .await?;
+        route!{$($rest)*}
+    };
 
-                read_response(response).await
-            }
+    ($(#[$m:meta])* ($method:ident ($($param:ident: $typ:ty,)*)) $name:ident: $url:expr => $ret:ty, $($rest:tt)*) => {
+        $(#[$m])*
+        #[doc = ""]
+        #[doc = concat!(
+            "Equivalent to `", stringify!($method), " /api/v1/",
+            $url,
+            "`\n# Errors\nIf `access_token` is not set.",
+        )]
+        pub async fn $name(&self, $($param: $typ,)*) -> Result<$ret> {
+            use log::debug;
+            use uuid::Uuid;
+
+            let call_id = Uuid::new_v4();
+
+            let form_data = json!({
+                $(
+                    stringify!($param): $param,
+                )*
+            });
+            let url = &self.route(concat!("/api/v1/", $url));
+            debug!(
+                url = url.as_str(), method = stringify!($method),
+                call_id:? = call_id,
+                form_data:serde = &form_data;
+                "making API request"
+            );
+
+            let request = self.authenticated(self.client.$method(url))
+                .json(&form_data)
+                .header("Accept", "application/json");
+            let response = self.send(request).await?;
+
+            read_response(response).await
         }
 
         route!{$($rest)*}
@@ -495,29 +524,102 @@ macro_rules! route {
 
 macro_rules! route_id {
 
-    ($(($method:ident) $name:ident[$id_type:ty]: $url:expr => $ret:ty,)*) => {
-        $(
-            doc_comment! {
-                concat!(
-                    "Equivalent to `", stringify!($method), " /api/v1/",
-                    $url,
-                    "`\n# Errors\nIf `access_token` is not set.",
-                    "\n",
-                    "```no_run",
-                    "use mastodon_async::prelude::*;\n",
-                    "let data = Data::default();\n",
-                    "let client = Mastodon::from(data);\n",
-                    "client.", stringify!($name), "(\"42\");\n",
-                    "#   Ok(())\n",
-                    "# }\n",
-                    "```"
-                ),
-                pub async fn $name(&self, id: &$id_type) -> Result<$ret> {
-                    self.$method(self.route(&format!(concat!("/api/v1/", $url), id))).await
-                }
+    ($(#[$m:meta])* ($method:ident<-$typ:ty) $name:ident[$id_type:ty]: $url:expr => $ret:ty, $($rest:tt)*) => {
+        $(#[$m])*
+        #[doc = ""]
+        #[doc = concat!(
+            "Equivalent to `", stringify!($method), " /api/v1/",
+            $url,
+            "`\n# Errors\nIf `access_token` is not set.",
+        )]
+        pub async fn $name(&self, id: impl Into<$id_type>, form: $typ) -> Result<$ret> {
+            use log::debug;
+            use uuid::Uuid;
+
+            let call_id = Uuid::new_v4();
+
+            let url = &self.route(format!(concat!("/api/v1/", $url), id.into()));
+            debug!(
+                url = url.as_str(), method = stringify!($method),
+                call_id:? = call_id,
+                form_data:serde = &form;
+                "making API request"
+            );
+
+            let request = self.authenticated(self.client.$method(url))
+                .json(&form)
+                .header("Accept", "application/json");
+            let response = self.send(request).await?;
+
+            read_response(response).await
+        }
+
+        route_id!{$($rest)*}
+    };
+
+    ($(#[$m:meta])* ($method:ident ($($param:ident: $typ:ty,)*)) $name:ident[$id_type:ty]: $url:expr => $ret:ty, $($rest:tt)*) => {
+        $(#[$m])*
+        #[doc = ""]
+        #[doc = concat!(
+            "Equivalent to `", stringify!($method), " /api/v1/",
+            $url,
+            "`\n# Errors\nIf `access_token` is not set.",
+        )]
+        pub async fn $name(&self, id: impl Into<$id_type>, $($param: $typ,)*) -> Result<$ret> {
+            use log::debug;
+            use uuid::Uuid;
+
+            let call_id = Uuid::new_v4();
+
+            let form_data = json!({
+                $(
+                    stringify!($param): $param,
+                )*
+            });
+            let url = &self.route(format!(concat!("/api/v1/", $url), id.into()));
+            debug!(
+                url = url.as_str(), method = stringify!($method),
+                call_id:? = call_id,
+                form_data:serde = &form_data;
+                "making API request"
+            );
+
+            let request = self.authenticated(self.client.$method(url))
+                .json(&form_data)
+                .header("Accept", "application/json");
+            let response = self.send(request).await?;
+
+            read_response(response).await
+        }
+
+        route_id!{$($rest)*}
+    };
+
+    (($method:ident) $name:ident[$id_type:ty]: $url:expr => $ret:ty, $($rest:tt)*) => {
+        doc_comment! {
+            concat!(
+                "Equivalent to `", stringify!($method), " /api/v1/",
+                $url,
+                "`\n# Errors\nIf `access_token` is not set.",
+                "\n",
+                "```no_run",
+                "use mastodon_async::prelude::*;\n",
+                "let data = Data::default();\n",
+                "let client = Mastodon::from(data);\n",
+                "client.", stringify!($name), "(\"42\");\n",
+                "#   Ok(())\n",
+                "# }\n",
+                "```"
+            ),
+            pub async fn $name(&self, id: impl Into<$id_type>) -> Result<$ret> {
+                self.$method(self.route(&format!(concat!("/api/v1/", $url), id.into()))).await
             }
-         )*
-    }
+        }
+
+        route_id!{$($rest)*}
+    };
+
+    () => {};
 }
 
 macro_rules! route_v2_id {
@@ -537,8 +639,8 @@ macro_rules! route_v2_id {
                 "# }\n",
                 "```"
             ),
-            pub async fn $name(&self, id: &$id_type) -> Result<$ret> {
-                self.$method(self.route(&format!(concat!("/api/v2/", $url), id))).await
+            pub async fn $name(&self, id: impl Into<$id_type>) -> Result<$ret> {
+                self.$method(self.route(&format!(concat!("/api/v2/", $url), id.into()))).await
             }
         }
 
@@ -551,15 +653,13 @@ macro_rules! route_v2_id {
                 $url,
                 "`\n# Errors\nIf `access_token` is not set.",
             ),
-            pub async fn $name(&self, id: $id_type, form: $typ) -> Result<$ret> {
+            pub async fn $name(&self, id: impl Into<$id_type>, form: $typ) -> Result<$ret> {
                 use log::debug;
                 use uuid::Uuid;
 
                 let call_id = Uuid::new_v4();
 
-                let form_data = serde_urlencoded::to_string(&form)?;
-
-                let url = &self.route(format!("/api/v2/{}?{form_data}", format!($url, id)));
+                let url = &self.route(format!(concat!("/api/v2/", $url), id.into()));
                 debug!(
                     url = url.as_str(), method = stringify!($method),
                     call_id:? = call_id,
@@ -567,10 +667,10 @@ macro_rules! route_v2_id {
                     "making API request"
                 );
 
-                let response = self.authenticated(self.client.$method(url))
-                    .header("Accept", "application/json")
-                    .send()
-                    .await?;
+                let request = self.authenticated(self.client.$method(url))
+                    .json(&form)
+                    .header("Accept", "application/json");
+                let response = self.send(request).await?;
 
                 read_response(response).await
             }
@@ -583,7 +683,7 @@ macro_rules! route_v2_id {
 
 macro_rules! paged_routes_with_id {
 
-    (($method:ident) $name:ident: $url:expr => $ret:ty, $($rest:tt)*) => {
+    (($method:ident) $name:ident[$id_type:ty]: $url:expr => $ret:ty, $($rest:tt)*) => {
         doc_comment! {
             concat!(
                 "Equivalent to `", stringify!($method), " /api/v1/",
@@ -597,15 +697,15 @@ macro_rules! paged_routes_with_id {
                 "client.", stringify!($name), "(\"some-id\");\n",
                 "```"
             ),
-            pub async fn $name(&self, id: impl AsRef<str>) -> Result<Page<$ret>> {
+            pub async fn $name(&self, id: impl Into<$id_type>) -> Result<Page<$ret>> {
                 use log::debug;
                 use uuid::Uuid;
 
                 let call_id = Uuid::new_v4();
-                let url = self.route(&format!(concat!("/api/v1/", $url), id.as_ref()));
+                let url = self.route(&format!(concat!("/api/v1/", $url), id.into()));
 
                 debug!(url = url, method = stringify!($method), call_id:? = call_id; "making API request");
-                let response = self.authenticated(self.client.$method(&url)).header("Accept", "application/json").send().await?;
+                let response = self.send(self.authenticated(self.client.$method(&url)).header("Accept", "application/json")).await?;
                 Page::new(self.clone(), response, call_id).await
             }
         }
@@ -639,6 +739,7 @@ tokio_test::block_on(async {
             Event::Notification(ref notification) => { /* .. */ },
             Event::Delete(ref id) => { /* .. */ },
             Event::FiltersChanged => { /* .. */ },
+            _ => { /* .. */ },
         }
         Ok(())
     }).await.unwrap();
@@ -647,7 +748,7 @@ tokio_test::block_on(async {
             pub async fn $fn_name(&self) -> Result<impl TryStream<Ok=(Event, Mastodon), Error=Error> + '_> {
                 use $crate::event_stream::event_stream;
                 let url = self.route(&format!("/api/v1/streaming/{}", $stream));
-                let response = self.authenticated(self.client.get(&url)).header("Accept", "application/json").send().await?;
+                let response = self.send(self.authenticated(self.client.get(&url)).header("Accept", "application/json")).await?;
                 debug!(
                     status:serde = crate::helpers::log::Status::from(&response), url = &url,
                     headers:serde = crate::helpers::log::Headers::from(&response);
@@ -657,8 +758,7 @@ tokio_test::block_on(async {
                 if status.is_success() {
                      Ok(event_stream(response, url, self))
                 } else {
-                    let response = response.json().await?;
-                    Err(Error::Api{ status, response })
+                    Err(Error::from_error_response(response).await)
                 }
             }
         }
@@ -688,6 +788,7 @@ tokio_test::block_on(async {
             Event::Notification(ref notification) => { /* .. */ },
             Event::Delete(ref id) => { /* .. */ },
             Event::FiltersChanged => { /* .. */ },
+            _ => { /* .. */ },
         }
         Ok(())
     }).await.unwrap();
@@ -696,9 +797,9 @@ tokio_test::block_on(async {
             pub async fn $fn_name(&self, $param: $param_type) -> Result<impl TryStream<Ok=(Event, Mastodon), Error=Error> + '_> {
                 use $crate::event_stream::event_stream;
                 let mut url: Url = self.route(concat!("/api/v1/streaming/", $stream)).parse()?;
-                url.query_pairs_mut().append_pair(stringify!($param), $param.as_ref());
+                url.query_pairs_mut().append_pair(stringify!($param), $param.as_ref().trim_start_matches('#'));
                 let url = url.to_string();
-                let response = self.authenticated(self.client.get(url.as_str())).header("Accept", "application/json").send().await?;
+                let response = self.send(self.authenticated(self.client.get(url.as_str())).header("Accept", "application/json")).await?;
                 debug!(
                     status:serde = crate::helpers::log::Status::from(&response), url:? = url,
                     headers:serde = crate::helpers::log::Headers::from(&response);
@@ -708,8 +809,7 @@ tokio_test::block_on(async {
                 if status.is_success() {
                      Ok(event_stream(response, url, self))
                 } else {
-                    let response = response.json().await?;
-                    Err(Error::Api{ status, response })
+                    Err(Error::from_error_response(response).await)
                 }
             }
         }
@@ -737,6 +837,7 @@ tokio_test::block_on(async {
             Event::Notification(ref notification) => { /* .. */ },
             Event::Delete(ref id) => { /* .. */ },
             Event::FiltersChanged => { /* .. */ },
+            _ => { /* .. */ },
         }
         Ok(())
     }).await.unwrap();
@@ -746,10 +847,10 @@ tokio_test::block_on(async {
                 use $crate::event_stream::event_stream;
                 let mut url: Url = self.route(concat!("/api/v1/streaming/", $stream)).parse()?;
                 if $param {
-                    url.query_pairs_mut().append_key_only(stringify!($param));
+                    url.query_pairs_mut().append_pair(stringify!($param), "true");
                 }
                 let url = url.to_string();
-                let response = self.authenticated(self.client.get(url.as_str())).header("Accept", "application/json").send().await?;
+                let response = self.send(self.authenticated(self.client.get(url.as_str())).header("Accept", "application/json")).await?;
                 debug!(
                     status:serde = crate::helpers::log::Status::from(&response), url:? = url,
                     headers:serde = crate::helpers::log::Headers::from(&response);
@@ -759,8 +860,7 @@ tokio_test::block_on(async {
                 if status.is_success() {
                      Ok(event_stream(response, url, self))
                 } else {
-                    let response = response.json().await?;
-                    Err(Error::Api{ status, response })
+                    Err(Error::from_error_response(response).await)
                 }
             }
         }
@@ -1,16 +1,43 @@
-use std::{borrow::Cow, ops::Deref, path::Path, sync::Arc};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    io,
+    ops::Deref,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::cache::ResponseCache;
 use crate::{
+    batch::Batch,
+    builder::MastodonBuilder,
+    capabilities::{AnyFilters, AnyInstance, Capabilities},
     entities::prelude::*,
     errors::{Error, Result},
     helpers::read_response::read_response,
+    middleware::Middleware,
     polling_time::PollingTime,
-    AddPushRequest, Data, NewStatus, Page, StatusesRequest, UpdatePushRequest,
+    post::PostBuilder,
+    rate_limit::{Bucket, RateLimit, Throttle},
+    requests::{
+        push_array, AdminAccountsRequest, FollowRequest, GroupedNotificationsRequest, MuteRequest,
+        NotificationsRequest, PublicTimelineRequest, ReportRequest, SearchRequest, TimelineRequest,
+        UpdateCredentialsRequest, UpdateMediaRequest,
+    },
+    retry::{Retry, IDEMPOTENCY_KEY},
+    thread::Thread,
+    timeline::Timeline,
+    transport::Transport,
+    AddPushRequest, Data, Language, NewStatus, Page, StatusesRequest, UpdatePushRequest,
 };
 use futures::TryStream;
-use log::{debug, error, trace};
-use mastodon_async_entities::attachment::ProcessedAttachment;
-use reqwest::{multipart::Part, Client, RequestBuilder};
+use log::{debug, error, trace, warn};
+use mastodon_async_entities::{attachment::ProcessedAttachment, auth::scopes::Scope};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use reqwest::{header::HeaderMap, multipart::Part, Client, Request, RequestBuilder, Response};
+use time::OffsetDateTime;
 use url::Url;
 use uuid::Uuid;
 
@@ -18,13 +45,25 @@ use uuid::Uuid;
 #[derive(Debug)]
 pub struct MastodonClient {
     pub(crate) client: Client,
+    pub(crate) transport: Arc<dyn Transport>,
+    pub(crate) middleware: Vec<Arc<dyn Middleware>>,
     /// Raw data about your mastodon instance.
     pub data: Data,
+    /// Headers sent with every request which doesn't set them itself.
+    pub(crate) headers: HeaderMap,
+    pub(crate) rate_limit: Mutex<Option<RateLimit>>,
+    pub(crate) rate_limits: Mutex<HashMap<Bucket, RateLimit>>,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) throttle: Option<Throttle>,
+    pub(crate) retry: Option<Retry>,
+    pub(crate) capabilities: Mutex<Option<Capabilities>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) cache: Option<Mutex<ResponseCache>>,
 }
 
 /// Your mastodon application client, handles all requests to and from Mastodon.
 #[derive(Debug, Clone)]
-pub struct Mastodon(Arc<MastodonClient>);
+pub struct Mastodon(pub(crate) Arc<MastodonClient>);
 
 // This ensures we don't accidentally make Mastodon not Send or Sync again
 static_assertions::assert_impl_all!(Mastodon: Send, Sync);
@@ -44,7 +83,12 @@ impl From<Data> for Mastodon {
     }
 }
 impl Mastodon {
-    methods![get and get_with_call_id, post and post_with_call_id, delete and delete_with_call_id,];
+    methods![
+        get and get_with_call_id,
+        post and post_with_call_id,
+        put and put_with_call_id,
+        delete and delete_with_call_id,
+    ];
 
     paged_routes! {
         (get) favourites: "favourites" => Status,
@@ -54,48 +98,180 @@ impl Mastodon {
         (get) instance_domain_blocks: "instance/domain_blocks" => DomainBlock,
         (get) follow_requests: "follow_requests" => Account,
         (get) get_home_timeline: "timelines/home" => Status,
-        (get) get_emojis: "custom_emojis" => CustomEmoji,
         (get) mutes: "mutes" => Account,
         (get) notifications: "notifications" => Notification,
-        (get) instance_peers: "instance/peers" => String,
-        (get) instance_activity: "instance/activity" => instance::Activity,
+        (get) notification_requests: "notifications/requests" => notification::Request,
         (get) instance_rules: "instance/rules" => instance::Rule,
         (get) reports: "reports" => Report,
         (get (q: &'a str, #[serde(skip_serializing_if = "Option::is_none")] limit: Option<u64>, following: bool,)) search_accounts: "accounts/search" => Account,
         (get) get_endorsements: "endorsements" => Account,
+        (get) followed_tags: "followed_tags" => Tag,
+        (get) conversations: "conversations" => Conversation,
+        (get (#[serde(skip_serializing_if = "Option::is_none")] limit: Option<u64>, #[serde(skip_serializing_if = "Option::is_none")] offset: Option<u64>,)) trending_tags: "trends/tags" => Tag,
+        (get (#[serde(skip_serializing_if = "Option::is_none")] limit: Option<u64>, #[serde(skip_serializing_if = "Option::is_none")] offset: Option<u64>,)) trending_statuses: "trends/statuses" => Status,
+        (get (#[serde(skip_serializing_if = "Option::is_none")] limit: Option<u64>, #[serde(skip_serializing_if = "Option::is_none")] offset: Option<u64>,)) trending_links: "trends/links" => TrendsLink,
+        (get (#[serde(skip_serializing_if = "Option::is_none")] resolved: Option<bool>, #[serde(skip_serializing_if = "Option::is_none")] account_id: Option<&'a AccountId>, #[serde(skip_serializing_if = "Option::is_none")] target_account_id: Option<&'a AccountId>,)) admin_reports: "admin/reports" => admin::Report,
+        (get) admin_email_domain_blocks: "admin/email_domain_blocks" => EmailDomainBlock,
+        (get) admin_canonical_email_blocks: "admin/canonical_email_blocks" => CanonicalEmailBlock,
+        (get) admin_domain_allows: "admin/domain_allows" => domain::Allow,
     }
 
     paged_routes_with_id! {
-        (get) followers: "accounts/{}/followers" => Account,
-        (get) following: "accounts/{}/following" => Account,
-        (get) reblogged_by: "statuses/{}/reblogged_by" => Account,
-        (get) favourited_by: "statuses/{}/favourited_by" => Account,
+        (get) followers[AccountId]: "accounts/{}/followers" => Account,
+        (get) following[AccountId]: "accounts/{}/following" => Account,
+        (get) reblogged_by[StatusId]: "statuses/{}/reblogged_by" => Account,
+        (get) favourited_by[StatusId]: "statuses/{}/favourited_by" => Account,
+        (get) account_endorsements[AccountId]: "accounts/{}/endorsements" => Account,
+        (get) list_accounts[ListId]: "lists/{}/accounts" => Account,
     }
 
     route! {
-        (delete (domain: String,)) unblock_domain: "domain_blocks" => Empty,
-        (get) instance: "instance" => Instance,
-        (get) verify_credentials: "accounts/verify_credentials" => Account,
-        (post (account_id: &str, status_ids: Vec<&str>, comment: String,)) report: "reports" => Report,
-        (post (domain: String,)) block_domain: "domain_blocks" => Empty,
-        (post (id: &str,)) authorize_follow_request: "accounts/follow_requests/authorize" => Empty,
-        (post (id: &str,)) reject_follow_request: "accounts/follow_requests/reject" => Empty,
+        (get) instance_v1: "instance" => instance::v1::Instance,
+        (get) filters_v1: "filters" => Vec<filter::v1::Filter>,
+        (get) instance_peers: "instance/peers" => Vec<String>,
+        (get) instance_activity: "instance/activity" => Vec<instance::Activity>,
+        (get) instance_extended_description: "instance/extended_description" => instance::ExtendedDescription,
+        (get) instance_privacy_policy: "instance/privacy_policy" => instance::PrivacyPolicy,
+        (get) instance_terms_of_service: "instance/terms_of_service" => instance::TermsOfService,
+        (get) instance_translation_languages: "instance/translation_languages" => instance::TranslationLanguages,
+        (get) verify_credentials: "accounts/verify_credentials" => CredentialAccount,
+        (post (account_id: &AccountId, status_ids: Vec<&StatusId>, comment: String,)) report: "reports" => Report,
         (get  (local: bool,)) get_public_timeline: "timelines/public" => Vec<Status>,
         (post (uri: Cow<'static, str>,)) follows: "follows" => Account,
         (post) clear_notifications: "notifications/clear" => Empty,
+        (get) notification_requests_merged: "notifications/requests/merged" => notification::RequestsMerged,
         (get) get_push_subscription: "push/subscription" => Subscription,
         (delete) delete_push_subscription: "push/subscription" => Empty,
         (get) get_follow_suggestions: "suggestions" => Vec<Account>,
         (post (app: forms::Application,)) create_app: "apps" => Application,
         (get) verify_app: "apps/verify_credentials" => Application,
+        (get) featured_tags: "featured_tags" => Vec<status::FeaturedTag>,
+        (post (name: String,)) feature_tag: "featured_tags" => status::FeaturedTag,
+        (get) featured_tag_suggestions: "featured_tags/suggestions" => Vec<Tag>,
+        (get) lists: "lists" => Vec<List>,
+        (get) announcements: "announcements" => Vec<Announcement>,
+        (get) preferences: "preferences" => Preferences,
+        (get) get_emojis: "custom_emojis" => Vec<CustomEmoji>,
+        (post (domain: String,)) create_email_domain_block: "admin/email_domain_blocks" => EmailDomainBlock,
+        (post (email: String,)) create_canonical_email_block: "admin/canonical_email_blocks" => CanonicalEmailBlock,
+        (post (canonical_email_hash: String,)) create_canonical_email_block_from_hash: "admin/canonical_email_blocks" => CanonicalEmailBlock,
+        (post (email: String,)) canonical_email_blocks_matching: "admin/canonical_email_blocks/test" => Vec<CanonicalEmailBlock>,
+        (post (domain: String,)) create_domain_allow: "admin/domain_allows" => domain::Allow,
+        (get) admin_trending_tags: "admin/trends/tags" => Vec<admin::Tag>,
+        (get) admin_trending_links: "admin/trends/links" => Vec<trends::Link>,
+        (get) admin_trending_statuses: "admin/trends/statuses" => Vec<trends::Status>,
+        /// Report an account to the moderators, with the full set of options
+        /// supported by `POST /api/v1/reports`.
+        ///
+        /// ```no_run
+        /// use mastodon_async::{entities::report::Category, prelude::*, requests::ReportRequest};
+        ///
+        /// tokio_test::block_on(async {
+        ///     let client = Mastodon::from(Data::default());
+        ///     let mut request = ReportRequest::new(AccountId::new("42"));
+        ///     request
+        ///         .category(Category::Spam)
+        ///         .status_ids([StatusId::new("108882889550545820")])
+        ///         .forward(true);
+        ///     let report = client.report_with(&request).await.unwrap();
+        /// });
+        /// ```
+        (post<-&ReportRequest) report_with: "reports" => Report,
+        /// Save the read positions in one or both timelines.
+        ///
+        /// The server responds with `409 Conflict` if another client updated the
+        /// marker at the same time; compare the returned
+        /// [`version`](Marker::version) with the last one seen to detect changes
+        /// made by other clients.
+        ///
+        /// ```no_run
+        /// use mastodon_async::prelude::*;
+        ///
+        /// tokio_test::block_on(async {
+        ///     let client = Mastodon::from(Data::default());
+        ///     let mut form = forms::marker::Update::new();
+        ///     form.home(&StatusId::new("103194548672408537"));
+        ///     let markers = client.set_markers(&form).await.unwrap();
+        /// });
+        /// ```
+        (post<-&forms::marker::Update) set_markers: "markers" => marker::Markers,
+        /// Get quantitative measures of server activity over a period of time.
+        ///
+        /// ```no_run
+        /// use mastodon_async::prelude::*;
+        /// use time::{Duration, OffsetDateTime};
+        ///
+        /// tokio_test::block_on(async {
+        ///     let client = Mastodon::from(Data::default());
+        ///     let now = OffsetDateTime::now_utc();
+        ///     let mut form = forms::measure::Query::new(now - Duration::weeks(1), now);
+        ///     form.keys([
+        ///         forms::measure::Key::ActiveUsers,
+        ///         forms::measure::Key::NewUsers,
+        ///     ]);
+        ///     for measure in client.admin_measures(&form).await.unwrap() {
+        ///         println!("{}: {}", measure.key, measure.total);
+        ///     }
+        /// });
+        /// ```
+        (post<-&forms::measure::Query) admin_measures: "admin/measures" => Vec<Measure>,
+        /// Get qualitative data about the server over a period of time, such as
+        /// the most-used languages or the disk space used.
+        (post<-&forms::dimension::Query) admin_dimensions: "admin/dimensions" => Vec<Dimension>,
+        /// Get user retention data for cohorts of users who signed up during the
+        /// requested period.
+        (post<-&forms::retention::Query) admin_retention: "admin/retention" => Vec<Cohort>,
+        /// Create a new list.
+        ///
+        /// ```no_run
+        /// use mastodon_async::prelude::*;
+        ///
+        /// tokio_test::block_on(async {
+        ///     let client = Mastodon::from(Data::default());
+        ///     let form = forms::list::List::builder("friends")
+        ///         .replies_policy(list::RepliesPolicy::List)
+        ///         .build()
+        ///         .unwrap();
+        ///     let list = client.create_list(&form).await.unwrap();
+        ///     client
+        ///         .add_accounts_to_list(&list.id, &[&AccountId::new("42")])
+        ///         .await
+        ///         .unwrap();
+        /// });
+        /// ```
+        (post<-&forms::list::List) create_list: "lists" => List,
+        /// Appeal a moderation decision against the user's account, i.e. the
+        /// strike given by an [`AccountWarning`], such as the one attached to a
+        /// `moderation_warning` notification. `text` is the reason for the
+        /// appeal, shown to the moderators.
+        ///
+        /// [`AccountWarning`]: crate::entities::account_warning::AccountWarning
+        ///
+        /// ```no_run
+        /// use mastodon_async::prelude::*;
+        ///
+        /// tokio_test::block_on(async {
+        ///     let client = Mastodon::from(Data::default());
+        ///     let appeal = client
+        ///         .appeal(AccountWarningId::new("3"), "The image was of my cat".into())
+        ///         .await
+        ///         .unwrap();
+        ///     assert!(appeal.state.is_pending());
+        /// });
+        /// ```
+        (post (strike_id: AccountWarningId, text: String,)) appeal: "appeals" => account_warning::Appeal,
     }
 
     route_v2! {
+        (get) instance: "instance" => Instance,
         (get (q: &'a str, resolve: bool,)) search: "search" => SearchResult,
         (post multipart with description (file: impl AsRef<Path>,)) media: "media" => Attachment,
         (post multipart with description (file: impl AsRef<Path>, thumbnail: impl AsRef<Path>,)) media_with_thumbnail: "media" => Attachment,
         (get) filters: "filters" => Vec<Filter>,
         (post<-forms::filter::Add) add_filter: "filters" => Filter,
+        (get) notification_policy: "notifications/policy" => notification::Policy,
+        (get) follow_suggestions: "suggestions" => Vec<account::Suggestion>,
+        (patch<-forms::notification::Policy) update_notification_policy: "notifications/policy" => notification::Policy,
     }
 
     route_id! {
@@ -104,10 +280,15 @@ impl Mastodon {
         (post) unfollow[AccountId]: "accounts/{}/unfollow" => Relationship,
         (post) block[AccountId]: "accounts/{}/block" => Relationship,
         (post) unblock[AccountId]: "accounts/{}/unblock" => Relationship,
-        (get) mute[AccountId]: "accounts/{}/mute" => Relationship,
-        (get) unmute[AccountId]: "accounts/{}/unmute" => Relationship,
+        (post) mute[AccountId]: "accounts/{}/mute" => Relationship,
+        (post) unmute[AccountId]: "accounts/{}/unmute" => Relationship,
+        (post) authorize_follow_request[AccountId]: "follow_requests/{}/authorize" => Relationship,
+        (post) reject_follow_request[AccountId]: "follow_requests/{}/reject" => Relationship,
         (get) get_notification[NotificationId]: "notifications/{}" => Notification,
         (post) dismiss_notification[NotificationId]: "notifications/{}/dismiss" => Empty,
+        (get) get_notification_request[NotificationRequestId]: "notifications/requests/{}" => notification::Request,
+        (post) accept_notification_request[NotificationRequestId]: "notifications/requests/{}/accept" => Empty,
+        (post) dismiss_notification_request[NotificationRequestId]: "notifications/requests/{}/dismiss" => Empty,
         (get) get_status[StatusId]: "statuses/{}" => Status,
         (get) get_context[StatusId]: "statuses/{}/context" => Context,
         (get) get_card[StatusId]: "statuses/{}/card" => Card,
@@ -115,11 +296,52 @@ impl Mastodon {
         (post) unreblog[StatusId]: "statuses/{}/unreblog" => Status,
         (post) favourite[StatusId]: "statuses/{}/favourite" => Status,
         (post) unfavourite[StatusId]: "statuses/{}/unfavourite" => Status,
+        (post) bookmark[StatusId]: "statuses/{}/bookmark" => Status,
+        (post) unbookmark[StatusId]: "statuses/{}/unbookmark" => Status,
+        (post) pin[StatusId]: "statuses/{}/pin" => Status,
+        (post) unpin[StatusId]: "statuses/{}/unpin" => Status,
         (delete) delete_status[StatusId]: "statuses/{}" => Empty,
         (delete) delete_from_suggestions[AccountId]: "suggestions/{}" => Empty,
         (post) endorse_user[AccountId]: "accounts/{}/pin" => Relationship,
         (post) unendorse_user[AccountId]: "accounts/{}/unpin" => Relationship,
         (get) attachment[AttachmentId]: "media/{}" => Attachment,
+        (delete) unfeature_tag[TagId]: "featured_tags/{}" => Empty,
+        (get) account_featured_tags[AccountId]: "accounts/{}/featured_tags" => Vec<status::FeaturedTag>,
+        (get) instance_terms_of_service_at[String]: "instance/terms_of_service/{}" => instance::TermsOfService,
+        (post) dismiss_announcement[AnnouncementId]: "announcements/{}/dismiss" => Empty,
+        (post) read_conversation[ConversationId]: "conversations/{}/read" => Conversation,
+        (delete) delete_conversation[ConversationId]: "conversations/{}" => Empty,
+        (get) get_list[ListId]: "lists/{}" => List,
+        (delete) delete_list[ListId]: "lists/{}" => Empty,
+        (get) account_lists[AccountId]: "accounts/{}/lists" => Vec<List>,
+        (get) admin_report[ReportId]: "admin/reports/{}" => admin::Report,
+        (post) assign_report_to_self[ReportId]: "admin/reports/{}/assign_to_self" => admin::Report,
+        (post) unassign_report[ReportId]: "admin/reports/{}/unassign" => admin::Report,
+        (post) resolve_report[ReportId]: "admin/reports/{}/resolve" => admin::Report,
+        (post) reopen_report[ReportId]: "admin/reports/{}/reopen" => admin::Report,
+        (get) admin_account[AccountId]: "admin/accounts/{}" => admin::Account,
+        (post) approve_account[AccountId]: "admin/accounts/{}/approve" => admin::Account,
+        (post) reject_account[AccountId]: "admin/accounts/{}/reject" => admin::Account,
+        (post) enable_account[AccountId]: "admin/accounts/{}/enable" => admin::Account,
+        (post) unsilence_account[AccountId]: "admin/accounts/{}/unsilence" => admin::Account,
+        (post) unsuspend_account[AccountId]: "admin/accounts/{}/unsuspend" => admin::Account,
+        (post) unsensitive_account[AccountId]: "admin/accounts/{}/unsensitive" => admin::Account,
+        (get) admin_email_domain_block[EmailDomainBlockId]: "admin/email_domain_blocks/{}" => EmailDomainBlock,
+        (delete) delete_email_domain_block[EmailDomainBlockId]: "admin/email_domain_blocks/{}" => Empty,
+        (get) admin_canonical_email_block[CanonicalEmailBlockId]: "admin/canonical_email_blocks/{}" => CanonicalEmailBlock,
+        (delete) delete_canonical_email_block[CanonicalEmailBlockId]: "admin/canonical_email_blocks/{}" => Empty,
+        (get) admin_domain_allow[AllowDomainId]: "admin/domain_allows/{}" => domain::Allow,
+        (delete) delete_domain_allow[AllowDomainId]: "admin/domain_allows/{}" => Empty,
+        (post) approve_trending_tag[TagId]: "admin/trends/tags/{}/approve" => admin::Tag,
+        (post) reject_trending_tag[TagId]: "admin/trends/tags/{}/reject" => admin::Tag,
+        (post) approve_trending_link[PreviewCardId]: "admin/trends/links/{}/approve" => trends::Link,
+        (post) reject_trending_link[PreviewCardId]: "admin/trends/links/{}/reject" => trends::Link,
+        /// Change the title, replies policy, or exclusivity of a list.
+        (put<-&forms::list::List) update_list[ListId]: "lists/{}" => List,
+        /// Set a private note on an account, only visible to the authenticated
+        /// user. It's returned as the `note` of the updated [`Relationship`]. An
+        /// empty `comment` removes the note.
+        (post (comment: String,)) set_account_note[AccountId]: "accounts/{}/note" => Relationship,
     }
 
     route_v2_id! {
@@ -133,8 +355,8 @@ impl Mastodon {
         (delete) delete_filter_keyword[KeywordId]: "filters/keywords/{}" => Empty,
         (get) filter_statuses[FilterId]: "filters/{}/statuses" => Vec<filter::Status>,
         (post<-forms::filter::Status) add_status_to_filter[FilterId]: "filters/{}/statuses" => filter::Status,
-        (get) filter_status[StatusId]: "filters/statuses/{}" => filter::Status,
-        (delete) disassociate_status_from_filter[StatusId]: "filters/statuses/{}" => Empty,
+        (get) filter_status[FilteredStatusId]: "filters/statuses/{}" => filter::Status,
+        (delete) disassociate_status_from_filter[FilteredStatusId]: "filters/statuses/{}" => Empty,
     }
 
     streaming! {
@@ -144,55 +366,515 @@ impl Mastodon {
         stream_public@"public",
         "All public posts known to the server, filtered for media attachments. Analogous to the federated timeline with 'only media' enabled."
         stream_public_media@"public/media",
-        "All public posts originating from this server."
+        "All public posts originating from this server. Pass `true` to only receive posts with media attachments (the `public:local:media` channel)."
         stream_local(flag only_media)@"public/local",
-        "All public posts originating from other servers."
+        "All public posts originating from other servers. Pass `true` to only receive posts with media attachments (the `public:remote:media` channel)."
         stream_remote(flag only_media)@"public/remote",
-        "All public posts using a certain hashtag."
-        stream_hashtag(tag: impl AsRef<str>, like "#bots")@"hashtag",
-        "All public posts using a certain hashtag, originating from this server."
-        stream_local_hashtag(tag: impl AsRef<str>, like "#bots")@"hashtag/local",
+        "All public posts using a certain hashtag. A leading `#` on the tag name is ignored."
+        stream_hashtag(tag: impl AsRef<str>, like "\"#bots\"")@"hashtag",
+        "All public posts using a certain hashtag, originating from this server. A leading `#` on the tag name is ignored."
+        stream_local_hashtag(tag: impl AsRef<str>, like "\"#bots\"")@"hashtag/local",
         "Notifications for the current user."
         stream_notifications@"user/notification",
         "Updates to a specific list."
-        stream_list(list: impl AsRef<str>, like "12345")@"list",
+        stream_list(list: &ListId, like "&ListId::new(\"12345\")")@"list",
         "Updates to direct conversations."
         stream_direct@"direct",
     }
 
+    /// Open a single WebSocket connection to the streaming API, over which
+    /// any number of channels can be subscribed to and unsubscribed from
+    /// while events are being received.
+    ///
+    /// Events are tagged with the channel they were received on. The
+    /// connection is made to the streaming API's URL advertised by the
+    /// instance, which may be on another host, or to `/api/v1/streaming` on
+    /// the instance's base URL if it doesn't advertise one.
+    /// Errors the server reports over the connection, e.g. when a
+    /// subscription is refused, are received as [`Error::Api`] items, after
+    /// which the stream carries on.
+    ///
+    /// ```no_run
+    /// use mastodon_async::prelude::*;
+    /// use mastodon_async::websocket::Channel;
+    /// use futures_util::TryStreamExt;
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let (subscriptions, events) = client.stream_multiplexed().await.unwrap();
+    ///     subscriptions.subscribe(&Channel::Hashtag("rust".into())).await.unwrap();
+    ///     subscriptions.subscribe(&Channel::List(ListId::new("12345"))).await.unwrap();
+    ///     events.try_for_each(|(channel, event)| async move {
+    ///         println!("{channel}: {event:?}");
+    ///         Ok(())
+    ///     }).await.unwrap();
+    /// });
+    /// ```
+    #[cfg(feature = "websocket")]
+    pub async fn stream_multiplexed(
+        &self,
+    ) -> Result<(
+        crate::websocket::Subscriptions,
+        impl TryStream<Ok = (crate::websocket::Channel, Event), Error = Error>,
+    )> {
+        crate::websocket::connect(self).await
+    }
+
+    /// Stream a channel over Server-Sent Events, transparently reconnecting
+    /// with exponential backoff whenever the connection is lost or can't be
+    /// established.
+    ///
+    /// Changes in the state of the connection are reported alongside the
+    /// events, and errors are reported as [`StreamEvent::Disconnected`]
+    /// rather than ending the stream. The stream only ends once the
+    /// configured maximum number of attempts is exceeded.
+    ///
+    /// [`StreamEvent::Disconnected`]: crate::streaming::StreamEvent::Disconnected
+    ///
+    /// ```no_run
+    /// use mastodon_async::prelude::*;
+    /// use mastodon_async::streaming::{Channel, StreamEvent, StreamOptions};
+    /// use futures_util::{pin_mut, StreamExt};
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let mut options = StreamOptions::new(Channel::User);
+    ///     options.backfill();
+    ///     let stream = client.stream_reconnecting(&options);
+    ///     pin_mut!(stream);
+    ///     while let Some(item) = stream.next().await {
+    ///         match item {
+    ///             StreamEvent::Event(event) => println!("{event:?}"),
+    ///             StreamEvent::Disconnected(err) => eprintln!("disconnected: {err:?}"),
+    ///             _ => {}
+    ///         }
+    ///     }
+    /// });
+    /// ```
+    pub fn stream_reconnecting(
+        &self,
+        options: &crate::streaming::StreamOptions,
+    ) -> impl futures::Stream<Item = crate::streaming::StreamEvent> + '_ {
+        crate::streaming::reconnecting(self, options.clone())
+    }
+
+    /// Follow the timeline matching a streamed channel: its recent history,
+    /// then the statuses posted since, without gaps or duplicates across
+    /// reconnections. See [`Timeline`].
+    ///
+    /// Fails for channels without a matching timeline, i.e. notifications
+    /// and direct conversations.
+    pub fn timeline(&self, options: &crate::streaming::StreamOptions) -> Result<Timeline> {
+        Timeline::new(self.clone(), options)
+    }
+
+    /// Check whether the streaming API is up and running. Succeeds if it is,
+    /// and returns an error describing the response otherwise.
+    ///
+    /// Equivalent to `get /api/v1/streaming/health`
+    pub async fn streaming_health(&self) -> Result<()> {
+        let call_id = Uuid::new_v4();
+        let url = self.route("/api/v1/streaming/health");
+        debug!(url = url, method = stringify!(get), call_id:? = call_id; "making API request");
+        let response = self.send(self.client.get(&url)).await?;
+        let status = response.status();
+        let body = response.text().await?;
+        debug!(url = url, status = status.as_u16(), body = body, call_id:? = call_id; "received API response");
+        if status.is_success() && body.trim() == "OK" {
+            Ok(())
+        } else {
+            Err(Error::Other(format!(
+                "streaming API is unhealthy: {status} {body:?}"
+            )))
+        }
+    }
+
     /// A new instance.
     pub fn new(client: Client, data: Data) -> Self {
-        Mastodon(Arc::new(MastodonClient { client, data }))
+        MastodonBuilder::new(data).build_with(client, HeaderMap::new())
     }
 
-    fn route(&self, url: impl AsRef<str>) -> String {
-        format!("{}{}", self.data.base, url.as_ref())
+    /// Configure a new instance, e.g. to throttle or retry requests.
+    pub fn builder(data: Data) -> MastodonBuilder {
+        MastodonBuilder::new(data)
+    }
+
+    /// The rate limit reported by the most recent response which included
+    /// one, if any.
+    ///
+    /// ```no_run
+    /// use mastodon_async::prelude::*;
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     client.get_home_timeline().await.unwrap();
+    ///     if let Some(rate_limit) = client.rate_limit() {
+    ///         println!("{} of {} requests left", rate_limit.remaining, rate_limit.limit);
+    ///     }
+    /// });
+    /// ```
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        *self
+            .rate_limit
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// The last known state of the rate limit which `bucket` counts against,
+    /// if a response to a request in that bucket has included one.
+    pub fn rate_limit_for(&self, bucket: Bucket) -> Option<RateLimit> {
+        self.rate_limits
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .get(&bucket)
+            .copied()
+    }
+
+    /// Determine which server software the instance runs, and what it
+    /// supports. Looked up once, the first time it's needed, and remembered
+    /// for the lifetime of the client.
+    ///
+    /// `/api/v2/instance` is tried first, then `/api/v1/instance`, and then
+    /// NodeInfo, for servers which implement neither.
+    pub async fn capabilities(&self) -> Result<Capabilities> {
+        let known = self
+            .capabilities
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone();
+        if let Some(capabilities) = known {
+            return Ok(capabilities);
+        }
+        let capabilities = self.fetch_capabilities().await?;
+        debug!(software = capabilities.software().to_string(), version:? = capabilities.version(); "determined instance capabilities");
+        *self
+            .capabilities
+            .lock()
+            .unwrap_or_else(|err| err.into_inner()) = Some(capabilities.clone());
+        Ok(capabilities)
+    }
+
+    async fn fetch_capabilities(&self) -> Result<Capabilities> {
+        match self
+            .get::<serde_json::Value>(self.route("/api/v2/instance"))
+            .await
+        {
+            Ok(instance) => return Ok(Capabilities::from_instance(&instance, true)),
+            Err(err) => debug!(err:? = err; "v2 instance endpoint unavailable, trying v1"),
+        }
+        match self
+            .get::<serde_json::Value>(self.route("/api/v1/instance"))
+            .await
+        {
+            Ok(instance) => return Ok(Capabilities::from_instance(&instance, false)),
+            Err(err) => debug!(err:? = err; "v1 instance endpoint unavailable, trying NodeInfo"),
+        }
+        let links: serde_json::Value = self.get(self.route("/.well-known/nodeinfo")).await?;
+        let href = links["links"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .rev()
+            .find_map(|link| link["href"].as_str())
+            .ok_or_else(|| Error::Other("no NodeInfo document linked".to_string()))?;
+        // the access token is sent along, so don't follow links elsewhere
+        if !href.starts_with(self.data.base.as_ref()) {
+            return Err(Error::Other(format!(
+                "NodeInfo document {href} isn't on this instance"
+            )));
+        }
+        let nodeinfo: serde_json::Value = self.get(href).await?;
+        Ok(Capabilities::from_nodeinfo(&nodeinfo))
+    }
+
+    /// Get information about the instance, from `/api/v2/instance` if it's
+    /// supported, and `/api/v1/instance` otherwise.
+    pub async fn any_instance(&self) -> Result<AnyInstance> {
+        if self.capabilities().await?.supports_instance_v2() {
+            Ok(AnyInstance::V2(Box::new(self.instance().await?)))
+        } else {
+            Ok(AnyInstance::V1(Box::new(self.instance_v1().await?)))
+        }
+    }
+
+    /// Get the current user's filters, from `/api/v2/filters` if it's
+    /// supported, and `/api/v1/filters` otherwise.
+    pub async fn any_filters(&self) -> Result<AnyFilters> {
+        if self.capabilities().await?.supports_filters_v2() {
+            Ok(AnyFilters::V2(self.filters().await?))
+        } else {
+            Ok(AnyFilters::V1(self.filters_v1().await?))
+        }
     }
 
-    /// Update the user credentials
-    pub async fn update_credentials(
+    /// Send a request, keeping track of the rate limit reported in the
+    /// response.
+    pub(crate) async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let mut request = request.build()?;
+        self.add_default_headers(request.headers_mut());
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(cache) = self.cache.as_ref() {
+            if ResponseCache::applies_to(&request) {
+                return self.send_cached(cache, request).await;
+            }
+        }
+        self.execute(request).await
+    }
+
+    /// Add the headers configured for every request to `headers`, unless
+    /// they're already set.
+    pub(crate) fn add_default_headers(&self, headers: &mut HeaderMap) {
+        for name in self.headers.keys() {
+            if !headers.contains_key(name) {
+                for value in self.headers.get_all(name) {
+                    headers.append(name, value.clone());
+                }
+            }
+        }
+    }
+
+    /// Send a request conditional on the cached response to it having
+    /// changed, and answer it from the cache if it hasn't.
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn send_cached(
         &self,
-        changes: account::CredentialsBuilder,
-    ) -> Result<Account> {
+        cache: &Mutex<ResponseCache>,
+        mut request: Request,
+    ) -> Result<Response> {
+        let url = request.url().to_string();
+        cache
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .prepare(&mut request);
+        let response = self.execute(request).await?;
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            let cached = cache
+                .lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .get(&url);
+            if let Some(cached) = cached {
+                debug!(url = url; "not modified, using cached response");
+                return Ok(cached);
+            }
+        }
+        ResponseCache::store(cache, &url, response).await
+    }
+
+    /// Send a request. If the client is throttled, first waits for the rate
+    /// limit to reset if it's nearly used up, and if it retries requests,
+    /// resends the request as long as it fails in a way that's worth
+    /// retrying.
+    async fn execute(&self, request: Request) -> Result<Response> {
+        self.execute_with(request, self.throttle.as_ref(), self.retry)
+            .await
+    }
+
+    /// Send a request like [`execute`](Self::execute), throttled and
+    /// retried with the given options rather than the client's.
+    pub(crate) async fn execute_with(
+        &self,
+        mut request: Request,
+        throttle: Option<&Throttle>,
+        retry: Option<Retry>,
+    ) -> Result<Response> {
+        if let Some(scope) = crate::scopes::required(request.method(), request.url().path()) {
+            self.require_scope(scope)?;
+        }
+        let bucket = Bucket::of(request.method(), request.url().path());
+        let mut attempt = 0;
+        loop {
+            if let Some(delay) = self.throttle_delay(throttle, bucket) {
+                debug!(bucket:? = bucket, delay:? = delay; "delaying request until the rate limit resets");
+                crate::helpers::time::sleep(delay).await;
+            }
+            let retry = retry
+                .filter(|retry| retry.applies_to(&request))
+                .and_then(|retry| Some((retry, request.try_clone()?)));
+            let result = self.execute_once(request).await;
+            if let Ok(response) = &result {
+                self.update_rate_limit(bucket, response);
+            }
+            attempt += 1;
+            let Some((retry, next)) = retry else {
+                return result;
+            };
+            let Some(delay) = retry.delay(attempt, &result) else {
+                return result;
+            };
+            match &result {
+                Ok(response) => {
+                    warn!(url = next.url().as_str(), status = response.status().as_u16(), attempt = attempt, delay:? = delay; "retrying request")
+                }
+                Err(err) => {
+                    warn!(url = next.url().as_str(), err:? = err, attempt = attempt, delay:? = delay; "retrying request")
+                }
+            }
+            crate::helpers::time::sleep(delay).await;
+            request = next;
+        }
+    }
+
+    /// Send `request` once, through the middleware.
+    async fn execute_once(&self, mut request: Request) -> Result<Response> {
+        for middleware in &self.middleware {
+            middleware.on_request(&mut request).await?;
+        }
+        let mut response = self.execute_with_timeout(request).await?;
+        for middleware in &self.middleware {
+            middleware.on_response(&mut response).await?;
+        }
+        Ok(response)
+    }
+
+    /// Send `request` with the transport, giving up after the configured
+    /// timeout.
+    async fn execute_with_timeout(&self, request: Request) -> Result<Response> {
+        let Some(duration) = self.timeout else {
+            return self.transport.execute(request).await;
+        };
+        match crate::helpers::time::timeout(duration, self.transport.execute(request)).await {
+            Ok(result) => result,
+            Err(_) => Err(Error::Io(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("no response from the server within {duration:?}"),
+            ))),
+        }
+    }
+
+    fn update_rate_limit(&self, bucket: Bucket, response: &Response) {
+        if let Some(rate_limit) = RateLimit::from_headers(response.headers()) {
+            trace!(rate_limit:serde = rate_limit, bucket:? = bucket; "rate limit updated");
+            *self
+                .rate_limit
+                .lock()
+                .unwrap_or_else(|err| err.into_inner()) = Some(rate_limit);
+            self.rate_limits
+                .lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .insert(bucket, rate_limit);
+        }
+    }
+
+    fn throttle_delay(&self, throttle: Option<&Throttle>, bucket: Bucket) -> Option<Duration> {
+        let throttle = throttle?;
+        let mut rate_limits = self
+            .rate_limits
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+        throttle.delay(rate_limits.get_mut(&bucket)?, OffsetDateTime::now_utc())
+    }
+
+    pub(crate) fn route(&self, url: impl AsRef<str>) -> String {
+        format!("{}{}", self.data.base, url.as_ref())
+    }
+
+    /// Check that the access token was granted `scope`, so that a request
+    /// which would be rejected by the server fails with a clearer
+    /// [`Error::MissingScope`] instead. Always succeeds when the granted
+    /// scopes aren't known.
+    ///
+    /// Every request is checked against the scope its endpoint requires
+    /// before it's sent, so this is only needed to find out ahead of time.
+    ///
+    /// ```
+    /// use mastodon_async::{entities::auth::scopes::{Read, Scope}, prelude::*};
+    ///
+    /// let data = Data {
+    ///     scopes: Some("write".parse().unwrap()),
+    ///     ..Data::default()
+    /// };
+    /// let client = Mastodon::from(data);
+    /// let err = client
+    ///     .require_scope(Scope::Read(Some(Read::Notifications)))
+    ///     .unwrap_err();
+    /// assert!(err.is_missing_scope());
+    /// ```
+    pub fn require_scope(&self, scope: Scope) -> Result<()> {
+        match &self.data.scopes {
+            Some(granted) if !granted.allows(scope) => Err(Error::MissingScope {
+                required: scope,
+                granted: granted.clone(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Update the profile and posting defaults of the authenticated account.
+    ///
+    /// The request is sent as a multipart form so that new avatar and header
+    /// images can be uploaded alongside the other changes.
+    pub async fn update_credentials(&self, request: UpdateCredentialsRequest) -> Result<Account> {
+        use reqwest::multipart::Form;
+
+        let call_id = Uuid::new_v4();
+        let mut form_data = Form::new();
+        for (name, value) in request.text_fields() {
+            form_data = form_data.text(name, value);
+        }
+        if let Some(avatar) = request.avatar {
+            form_data = form_data.part("avatar", Self::get_form_part(avatar)?);
+        }
+        if let Some(header) = request.header {
+            form_data = form_data.part("header", Self::get_form_part(header)?);
+        }
+
         let url = self.route("/api/v1/accounts/update_credentials");
-        let response = self
-            .client
-            .patch(&url)
-            .json(&changes.build()?)
-            .send()
-            .await?;
+        debug!(
+            url = url, method = "patch",
+            multipart_form_data:? = form_data, call_id:? = call_id;
+            "making API request"
+        );
+        let request = self
+            .authenticated(self.client.patch(&url))
+            .multipart(form_data)
+            .header("Accept", "application/json");
+        let response = self.send(request).await?;
 
         read_response(response).await
     }
 
     /// Post a new status to the account.
+    ///
+    /// The request carries a freshly generated `Idempotency-Key`, so that if
+    /// the client [retries](crate::retry::Retry::idempotent_posts) it, the
+    /// status is only posted once. To safely repeat the call yourself, e.g.
+    /// after a timeout, use [`Mastodon::new_status_with_idempotency_key`].
     pub async fn new_status(&self, status: NewStatus) -> Result<Status> {
+        self.new_status_with_idempotency_key(status, Uuid::new_v4().to_string())
+            .await
+    }
+
+    /// Post a new status to the account, with an `Idempotency-Key` which the
+    /// server uses to recognize repeats of the same request. Calling this
+    /// again with the same key returns the status posted by the first call
+    /// rather than posting a duplicate, for up to an hour.
+    ///
+    /// // Example
+    ///
+    /// ```no_run
+    /// use mastodon_async::prelude::*;
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let status = NewStatusBuilder::default().status("hello").build().unwrap();
+    ///     let key = "a unique key for this status";
+    ///     let posted = match client.new_status_with_idempotency_key(status.clone(), key).await {
+    ///         Ok(posted) => posted,
+    ///         Err(_) => client.new_status_with_idempotency_key(status, key).await.unwrap(),
+    ///     };
+    /// });
+    /// ```
+    pub async fn new_status_with_idempotency_key(
+        &self,
+        status: NewStatus,
+        idempotency_key: impl AsRef<str>,
+    ) -> Result<Status> {
+        let status = self.check_local_only(status).await?;
         let url = self.route("/api/v1/statuses");
-        let response = self
+        let request = self
             .authenticated(self.client.post(&url))
-            .json(&status)
-            .send()
-            .await?;
+            .header(IDEMPOTENCY_KEY, idempotency_key.as_ref())
+            .json(&status);
+        let response = self.send(request).await?;
         debug!(
             status:serde = crate::helpers::log::Status::from(&response), url = url,
             headers:serde = crate::helpers::log::Headers::from(&response);
@@ -201,6 +883,27 @@ impl Mastodon {
         read_response(response).await
     }
 
+    /// Make sure `local_only` is only sent to instances which support it, so
+    /// that a status meant to stay local is never federated by one which
+    /// would ignore it.
+    async fn check_local_only(&self, mut status: NewStatus) -> Result<NewStatus> {
+        let Some(local_only) = status.local_only else {
+            return Ok(status);
+        };
+        let capabilities = self.capabilities().await?;
+        if capabilities.supports_local_only() {
+            return Ok(status);
+        }
+        if local_only {
+            return Err(Error::Unsupported {
+                feature: "local-only statuses",
+                software: capabilities.to_string(),
+            });
+        }
+        status.local_only = None;
+        Ok(status)
+    }
+
     /// Get timeline filtered by a hashtag(eg. `#coffee`) either locally or
     /// federated.
     pub async fn get_tagged_timeline(&self, hashtag: String, local: bool) -> Result<Vec<Status>> {
@@ -214,6 +917,27 @@ impl Mastodon {
         self.get(url).await
     }
 
+    /// Get a hashtag, by its name with or without the leading `#`.
+    pub async fn get_tag(&self, name: impl AsRef<str>) -> Result<Tag> {
+        self.get(self.tag_url(name.as_ref(), "")).await
+    }
+
+    /// Follow a hashtag, so that statuses using it show up in the home
+    /// timeline.
+    pub async fn follow_tag(&self, name: impl AsRef<str>) -> Result<Tag> {
+        self.post(self.tag_url(name.as_ref(), "/follow")).await
+    }
+
+    /// Stop following a hashtag.
+    pub async fn unfollow_tag(&self, name: impl AsRef<str>) -> Result<Tag> {
+        self.post(self.tag_url(name.as_ref(), "/unfollow")).await
+    }
+
+    fn tag_url(&self, name: &str, action: &str) -> String {
+        let name = utf8_percent_encode(name.trim_start_matches('#'), NON_ALPHANUMERIC);
+        self.route(format!("/api/v1/tags/{name}{action}"))
+    }
+
     /// Get statuses of a single account by id. Optionally only with pictures
     /// and or excluding replies.
     ///
@@ -238,111 +962,815 @@ impl Mastodon {
     ///     let statuses = client.statuses(&AccountId::new("user-id"), request).await.unwrap();
     /// });
     /// ```
+    ///
+    /// Statuses the account has pinned to its profile, see also
+    /// [`Mastodon::pin`] and [`Mastodon::unpin`]:
+    ///
+    /// ```no_run
+    /// use mastodon_async::prelude::*;
+    /// tokio_test::block_on(async {
+    ///     let data = Data::default();
+    ///     let client = Mastodon::from(data);
+    ///     let mut request = StatusesRequest::new();
+    ///     request.pinned();
+    ///     let pinned = client.statuses(&AccountId::new("user-id"), request).await.unwrap();
+    /// });
+    /// ```
     pub async fn statuses<'a, 'b: 'a>(
         &'b self,
-        id: &'b AccountId,
+        id: impl Into<AccountId>,
         request: StatusesRequest<'a>,
     ) -> Result<Page<Status>> {
+        let id = id.into();
         let call_id = Uuid::new_v4();
         let mut url = format!("{}/api/v1/accounts/{}/statuses", self.data.base, id);
 
         url += request.to_query_string()?.as_str();
 
         debug!(url = url, method = stringify!($method), call_id:? = call_id; "making API request");
-        let response = self.client.get(&url).send().await?;
+        let response = self.send(self.client.get(&url)).await?;
 
         Page::new(self.clone(), response, call_id).await
     }
 
-    /// Returns the client account's relationship to a list of other accounts.
-    /// Such as whether they follow them or vice versa.
-    pub async fn relationships(&self, ids: &[&AccountId]) -> Result<Page<Relationship>> {
-        let call_id = Uuid::new_v4();
-        let mut url = self.route("/api/v1/accounts/relationships?");
+    /// Follow an account, choosing whether its boosts are shown, whether to be
+    /// notified of its posts, and which languages to show posts in.
+    ///
+    /// If the account is already followed, its follow options are updated
+    /// instead; see also [`Mastodon::update_follow`].
+    ///
+    /// ```no_run
+    /// use mastodon_async::{prelude::*, requests::FollowRequest};
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let mut request = FollowRequest::new();
+    ///     request.reblogs(false).notify(true);
+    ///     client.follow_with(&AccountId::new("42"), &request).await.unwrap();
+    /// });
+    /// ```
+    pub async fn follow_with(
+        &self,
+        id: impl Into<AccountId>,
+        request: &FollowRequest,
+    ) -> Result<Relationship> {
+        let id = id.into();
+        let url = self.route(format!(
+            "/api/v1/accounts/{id}/follow{}",
+            request.to_query_string()?
+        ));
 
-        if ids.len() == 1 {
-            url += "id=";
-            url += ids[0].as_ref();
-        } else {
-            for id in ids {
-                url += "id[]=";
-                url += id.as_ref();
-                url += "&";
-            }
-            url.pop();
-        }
+        self.post(url).await
+    }
 
-        debug!(
-            url = url, method = stringify!($method),
-            call_id:? = call_id, account_ids:serde = ids;
-            "making API request"
-        );
-        let response = self.client.get(&url).send().await?;
+    /// Change the options of an existing follow. Options which aren't set in
+    /// `request` are left as they were.
+    pub async fn update_follow(
+        &self,
+        id: impl Into<AccountId>,
+        request: &FollowRequest,
+    ) -> Result<Relationship> {
+        let id = id.into();
+        self.follow_with(id, request).await
+    }
 
-        Page::new(self.clone(), response, call_id).await
+    /// React to an announcement with an emoji. `name` is either a unicode
+    /// emoji, or the shortcode of a custom emoji.
+    pub async fn add_announcement_reaction(
+        &self,
+        id: impl Into<AnnouncementId>,
+        name: impl AsRef<str>,
+    ) -> Result<Empty> {
+        let id = id.into();
+        self.put(self.announcement_reaction_url(&id, name.as_ref()))
+            .await
     }
 
-    /// Add a push notifications subscription
-    pub async fn add_push_subscription(&self, request: &AddPushRequest) -> Result<Subscription> {
-        let call_id = Uuid::new_v4();
-        let request = request.build();
-        let url = &self.route("/api/v1/push/subscription");
-        debug!(
-            url = url, method = stringify!($method),
-            call_id:? = call_id, post_body:serde = request;
-            "making API request"
-        );
-        let response = self.client.post(url).json(&request).send().await?;
+    /// Remove a reaction previously added with
+    /// [`Mastodon::add_announcement_reaction`].
+    pub async fn remove_announcement_reaction(
+        &self,
+        id: impl Into<AnnouncementId>,
+        name: impl AsRef<str>,
+    ) -> Result<Empty> {
+        let id = id.into();
+        self.delete(self.announcement_reaction_url(&id, name.as_ref()))
+            .await
+    }
 
-        read_response(response).await
+    fn announcement_reaction_url(&self, id: &AnnouncementId, name: &str) -> String {
+        let name = utf8_percent_encode(name, NON_ALPHANUMERIC);
+        self.route(format!("/api/v1/announcements/{id}/reactions/{name}"))
     }
 
-    /// Update the `data` portion of the push subscription associated with this
-    /// access token
-    pub async fn update_push_data(&self, request: &UpdatePushRequest) -> Result<Subscription> {
-        let call_id = Uuid::new_v4();
-        let request = request.build();
-        let url = &self.route("/api/v1/push/subscription");
-        debug!(
-            url = url, method = stringify!($method),
-            call_id:? = call_id, post_body:serde = request;
-            "making API request"
-        );
-        let response = self.client.post(url).json(&request).send().await?;
+    /// React to a status with an emoji, on Pleroma and Akkoma. `emoji` is
+    /// either a unicode emoji, or the shortcode of a custom emoji. Returns
+    /// the status with its updated reactions.
+    #[cfg(feature = "pleroma")]
+    pub async fn add_status_reaction(
+        &self,
+        id: impl Into<StatusId>,
+        emoji: impl AsRef<str>,
+    ) -> Result<Status> {
+        let id = id.into();
+        self.put(self.status_reaction_url(&id, emoji.as_ref()))
+            .await
+    }
 
-        read_response(response).await
+    /// Remove a reaction previously added with
+    /// [`Mastodon::add_status_reaction`].
+    #[cfg(feature = "pleroma")]
+    pub async fn remove_status_reaction(
+        &self,
+        id: impl Into<StatusId>,
+        emoji: impl AsRef<str>,
+    ) -> Result<Status> {
+        let id = id.into();
+        self.delete(self.status_reaction_url(&id, emoji.as_ref()))
+            .await
     }
 
-    /// Get all accounts that follow the authenticated user
-    pub async fn follows_me(&self) -> Result<Page<Account>> {
-        let me = self.verify_credentials().await?;
-        self.followers(&me.id).await
+    /// Get the emoji reactions to a status, along with the accounts which
+    /// reacted, on Pleroma and Akkoma.
+    #[cfg(feature = "pleroma")]
+    pub async fn status_reactions(
+        &self,
+        id: impl Into<StatusId>,
+    ) -> Result<Vec<crate::entities::pleroma::EmojiReaction>> {
+        let id = id.into();
+        self.get(self.route(format!("/api/v1/pleroma/statuses/{id}/reactions")))
+            .await
     }
 
-    /// Get all accounts that the authenticated user follows
-    pub async fn followed_by_me(&self) -> Result<Page<Account>> {
-        let me = self.verify_credentials().await?;
-        self.following(&me.id).await
+    #[cfg(feature = "pleroma")]
+    fn status_reaction_url(&self, id: &StatusId, emoji: &str) -> String {
+        let emoji = utf8_percent_encode(emoji, NON_ALPHANUMERIC);
+        self.route(format!("/api/v1/pleroma/statuses/{id}/reactions/{emoji}"))
     }
 
-    /// Wait for the media to be done processing and return it with the URL.
+    /// Get the aliases of the authenticated user's account, as
+    /// `username@domain`, on Pleroma and Akkoma. An account can be moved to
+    /// another one which has it as an alias.
+    #[cfg(feature = "pleroma")]
+    pub async fn account_aliases(&self) -> Result<Vec<String>> {
+        let aliases: crate::entities::pleroma::Aliases =
+            self.get(self.route("/api/pleroma/aliases")).await?;
+        Ok(aliases.aliases)
+    }
+
+    /// Add `acct`, as `username@domain`, to the aliases of the
+    /// authenticated user's account, on Pleroma and Akkoma, so that it can
+    /// be moved to this one.
+    #[cfg(feature = "pleroma")]
+    pub async fn add_account_alias(&self, acct: impl AsRef<str>) -> Result<Empty> {
+        self.put(self.account_alias_url(acct.as_ref())?).await
+    }
+
+    /// Remove an alias previously added with
+    /// [`Mastodon::add_account_alias`].
+    #[cfg(feature = "pleroma")]
+    pub async fn remove_account_alias(&self, acct: impl AsRef<str>) -> Result<Empty> {
+        self.delete(self.account_alias_url(acct.as_ref())?).await
+    }
+
+    #[cfg(feature = "pleroma")]
+    fn account_alias_url(&self, acct: &str) -> Result<String> {
+        let qs = serde_urlencoded::to_string([("alias", acct)])?;
+        Ok(self.route(format!("/api/pleroma/aliases?{qs}")))
+    }
+
+    /// Get the saved read positions in the given timelines, to resume reading
+    /// where another client left off.
+    pub async fn markers(&self, timelines: &[marker::Timeline]) -> Result<marker::Markers> {
+        let mut qs = String::new();
+        let timelines = timelines.iter().map(|timeline| {
+            if timeline.is_home() {
+                "home"
+            } else {
+                "notifications"
+            }
+        });
+        push_array(&mut qs, "timeline", timelines)?;
+
+        self.get(self.route(format!("/api/v1/markers?{qs}"))).await
+    }
+
+    /// Add accounts to a list. The accounts must already be followed by the
+    /// authenticated user.
+    pub async fn add_accounts_to_list(
+        &self,
+        id: impl Into<ListId>,
+        account_ids: &[&AccountId],
+    ) -> Result<Empty> {
+        let id = id.into();
+        self.post(self.list_accounts_url(&id, account_ids)?).await
+    }
+
+    /// Remove accounts from a list.
+    pub async fn remove_accounts_from_list(
+        &self,
+        id: impl Into<ListId>,
+        account_ids: &[&AccountId],
+    ) -> Result<Empty> {
+        let id = id.into();
+        self.delete(self.list_accounts_url(&id, account_ids)?).await
+    }
+
+    fn list_accounts_url(&self, id: &ListId, account_ids: &[&AccountId]) -> Result<String> {
+        let mut qs = String::new();
+        push_array(&mut qs, "account_ids", account_ids)?;
+        Ok(self.route(format!("/api/v1/lists/{id}/accounts?{qs}")))
+    }
+
+    /// Block a domain for the authenticated user, hiding all of its posts
+    /// and notifications and removing any followers from it. This is
+    /// unrelated to the instance-wide domain blocks managed by moderators.
     ///
-    /// `Default::default()` may be passed as the polling time to select a
-    /// polling time of 500ms.
+    /// Blocked domains can be listed with [`Mastodon::domain_blocks`]:
     ///
-    /// ## Example
-    /// ```rust,no_run
+    /// ```no_run
+    /// use futures_util::StreamExt;
     /// use mastodon_async::prelude::*;
-    /// let mastodon = Mastodon::from(Data::default());
+    ///
     /// tokio_test::block_on(async {
-    ///     let attachment = mastodon.media("/path/to/some/file.jpg", None).await.expect("upload");
-    ///     let attachment = mastodon.wait_for_processing(attachment, Default::default()).await.expect("processing");
-    ///     println!("{}", attachment.url);
+    ///     let client = Mastodon::from(Data::default());
+    ///     client.block_domain("spam.example").await.unwrap();
+    ///     client
+    ///         .domain_blocks()
+    ///         .await
+    ///         .unwrap()
+    ///         .items_iter()
+    ///         .for_each(|domain| async move { println!("{domain}") })
+    ///         .await;
     /// });
     /// ```
-    ///
-    /// For a different polling time, use `.into()` on a `std::time::Duration`.
-    /// ```rust,no_run
+    pub async fn block_domain(&self, domain: impl AsRef<str>) -> Result<Empty> {
+        self.post(self.domain_blocks_url(domain.as_ref())?).await
+    }
+
+    /// Remove a domain block previously added with
+    /// [`Mastodon::block_domain`].
+    pub async fn unblock_domain(&self, domain: impl AsRef<str>) -> Result<Empty> {
+        self.delete(self.domain_blocks_url(domain.as_ref())?).await
+    }
+
+    fn domain_blocks_url(&self, domain: &str) -> Result<String> {
+        let qs = serde_urlencoded::to_string([("domain", domain)])?;
+        Ok(self.route(format!("/api/v1/domain_blocks?{qs}")))
+    }
+
+    /// Mute an account, optionally only for a limited time or without hiding
+    /// notifications from it.
+    ///
+    /// ```no_run
+    /// use mastodon_async::{prelude::*, requests::MuteRequest};
+    /// use std::time::Duration;
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let mut request = MuteRequest::new();
+    ///     request.duration(Duration::from_secs(60 * 60));
+    ///     client.mute_with(&AccountId::new("42"), &request).await.unwrap();
+    /// });
+    /// ```
+    pub async fn mute_with(
+        &self,
+        id: impl Into<AccountId>,
+        request: &MuteRequest,
+    ) -> Result<Relationship> {
+        let id = id.into();
+        let url = self.route(format!(
+            "/api/v1/accounts/{id}/mute{}",
+            request.to_query_string()?
+        ));
+
+        self.post(url).await
+    }
+
+    /// Search for accounts, statuses and hashtags, with the full set of
+    /// options supported by `GET /api/v2/search`.
+    ///
+    /// ```no_run
+    /// use mastodon_async::{prelude::*, requests::{SearchRequest, SearchType}};
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let mut request = SearchRequest::new("#rustlang");
+    ///     request.search_type(SearchType::Hashtags).limit(10);
+    ///     let result = client.search_with(&request).await.unwrap();
+    ///     request.next_page(result.hashtags.len());
+    ///     let next = client.search_with(&request).await.unwrap();
+    /// });
+    /// ```
+    pub async fn search_with(&self, request: &SearchRequest<'_>) -> Result<SearchResult> {
+        let call_id = Uuid::new_v4();
+        let url = self.route(format!("/api/v2/search{}", request.to_query_string()?));
+
+        self.get_with_call_id(url, call_id).await
+    }
+
+    /// Get notifications for the authenticated account, filtered by type or
+    /// by the account which triggered them.
+    ///
+    /// ```no_run
+    /// use mastodon_async::{
+    ///     entities::notification::Type, prelude::*, requests::NotificationsRequest,
+    /// };
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let mut request = NotificationsRequest::new();
+    ///     request.types([Type::Mention]);
+    ///     let mentions = client.notifications_with(&request).await.unwrap();
+    /// });
+    /// ```
+    pub async fn notifications_with(
+        &self,
+        request: &NotificationsRequest<'_>,
+    ) -> Result<Page<Notification>> {
+        let call_id = Uuid::new_v4();
+        let url = self.route(format!(
+            "/api/v1/notifications{}",
+            request.to_query_string()?
+        ));
+
+        debug!(url = url, method = "get", call_id:? = call_id; "making API request");
+        let response = self.send(self.authenticated(self.client.get(&url))).await?;
+
+        Page::new(self.clone(), response, call_id).await
+    }
+
+    /// Get the home timeline, choosing which part of it to fetch.
+    ///
+    /// ```no_run
+    /// use mastodon_async::{prelude::*, requests::TimelineRequest};
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let mut request = TimelineRequest::new();
+    ///     request.min_id("109348238547925380");
+    ///     let newer = client.home_timeline_with(&request).await.unwrap();
+    /// });
+    /// ```
+    pub async fn home_timeline_with(&self, request: &TimelineRequest<'_>) -> Result<Page<Status>> {
+        let call_id = Uuid::new_v4();
+        let url = self.route(format!(
+            "/api/v1/timelines/home{}",
+            request.to_query_string()?
+        ));
+
+        debug!(url = url, method = "get", call_id:? = call_id; "making API request");
+        let response = self.send(self.authenticated(self.client.get(&url))).await?;
+
+        Page::new(self.clone(), response, call_id).await
+    }
+
+    /// Get the public timeline: the statuses of every account this instance
+    /// knows about, or only local or remote ones, optionally only those with
+    /// media attached.
+    ///
+    /// ```no_run
+    /// use mastodon_async::{prelude::*, requests::PublicTimelineRequest};
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let mut request = PublicTimelineRequest::new();
+    ///     request.local().only_media();
+    ///     let local_media = client.public_timeline(&request).await.unwrap();
+    /// });
+    /// ```
+    pub async fn public_timeline(
+        &self,
+        request: &PublicTimelineRequest<'_>,
+    ) -> Result<Page<Status>> {
+        let call_id = Uuid::new_v4();
+        let url = self.route(format!(
+            "/api/v1/timelines/public{}",
+            request.to_query_string()?
+        ));
+
+        debug!(url = url, method = "get", call_id:? = call_id; "making API request");
+        let response = self.send(self.authenticated(self.client.get(&url))).await?;
+
+        Page::new(self.clone(), response, call_id).await
+    }
+
+    /// A fetcher for many statuses or accounts by their IDs, with bounded
+    /// concurrency, throttling and retries. See [`Batch`].
+    pub fn batch(&self) -> Batch {
+        Batch::new(self.clone())
+    }
+
+    /// Get a status and the conversation around it, arranged as a tree of
+    /// replies.
+    ///
+    /// The server may leave statuses out of the context of a long thread.
+    /// If `fetch_missing` is set, the statuses replied to by statuses in the
+    /// thread which were left out are fetched one by one, up to 40 of them.
+    pub async fn resolve_thread(
+        &self,
+        id: impl Into<StatusId>,
+        fetch_missing: bool,
+    ) -> Result<Thread> {
+        const MAX_MISSING: usize = 40;
+
+        let id = id.into();
+        let status = self.get_status(&id).await?;
+        let context = self.get_context(&id).await?;
+        let mut thread = Thread::new(status, context);
+        if !fetch_missing {
+            return Ok(thread);
+        }
+        let mut attempted = HashSet::new();
+        while attempted.len() < MAX_MISSING {
+            let Some(missing) = thread
+                .missing_parents()
+                .into_iter()
+                .find(|id| !attempted.contains(id))
+            else {
+                break;
+            };
+            attempted.insert(missing.clone());
+            match self.get_status(&missing).await {
+                Ok(status) => thread.insert(status),
+                Err(err) => {
+                    debug!(id = missing.as_ref(), err:? = err; "couldn't fetch status missing from thread")
+                }
+            }
+        }
+        Ok(thread)
+    }
+
+    /// Get the accounts which boosted a status, choosing which part of the
+    /// list to fetch, e.g. to fetch up to 80 accounts per page rather than
+    /// the default 40. Use [`Page::next_page`] to fetch the rest.
+    ///
+    /// ```no_run
+    /// use mastodon_async::{prelude::*, requests::TimelineRequest};
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let mut request = TimelineRequest::new();
+    ///     request.limit(80);
+    ///     let boosters = client
+    ///         .reblogged_by_with("109348238547925380", &request)
+    ///         .await
+    ///         .unwrap()
+    ///         .items_iter();
+    /// });
+    /// ```
+    pub async fn reblogged_by_with(
+        &self,
+        id: impl Into<StatusId>,
+        request: &TimelineRequest<'_>,
+    ) -> Result<Page<Account>> {
+        let id = id.into();
+        self.accounts_page(&format!("statuses/{id}/reblogged_by"), request)
+            .await
+    }
+
+    /// Get the accounts which favourited a status, choosing which part of
+    /// the list to fetch, like [`Mastodon::reblogged_by_with`].
+    pub async fn favourited_by_with(
+        &self,
+        id: impl Into<StatusId>,
+        request: &TimelineRequest<'_>,
+    ) -> Result<Page<Account>> {
+        let id = id.into();
+        self.accounts_page(&format!("statuses/{id}/favourited_by"), request)
+            .await
+    }
+
+    /// Get the accounts which follow an account, choosing which part of the
+    /// list to fetch, e.g. to fetch up to 80 accounts per page rather than
+    /// the default 40. The list is paged by the server's `Link` headers, so
+    /// the rest can be fetched with [`Page::next_page`], or all of it with
+    /// [`Page::items_stream`].
+    ///
+    /// ```no_run
+    /// use futures_util::TryStreamExt;
+    /// use mastodon_async::{prelude::*, requests::TimelineRequest};
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let mut request = TimelineRequest::new();
+    ///     request.limit(80);
+    ///     let followers: Vec<Account> = client
+    ///         .followers_with(AccountId::new("some-id"), &request)
+    ///         .await
+    ///         .unwrap()
+    ///         .items_stream()
+    ///         .try_collect()
+    ///         .await
+    ///         .unwrap();
+    /// });
+    /// ```
+    pub async fn followers_with(
+        &self,
+        id: impl Into<AccountId>,
+        request: &TimelineRequest<'_>,
+    ) -> Result<Page<Account>> {
+        let id = id.into();
+        self.accounts_page(&format!("accounts/{id}/followers"), request)
+            .await
+    }
+
+    /// Get the accounts an account follows, choosing which part of the list
+    /// to fetch, like [`Mastodon::followers_with`].
+    pub async fn following_with(
+        &self,
+        id: impl Into<AccountId>,
+        request: &TimelineRequest<'_>,
+    ) -> Result<Page<Account>> {
+        let id = id.into();
+        self.accounts_page(&format!("accounts/{id}/following"), request)
+            .await
+    }
+
+    /// Get the accounts the authenticated user has blocked, choosing which
+    /// part of the list to fetch. Combined with [`Page::items_stream`], this
+    /// allows going through the whole list, e.g. to unblock accounts in bulk.
+    ///
+    /// ```no_run
+    /// use futures_util::TryStreamExt;
+    /// use mastodon_async::{prelude::*, requests::TimelineRequest};
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let mut request = TimelineRequest::new();
+    ///     request.limit(80);
+    ///     client
+    ///         .blocks_with(&request)
+    ///         .await
+    ///         .unwrap()
+    ///         .items_stream()
+    ///         .try_for_each(|account| {
+    ///             let client = client.clone();
+    ///             async move { client.unblock(&account.id).await.map(|_| ()) }
+    ///         })
+    ///         .await
+    ///         .unwrap();
+    /// });
+    /// ```
+    pub async fn blocks_with(&self, request: &TimelineRequest<'_>) -> Result<Page<Account>> {
+        self.accounts_page("blocks", request).await
+    }
+
+    /// Get the accounts the authenticated user has muted, choosing which
+    /// part of the list to fetch, like [`Mastodon::blocks_with`].
+    pub async fn mutes_with(&self, request: &TimelineRequest<'_>) -> Result<Page<Account>> {
+        self.accounts_page("mutes", request).await
+    }
+
+    async fn accounts_page(
+        &self,
+        path: &str,
+        request: &TimelineRequest<'_>,
+    ) -> Result<Page<Account>> {
+        let call_id = Uuid::new_v4();
+        let url = self.route(format!("/api/v1/{path}{}", request.to_query_string()?));
+
+        debug!(url = url, method = "get", call_id:? = call_id; "making API request");
+        let response = self.send(self.authenticated(self.client.get(&url))).await?;
+
+        Page::new(self.clone(), response, call_id).await
+    }
+
+    /// Fetch a page of results from a URL previously read from
+    /// [`Page::next`] or [`Page::prev`], to resume paging from where an
+    /// earlier run left off.
+    ///
+    /// The URL must be on this client's instance, so that the access token
+    /// isn't sent elsewhere.
+    ///
+    /// ```no_run
+    /// use mastodon_async::{page::Page, prelude::*};
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let page = client.get_home_timeline().await.unwrap();
+    ///     // persist this somewhere...
+    ///     let cursor = page.next.unwrap();
+    ///     // ...and later:
+    ///     let page: Page<Status> = client.resume_page(cursor).await.unwrap();
+    /// });
+    /// ```
+    pub async fn resume_page<T>(&self, url: Url) -> Result<Page<T>>
+    where
+        T: for<'de> serde::Deserialize<'de> + serde::Serialize,
+    {
+        let base: Url = self.data.base.parse()?;
+        if url.origin() != base.origin() {
+            return Err(Error::Other(format!(
+                "refusing to follow {url}, which isn't on {base}"
+            )));
+        }
+        let call_id = Uuid::new_v4();
+        debug!(url = url.as_str(), method = "get", call_id:? = call_id; "making API request");
+        let response = self.send(self.authenticated(self.client.get(url))).await?;
+
+        Page::new(self.clone(), response, call_id).await
+    }
+
+    /// List the accounts known to the server, with moderation details, using
+    /// the filters supported by `GET /api/v2/admin/accounts`.
+    ///
+    /// ```no_run
+    /// use mastodon_async::{
+    ///     prelude::*,
+    ///     requests::{AccountOrigin, AccountStatus, AdminAccountsRequest},
+    /// };
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let mut request = AdminAccountsRequest::new();
+    ///     request
+    ///         .origin(AccountOrigin::Local)
+    ///         .status(AccountStatus::Pending);
+    ///     let pending = client.admin_accounts(&request).await.unwrap();
+    ///     for account in pending.initial_items {
+    ///         client.approve_account(&account.id).await.unwrap();
+    ///     }
+    /// });
+    /// ```
+    pub async fn admin_accounts(
+        &self,
+        request: &AdminAccountsRequest<'_>,
+    ) -> Result<Page<admin::Account>> {
+        let call_id = Uuid::new_v4();
+        let url = self.route(format!(
+            "/api/v2/admin/accounts{}",
+            request.to_query_string()?
+        ));
+
+        debug!(url = url, method = "get", call_id:? = call_id; "making API request");
+        let response = self.send(self.authenticated(self.client.get(&url))).await?;
+
+        Page::new(self.clone(), response, call_id).await
+    }
+
+    /// Get notifications grouped by type and target, as Mastodon 4.3 and
+    /// later presents them. Older instances don't support this endpoint;
+    /// use [`Mastodon::notifications_with`] for those.
+    pub async fn grouped_notifications(
+        &self,
+        request: &GroupedNotificationsRequest<'_>,
+    ) -> Result<notification::GroupedResults> {
+        let call_id = Uuid::new_v4();
+        let url = self.route(format!(
+            "/api/v2/notifications{}",
+            request.to_query_string()?
+        ));
+
+        self.get_with_call_id(url, call_id).await
+    }
+
+    /// Accept several notification requests at once, merging their filtered
+    /// notifications into the regular notifications list.
+    pub async fn accept_notification_requests(
+        &self,
+        ids: &[&NotificationRequestId],
+    ) -> Result<Empty> {
+        self.post(self.notification_requests_url("accept", ids)?)
+            .await
+    }
+
+    /// Dismiss several notification requests at once, deleting their filtered
+    /// notifications.
+    pub async fn dismiss_notification_requests(
+        &self,
+        ids: &[&NotificationRequestId],
+    ) -> Result<Empty> {
+        self.post(self.notification_requests_url("dismiss", ids)?)
+            .await
+    }
+
+    fn notification_requests_url(
+        &self,
+        action: &str,
+        ids: &[&NotificationRequestId],
+    ) -> Result<String> {
+        let mut qs = String::new();
+        push_array(&mut qs, "id", ids)?;
+        Ok(self.route(format!("/api/v1/notifications/requests/{action}?{qs}")))
+    }
+
+    /// Translate the content of a status into `lang`, or into the user's
+    /// interface language if `lang` is `None`. Which languages can be
+    /// translated is given by [`Mastodon::instance_translation_languages`].
+    pub async fn translate_status(
+        &self,
+        id: impl Into<StatusId>,
+        lang: Option<Language>,
+    ) -> Result<status::Translation> {
+        let id = id.into();
+        let mut url = self.route(format!("/api/v1/statuses/{id}/translate"));
+        if let Some(lang) = lang {
+            url += "?lang=";
+            url += lang.to_639_1().unwrap_or_else(|| lang.to_639_3());
+        }
+
+        self.post(url).await
+    }
+
+    /// Returns the client account's relationship to a list of other accounts.
+    /// Such as whether they follow them or vice versa.
+    ///
+    /// All of the accounts are looked up in a single request. Relationships
+    /// with suspended accounts are only included if `with_suspended` is set.
+    pub async fn relationships(
+        &self,
+        ids: &[&AccountId],
+        with_suspended: bool,
+    ) -> Result<Vec<Relationship>> {
+        let call_id = Uuid::new_v4();
+        let mut qs = String::new();
+        push_array(&mut qs, "id", ids)?;
+        if with_suspended {
+            if !qs.is_empty() {
+                qs.push('&');
+            }
+            qs += "with_suspended=true";
+        }
+        let url = self.route(format!("/api/v1/accounts/relationships?{qs}"));
+
+        debug!(
+            url = url, method = "get",
+            call_id:? = call_id, account_ids:serde = ids;
+            "making API request"
+        );
+        let response = self.send(self.authenticated(self.client.get(&url))).await?;
+
+        read_response(response).await
+    }
+
+    /// Add a push notifications subscription
+    pub async fn add_push_subscription(&self, request: &AddPushRequest) -> Result<Subscription> {
+        let call_id = Uuid::new_v4();
+        let request = request.build();
+        let url = &self.route("/api/v1/push/subscription");
+        debug!(
+            url = url, method = stringify!($method),
+            call_id:? = call_id, post_body:serde = request;
+            "making API request"
+        );
+        let response = self.send(self.client.post(url).json(&request)).await?;
+
+        read_response(response).await
+    }
+
+    /// Update the `data` portion of the push subscription associated with this
+    /// access token
+    pub async fn update_push_data(&self, request: &UpdatePushRequest) -> Result<Subscription> {
+        let call_id = Uuid::new_v4();
+        let request = request.build();
+        let url = &self.route("/api/v1/push/subscription");
+        debug!(
+            url = url, method = stringify!($method),
+            call_id:? = call_id, post_body:serde = request;
+            "making API request"
+        );
+        let response = self.send(self.client.post(url).json(&request)).await?;
+
+        read_response(response).await
+    }
+
+    /// Get all accounts that follow the authenticated user
+    pub async fn follows_me(&self) -> Result<Page<Account>> {
+        let me = self.verify_credentials().await?;
+        self.followers(&me.id).await
+    }
+
+    /// Get all accounts that the authenticated user follows
+    pub async fn followed_by_me(&self) -> Result<Page<Account>> {
+        let me = self.verify_credentials().await?;
+        self.following(&me.id).await
+    }
+
+    /// Post a status with media attached, uploading the files and waiting
+    /// for them to be processed first. See [`PostBuilder`].
+    pub fn post_with_media(&self, status: NewStatus) -> PostBuilder {
+        PostBuilder::new(self.clone(), status)
+    }
+
+    /// Wait for the media to be done processing and return it with the URL.
+    ///
+    /// `Default::default()` may be passed as the polling time to select a
+    /// polling time of 500ms.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use mastodon_async::prelude::*;
+    /// let mastodon = Mastodon::from(Data::default());
+    /// tokio_test::block_on(async {
+    ///     let attachment = mastodon.media("/path/to/some/file.jpg", None).await.expect("upload");
+    ///     let attachment = mastodon.wait_for_processing(attachment, Default::default()).await.expect("processing");
+    ///     println!("{}", attachment.url);
+    /// });
+    /// ```
+    ///
+    /// For a different polling time, use `.into()` on a `std::time::Duration`.
+    /// ```rust,no_run
     /// use mastodon_async::prelude::*;
     /// use std::time::Duration;
     /// let mastodon = Mastodon::from(Data::default());
@@ -375,9 +1803,312 @@ impl Mastodon {
                 });
             } else {
                 attachment = self.attachment(&id).await?;
-                tokio::time::sleep(*polling_time).await;
+                crate::helpers::time::sleep(*polling_time).await;
+            }
+        }
+    }
+
+    /// Update the description, focal point, or thumbnail of media which has
+    /// already been uploaded. The attachment can only be updated while it is
+    /// not yet attached to a status.
+    ///
+    /// Equivalent to `PUT /api/v1/media/:id`.
+    pub async fn update_media(
+        &self,
+        id: impl Into<AttachmentId>,
+        request: UpdateMediaRequest,
+    ) -> Result<Attachment> {
+        let id = id.into();
+        use reqwest::multipart::Form;
+
+        let call_id = Uuid::new_v4();
+        let mut form_data = Form::new();
+        if let Some(description) = request.description {
+            form_data = form_data.text("description", description);
+        }
+        if let Some(focus) = request.focus {
+            form_data = form_data.text("focus", focus.to_string());
+        }
+        if let Some(thumbnail) = request.thumbnail {
+            form_data = form_data.part("thumbnail", Self::get_form_part(thumbnail)?);
+        }
+
+        let url = self.route(format!("/api/v1/media/{id}"));
+        debug!(
+            url = url, method = "put",
+            multipart_form_data:? = form_data, call_id:? = call_id;
+            "making API request"
+        );
+        let request = self
+            .authenticated(self.client.put(&url))
+            .multipart(form_data)
+            .header("Accept", "application/json");
+        let response = self.send(request).await?;
+
+        read_response(response).await
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Upload a file to be attached to a status, streaming it from disk
+    /// rather than reading it into memory first.
+    ///
+    /// `progress` is called each time a chunk of the file is sent, with the
+    /// number of bytes which have been sent so far and the total size of
+    /// the file.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use mastodon_async::prelude::*;
+    /// let mastodon = Mastodon::from(Data::default());
+    /// tokio_test::block_on(async {
+    ///     let attachment = mastodon
+    ///         .media_with_progress("/path/to/some/video.mp4", None, |sent, total| {
+    ///             println!("uploaded {sent} of {} bytes", total.unwrap_or_default());
+    ///         })
+    ///         .await
+    ///         .expect("upload");
+    /// });
+    /// ```
+    pub async fn media_with_progress(
+        &self,
+        file: impl AsRef<Path>,
+        description: Option<String>,
+        progress: impl Fn(u64, Option<u64>) + Send + Sync + 'static,
+    ) -> Result<Attachment> {
+        let path = file.as_ref();
+        let file = match tokio::fs::File::open(path).await {
+            Ok(file) => file,
+            Err(err) => {
+                error!(path:? = path, error:? = err; "error opening file for streaming upload");
+                return Err(err.into());
             }
+        };
+        let length = file.metadata().await.ok().map(|metadata| metadata.len());
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string_lossy().to_string());
+        self.media_from_reader(file, file_name, length, description, progress)
+            .await
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Upload media to be attached to a status from any [`AsyncRead`]
+    /// source, streaming the request body as it is read.
+    ///
+    /// If `length` is known, it is sent as the size of the upload and passed
+    /// along to `progress` as the total; otherwise, the upload is sent
+    /// without a declared length.
+    ///
+    /// [`AsyncRead`]: tokio::io::AsyncRead
+    pub async fn media_from_reader(
+        &self,
+        reader: impl tokio::io::AsyncRead + Send + 'static,
+        file_name: impl Into<String>,
+        length: Option<u64>,
+        description: Option<String>,
+        progress: impl Fn(u64, Option<u64>) + Send + Sync + 'static,
+    ) -> Result<Attachment> {
+        use reqwest::multipart::Form;
+
+        let call_id = Uuid::new_v4();
+        let part = Self::get_streaming_form_part(reader, file_name.into(), length, progress);
+        let form_data = Form::new().part("file", part);
+        let form_data = if let Some(description) = description {
+            form_data.text("description", description)
+        } else {
+            form_data
+        };
+
+        let url = self.route("/api/v2/media");
+        debug!(
+            url = url, method = "post", length:? = length,
+            multipart_form_data:? = form_data, call_id:? = call_id;
+            "making streaming upload API request"
+        );
+        let request = self
+            .authenticated(self.client.post(&url))
+            .multipart(form_data)
+            .header("Accept", "application/json");
+        let response = self.send(request).await?;
+
+        read_response(response).await
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Download the media of an attachment to the file at `path`, streaming
+    /// it to disk as it's received, and return its size in bytes.
+    ///
+    /// The copy cached by the instance is downloaded, or the original on the
+    /// remote server if the instance hasn't cached it. Redirects are
+    /// followed. No access token is sent, since media may be served from
+    /// another host than the API.
+    ///
+    /// ```no_run
+    /// use mastodon_async::prelude::*;
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let status = client.get_status(&StatusId::new("109348238547925380")).await.unwrap();
+    ///     for (i, attachment) in status.media_attachments.iter().enumerate() {
+    ///         client.download_attachment(attachment, format!("attachment-{i}")).await.unwrap();
+    ///     }
+    /// });
+    /// ```
+    pub async fn download_attachment(
+        &self,
+        attachment: &Attachment,
+        path: impl AsRef<Path>,
+    ) -> Result<u64> {
+        self.download_attachment_with_progress(attachment, path, |_, _| {})
+            .await
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Download the media of an attachment to the file at `path` like
+    /// [`download_attachment`](Self::download_attachment).
+    ///
+    /// `progress` is called each time a chunk of the media is written, with
+    /// the number of bytes which have been written so far and the size of
+    /// the media, if the server declared it.
+    ///
+    /// If the download fails, the partly written file is removed.
+    pub async fn download_attachment_with_progress(
+        &self,
+        attachment: &Attachment,
+        path: impl AsRef<Path>,
+        mut progress: impl FnMut(u64, Option<u64>) + Send,
+    ) -> Result<u64> {
+        use futures::TryStreamExt;
+        use tokio::io::AsyncWriteExt;
+
+        let path = path.as_ref();
+        let response = self.attachment_response(attachment).await?;
+        let total = response.content_length();
+        let mut file = tokio::fs::File::create(path).await?;
+        let mut body = response.bytes_stream();
+        let result: Result<u64> = async {
+            let mut written = 0;
+            while let Some(chunk) = body.try_next().await? {
+                file.write_all(&chunk).await?;
+                written += chunk.len() as u64;
+                progress(written, total);
+            }
+            file.flush().await?;
+            Ok(written)
         }
+        .await;
+        if let Err(err) = &result {
+            error!(path:? = path, error:? = err; "error downloading attachment");
+            drop(file);
+            let _ = tokio::fs::remove_file(path).await;
+        }
+        result
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Download the media of an attachment like
+    /// [`download_attachment`](Self::download_attachment), returning a
+    /// reader which streams the media as it's received rather than saving
+    /// it to a file.
+    ///
+    /// ```no_run
+    /// use mastodon_async::prelude::*;
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let attachment = client.attachment("1234").await.unwrap();
+    ///     let mut reader = client.download_attachment_reader(&attachment).await.unwrap();
+    ///     tokio::io::copy(&mut reader, &mut tokio::io::sink()).await.unwrap();
+    /// });
+    /// ```
+    pub async fn download_attachment_reader(
+        &self,
+        attachment: &Attachment,
+    ) -> Result<impl tokio::io::AsyncRead + Send + Unpin> {
+        use futures::TryStreamExt;
+
+        let response = self.attachment_response(attachment).await?;
+        let body = response
+            .bytes_stream()
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err));
+        Ok(tokio_util::io::StreamReader::new(Box::pin(body)))
+    }
+
+    /// Request the media of an attachment, preferring the copy cached by
+    /// the instance to the original on the remote server.
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn attachment_response(&self, attachment: &Attachment) -> Result<Response> {
+        let url = attachment
+            .url
+            .as_ref()
+            .or(attachment.remote_url.as_ref())
+            .ok_or_else(|| {
+                crate::format_err!("attachment {} has no URL to download", attachment.id)
+            })?;
+        let call_id = Uuid::new_v4();
+        debug!(url = url.as_str(), method = "get", call_id:? = call_id; "downloading attachment");
+        // bypass the response cache, which would buffer the whole body
+        let mut request = self.client.get(url.clone()).build()?;
+        let response = if self.is_instance_url(url) {
+            self.add_default_headers(request.headers_mut());
+            self.execute(request).await?
+        } else {
+            // media on other servers mustn't see the headers or middleware
+            // meant for the instance, nor count against its rate limit
+            self.execute_with_timeout(request).await?
+        };
+        if response.status().is_success() {
+            Ok(response)
+        } else {
+            Err(Error::from_error_response(response).await)
+        }
+    }
+
+    /// Whether `url` is on the instance, rather than another server.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn is_instance_url(&self, url: &Url) -> bool {
+        Url::parse(&self.data.base).is_ok_and(|base| base.origin() == url.origin())
+    }
+
+    /// Revoke the access token this client uses, logging the user out.
+    ///
+    /// The client is consumed, since its token is no longer valid. The
+    /// returned `Data` keeps the app's client ID and secret, but has an empty
+    /// token, so it can be saved in place of the old data and used to log in
+    /// again without registering a new app. Any clones of this client still
+    /// hold the revoked token, and their requests will fail.
+    ///
+    /// ```no_run
+    /// use mastodon_async::prelude::*;
+    ///
+    /// tokio_test::block_on(async {
+    ///     let client = Mastodon::from(Data::default());
+    ///     let data = client.revoke_token().await.unwrap();
+    ///     assert!(data.token.is_empty());
+    /// });
+    /// ```
+    pub async fn revoke_token(self) -> Result<Data> {
+        let call_id = Uuid::new_v4();
+        let url = self.route("/oauth/revoke");
+        debug!(url = url, method = "post", call_id:? = call_id; "revoking access token");
+        let response = self
+            .send(
+                self.client
+                    .post(&url)
+                    .form(&[
+                        ("client_id", &self.data.client_id),
+                        ("client_secret", &self.data.client_secret),
+                        ("token", &self.data.token),
+                    ])
+                    .header("Accept", "application/json"),
+            )
+            .await?;
+        read_response::<Empty>(response).await?;
+
+        let mut data = self.data.clone();
+        data.token = Default::default();
+        Ok(data)
     }
 
     /// Set the bearer authentication token
@@ -409,6 +2140,41 @@ impl Mastodon {
             }
         }
     }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Return a part for a multipart form submission which streams its body
+    /// from `reader`, reporting each chunk sent to `progress`.
+    fn get_streaming_form_part(
+        reader: impl tokio::io::AsyncRead + Send + 'static,
+        file_name: String,
+        length: Option<u64>,
+        progress: impl Fn(u64, Option<u64>) + Send + Sync + 'static,
+    ) -> Part {
+        use futures::TryStreamExt;
+        use reqwest::Body;
+        use tokio_util::io::ReaderStream;
+
+        let mut sent = 0u64;
+        let stream = ReaderStream::new(reader).inspect_ok(move |chunk| {
+            sent += chunk.len() as u64;
+            progress(sent, length);
+        });
+        let body = Body::wrap_stream(stream);
+        let part = if let Some(length) = length {
+            Part::stream_with_length(body, length)
+        } else {
+            Part::stream(body)
+        };
+        part.file_name(file_name)
+    }
+}
+
+/// Whether `base` is the plain HTTP URL of a Tor onion service, which is
+/// already encrypted and authenticated by Tor, so doesn't need HTTPS.
+fn is_onion(base: &str) -> bool {
+    Url::parse(base).is_ok_and(|url| {
+        url.scheme() == "http" && url.host_str().is_some_and(|host| host.ends_with(".onion"))
+    })
 }
 
 impl MastodonUnauthenticated {
@@ -417,15 +2183,24 @@ impl MastodonUnauthenticated {
     /// Create a new client for unauthenticated requests to a given Mastodon
     /// instance.
     pub fn new(base: impl AsRef<str>) -> Result<MastodonUnauthenticated> {
+        Self::new_with_client(base, Client::new())
+    }
+
+    /// Create a new client for unauthenticated requests to a given Mastodon
+    /// instance, sending them with `client`.
+    pub fn new_with_client(
+        base: impl AsRef<str>,
+        client: Client,
+    ) -> Result<MastodonUnauthenticated> {
         let base = base.as_ref();
-        let base = if base.starts_with("https://") {
+        let base = if base.starts_with("https://") || is_onion(base) {
             base.to_string()
         } else {
             format!("https://{}", base.trim_start_matches("http://"))
         };
         trace!(base = base; "creating new mastodon client");
         Ok(MastodonUnauthenticated {
-            client: Client::new(),
+            client,
             base: Url::parse(&base)?,
         })
     }
@@ -435,14 +2210,16 @@ impl MastodonUnauthenticated {
     }
 
     /// GET /api/v1/statuses/:id
-    pub async fn get_status(&self, id: &StatusId) -> Result<Status> {
+    pub async fn get_status(&self, id: impl Into<StatusId>) -> Result<Status> {
+        let id = id.into();
         let route = self.route("/api/v1/statuses")?;
         let route = route.join(id.as_ref())?;
         self.get(route.as_str()).await
     }
 
     /// GET /api/v1/statuses/:id/context
-    pub async fn get_context(&self, id: &StatusId) -> Result<Context> {
+    pub async fn get_context(&self, id: impl Into<StatusId>) -> Result<Context> {
+        let id = id.into();
         let route = self.route("/api/v1/statuses")?;
         let route = route.join(id.as_ref())?;
         let route = route.join("context")?;
@@ -450,7 +2227,8 @@ impl MastodonUnauthenticated {
     }
 
     /// GET /api/v1/statuses/:id/card
-    pub async fn get_card(&self, id: &StatusId) -> Result<Card> {
+    pub async fn get_card(&self, id: impl Into<StatusId>) -> Result<Card> {
+        let id = id.into();
         let route = self.route("/api/v1/statuses")?;
         let route = route.join(id.as_ref())?;
         let route = route.join("card")?;
@@ -462,6 +2240,10 @@ impl MastodonUnauthenticated {
     fn authenticated(&self, request: RequestBuilder) -> RequestBuilder {
         request
     }
+
+    async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        Ok(request.send().await?)
+    }
 }
 impl Deref for Mastodon {
     type Target = Arc<MastodonClient>;
@@ -476,3 +2258,409 @@ impl From<MastodonClient> for Mastodon {
         Mastodon(Arc::new(value))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::fixtures::{self, Fake, Reply};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_unauthenticated_base() {
+        let client = MastodonUnauthenticated::new("mastodon.example").unwrap();
+        assert_eq!(client.base.as_str(), "https://mastodon.example/");
+        let client = MastodonUnauthenticated::new("http://mastodon.example").unwrap();
+        assert_eq!(client.base.as_str(), "https://mastodon.example/");
+        let onion = "http://mastodonexamplexyz.onion/";
+        let client = MastodonUnauthenticated::new(onion).unwrap();
+        assert_eq!(client.base.as_str(), onion);
+    }
+
+    #[tokio::test]
+    async fn test_capabilities_fallback() {
+        let routes = Fake::routes([
+            (
+                "/.well-known/nodeinfo",
+                r#"{"links":[{"rel":"http://nodeinfo.diaspora.software/ns/schema/2.0","href":"https://pleroma.example/nodeinfo/2.0.json"}]}"#,
+            ),
+            (
+                "/nodeinfo/2.0.json",
+                r#"{"version":"2.0","software":{"name":"pleroma","version":"2.6.1"}}"#,
+            ),
+            ("/api/v1/filters", "[]"),
+        ]);
+        let data = Data {
+            base: "https://pleroma.example".into(),
+            ..Data::default()
+        };
+        let client = fixtures::client_with(data, routes.clone());
+        let capabilities = client.capabilities().await.unwrap();
+        assert_eq!(
+            capabilities.software(),
+            &crate::capabilities::Software::Pleroma
+        );
+        assert!(capabilities.supports_editing());
+        assert!(!capabilities.supports_filters_v2());
+        assert_eq!(
+            routes.paths(),
+            [
+                "/api/v2/instance",
+                "/api/v1/instance",
+                "/.well-known/nodeinfo",
+                "/nodeinfo/2.0.json"
+            ]
+        );
+
+        let AnyFilters::V1(filters) = client.any_filters().await.unwrap() else {
+            panic!("expected v1 filters")
+        };
+        assert!(filters.is_empty());
+        assert_eq!(routes.paths().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_missing_scope() {
+        let routes = Fake::routes([("/api/v1/timelines/home", "[]")]);
+        let data = Data {
+            base: "https://mastodon.example".into(),
+            scopes: Some("read:statuses".parse().unwrap()),
+            ..Data::default()
+        };
+        let client = fixtures::client_with(data, routes.clone());
+        let err = client.favourites().await.unwrap_err();
+        assert!(err.is_missing_scope());
+        assert!(routes.paths().is_empty());
+        client.get_home_timeline().await.unwrap();
+        assert_eq!(routes.paths(), ["/api/v1/timelines/home"]);
+    }
+
+    /// A fake answering every request with an empty list, linking to the
+    /// next page until `pages` have been served.
+    fn pages(pages: usize) -> Arc<Fake> {
+        let served = AtomicUsize::new(0);
+        Fake::new(move |request| {
+            let served = served.fetch_add(1, Ordering::SeqCst) + 1;
+            let reply = Reply::ok("[]");
+            if served < pages {
+                let mut next = request.url().clone();
+                next.set_query(Some(&format!("max_id={served}")));
+                reply.header("Link", format!("<{next}>; rel=\"next\""))
+            } else {
+                reply
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_reblogged_by_pages() {
+        let pages = pages(3);
+        let client = fixtures::client(pages.clone());
+        let mut request = TimelineRequest::new();
+        request.limit(80);
+        let mut page = client.reblogged_by_with("1", &request).await.unwrap();
+        assert!(page.initial_items.is_empty());
+        assert_eq!(page.next_page().await.unwrap(), Some(vec![]));
+        assert_eq!(page.next_page().await.unwrap(), None);
+        let mut page = client.favourited_by("2").await.unwrap();
+        assert_eq!(page.next_page().await.unwrap(), None);
+        assert_eq!(
+            pages.urls(),
+            [
+                "https://mastodon.example/api/v1/statuses/1/reblogged_by?limit=80",
+                "https://mastodon.example/api/v1/statuses/1/reblogged_by?max_id=1",
+                "https://mastodon.example/api/v1/statuses/1/reblogged_by?max_id=2",
+                "https://mastodon.example/api/v1/statuses/2/favourited_by",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_followers_stream() {
+        use futures_util::TryStreamExt;
+
+        let pages = pages(2);
+        let client = fixtures::client(pages.clone());
+        let mut request = TimelineRequest::new();
+        request.limit(80);
+        let followers: Vec<Account> = client
+            .followers_with(AccountId::new("1"), &request)
+            .await
+            .unwrap()
+            .items_stream()
+            .try_collect()
+            .await
+            .unwrap();
+        assert!(followers.is_empty());
+        client
+            .following_with(AccountId::new("2"), &TimelineRequest::new())
+            .await
+            .unwrap();
+        assert_eq!(
+            pages.urls(),
+            [
+                "https://mastodon.example/api/v1/accounts/1/followers?limit=80",
+                "https://mastodon.example/api/v1/accounts/1/followers?max_id=1",
+                "https://mastodon.example/api/v1/accounts/2/following?",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_blocks_and_mutes_pages() {
+        let pages = pages(2);
+        let client = fixtures::client(pages.clone());
+        let mut request = TimelineRequest::new();
+        request.limit(80);
+        let mut page = client.blocks_with(&request).await.unwrap();
+        assert_eq!(page.next_page().await.unwrap(), None);
+        client.mutes_with(&request).await.unwrap();
+        assert_eq!(
+            pages.urls(),
+            [
+                "https://mastodon.example/api/v1/blocks?limit=80",
+                "https://mastodon.example/api/v1/blocks?max_id=1",
+                "https://mastodon.example/api/v1/mutes?limit=80",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_thread() {
+        use crate::thread::tests::status;
+
+        fn json(value: impl serde::Serialize) -> String {
+            serde_json::to_string(&value).unwrap()
+        }
+        let context = Context {
+            ancestors: vec![status("2", Some("1"), 2)],
+            descendants: vec![],
+        };
+        let routes = Fake::routes([
+            ("/api/v1/statuses/3", json(status("3", Some("2"), 3))),
+            ("/api/v1/statuses/3/context", json(context)),
+            ("/api/v1/statuses/1", json(status("1", Some("0"), 1))),
+        ]);
+        let client = fixtures::client(routes.clone());
+        let thread = client.resolve_thread("3", false).await.unwrap();
+        assert_eq!(thread.len(), 2);
+        assert_eq!(thread.missing_parents(), [StatusId::new("1")]);
+
+        let thread = client.resolve_thread("3", true).await.unwrap();
+        assert_eq!(thread.len(), 3);
+        assert_eq!(thread.root().id, StatusId::new("1"));
+        assert_eq!(thread.missing_parents(), [StatusId::new("0")]);
+        assert_eq!(
+            routes.paths()[2..],
+            [
+                "/api/v1/statuses/3",
+                "/api/v1/statuses/3/context",
+                "/api/v1/statuses/1",
+                "/api/v1/statuses/0"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_local_only() {
+        let routes = Fake::routes([("/api/v2/instance", r#"{"version":"4.2.0"}"#)]);
+        let client = fixtures::client(routes.clone());
+        let status = NewStatusBuilder::default().status("hello").build().unwrap();
+        assert_eq!(
+            client.check_local_only(status.clone()).await.unwrap(),
+            status
+        );
+        assert!(routes.paths().is_empty());
+
+        let mut local = status.clone();
+        local.local_only = Some(false);
+        assert_eq!(
+            client.check_local_only(local.clone()).await.unwrap(),
+            status
+        );
+        local.local_only = Some(true);
+        let Err(Error::Unsupported { software, .. }) = client.new_status(local).await else {
+            panic!("expected local-only statuses to be unsupported")
+        };
+        assert_eq!(software, "Mastodon 4.2.0");
+        assert_eq!(routes.paths(), ["/api/v2/instance"]);
+    }
+
+    #[cfg(feature = "pleroma")]
+    #[tokio::test]
+    async fn test_status_reactions() {
+        let routes = Fake::routes([(
+            "/api/v1/pleroma/statuses/1/reactions",
+            r#"[{"name":"👍","count":1,"me":true,"accounts":[]}]"#,
+        )]);
+        let data = Data {
+            base: "https://akkoma.example".into(),
+            ..Data::default()
+        };
+        let client = fixtures::client_with(data, routes.clone());
+        let reactions = client.status_reactions("1").await.unwrap();
+        assert_eq!(reactions[0].name, "👍");
+        assert!(reactions[0].me);
+        // not found, but the emoji must have been escaped
+        assert!(client.add_status_reaction("1", "👍").await.is_err());
+        assert_eq!(
+            routes.paths()[1],
+            "/api/v1/pleroma/statuses/1/reactions/%F0%9F%91%8D"
+        );
+    }
+
+    #[cfg(feature = "pleroma")]
+    #[tokio::test]
+    async fn test_account_aliases() {
+        let server = fixtures::Server::bind().await;
+        let client = Mastodon::from(server.data());
+        let (aliases, request) = tokio::join!(
+            client.account_aliases(),
+            server.respond("200 OK", r#"{"aliases":["old@pleroma.example"]}"#)
+        );
+        assert_eq!(aliases.unwrap(), ["old@pleroma.example"]);
+        assert!(request.starts_with("GET /api/pleroma/aliases HTTP/1.1\r\n"));
+        let (added, request) = tokio::join!(
+            client.add_account_alias("old@pleroma.example"),
+            server.respond("200 OK", "{}")
+        );
+        added.unwrap();
+        assert!(request
+            .starts_with("PUT /api/pleroma/aliases?alias=old%40pleroma.example HTTP/1.1\r\n"));
+        let (removed, request) = tokio::join!(
+            client.remove_account_alias("old@pleroma.example"),
+            server.respond("200 OK", "{}")
+        );
+        removed.unwrap();
+        assert!(request
+            .starts_with("DELETE /api/pleroma/aliases?alias=old%40pleroma.example HTTP/1.1\r\n"));
+    }
+
+    fn attachment(url: Option<&str>, remote_url: Option<&str>) -> Attachment {
+        serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "type": "image",
+            "url": url,
+            "preview_url": "https://files.mastodon.example/small/1.png",
+            "remote_url": remote_url,
+            "description": null,
+            "blurhash": null
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_download_attachment() {
+        let routes = Fake::routes([("/original/1.png", "the media")]);
+        let client = fixtures::client(routes.clone());
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("1.png");
+        let local = attachment(Some("https://files.mastodon.example/original/1.png"), None);
+        let mut reported = vec![];
+        let written = client
+            .download_attachment_with_progress(&local, &path, |written, total| {
+                reported.push((written, total))
+            })
+            .await
+            .unwrap();
+        assert_eq!(written, 9);
+        assert_eq!(reported, [(9, Some(9))]);
+        assert_eq!(std::fs::read(&path).unwrap(), b"the media");
+
+        let remote = attachment(None, Some("https://remote.example/original/1.png"));
+        let mut reader = client.download_attachment_reader(&remote).await.unwrap();
+        let mut media = String::new();
+        tokio::io::AsyncReadExt::read_to_string(&mut reader, &mut media)
+            .await
+            .unwrap();
+        assert_eq!(media, "the media");
+
+        let missing = attachment(Some("https://files.mastodon.example/original/2.png"), None);
+        let path = dir.path().join("2.png");
+        let err = client
+            .download_attachment(&missing, &path)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Api { .. }), "{err:?}");
+        assert!(!path.exists());
+        assert!(client
+            .download_attachment(&attachment(None, None), &path)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_download_remote_attachment() {
+        #[derive(Debug)]
+        struct Deny;
+
+        impl Middleware for Deny {
+            fn on_request<'a>(
+                &'a self,
+                _request: &'a mut Request,
+            ) -> crate::middleware::HookFuture<'a> {
+                Box::pin(async { Err(Error::Other("denied".to_string())) })
+            }
+        }
+
+        let routes = Fake::routes([("/original/1.png", "the media")]);
+        let client = MastodonBuilder::new(Data {
+            base: "https://mastodon.example".into(),
+            ..Data::default()
+        })
+        .transport(routes.clone())
+        .middleware(Deny)
+        .build()
+        .unwrap();
+        let remote = attachment(None, Some("https://remote.example/original/1.png"));
+        let mut reader = client.download_attachment_reader(&remote).await.unwrap();
+        let mut media = String::new();
+        tokio::io::AsyncReadExt::read_to_string(&mut reader, &mut media)
+            .await
+            .unwrap();
+        assert_eq!(media, "the media");
+
+        let local = attachment(Some("https://mastodon.example/original/1.png"), None);
+        let Err(Error::Other(message)) = client.download_attachment_reader(&local).await else {
+            panic!("expected the middleware's error")
+        };
+        assert_eq!(message, "denied");
+        assert_eq!(routes.paths(), ["/original/1.png"]);
+    }
+
+    #[tokio::test]
+    async fn test_tags() {
+        let routes = Fake::routes([
+            (
+                "/api/v1/tags/rust",
+                r#"{"name":"rust","url":"https://mastodon.example/tags/rust","history":[]}"#,
+            ),
+            (
+                "/api/v1/tags/caf%C3%A9%2Fbar/follow",
+                r#"{"name":"café/bar","url":"https://mastodon.example/tags/caf%C3%A9","history":[],"following":true}"#,
+            ),
+        ]);
+        let client = fixtures::client(routes.clone());
+        assert_eq!(client.get_tag("#rust").await.unwrap().name, "rust");
+        client.follow_tag("café/bar").await.unwrap();
+        assert_eq!(
+            routes.paths(),
+            ["/api/v1/tags/rust", "/api/v1/tags/caf%C3%A9%2Fbar/follow"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_appeal() {
+        let routes = Fake::routes([(
+            "/api/v1/appeals",
+            r#"{"text": "It was a cat", "state": "pending"}"#,
+        )]);
+        let client = fixtures::client(routes.clone());
+        let appeal = client
+            .appeal("3".into(), "It was a cat".into())
+            .await
+            .unwrap();
+        assert_eq!(appeal.text, "It was a cat");
+        assert!(appeal.state.is_pending());
+        assert_eq!(routes.paths(), ["/api/v1/appeals"]);
+    }
+}
@@ -0,0 +1,136 @@
+use std::fmt;
+
+use reqwest::{Request, Response};
+
+use crate::errors::Result;
+
+/// The future returned by the hooks of a [`Middleware`]. It must be `Send`,
+/// except on WebAssembly, like [`ResponseFuture`](crate::transport::ResponseFuture).
+#[cfg(not(target_arch = "wasm32"))]
+pub type HookFuture<'a> = futures::future::BoxFuture<'a, Result<()>>;
+/// The future returned by the hooks of a [`Middleware`]. It must be `Send`,
+/// except on WebAssembly, like [`ResponseFuture`](crate::transport::ResponseFuture).
+#[cfg(target_arch = "wasm32")]
+pub type HookFuture<'a> = futures::future::LocalBoxFuture<'a, Result<()>>;
+
+/// Hooks called around every HTTP request a [`Mastodon`](crate::Mastodon)
+/// client sends, added with
+/// [`MastodonBuilder::middleware`](crate::builder::MastodonBuilder::middleware),
+/// e.g. for custom authentication schemes, metrics, or logging.
+///
+/// The hooks are called for each attempt at sending a request, so a request
+/// which is [retried](crate::retry::Retry) passes through them more than
+/// once. Connecting to the streaming API over WebSocket, and downloading
+/// media from servers other than the instance, don't go through them.
+///
+/// // Example
+///
+/// ```
+/// use mastodon_async::{middleware::{HookFuture, Middleware}, prelude::*};
+/// use reqwest::{header::HeaderValue, Request, Response};
+///
+/// #[derive(Debug)]
+/// struct Signature(HeaderValue);
+///
+/// impl Middleware for Signature {
+///     fn on_request<'a>(&'a self, request: &'a mut Request) -> HookFuture<'a> {
+///         Box::pin(async move {
+///             request.headers_mut().insert("Signature", self.0.clone());
+///             Ok(())
+///         })
+///     }
+///
+///     fn on_response<'a>(&'a self, response: &'a mut Response) -> HookFuture<'a> {
+///         Box::pin(async move {
+///             println!("{} {}", response.status(), response.url());
+///             Ok(())
+///         })
+///     }
+/// }
+///
+/// let client = Mastodon::builder(Data::default())
+///     .middleware(Signature(HeaderValue::from_static("keyId=\"my-key\"")))
+///     .build()
+///     .unwrap();
+/// ```
+pub trait Middleware: fmt::Debug + Send + Sync {
+    /// Called before `request` is sent, after the client has added its own
+    /// headers. Returning an error fails the request without sending it.
+    fn on_request<'a>(&'a self, request: &'a mut Request) -> HookFuture<'a> {
+        let _ = request;
+        Box::pin(futures::future::ready(Ok(())))
+    }
+
+    /// Called once the status and headers of `response` have been received,
+    /// whether or not it was successful. Returning an error fails the
+    /// request with it instead.
+    fn on_response<'a>(&'a self, response: &'a mut Response) -> HookFuture<'a> {
+        let _ = response;
+        Box::pin(futures::future::ready(Ok(())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{helpers::fixtures::Server, Error, Mastodon};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug)]
+    struct Counter {
+        responses: AtomicUsize,
+    }
+
+    impl Middleware for &'static Counter {
+        fn on_request<'a>(&'a self, request: &'a mut Request) -> HookFuture<'a> {
+            Box::pin(async move {
+                request
+                    .headers_mut()
+                    .insert("X-Test", "counted".parse().unwrap());
+                Ok(())
+            })
+        }
+
+        fn on_response<'a>(&'a self, _response: &'a mut Response) -> HookFuture<'a> {
+            Box::pin(async move {
+                self.responses.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            })
+        }
+    }
+
+    #[derive(Debug)]
+    struct Deny;
+
+    impl Middleware for Deny {
+        fn on_request<'a>(&'a self, _request: &'a mut Request) -> HookFuture<'a> {
+            Box::pin(async { Err(Error::Other("denied".to_string())) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hooks() {
+        static COUNTER: Counter = Counter {
+            responses: AtomicUsize::new(0),
+        };
+        let server = Server::bind().await;
+        let client = Mastodon::builder(server.data())
+            .middleware(&COUNTER)
+            .build()
+            .unwrap();
+        let (peers, request) =
+            tokio::join!(client.instance_peers(), server.respond("200 OK", "[]"));
+        assert!(peers.unwrap().is_empty());
+        assert!(request.contains("x-test: counted"), "{request}");
+        assert_eq!(COUNTER.responses.load(Ordering::SeqCst), 1);
+
+        let client = Mastodon::builder(server.data())
+            .middleware(Deny)
+            .build()
+            .unwrap();
+        let Err(Error::Other(message)) = client.instance_peers().await else {
+            panic!("expected the middleware's error")
+        };
+        assert_eq!(message, "denied");
+    }
+}
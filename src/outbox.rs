@@ -0,0 +1,358 @@
+use std::{
+    collections::VecDeque,
+    io,
+    path::{Path, PathBuf},
+};
+
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    entities::status::{NewStatus, Status},
+    errors::{Error, Result},
+    Mastodon,
+};
+
+/// A queue of statuses waiting to be posted, saved to a file so that they
+/// survive restarts, for clients whose connection to the instance is
+/// unreliable.
+///
+/// [`Outbox::new_status`] posts a status right away if it can, and queues
+/// it if the instance can't be reached or fails with a server error.
+/// Queued statuses are posted by [`Outbox::flush`], in the order they were
+/// queued, each with the `Idempotency-Key` it was first sent with, so that a
+/// status which did reach the instance before the connection failed isn't
+/// posted twice, as long as it's flushed within the hour the server
+/// remembers keys for.
+///
+/// // Example
+///
+/// ```no_run
+/// use mastodon_async::{outbox::{Outbox, Outcome}, prelude::*};
+///
+/// tokio_test::block_on(async {
+///     let client = Mastodon::from(Data::default());
+///     let mut outbox = Outbox::open("outbox.json").await.unwrap();
+///     let status = NewStatusBuilder::default().status("hello").build().unwrap();
+///     let (outcome, _) = outbox.new_status(&client, status).await.unwrap();
+///     match outcome {
+///         Outcome::Posted(status) => println!("posted {}", status.id),
+///         Outcome::Queued { .. } => println!("{} statuses waiting", outbox.len()),
+///     }
+///     // later, e.g. once the network is back
+///     let flushed = outbox.flush(&client).await.unwrap();
+///     println!("posted {} queued statuses", flushed.posted.len());
+/// });
+/// ```
+#[derive(Debug)]
+pub struct Outbox {
+    path: PathBuf,
+    entries: VecDeque<Entry>,
+}
+
+/// A status waiting in an [`Outbox`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Entry {
+    /// The key the status is posted with, which the server uses to
+    /// recognize repeated attempts to post it.
+    pub idempotency_key: String,
+    /// The status to post.
+    pub status: NewStatus,
+}
+
+/// What happened to a status passed to [`Outbox::new_status`].
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    /// The status was posted.
+    Posted(Status),
+    /// The status was queued, to be posted by [`Outbox::flush`].
+    Queued {
+        /// The key the status will be posted with.
+        idempotency_key: String,
+    },
+}
+
+/// The result of [`Outbox::flush`], or of the flush done by
+/// [`Outbox::new_status`].
+#[derive(Debug, Default)]
+pub struct Flushed {
+    /// The statuses which were posted, in the order they were queued.
+    pub posted: Vec<Status>,
+    /// The queued statuses which the instance rejected, e.g. because they
+    /// were invalid, and which were removed from the outbox rather than
+    /// retried, with the errors they were rejected with.
+    pub rejected: Vec<(Entry, Error)>,
+}
+
+impl Outbox {
+    /// Open the outbox saved at `path`, or an empty one if there's no file
+    /// there yet. The file is only written once a status is queued.
+    pub async fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let entries = match tokio::fs::read(&path).await {
+            Ok(contents) => serde_json::from_slice(&contents)?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => VecDeque::new(),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(Self { path, entries })
+    }
+
+    /// The file the outbox is saved to.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// How many statuses are waiting to be posted.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no statuses are waiting to be posted.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The statuses waiting to be posted, oldest first.
+    pub fn entries(&self) -> impl Iterator<Item = &Entry> {
+        self.entries.iter()
+    }
+
+    /// Queue a status to be posted by [`flush`](Self::flush), without
+    /// trying to post it now, returning the key it will be posted with.
+    pub async fn enqueue(&mut self, status: NewStatus) -> Result<String> {
+        let idempotency_key = Uuid::new_v4().to_string();
+        self.push(Entry {
+            idempotency_key: idempotency_key.clone(),
+            status,
+        })
+        .await?;
+        Ok(idempotency_key)
+    }
+
+    /// Post a status, or queue it if the instance can't be reached or
+    /// fails with a server error. If statuses are already waiting, they're
+    /// flushed first, and the status is queued behind any which still
+    /// can't be posted, so that statuses are always posted in order. The
+    /// result of that flush is returned along with what happened to the
+    /// status.
+    ///
+    /// Errors which retrying wouldn't fix, e.g. an invalid status, are
+    /// returned rather than queued.
+    pub async fn new_status(
+        &mut self,
+        client: &Mastodon,
+        status: NewStatus,
+    ) -> Result<(Outcome, Flushed)> {
+        let flushed = if self.is_empty() {
+            Flushed::default()
+        } else {
+            self.flush(client).await?
+        };
+        let idempotency_key = Uuid::new_v4().to_string();
+        if !self.is_empty() {
+            return Ok((self.queue(idempotency_key, status).await?, flushed));
+        }
+        let outcome = match client
+            .new_status_with_idempotency_key(status.clone(), &idempotency_key)
+            .await
+        {
+            Ok(posted) => Outcome::Posted(posted),
+            Err(err) if is_transient(&err) => {
+                warn!(err:? = err; "queueing status which couldn't be posted");
+                self.queue(idempotency_key, status).await?
+            }
+            Err(err) => return Err(err),
+        };
+        Ok((outcome, flushed))
+    }
+
+    async fn queue(&mut self, idempotency_key: String, status: NewStatus) -> Result<Outcome> {
+        self.push(Entry {
+            idempotency_key: idempotency_key.clone(),
+            status,
+        })
+        .await?;
+        Ok(Outcome::Queued { idempotency_key })
+    }
+
+    /// Post the queued statuses, oldest first, until they've all been
+    /// posted or one can't be posted yet because the instance can't be
+    /// reached or fails with a server error. Statuses the instance rejects
+    /// are removed and returned in [`Flushed::rejected`].
+    ///
+    /// The outbox is saved after each status, so that a status is never
+    /// lost or posted twice if the program stops while flushing.
+    pub async fn flush(&mut self, client: &Mastodon) -> Result<Flushed> {
+        let mut flushed = Flushed::default();
+        while let Some(entry) = self.entries.front() {
+            debug!(idempotency_key = entry.idempotency_key; "posting queued status");
+            let result = client
+                .new_status_with_idempotency_key(entry.status.clone(), &entry.idempotency_key)
+                .await;
+            match result {
+                Err(err) if is_transient(&err) => {
+                    warn!(err:? = err, pending = self.entries.len(); "stopped flushing the outbox");
+                    break;
+                }
+                Ok(posted) => {
+                    self.entries.pop_front();
+                    flushed.posted.push(posted);
+                }
+                Err(err) => {
+                    let entry = self.entries.pop_front().expect("front entry");
+                    flushed.rejected.push((entry, err));
+                }
+            }
+            self.save().await?;
+        }
+        Ok(flushed)
+    }
+
+    async fn push(&mut self, entry: Entry) -> Result<()> {
+        self.entries.push_back(entry);
+        if let Err(err) = self.save().await {
+            self.entries.pop_back();
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// Save the outbox, replacing the file atomically so that it's never
+    /// left half written.
+    async fn save(&self) -> Result<()> {
+        let contents = serde_json::to_vec(&self.entries)?;
+        let mut temporary = self.path.clone().into_os_string();
+        temporary.push(".tmp");
+        tokio::fs::write(&temporary, contents).await?;
+        tokio::fs::rename(&temporary, &self.path).await?;
+        Ok(())
+    }
+}
+
+/// Whether posting a status which failed with `err` is worth retrying
+/// later, i.e. the instance couldn't be reached or failed with a server
+/// error.
+fn is_transient(err: &Error) -> bool {
+    match err {
+        Error::Http(err) => err.is_connect() || err.is_timeout(),
+        Error::Io(_) | Error::RateLimited { .. } => true,
+        Error::Api { status, .. } => status.is_server_error(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    use reqwest::StatusCode;
+
+    use super::*;
+    use crate::{
+        entities::prelude::NewStatusBuilder,
+        helpers::fixtures::{self, Fake, Reply},
+        thread::tests::status,
+    };
+
+    /// A fake answering posted statuses with a 503 while `down` is set,
+    /// and rejecting statuses without text otherwise.
+    fn instance(down: Arc<AtomicBool>) -> Arc<Fake> {
+        Fake::new(move |request| {
+            let body = request.body().and_then(|body| body.as_bytes()).unwrap();
+            let new_status: NewStatus = serde_json::from_slice(body).unwrap();
+            if down.load(Ordering::SeqCst) {
+                Reply::status(StatusCode::SERVICE_UNAVAILABLE, "{}")
+            } else if let Some(text) = new_status.status {
+                Reply::ok(serde_json::to_string(&status(&text, None, 0)).unwrap())
+            } else {
+                Reply::status(
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    r#"{"error":"Validation failed"}"#,
+                )
+            }
+        })
+    }
+
+    fn new_status(text: &str) -> NewStatus {
+        NewStatusBuilder::default().status(text).build().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_queue_and_flush_in_order() {
+        let down = Arc::new(AtomicBool::new(false));
+        let instance = instance(down.clone());
+        let client = fixtures::client(instance.clone());
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("outbox.json");
+        let mut outbox = Outbox::open(&path).await.unwrap();
+        assert!(outbox.is_empty());
+        assert!(!path.exists());
+
+        down.store(true, Ordering::SeqCst);
+        let (Outcome::Queued { idempotency_key }, _) =
+            outbox.new_status(&client, new_status("1")).await.unwrap()
+        else {
+            panic!("expected the status to be queued")
+        };
+        outbox.enqueue(NewStatus::default()).await.unwrap();
+        down.store(false, Ordering::SeqCst);
+
+        // reopened, as if after a restart
+        let mut outbox = Outbox::open(&path).await.unwrap();
+        assert_eq!(outbox.len(), 2);
+        let (Outcome::Posted(posted), flushed) =
+            outbox.new_status(&client, new_status("2")).await.unwrap()
+        else {
+            panic!("expected the status to be posted")
+        };
+        assert_eq!(posted.id.as_ref(), "2");
+        assert_eq!(flushed.posted.len(), 1);
+        assert_eq!(flushed.rejected.len(), 1);
+        assert!(flushed.rejected[0].0.status.status.is_none());
+        assert!(outbox.is_empty());
+        // the first status was sent again with the same idempotency key
+        let sent: Vec<(String, Option<String>)> = instance
+            .requests()
+            .iter()
+            .map(|request| {
+                let key = request.headers["Idempotency-Key"].to_str().unwrap();
+                (key.to_string(), request.json::<NewStatus>().status)
+            })
+            .collect();
+        assert_eq!(sent.len(), 4);
+        assert_eq!(sent[0], (idempotency_key.clone(), Some("1".to_string())));
+        assert_eq!(sent[1], (idempotency_key, Some("1".to_string())));
+        assert_eq!(sent[2].1, None);
+        assert_eq!(sent[3].1, Some("2".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_flush() {
+        let down = Arc::new(AtomicBool::new(false));
+        let instance = instance(down.clone());
+        let client = fixtures::client(instance.clone());
+        let dir = tempfile::tempdir().unwrap();
+        let mut outbox = Outbox::open(dir.path().join("outbox.json")).await.unwrap();
+        outbox.enqueue(new_status("1")).await.unwrap();
+        outbox.enqueue(NewStatus::default()).await.unwrap();
+        outbox.enqueue(new_status("3")).await.unwrap();
+
+        down.store(true, Ordering::SeqCst);
+        let flushed = outbox.flush(&client).await.unwrap();
+        assert!(flushed.posted.is_empty());
+        assert_eq!(outbox.len(), 3);
+
+        down.store(false, Ordering::SeqCst);
+        let flushed = outbox.flush(&client).await.unwrap();
+        let ids: Vec<&str> = flushed.posted.iter().map(|s| s.id.as_ref()).collect();
+        assert_eq!(ids, ["1", "3"]);
+        assert_eq!(flushed.rejected.len(), 1);
+        assert!(flushed.rejected[0].0.status.status.is_none());
+        assert!(outbox.is_empty());
+    }
+}
@@ -1,9 +1,13 @@
 use super::{Mastodon, Result};
 use crate::{entities::itemsiter::ItemsIter, helpers::read_response::read_response, Error};
-use futures::Stream;
+use futures::{stream::unfold, Stream};
 use log::{debug, error, trace};
-use reqwest::{header::LINK, Response, Url};
+use reqwest::{
+    header::{HeaderMap, LINK},
+    Response, Url,
+};
 use serde::{Deserialize, Serialize};
+use std::{future::Future, pin::Pin, task::Poll};
 use uuid::Uuid;
 
 macro_rules! pages {
@@ -32,10 +36,10 @@ macro_rules! pages {
                     "making API request"
                 );
                 let url: String = url.to_string();
-                let response = self.mastodon.authenticated(self.mastodon.client.get(&url)).send().await?;
+                let response = self.mastodon.send(self.mastodon.authenticated(self.mastodon.client.get(&url))).await?;
                 match response.error_for_status() {
                     Ok(response) => {
-                        let (prev, next) = get_links(&response, self.call_id)?;
+                        let (prev, next) = get_links(response.headers(), self.call_id)?;
                         let response: Vec<T> = read_response(response).await?;
                         if response.is_empty() && prev.is_none() && next.is_none() {
                             debug!(
@@ -73,8 +77,9 @@ macro_rules! pages {
     }
 }
 
-/// Owned version of the `Page` struct in this module. Allows this to be more
-/// easily stored for later use
+/// Represents a single page of API results
+///
+/// `Page` is owned, which allows it to be easily stored for later use
 ///
 /// // Example
 ///
@@ -100,8 +105,6 @@ macro_rules! pages {
 ///     };
 /// });
 /// ```
-
-/// Represents a single page of API results
 #[derive(Debug, Clone)]
 pub struct Page<T: for<'de> Deserialize<'de> + Serialize> {
     mastodon: Mastodon,
@@ -114,7 +117,7 @@ pub struct Page<T: for<'de> Deserialize<'de> + Serialize> {
     pub(crate) call_id: Uuid,
 }
 
-impl<'a, T: for<'de> Deserialize<'de> + Serialize> Page<T> {
+impl<T: for<'de> Deserialize<'de> + Serialize> Page<T> {
     pages! {
         next: next_page,
         prev: prev_page
@@ -124,7 +127,7 @@ impl<'a, T: for<'de> Deserialize<'de> + Serialize> Page<T> {
     pub(crate) async fn new(mastodon: Mastodon, response: Response, call_id: Uuid) -> Result<Self> {
         let status = response.status();
         if status.is_success() {
-            let (prev, next) = get_links(&response, call_id)?;
+            let (prev, next) = get_links(response.headers(), call_id)?;
             let initial_items = read_response(response).await?;
             debug!(
                 initial_items:serde = initial_items, prev:? = prev,
@@ -139,10 +142,210 @@ impl<'a, T: for<'de> Deserialize<'de> + Serialize> Page<T> {
                 call_id,
             })
         } else {
-            let response = response.json().await?;
-            Err(Error::Api { status, response })
+            Err(Error::from_error_response(response).await)
         }
     }
+
+    /// The `max_id` of the next (older) page, if there is one. Store this to
+    /// continue paging backwards later, e.g. with a request's `max_id`.
+    pub fn next_max_id(&self) -> Option<String> {
+        query_param(self.next.as_ref()?, &["max_id"])
+    }
+
+    /// The `min_id` (or `since_id`) of the previous (newer) page, if there is
+    /// one. Store this to check for newer items later, e.g. with a request's
+    /// `min_id`.
+    pub fn prev_min_id(&self) -> Option<String> {
+        query_param(self.prev.as_ref()?, &["min_id", "since_id"])
+    }
+
+    /// Collect the items on this page and those on following pages, until an
+    /// item matches `stop` or the `budget` is used up. The item which
+    /// matched isn't included.
+    ///
+    /// // Example
+    ///
+    /// ```no_run
+    /// use mastodon_async::{page::Budget, prelude::*};
+    /// use time::{Duration, OffsetDateTime};
+    ///
+    /// let mastodon = Mastodon::from(Data::default());
+    ///
+    /// tokio_test::block_on(async {
+    ///     let cutoff = OffsetDateTime::now_utc() - Duration::days(7);
+    ///     let last_week = mastodon
+    ///         .get_home_timeline()
+    ///         .await
+    ///         .unwrap()
+    ///         .backfill_until(Budget { pages: Some(20), ..Budget::default() }, |status| {
+    ///             status.created_at < cutoff
+    ///         })
+    ///         .await
+    ///         .unwrap();
+    /// });
+    /// ```
+    pub async fn backfill_until(
+        mut self,
+        budget: Budget,
+        mut stop: impl FnMut(&T) -> bool,
+    ) -> Result<Vec<T>> {
+        let mut collected = vec![];
+        let mut items = std::mem::take(&mut self.initial_items);
+        let mut pages = 1;
+        loop {
+            for item in items {
+                if stop(&item) {
+                    debug!(pages = pages, items = collected.len(), call_id:? = self.call_id; "backfill stopped by predicate");
+                    return Ok(collected);
+                }
+                collected.push(item);
+                if budget.items.is_some_and(|max| collected.len() >= max) {
+                    debug!(pages = pages, items = collected.len(), call_id:? = self.call_id; "backfill item budget exhausted");
+                    return Ok(collected);
+                }
+            }
+            if budget.pages.is_some_and(|max| pages >= max) {
+                debug!(pages = pages, items = collected.len(), call_id:? = self.call_id; "backfill page budget exhausted");
+                return Ok(collected);
+            }
+            match self.next_page().await? {
+                Some(next_items) if !next_items.is_empty() => {
+                    items = next_items;
+                    pages += 1;
+                }
+                _ => return Ok(collected),
+            }
+        }
+    }
+
+    /// Returns a stream of the items on this page followed by those on each
+    /// following page, which are only requested once the items before them
+    /// have been consumed.
+    ///
+    /// Unlike [`Page::items_iter`], errors fetching a page are yielded rather
+    /// than silently ending the stream. The stream ends after yielding an
+    /// error.
+    ///
+    /// // Example
+    ///
+    /// ```no_run
+    /// use mastodon_async::prelude::*;
+    /// use futures_util::TryStreamExt;
+    ///
+    /// let mastodon = Mastodon::from(Data::default());
+    ///
+    /// tokio_test::block_on(async {
+    ///     let followers: Vec<Account> = mastodon
+    ///         .followers(&AccountId::new("some-id"))
+    ///         .await
+    ///         .unwrap()
+    ///         .items_stream()
+    ///         .try_filter(|account| std::future::ready(!account.bot))
+    ///         .try_collect()
+    ///         .await
+    ///         .unwrap();
+    /// });
+    /// ```
+    pub fn items_stream(mut self) -> impl Stream<Item = Result<T>> {
+        let items = std::mem::take(&mut self.initial_items).into_iter();
+        unfold(Some((self, items)), |state| async move {
+            let (mut page, mut items) = state?;
+            loop {
+                if let Some(item) = items.next() {
+                    return Some((Ok(item), Some((page, items))));
+                }
+                match page.next_page().await {
+                    Ok(Some(next_items)) if !next_items.is_empty() => {
+                        items = next_items.into_iter();
+                    }
+                    Ok(_) => return None,
+                    Err(err) => return Some((Err(err), None)),
+                }
+            }
+        })
+    }
+}
+
+impl<T: for<'de> Deserialize<'de> + Serialize> Page<T> {
+    /// Like [`Page::items_stream`], but each following page is requested as
+    /// soon as the items before it start being consumed, rather than once
+    /// they all have been, so that consumers going through every page wait
+    /// less for each one. At most one page is fetched ahead, and the request
+    /// makes progress whenever the stream is polled.
+    ///
+    /// Requests for the pages which are fetched ahead go through the same
+    /// [`Throttle`](crate::rate_limit::Throttle) and middleware as any other,
+    /// but a page may be fetched which is never consumed, if the stream is
+    /// dropped before reaching it.
+    ///
+    /// // Example
+    ///
+    /// ```no_run
+    /// use mastodon_async::prelude::*;
+    /// use futures_util::TryStreamExt;
+    ///
+    /// let mastodon = Mastodon::from(Data::default());
+    ///
+    /// tokio_test::block_on(async {
+    ///     let following: Vec<Account> = mastodon
+    ///         .following(&AccountId::new("some-id"))
+    ///         .await
+    ///         .unwrap()
+    ///         .items_stream_prefetched()
+    ///         .try_collect()
+    ///         .await
+    ///         .unwrap();
+    /// });
+    /// ```
+    pub fn items_stream_prefetched(mut self) -> impl Stream<Item = Result<T>> {
+        let state = Prefetch {
+            items: std::mem::take(&mut self.initial_items).into_iter(),
+            pending: Some(Box::pin(fetch_next(self))),
+            fetched: None,
+        };
+        unfold(state, |mut state| async move {
+            loop {
+                if let Some(item) = state.items.next() {
+                    if let Some(pending) = &mut state.pending {
+                        if let Poll::Ready(fetched) = futures::poll!(pending.as_mut()) {
+                            state.pending = None;
+                            state.fetched = Some(fetched);
+                        }
+                    }
+                    return Some((Ok(item), state));
+                }
+                let (page, next_items) = match state.fetched.take() {
+                    Some(fetched) => fetched,
+                    None => state.pending.take()?.await,
+                };
+                match next_items {
+                    Ok(Some(next_items)) if !next_items.is_empty() => {
+                        state.items = next_items.into_iter();
+                        state.pending = Some(Box::pin(fetch_next(page)));
+                    }
+                    Ok(_) => return None,
+                    Err(err) => return Some((Err(err), state)),
+                }
+            }
+        })
+    }
+}
+
+/// The state of [`Page::items_stream_prefetched`]: the items left on the
+/// current page, and the request for the next one, or its result once it
+/// completed.
+struct Prefetch<I, F: Future> {
+    items: I,
+    pending: Option<Pin<Box<F>>>,
+    fetched: Option<F::Output>,
+}
+
+/// Fetch the page after `page`, returning `page` along with it.
+async fn fetch_next<T: for<'de> Deserialize<'de> + Serialize>(
+    mut page: Page<T>,
+) -> (Page<T>, Result<Option<Vec<T>>>) {
+    let next_items = page.next_page().await;
+    (page, next_items)
 }
 
 impl<T: Clone + for<'de> Deserialize<'de> + Serialize> Page<T> {
@@ -156,6 +359,9 @@ impl<T: Clone + for<'de> Deserialize<'de> + Serialize> Page<T> {
     /// more of them, until
     /// there are no more items.
     ///
+    /// Errors fetching a page end the stream without being reported; use
+    /// [`Page::items_stream`] to handle them.
+    ///
     /// // Example
     ///
     /// ```no_run
@@ -178,11 +384,34 @@ impl<T: Clone + for<'de> Deserialize<'de> + Serialize> Page<T> {
     }
 }
 
-fn get_links(response: &Response, call_id: Uuid) -> Result<(Option<Url>, Option<Url>)> {
+/// Limits on how much [`Page::backfill_until`] fetches. `None` means no
+/// limit.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Budget {
+    /// The most pages to fetch, including the first one.
+    pub pages: Option<usize>,
+    /// The most items to collect.
+    pub items: Option<usize>,
+}
+
+/// The value of the first of `keys` found in the query of `url`.
+fn query_param(url: &Url, keys: &[&str]) -> Option<String> {
+    keys.iter().find_map(|key| {
+        url.query_pairs()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.into_owned())
+    })
+}
+
+/// Read the `next` and `prev` URLs out of a `Link` header. These are followed
+/// verbatim rather than rebuilt from `max_id`/`min_id`, since some endpoints
+/// (e.g. favourites and bookmarks) page by internal IDs which don't match
+/// those of the returned entities.
+fn get_links(headers: &HeaderMap, call_id: Uuid) -> Result<(Option<Url>, Option<Url>)> {
     let mut prev = None;
     let mut next = None;
 
-    if let Some(link_header) = response.headers().get(LINK) {
+    if let Some(link_header) = headers.get(LINK) {
         let link_header = link_header.to_str()?;
         let raw_link_header = link_header.to_string();
         trace!(link_header = link_header, call_id:? = call_id; "parsing link header");
@@ -204,3 +433,154 @@ fn get_links(response: &Response, call_id: Uuid) -> Result<(Option<Url>, Option<
 
     Ok((prev, next))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::fixtures::{self, Fake, Reply, Server};
+    use futures::{StreamExt, TryStreamExt};
+
+    fn page(server: &Server) -> Page<u32> {
+        let base = server.base();
+        Page {
+            mastodon: Mastodon::from(server.data()),
+            next: Some(
+                format!("{base}/api/v1/timelines/home?max_id=2")
+                    .parse()
+                    .unwrap(),
+            ),
+            prev: None,
+            initial_items: vec![1, 2],
+            call_id: Uuid::new_v4(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cursors() {
+        let server = Server::bind().await;
+        let mut page = page(&server);
+        assert_eq!(page.next_max_id().as_deref(), Some("2"));
+        assert_eq!(page.prev_min_id(), None);
+        page.prev = Some(
+            "https://example.com/api/v1/timelines/home?since_id=7&limit=20"
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(page.prev_min_id().as_deref(), Some("7"));
+    }
+
+    #[tokio::test]
+    async fn test_items_stream_follows_pages() {
+        let server = Server::bind().await;
+        let page = page(&server);
+        let (items, _) = futures::join!(
+            page.items_stream().try_collect::<Vec<_>>(),
+            server.respond("200 OK", "[3, 4]")
+        );
+        assert_eq!(items.unwrap(), vec![1, 2, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_items_stream_prefetched() {
+        let pages = Fake::new(|_| Reply::ok("[3, 4]"));
+        let page = Page {
+            mastodon: fixtures::client(pages.clone()),
+            next: Some(
+                "https://mastodon.example/api/v1/timelines/home?max_id=2"
+                    .parse()
+                    .unwrap(),
+            ),
+            prev: None,
+            initial_items: vec![1, 2],
+            call_id: Uuid::new_v4(),
+        };
+        let items = page.items_stream_prefetched();
+        futures::pin_mut!(items);
+        assert_eq!(items.next().await.unwrap().unwrap(), 1);
+        // the second page was fetched while the first was being consumed
+        assert_eq!(
+            pages.urls(),
+            ["https://mastodon.example/api/v1/timelines/home?max_id=2"]
+        );
+        let rest: Vec<u32> = items.try_collect().await.unwrap();
+        assert_eq!(rest, vec![2, 3, 4]);
+        // the last page has no link to another
+        assert_eq!(pages.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_backfill_until_predicate() {
+        let server = Server::bind().await;
+        let page = page(&server);
+        let (items, _) = futures::join!(
+            page.backfill_until(Budget::default(), |item| *item == 4),
+            server.respond("200 OK", "[3, 4, 5]")
+        );
+        assert_eq!(items.unwrap(), vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_backfill_until_budget() {
+        let server = Server::bind().await;
+        let items = page(&server)
+            .backfill_until(
+                Budget {
+                    pages: Some(1),
+                    ..Budget::default()
+                },
+                |_| false,
+            )
+            .await;
+        assert_eq!(items.unwrap(), vec![1, 2]);
+        let items = page(&server)
+            .backfill_until(
+                Budget {
+                    items: Some(1),
+                    ..Budget::default()
+                },
+                |_| false,
+            )
+            .await;
+        assert_eq!(items.unwrap(), vec![1]);
+    }
+
+    #[tokio::test]
+    async fn test_items_stream_yields_errors() {
+        let server = Server::bind().await;
+        let page = page(&server);
+        let (items, _) = futures::join!(
+            page.items_stream().collect::<Vec<_>>(),
+            server.respond("500 Internal Server Error", "{}")
+        );
+        assert_eq!(items.len(), 3);
+        assert!(items[2].is_err());
+    }
+
+    #[test]
+    fn test_get_links_follows_opaque_ids() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            LINK,
+            "<https://example.com/api/v1/favourites?max_id=21626>; rel=\"next\", \
+             <https://example.com/api/v1/favourites?min_id=21650>; rel=\"prev\""
+                .parse()
+                .unwrap(),
+        );
+        let (prev, next) = get_links(&headers, Uuid::new_v4()).expect("links");
+        assert_eq!(
+            next.unwrap().as_str(),
+            "https://example.com/api/v1/favourites?max_id=21626"
+        );
+        assert_eq!(
+            prev.unwrap().as_str(),
+            "https://example.com/api/v1/favourites?min_id=21650"
+        );
+    }
+
+    #[test]
+    fn test_get_links_without_header() {
+        let (prev, next) = get_links(&HeaderMap::new(), Uuid::new_v4()).expect("links");
+        assert!(prev.is_none());
+        assert!(next.is_none());
+    }
+}
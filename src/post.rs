@@ -0,0 +1,260 @@
+use std::path::PathBuf;
+
+use log::warn;
+use uuid::Uuid;
+
+use crate::{
+    entities::{attachment::ProcessedAttachment, status::Status},
+    errors::Error,
+    polling_time::PollingTime,
+    Mastodon, NewStatus,
+};
+
+/// Posts a status with media attached, as returned by [`Mastodon::post_with_media`]:
+/// uploads each file, waits for the server to process it, and posts the
+/// status with the media attached.
+///
+/// If some of the media can't be uploaded, the status isn't posted, and
+/// [`publish`](Self::publish) fails with [`PostError::Media`]. The media
+/// which was uploaded is kept, so publishing again only uploads the files
+/// which failed. The status is posted with the same `Idempotency-Key` each
+/// time, so that it isn't posted twice if publishing again after posting it
+/// failed.
+///
+/// // Example
+///
+/// ```no_run
+/// use mastodon_async::{post::PostError, prelude::*};
+///
+/// tokio_test::block_on(async {
+///     let client = Mastodon::from(Data::default());
+///     let status = NewStatusBuilder::default().status("look at my cat").build().unwrap();
+///     let mut post = client.post_with_media(status);
+///     post.media("cat.jpg", Some("A cat asleep on a keyboard".into()))
+///         .media("cat.mp4", Some("The cat waking up".into()));
+///     match post.publish().await {
+///         Ok(status) => println!("posted {}", status.id),
+///         Err(PostError::Media { failed }) => {
+///             for (path, err) in failed {
+///                 eprintln!("couldn't upload {}: {err}", path.display());
+///             }
+///         }
+///         Err(err) => eprintln!("{err}"),
+///     }
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct PostBuilder {
+    client: Mastodon,
+    status: NewStatus,
+    media: Vec<Media>,
+    polling_time: PollingTime,
+    idempotency_key: String,
+}
+
+/// A file to attach, and the attachment once it's uploaded.
+#[derive(Debug, Clone)]
+struct Media {
+    path: PathBuf,
+    description: Option<String>,
+    uploaded: Option<ProcessedAttachment>,
+}
+
+/// Why [`PostBuilder::publish`] failed.
+#[derive(Debug, thiserror::Error)]
+pub enum PostError {
+    /// Some of the media couldn't be uploaded or processed, so the status
+    /// wasn't posted.
+    #[error("{} of the files couldn't be uploaded", .failed.len())]
+    Media {
+        /// The files which couldn't be uploaded, in the order they were
+        /// added, with why.
+        failed: Vec<(PathBuf, Error)>,
+    },
+    /// The media was uploaded, but the status couldn't be posted.
+    #[error("the status couldn't be posted")]
+    Status(#[source] Error),
+}
+
+impl PostBuilder {
+    /// Post `status`, with no media attached yet.
+    pub fn new(client: Mastodon, status: NewStatus) -> Self {
+        Self {
+            client,
+            status,
+            media: vec![],
+            polling_time: PollingTime::default(),
+            idempotency_key: Uuid::new_v4().to_string(),
+        }
+    }
+
+    /// Attach the file at `path`, with a description for people who can't
+    /// see or hear it. Media is attached in the order it's added.
+    pub fn media(&mut self, path: impl Into<PathBuf>, description: Option<String>) -> &mut Self {
+        self.media.push(Media {
+            path: path.into(),
+            description,
+            uploaded: None,
+        });
+        self
+    }
+
+    /// How long to wait between checks on whether the server is done
+    /// processing the media. Defaults to half a second.
+    pub fn polling_time(&mut self, polling_time: PollingTime) -> &mut Self {
+        self.polling_time = polling_time;
+        self
+    }
+
+    /// The media which was uploaded, in the order it was added.
+    pub fn uploaded(&self) -> impl Iterator<Item = &ProcessedAttachment> {
+        self.media
+            .iter()
+            .filter_map(|media| media.uploaded.as_ref())
+    }
+
+    /// Upload the media which hasn't been yet, one file at a time, wait for
+    /// it to be processed, and post the status with it attached, after any
+    /// media the status already had.
+    pub async fn publish(&mut self) -> Result<Status, PostError> {
+        let client = &self.client;
+        let polling_time = self.polling_time;
+        let mut failed = vec![];
+        for media in self
+            .media
+            .iter_mut()
+            .filter(|media| media.uploaded.is_none())
+        {
+            let uploaded = async {
+                let attachment = client.media(&media.path, media.description.clone()).await?;
+                client.wait_for_processing(attachment, polling_time).await
+            }
+            .await;
+            match uploaded {
+                Ok(uploaded) => media.uploaded = Some(uploaded),
+                Err(err) => {
+                    warn!(path:? = media.path, err:? = err; "failed to upload media");
+                    failed.push((media.path.clone(), err));
+                }
+            }
+        }
+        if !failed.is_empty() {
+            return Err(PostError::Media { failed });
+        }
+
+        let mut status = self.status.clone();
+        if !self.media.is_empty() {
+            let ids = status.media_ids.get_or_insert_with(Vec::new);
+            ids.extend(self.uploaded().map(|attachment| attachment.id.clone()));
+        }
+        self.client
+            .new_status_with_idempotency_key(status, &self.idempotency_key)
+            .await
+            .map_err(PostError::Status)
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    };
+
+    use reqwest::{Method, StatusCode};
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        entities::prelude::NewStatusBuilder,
+        helpers::fixtures::{self, Fake, Recorded, Reply},
+        thread::tests::status,
+    };
+
+    fn attachment(id: &str, processed: bool) -> String {
+        let url = processed.then(|| format!("https://files.mastodon.example/{id}.png"));
+        json!({
+            "id": id,
+            "type": "image",
+            "url": url,
+            "preview_url": format!("https://files.mastodon.example/small/{id}.png"),
+            "remote_url": null,
+            "description": null,
+            "blurhash": null
+        })
+        .to_string()
+    }
+
+    /// A fake failing the second upload, and answering the others with
+    /// attachments which are processed once they're fetched again.
+    fn instance() -> Arc<Fake> {
+        let uploads = AtomicU32::new(0);
+        Fake::new(
+            move |request| match (request.method(), request.url().path()) {
+                (&Method::POST, "/api/v2/media") => {
+                    match uploads.fetch_add(1, Ordering::SeqCst) + 1 {
+                        2 => Reply::status(StatusCode::INTERNAL_SERVER_ERROR, "{}"),
+                        upload => Reply::status(
+                            StatusCode::ACCEPTED,
+                            attachment(&upload.to_string(), false),
+                        ),
+                    }
+                }
+                (&Method::GET, path) => {
+                    let id = path.trim_start_matches("/api/v1/media/");
+                    Reply::ok(attachment(id, true))
+                }
+                (&Method::POST, "/api/v1/statuses") => {
+                    Reply::ok(serde_json::to_string(&status("100", None, 0)).unwrap())
+                }
+                _ => Reply::not_found(),
+            },
+        )
+    }
+
+    /// The requests `instance` received to `method` `path`.
+    fn requests(instance: &Fake, method: Method, path: &str) -> Vec<Recorded> {
+        instance
+            .requests()
+            .into_iter()
+            .filter(|request| request.method == method && request.url.path() == path)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_publish_after_failed_upload() {
+        let instance = instance();
+        let client = fixtures::client(instance.clone());
+        let dir = tempfile::tempdir().unwrap();
+        let first = dir.path().join("first.png");
+        let second = dir.path().join("second.png");
+        std::fs::write(&first, "first").unwrap();
+        std::fs::write(&second, "second").unwrap();
+
+        let status = NewStatusBuilder::default()
+            .status("two pictures")
+            .build()
+            .unwrap();
+        let mut post = client.post_with_media(status);
+        post.media(&first, Some("the first".into()))
+            .media(&second, None)
+            .polling_time(std::time::Duration::from_millis(1).into());
+        let Err(PostError::Media { failed }) = post.publish().await else {
+            panic!("expected the second upload to fail")
+        };
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].0, second);
+        assert_eq!(post.uploaded().count(), 1);
+        assert!(requests(&instance, Method::POST, "/api/v1/statuses").is_empty());
+
+        let posted = post.publish().await.unwrap();
+        assert_eq!(posted.id.as_ref(), "100");
+        assert_eq!(requests(&instance, Method::POST, "/api/v2/media").len(), 3);
+        let posted = requests(&instance, Method::POST, "/api/v1/statuses");
+        assert_eq!(posted.len(), 1);
+        assert_eq!(posted[0].headers["Idempotency-Key"], post.idempotency_key);
+        let body: serde_json::Value = posted[0].json();
+        assert_eq!(body["media_ids"], json!(["1", "3"]));
+        assert_eq!(body["status"], "two pictures");
+    }
+}
@@ -0,0 +1,240 @@
+use std::time::Duration;
+
+use reqwest::{header::HeaderMap, Method};
+use serde::{Deserialize, Serialize};
+use time::{format_description::well_known::Iso8601, serde::iso8601, OffsetDateTime};
+
+/// The state of a rate limit, as reported by the `X-RateLimit-*` headers of a
+/// response.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/api/rate-limits/)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimit {
+    /// The number of requests permitted per time period.
+    pub limit: u64,
+    /// The number of requests which can still be made in this time period.
+    pub remaining: u64,
+    /// When the rate limit resets.
+    #[serde(with = "iso8601::option")]
+    pub reset: Option<OffsetDateTime>,
+}
+
+impl RateLimit {
+    /// Read the rate limit out of the headers of a response, if they include
+    /// one.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let header = |name| headers.get(name)?.to_str().ok();
+        let limit = header("X-RateLimit-Limit")?.trim().parse().ok()?;
+        let remaining = header("X-RateLimit-Remaining")?.trim().parse().ok()?;
+        let reset = header("X-RateLimit-Reset")
+            .and_then(|reset| OffsetDateTime::parse(reset.trim(), &Iso8601::DEFAULT).ok());
+        Some(RateLimit {
+            limit,
+            remaining,
+            reset,
+        })
+    }
+}
+
+/// Groups of requests which the server counts against separate rate limits.
+///
+/// See also [the API documentation](https://docs.joinmastodon.org/api/rate-limits/#per-endpoint)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Bucket {
+    /// Requests which only count against the overall limit.
+    Default,
+    /// Media uploads.
+    Media,
+    /// Deleting statuses and undoing reblogs.
+    StatusDeletion,
+}
+
+impl Bucket {
+    /// The bucket which a request with the given method and path counts
+    /// against.
+    pub fn of(method: &Method, path: &str) -> Self {
+        let path = path.trim_end_matches('/');
+        if *method == Method::POST && (path == "/api/v1/media" || path == "/api/v2/media") {
+            Bucket::Media
+        } else if let Some(status) = path.strip_prefix("/api/v1/statuses/") {
+            match (method, status.split_once('/')) {
+                (&Method::DELETE, None) | (&Method::POST, Some((_, "unreblog"))) => {
+                    Bucket::StatusDeletion
+                }
+                _ => Bucket::Default,
+            }
+        } else {
+            Bucket::Default
+        }
+    }
+}
+
+/// Options for delaying requests which would otherwise exceed the rate limit,
+/// for use with [`MastodonBuilder::throttle`](crate::builder::MastodonBuilder::throttle).
+///
+/// // Example
+///
+/// ```
+/// use std::time::Duration;
+/// use mastodon_async::rate_limit::Throttle;
+/// let mut throttle = Throttle::new();
+/// throttle.reserve(10).max_delay(Duration::from_secs(60));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Throttle {
+    reserve: u64,
+    max_delay: Option<Duration>,
+}
+
+impl Default for Throttle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Throttle {
+    /// Delay requests once no more than one remains in a rate limit, until
+    /// the rate limit resets.
+    pub fn new() -> Self {
+        Self {
+            reserve: 1,
+            max_delay: None,
+        }
+    }
+
+    /// Delay requests once no more than `reserve` requests remain in a rate
+    /// limit, leaving them for other clients using the same account.
+    pub fn reserve(&mut self, reserve: u64) -> &mut Self {
+        self.reserve = reserve;
+        self
+    }
+
+    /// The longest time to delay a request. Once it has passed the request
+    /// is sent, even if the rate limit hasn't reset yet.
+    pub fn max_delay(&mut self, delay: Duration) -> &mut Self {
+        self.max_delay = Some(delay);
+        self
+    }
+
+    /// How long to wait before sending a request, given the last known state
+    /// of its rate limit. A request which can be sent right away is counted
+    /// against `state`, so that concurrent requests don't all use up the
+    /// last of the rate limit.
+    pub(crate) fn delay(&self, state: &mut RateLimit, now: OffsetDateTime) -> Option<Duration> {
+        if state.remaining > self.reserve {
+            state.remaining -= 1;
+            return None;
+        }
+        let reset = state.reset?;
+        if reset <= now {
+            // The period has passed since the state was last updated, so
+            // the full limit is available again.
+            state.remaining = state.limit.saturating_sub(1);
+            state.reset = None;
+            return None;
+        }
+        let delay = Duration::try_from(reset - now).ok()?;
+        Some(match self.max_delay {
+            Some(max_delay) => delay.min(max_delay),
+            None => delay,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::{Date, Month};
+
+    #[test]
+    fn test_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-RateLimit-Limit", "300".parse().unwrap());
+        headers.insert("X-RateLimit-Remaining", "299".parse().unwrap());
+        headers.insert(
+            "X-RateLimit-Reset",
+            "2022-11-15T21:00:00.000Z".parse().unwrap(),
+        );
+        let rate_limit = RateLimit::from_headers(&headers).expect("rate limit");
+        assert_eq!(rate_limit.limit, 300);
+        assert_eq!(rate_limit.remaining, 299);
+        let reset = Date::from_calendar_date(2022, Month::November, 15)
+            .unwrap()
+            .with_hms(21, 0, 0)
+            .unwrap()
+            .assume_utc();
+        assert_eq!(rate_limit.reset, Some(reset));
+    }
+
+    #[test]
+    fn test_missing_headers() {
+        assert!(RateLimit::from_headers(&HeaderMap::new()).is_none());
+        let mut headers = HeaderMap::new();
+        headers.insert("X-RateLimit-Limit", "300".parse().unwrap());
+        headers.insert("X-RateLimit-Remaining", "lots".parse().unwrap());
+        assert!(RateLimit::from_headers(&headers).is_none());
+    }
+
+    #[test]
+    fn test_bucket() {
+        assert_eq!(Bucket::of(&Method::POST, "/api/v2/media"), Bucket::Media);
+        assert_eq!(Bucket::of(&Method::POST, "/api/v1/media/"), Bucket::Media);
+        assert_eq!(Bucket::of(&Method::GET, "/api/v1/media"), Bucket::Default);
+        assert_eq!(
+            Bucket::of(&Method::DELETE, "/api/v1/statuses/1234"),
+            Bucket::StatusDeletion
+        );
+        assert_eq!(
+            Bucket::of(&Method::POST, "/api/v1/statuses/1234/unreblog"),
+            Bucket::StatusDeletion
+        );
+        assert_eq!(
+            Bucket::of(&Method::POST, "/api/v1/statuses/1234/reblog"),
+            Bucket::Default
+        );
+        assert_eq!(
+            Bucket::of(&Method::GET, "/api/v1/statuses/1234"),
+            Bucket::Default
+        );
+    }
+
+    #[test]
+    fn test_throttle_delay() {
+        let now = OffsetDateTime::now_utc();
+        let mut state = RateLimit {
+            limit: 300,
+            remaining: 3,
+            reset: Some(now + time::Duration::seconds(30)),
+        };
+        let mut throttle = Throttle::new();
+        throttle.reserve(2);
+        assert_eq!(throttle.delay(&mut state, now), None);
+        assert_eq!(state.remaining, 2);
+        assert_eq!(
+            throttle.delay(&mut state, now),
+            Some(Duration::from_secs(30))
+        );
+        throttle.max_delay(Duration::from_secs(10));
+        assert_eq!(
+            throttle.delay(&mut state, now),
+            Some(Duration::from_secs(10))
+        );
+        let later = now + time::Duration::seconds(31);
+        assert_eq!(throttle.delay(&mut state, later), None);
+        assert_eq!(state.remaining, 299);
+        assert_eq!(state.reset, None);
+    }
+
+    #[test]
+    fn test_throttle_without_reset() {
+        let mut state = RateLimit {
+            limit: 300,
+            remaining: 0,
+            reset: None,
+        };
+        assert_eq!(
+            Throttle::new().delay(&mut state, OffsetDateTime::now_utc()),
+            None
+        );
+    }
+}
@@ -1,6 +1,8 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use log::{debug, error, trace};
 use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 use reqwest::Client;
+use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
 use crate::{
@@ -18,6 +20,7 @@ pub struct Registration {
     client: Client,
     app_builder: forms::ApplicationBuilder,
     force_login: bool,
+    pkce: bool,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -32,9 +35,22 @@ fn default_redirect_uri() -> String {
     DEFAULT_REDIRECT_URI.to_string()
 }
 
+/// Generate a random PKCE code verifier, 43 characters long.
+fn generate_code_verifier() -> Result<String> {
+    let mut bytes = [0u8; 32];
+    getrandom::getrandom(&mut bytes).map_err(std::io::Error::from)?;
+    Ok(URL_SAFE_NO_PAD.encode(bytes))
+}
+
+/// The S256 PKCE code challenge for a code verifier.
+fn code_challenge(code_verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+}
+
 #[derive(Serialize, Deserialize)]
 struct AccessToken {
     access_token: String,
+    scope: Option<Scopes>,
 }
 
 impl Registration {
@@ -62,6 +78,7 @@ impl Registration {
             client,
             app_builder: forms::ApplicationBuilder::default(),
             force_login: false,
+            pkce: false,
         }
     }
 }
@@ -74,6 +91,7 @@ impl Registration {
             client: Client::new(),
             app_builder: forms::ApplicationBuilder::default(),
             force_login: false,
+            pkce: false,
         }
     }
 
@@ -113,6 +131,15 @@ impl Registration {
         self
     }
 
+    /// Use PKCE (Proof Key for Code Exchange) to secure the authorization
+    /// code, as recommended for apps which can't keep their client secret
+    /// confidential, like desktop and mobile apps. Requires Mastodon 4.3 or
+    /// later.
+    pub fn pkce(&mut self, pkce: bool) -> &mut Self {
+        self.pkce = pkce;
+        self
+    }
+
     /// Register the given application
     ///
     /// ```no_run
@@ -150,6 +177,7 @@ impl Registration {
             redirect: oauth.redirect_uri,
             scopes: app.scopes().clone(),
             force_login: self.force_login,
+            code_verifier: self.code_verifier()?,
         })
     }
 
@@ -185,9 +213,18 @@ impl Registration {
             redirect: oauth.redirect_uri,
             scopes: app.scopes().clone(),
             force_login: self.force_login,
+            code_verifier: self.code_verifier()?,
         })
     }
 
+    fn code_verifier(&self) -> Result<Option<String>> {
+        if self.pkce {
+            generate_code_verifier().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
     async fn send_app(&self, app: &forms::Application) -> Result<OAuth> {
         let url = format!("{}/api/v1/apps", self.base);
         let call_id = Uuid::new_v4();
@@ -260,8 +297,28 @@ impl Registered {
             redirect: redirect.to_string(),
             scopes,
             force_login,
+            code_verifier: None,
         }
     }
+
+    /// Use the given PKCE code verifier when authorizing, e.g. to resume an
+    /// authorization started with [`Registration::pkce`] after saving its
+    /// [`code_verifier`](Registered::code_verifier).
+    pub fn with_code_verifier(mut self, code_verifier: impl Into<String>) -> Registered {
+        self.code_verifier = Some(code_verifier.into());
+        self
+    }
+
+    /// Where the user is redirected to after authorizing the app.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn redirect(&self) -> &str {
+        &self.redirect
+    }
+
+    /// The PKCE code verifier used when authorizing, if PKCE is enabled.
+    pub fn code_verifier(&self) -> Option<&str> {
+        self.code_verifier.as_deref()
+    }
 }
 
 impl Registered {
@@ -314,7 +371,7 @@ impl Registered {
     pub fn authorize_url(&self) -> Result<String> {
         let scopes = format!("{}", self.scopes);
         let scopes: String = utf8_percent_encode(&scopes, NON_ALPHANUMERIC).collect();
-        let url = if self.force_login {
+        let mut url = if self.force_login {
             format!(
                 "{}/oauth/authorize?client_id={}&redirect_uri={}&scope={}&force_login=true&\
                  response_type=code",
@@ -326,18 +383,23 @@ impl Registered {
                 self.base, self.client_id, self.redirect, scopes,
             )
         };
+        if let Some(code_verifier) = &self.code_verifier {
+            url += "&code_challenge_method=S256&code_challenge=";
+            url += &code_challenge(code_verifier);
+        }
 
         Ok(url)
     }
 
     /// Construct authentication data once token is known
-    fn registered(&self, token: String) -> Data {
+    fn registered(&self, token: String, scopes: Option<Scopes>) -> Data {
         Data {
             base: self.base.clone().into(),
             client_id: self.client_id.clone().into(),
             client_secret: self.client_secret.clone().into(),
             redirect: self.redirect.clone().into(),
             token: token.into(),
+            scopes,
         }
     }
 
@@ -347,12 +409,16 @@ impl Registered {
     where
         C: AsRef<str>,
     {
-        let url =
+        let mut url =
             format!(
             "{}/oauth/token?client_id={}&client_secret={}&code={}&grant_type=authorization_code&\
              redirect_uri={}",
             self.base, self.client_id, self.client_secret, code.as_ref(), self.redirect
         );
+        if let Some(code_verifier) = &self.code_verifier {
+            url += "&code_verifier=";
+            url += code_verifier;
+        }
         debug!(url = url; "completing registration");
         let response = self.client.post(&url).send().await?;
         debug!(
@@ -362,7 +428,7 @@ impl Registered {
         );
         let token: AccessToken = read_response(response).await?;
         debug!(url = url, body:serde = token; "parsed response body");
-        let data = self.registered(token.access_token);
+        let data = self.registered(token.access_token, token.scope);
         trace!(auth_data:serde = data; "registered");
 
         Ok(Mastodon::new(self.client.clone(), data))
@@ -380,6 +446,7 @@ pub struct Registered {
     redirect: String,
     scopes: Scopes,
     force_login: bool,
+    code_verifier: Option<String>,
 }
 
 #[cfg(test)]
@@ -448,6 +515,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_code_challenge() {
+        // From RFC 7636, appendix B
+        assert_eq!(
+            code_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+
+    #[test]
+    fn test_pkce_authorize_url() {
+        let mut r = Registration::new("https://example.com");
+        r.pkce(true);
+        let code_verifier = r.code_verifier().unwrap().expect("code verifier");
+        assert_eq!(code_verifier.len(), 43);
+        let registered = Registered::from_parts(
+            "https://example.com",
+            "client-id",
+            "client-secret",
+            DEFAULT_REDIRECT_URI,
+            Scopes::read_all(),
+            false,
+        )
+        .with_code_verifier(code_verifier.clone());
+        let url = registered.authorize_url().unwrap();
+        assert!(url.ends_with(&format!(
+            "&code_challenge_method=S256&code_challenge={}",
+            code_challenge(&code_verifier)
+        )));
+    }
+
     #[test]
     fn test_default_redirect_uri() {
         assert_eq!(&default_redirect_uri()[..], DEFAULT_REDIRECT_URI);
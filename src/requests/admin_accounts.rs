@@ -0,0 +1,225 @@
+use super::push_array;
+use crate::{
+    entities::ids::{AccountId, RoleId},
+    errors::Error,
+};
+use serde::Serialize;
+use std::borrow::Cow;
+
+/// Whether an account is local to the server or federated from another one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AccountOrigin {
+    /// Accounts on this server
+    Local,
+    /// Accounts on other servers
+    Remote,
+}
+
+/// The moderation state of an account.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AccountStatus {
+    /// Accounts in good standing
+    Active,
+    /// Accounts waiting for their registration to be approved
+    Pending,
+    /// Accounts whose login has been disabled
+    Disabled,
+    /// Accounts which have been silenced
+    Silenced,
+    /// Accounts which have been suspended
+    Suspended,
+}
+
+/// Filter accounts by the permissions of their role.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AccountPermissions {
+    /// Accounts with any role which grants moderation permissions
+    Staff,
+}
+
+/// Builder for making a client.admin_accounts() call
+///
+/// // Example
+///
+/// ```
+/// use mastodon_async::requests::{AccountOrigin, AccountStatus, AdminAccountsRequest};
+/// use mastodon_async::entities::ids::RoleId;
+///
+/// let mut request = AdminAccountsRequest::new();
+/// request
+///     .origin(AccountOrigin::Local)
+///     .status(AccountStatus::Pending)
+///     .role_ids([RoleId::new(1), RoleId::new(2)]);
+/// assert_eq!(
+///     &request.to_query_string().expect("Couldn't serialize qs")[..],
+///     "?origin=local&status=pending&role_ids[]=1&role_ids[]=2"
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct AdminAccountsRequest<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    origin: Option<AccountOrigin>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<AccountStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    permissions: Option<AccountPermissions>,
+    #[serde(skip)]
+    role_ids: Vec<RoleId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    invited_by: Option<AccountId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    username: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    display_name: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    by_domain: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    email: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ip: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_id: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    since_id: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_id: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    limit: Option<usize>,
+}
+
+impl<'a> AdminAccountsRequest<'a> {
+    /// Construct a new `AdminAccountsRequest`, which matches all accounts
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only return local or remote accounts
+    pub fn origin(&mut self, origin: AccountOrigin) -> &mut Self {
+        self.origin = Some(origin);
+        self
+    }
+
+    /// Only return accounts in the given moderation state
+    pub fn status(&mut self, status: AccountStatus) -> &mut Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Only return accounts whose role grants the given permissions
+    pub fn permissions(&mut self, permissions: AccountPermissions) -> &mut Self {
+        self.permissions = Some(permissions);
+        self
+    }
+
+    /// Only return accounts with one of the given roles
+    pub fn role_ids(&mut self, role_ids: impl IntoIterator<Item = RoleId>) -> &mut Self {
+        self.role_ids.extend(role_ids);
+        self
+    }
+
+    /// Only return accounts invited by the given account
+    pub fn invited_by(&mut self, account_id: &AccountId) -> &mut Self {
+        self.invited_by = Some(account_id.clone());
+        self
+    }
+
+    /// Only return accounts whose username contains this string
+    pub fn username(&mut self, username: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.username = Some(username.into());
+        self
+    }
+
+    /// Only return accounts whose display name contains this string
+    pub fn display_name(&mut self, display_name: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.display_name = Some(display_name.into());
+        self
+    }
+
+    /// Only return accounts on the given domain
+    pub fn by_domain(&mut self, domain: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.by_domain = Some(domain.into());
+        self
+    }
+
+    /// Only return accounts whose email address contains this string
+    pub fn email(&mut self, email: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.email = Some(email.into());
+        self
+    }
+
+    /// Only return accounts which have used this IP address
+    pub fn ip(&mut self, ip: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.ip = Some(ip.into());
+        self
+    }
+
+    /// Only return accounts older than this ID
+    pub fn max_id(&mut self, max_id: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.max_id = Some(max_id.into());
+        self
+    }
+
+    /// Only return accounts newer than this ID
+    pub fn since_id(&mut self, since_id: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.since_id = Some(since_id.into());
+        self
+    }
+
+    /// Only return accounts immediately newer than this ID
+    pub fn min_id(&mut self, min_id: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.min_id = Some(min_id.into());
+        self
+    }
+
+    /// Maximum number of accounts to return
+    pub fn limit(&mut self, limit: usize) -> &mut Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Serialize into a query string
+    pub fn to_query_string(&self) -> Result<String, Error> {
+        let mut qs = serde_urlencoded::to_string(self)?;
+        push_array(&mut qs, "role_ids", &self.role_ids)?;
+        Ok(format!("?{qs}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let request = AdminAccountsRequest::new();
+        assert_eq!(request.to_query_string().unwrap(), "?");
+    }
+
+    #[test]
+    fn test_filters() {
+        let mut request = AdminAccountsRequest::new();
+        request
+            .origin(AccountOrigin::Remote)
+            .permissions(AccountPermissions::Staff)
+            .invited_by(&AccountId::new("42"))
+            .by_domain("example.com")
+            .email("@spam.example")
+            .ip("192.168.0.0/16")
+            .limit(5);
+        assert_eq!(
+            request.to_query_string().unwrap(),
+            "?origin=remote&permissions=staff&invited_by=42&by_domain=example.com\
+             &email=%40spam.example&ip=192.168.0.0%2F16&limit=5"
+        );
+    }
+
+    #[test]
+    fn test_role_ids_only() {
+        let mut request = AdminAccountsRequest::new();
+        request.role_ids([RoleId::new(3)]);
+        assert_eq!(request.to_query_string().unwrap(), "?role_ids[]=3");
+    }
+}
@@ -0,0 +1,101 @@
+use super::push_array;
+use crate::{errors::Error, Language};
+use serde::Serialize;
+
+/// Builder for making a client.follow_with() call
+///
+/// // Example
+///
+/// ```
+/// use mastodon_async::{requests::FollowRequest, Language};
+///
+/// let mut request = FollowRequest::new();
+/// request.reblogs(false).notify(true).languages([Language::Eng, Language::Deu]);
+/// assert_eq!(
+///     &request.to_query_string().expect("Couldn't serialize qs")[..],
+///     "?reblogs=false&notify=true&languages[]=en&languages[]=de"
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct FollowRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reblogs: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notify: Option<bool>,
+    #[serde(skip)]
+    languages: Vec<Language>,
+}
+
+impl FollowRequest {
+    /// Construct a new `FollowRequest`. By default the followed account's
+    /// boosts are shown, no notifications are sent for its posts, and posts
+    /// in all languages are shown.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether boosts from the followed account are shown in the home
+    /// timeline
+    pub fn reblogs(&mut self, reblogs: bool) -> &mut Self {
+        self.reblogs = Some(reblogs);
+        self
+    }
+
+    /// Set whether to receive a notification each time the followed account
+    /// posts
+    pub fn notify(&mut self, notify: bool) -> &mut Self {
+        self.notify = Some(notify);
+        self
+    }
+
+    /// Only show posts from the followed account in the given languages in
+    /// the home timeline
+    pub fn languages(&mut self, languages: impl IntoIterator<Item = Language>) -> &mut Self {
+        self.languages.extend(languages);
+        self
+    }
+
+    /// Serialize into a query string
+    pub fn to_query_string(&self) -> Result<String, Error> {
+        let mut qs = serde_urlencoded::to_string(self)?;
+        let languages = self
+            .languages
+            .iter()
+            .map(|lang| lang.to_639_1().unwrap_or_else(|| lang.to_639_3()));
+        push_array(&mut qs, "languages", languages)?;
+        Ok(format!("?{qs}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let request = FollowRequest::new();
+        assert_eq!(
+            request,
+            FollowRequest {
+                reblogs: None,
+                notify: None,
+                languages: vec![],
+            }
+        );
+        assert_eq!(request.to_query_string().unwrap(), "?");
+    }
+
+    #[test]
+    fn test_languages() {
+        let mut request = FollowRequest::new();
+        request.languages([Language::Fra]);
+        assert_eq!(request.to_query_string().unwrap(), "?languages[]=fr");
+    }
+
+    #[test]
+    fn test_languages_without_639_1_code() {
+        let mut request = FollowRequest::new();
+        request.languages([Language::Gsw]);
+        assert_eq!(request.to_query_string().unwrap(), "?languages[]=gsw");
+    }
+}
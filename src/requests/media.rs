@@ -0,0 +1,104 @@
+use std::path::{Path, PathBuf};
+
+use crate::entities::attachment::FocalPoint;
+
+/// Builder to pass to the Mastodon::update_media method
+///
+/// // Example
+///
+/// ```no_run
+/// use mastodon_async::{prelude::*, requests::UpdateMediaRequest};
+/// use mastodon_async::entities::attachment::FocalPoint;
+///
+/// tokio_test::block_on(async {
+///     let client = Mastodon::from(Data::default());
+///
+///     let mut request = UpdateMediaRequest::new();
+///     request
+///         .description("A cat sleeping in a sunbeam")
+///         .focus(FocalPoint::new(-0.5, 0.25));
+///     client
+///         .update_media(&AttachmentId::new("12345"), request)
+///         .await
+///         .unwrap();
+/// });
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct UpdateMediaRequest {
+    pub(crate) description: Option<String>,
+    pub(crate) focus: Option<FocalPoint>,
+    pub(crate) thumbnail: Option<PathBuf>,
+}
+
+impl UpdateMediaRequest {
+    /// Construct a new, empty `UpdateMediaRequest`. Fields which aren't set
+    /// are left unchanged on the server.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the alt-text of the attachment.
+    pub fn description(&mut self, description: impl Into<String>) -> &mut Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the point on the image which should remain in view when it's
+    /// cropped for previews.
+    ///
+    /// // Example
+    ///
+    /// ```
+    /// use mastodon_async::{entities::attachment::Focus, requests::UpdateMediaRequest};
+    ///
+    /// let mut request = UpdateMediaRequest::new();
+    /// request.focus(Focus { x: 0.0, y: 1.0 });
+    /// ```
+    pub fn focus(&mut self, focus: FocalPoint) -> &mut Self {
+        self.focus = Some(focus);
+        self
+    }
+
+    /// Upload a new custom thumbnail for the attachment, replacing the one
+    /// previously generated or uploaded.
+    pub fn thumbnail(&mut self, path: impl AsRef<Path>) -> &mut Self {
+        self.thumbnail = Some(path.as_ref().to_path_buf());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let request = UpdateMediaRequest::new();
+        assert_eq!(
+            request,
+            UpdateMediaRequest {
+                description: None,
+                focus: None,
+                thumbnail: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_all_fields() {
+        let mut request = UpdateMediaRequest::new();
+        request
+            .description("alt text")
+            .focus(FocalPoint::new(0.5, -0.25))
+            .thumbnail("/tmp/thumb.png");
+        assert_eq!(
+            request,
+            UpdateMediaRequest {
+                description: Some("alt text".to_string()),
+                focus: Some(FocalPoint { x: 0.5, y: -0.25 }),
+                thumbnail: Some(PathBuf::from("/tmp/thumb.png")),
+            }
+        );
+        assert_eq!(request.focus.unwrap().to_string(), "0.5,-0.25");
+    }
+}
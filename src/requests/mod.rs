@@ -1,7 +1,83 @@
+use crate::errors::Error;
+use serde::Serialize;
+
+/// Data structures for the MastodonClient::admin_accounts method
+pub use self::admin_accounts::{
+    AccountOrigin, AccountPermissions, AccountStatus, AdminAccountsRequest,
+};
+/// Data structure for the MastodonClient::follow_with method
+pub use self::follow::FollowRequest;
+/// Data structure for the MastodonClient::update_media method
+pub use self::media::UpdateMediaRequest;
+/// Data structure for the MastodonClient::mute_with method
+pub use self::mute::MuteRequest;
+/// Data structures for the MastodonClient::notifications_with and
+/// MastodonClient::grouped_notifications methods
+pub use self::notifications::{ExpandAccounts, GroupedNotificationsRequest, NotificationsRequest};
+/// Data structure for the MastodonClient::public_timeline method
+pub use self::public_timeline::PublicTimelineRequest;
 /// Data structure for the MastodonClient::add_push_subscription method
 pub use self::push::{AddPushRequest, Keys, UpdatePushRequest};
+/// Data structure for the MastodonClient::report_with method
+pub use self::report::ReportRequest;
+/// Data structure for the MastodonClient::search_with method
+pub use self::search::{SearchRequest, SearchType};
 /// Data structure for the MastodonClient::statuses method
 pub use self::statuses::StatusesRequest;
+/// Data structure for the MastodonClient::home_timeline_with method
+pub use self::timeline::TimelineRequest;
+/// Data structure for the MastodonClient::update_credentials method
+pub use self::update_credentials::UpdateCredentialsRequest;
+
+/// Whether `b` is false, for skipping flags which aren't set when
+/// serializing a request.
+pub(crate) fn is_false(b: &bool) -> bool {
+    !*b
+}
+
+/// Append `name[]=value` to the query string `qs` for each of `values`,
+/// URL-encoded, since `serde_urlencoded` can't serialize sequences.
+pub(crate) fn push_array<T: Serialize>(
+    qs: &mut String,
+    name: &str,
+    values: impl IntoIterator<Item = T>,
+) -> Result<(), Error> {
+    for value in values {
+        if !qs.is_empty() {
+            qs.push('&');
+        }
+        *qs += name;
+        *qs += "[]";
+        // serializes as "=<value>", since the key is empty
+        *qs += &serde_urlencoded::to_string([("", value)])?;
+    }
+    Ok(())
+}
 
+mod admin_accounts;
+mod follow;
+mod media;
+mod mute;
+mod notifications;
+mod public_timeline;
 mod push;
+mod report;
+mod search;
 mod statuses;
+mod timeline;
+mod update_credentials;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_array() {
+        let mut qs = String::new();
+        push_array(&mut qs, "id", ["1", "a&b=c"]).unwrap();
+        assert_eq!(qs, "id[]=1&id[]=a%26b%3Dc");
+        push_array(&mut qs, "type", Vec::<&str>::new()).unwrap();
+        push_array(&mut qs, "type", ["#tag"]).unwrap();
+        assert_eq!(qs, "id[]=1&id[]=a%26b%3Dc&type[]=%23tag");
+    }
+}
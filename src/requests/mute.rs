@@ -0,0 +1,97 @@
+use crate::errors::Error;
+use serde::{Serialize, Serializer};
+use std::time::Duration;
+
+/// Builder for making a client.mute_with() call
+///
+/// // Example
+///
+/// ```
+/// use mastodon_async::requests::MuteRequest;
+/// use std::time::Duration;
+///
+/// let mut request = MuteRequest::new();
+/// request.notifications(false).duration(Duration::from_secs(60 * 60 * 24));
+/// assert_eq!(
+///     &request.to_query_string().expect("Couldn't serialize qs")[..],
+///     "?notifications=false&duration=86400"
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct MuteRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notifications: Option<bool>,
+    #[serde(
+        serialize_with = "serialize_duration_as_seconds",
+        skip_serializing_if = "Option::is_none"
+    )]
+    duration: Option<Duration>,
+}
+
+fn serialize_duration_as_seconds<S: Serializer>(
+    duration: &Option<Duration>,
+    s: S,
+) -> Result<S::Ok, S::Error> {
+    match duration {
+        Some(duration) => s.serialize_u64(duration.as_secs()),
+        None => s.serialize_none(),
+    }
+}
+
+impl MuteRequest {
+    /// Construct a new `MuteRequest`. By default the mute also hides
+    /// notifications from the account, and lasts indefinitely.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether notifications from the muted account are also hidden
+    pub fn notifications(&mut self, notifications: bool) -> &mut Self {
+        self.notifications = Some(notifications);
+        self
+    }
+
+    /// Set how long the mute should last, to the nearest second. A duration
+    /// of zero mutes indefinitely.
+    pub fn duration(&mut self, duration: Duration) -> &mut Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// Serialize into a query string
+    pub fn to_query_string(&self) -> Result<String, Error> {
+        Ok(format!("?{}", serde_urlencoded::to_string(self)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let request = MuteRequest::new();
+        assert_eq!(
+            request,
+            MuteRequest {
+                notifications: None,
+                duration: None,
+            }
+        );
+        assert_eq!(request.to_query_string().unwrap(), "?");
+    }
+
+    #[test]
+    fn test_duration() {
+        let mut request = MuteRequest::new();
+        request.duration(Duration::from_millis(90_500));
+        assert_eq!(
+            request,
+            MuteRequest {
+                notifications: None,
+                duration: Some(Duration::from_millis(90_500)),
+            }
+        );
+        assert_eq!(request.to_query_string().unwrap(), "?duration=90");
+    }
+}
@@ -0,0 +1,320 @@
+use super::push_array;
+use crate::{entities::notification::Type, errors::Error};
+use serde::Serialize;
+use std::borrow::Cow;
+
+/// Builder for making a client.notifications_with() call
+///
+/// // Example
+///
+/// ```
+/// use mastodon_async::{entities::notification::Type, requests::NotificationsRequest};
+/// let mut request = NotificationsRequest::new();
+/// request.types([Type::Mention]).limit(10);
+/// assert_eq!(
+///     &request.to_query_string().expect("Couldn't serialize qs")[..],
+///     "?limit=10&types[]=mention"
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct NotificationsRequest<'a> {
+    #[serde(skip)]
+    types: Vec<Type>,
+    #[serde(skip)]
+    exclude_types: Vec<Type>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account_id: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_id: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    since_id: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_id: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    limit: Option<usize>,
+}
+
+impl<'a> NotificationsRequest<'a> {
+    /// Construct a new `NotificationsRequest` object
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only return notifications of the given types
+    pub fn types(&mut self, types: impl IntoIterator<Item = Type>) -> &mut Self {
+        self.types.extend(types);
+        self
+    }
+
+    /// Don't return notifications of the given types
+    ///
+    /// // Example
+    ///
+    /// ```
+    /// use mastodon_async::{entities::notification::Type, requests::NotificationsRequest};
+    /// let mut request = NotificationsRequest::new();
+    /// request.exclude_types([Type::Favourite, Type::Reblog]);
+    /// assert_eq!(
+    ///     &request.to_query_string().expect("Couldn't serialize qs")[..],
+    ///     "?exclude_types[]=favourite&exclude_types[]=reblog"
+    /// );
+    /// ```
+    pub fn exclude_types(&mut self, types: impl IntoIterator<Item = Type>) -> &mut Self {
+        self.exclude_types.extend(types);
+        self
+    }
+
+    /// Only return notifications received from the given account
+    pub fn account_id(&mut self, account_id: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.account_id = Some(account_id.into());
+        self
+    }
+
+    /// Only return notifications older than this ID
+    pub fn max_id(&mut self, max_id: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.max_id = Some(max_id.into());
+        self
+    }
+
+    /// Only return notifications newer than this ID
+    pub fn since_id(&mut self, since_id: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.since_id = Some(since_id.into());
+        self
+    }
+
+    /// Only return notifications immediately newer than this ID
+    pub fn min_id(&mut self, min_id: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.min_id = Some(min_id.into());
+        self
+    }
+
+    /// Maximum number of notifications to return
+    pub fn limit(&mut self, limit: usize) -> &mut Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Serialize into a query string
+    pub fn to_query_string(&self) -> Result<String, Error> {
+        let mut qs = serde_urlencoded::to_string(self)?;
+        push_array(&mut qs, "types", &self.types)?;
+        push_array(&mut qs, "exclude_types", &self.exclude_types)?;
+        Ok(format!("?{qs}"))
+    }
+}
+
+/// How much of each account to include in a grouped notifications response.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExpandAccounts {
+    /// Full [`Account`](crate::entities::account::Account) objects.
+    Full,
+    /// Only enough of each account to render an avatar, in the response's
+    /// `partial_accounts` list.
+    PartialAvatars,
+}
+
+/// Builder for making a client.grouped_notifications() call
+///
+/// // Example
+///
+/// ```
+/// use mastodon_async::{entities::notification::Type, requests::GroupedNotificationsRequest};
+/// let mut request = GroupedNotificationsRequest::new();
+/// request
+///     .grouped_types([Type::Favourite, Type::Reblog])
+///     .exclude_types([Type::Follow]);
+/// assert_eq!(
+///     &request.to_query_string().expect("Couldn't serialize qs")[..],
+///     "?exclude_types[]=follow&grouped_types[]=favourite&grouped_types[]=reblog"
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct GroupedNotificationsRequest<'a> {
+    #[serde(skip)]
+    types: Vec<Type>,
+    #[serde(skip)]
+    exclude_types: Vec<Type>,
+    #[serde(skip)]
+    grouped_types: Vec<Type>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account_id: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expand_accounts: Option<ExpandAccounts>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    include_filtered: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_id: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    since_id: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_id: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    limit: Option<usize>,
+}
+
+impl<'a> GroupedNotificationsRequest<'a> {
+    /// Construct a new `GroupedNotificationsRequest` object
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only return notifications of the given types
+    pub fn types(&mut self, types: impl IntoIterator<Item = Type>) -> &mut Self {
+        self.types.extend(types);
+        self
+    }
+
+    /// Don't return notifications of the given types
+    pub fn exclude_types(&mut self, types: impl IntoIterator<Item = Type>) -> &mut Self {
+        self.exclude_types.extend(types);
+        self
+    }
+
+    /// Group notifications of these types. Notifications of other types are
+    /// returned as single-notification groups.
+    pub fn grouped_types(&mut self, types: impl IntoIterator<Item = Type>) -> &mut Self {
+        self.grouped_types.extend(types);
+        self
+    }
+
+    /// Only return notifications received from the given account
+    pub fn account_id(&mut self, account_id: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.account_id = Some(account_id.into());
+        self
+    }
+
+    /// Choose whether full or partial accounts are returned
+    pub fn expand_accounts(&mut self, expand_accounts: ExpandAccounts) -> &mut Self {
+        self.expand_accounts = Some(expand_accounts);
+        self
+    }
+
+    /// Also return notifications which were filtered by the notification
+    /// policy
+    pub fn include_filtered(&mut self, include_filtered: bool) -> &mut Self {
+        self.include_filtered = Some(include_filtered);
+        self
+    }
+
+    /// Only return notifications older than this ID. Pass the `page_min_id`
+    /// of the last group to fetch the next page.
+    pub fn max_id(&mut self, max_id: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.max_id = Some(max_id.into());
+        self
+    }
+
+    /// Only return notifications newer than this ID
+    pub fn since_id(&mut self, since_id: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.since_id = Some(since_id.into());
+        self
+    }
+
+    /// Only return notifications immediately newer than this ID
+    pub fn min_id(&mut self, min_id: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.min_id = Some(min_id.into());
+        self
+    }
+
+    /// Maximum number of groups to return
+    pub fn limit(&mut self, limit: usize) -> &mut Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Serialize into a query string
+    pub fn to_query_string(&self) -> Result<String, Error> {
+        let mut qs = serde_urlencoded::to_string(self)?;
+        push_array(&mut qs, "types", &self.types)?;
+        push_array(&mut qs, "exclude_types", &self.exclude_types)?;
+        push_array(&mut qs, "grouped_types", &self.grouped_types)?;
+        Ok(format!("?{qs}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let request = NotificationsRequest::new();
+        assert_eq!(
+            request,
+            NotificationsRequest {
+                types: vec![],
+                exclude_types: vec![],
+                account_id: None,
+                max_id: None,
+                since_id: None,
+                min_id: None,
+                limit: None,
+            }
+        );
+        assert_eq!(request.to_query_string().unwrap(), "?");
+    }
+
+    #[test]
+    fn test_all_fields() {
+        let mut request = NotificationsRequest::new();
+        request
+            .types([Type::Mention, Type::Status])
+            .exclude_types([Type::SignUp])
+            .account_id("42")
+            .max_id("9")
+            .since_id("1")
+            .min_id("2")
+            .limit(5);
+        assert_eq!(
+            request,
+            NotificationsRequest {
+                types: vec![Type::Mention, Type::Status],
+                exclude_types: vec![Type::SignUp],
+                account_id: Some("42".into()),
+                max_id: Some("9".into()),
+                since_id: Some("1".into()),
+                min_id: Some("2".into()),
+                limit: Some(5),
+            }
+        );
+        assert_eq!(
+            request.to_query_string().unwrap(),
+            "?account_id=42&max_id=9&since_id=1&min_id=2&limit=5\
+             &types[]=mention&types[]=status&exclude_types[]=admin.sign_up"
+        );
+    }
+
+    #[test]
+    fn test_grouped_all_fields() {
+        let mut request = GroupedNotificationsRequest::new();
+        request
+            .types([Type::Mention])
+            .exclude_types([Type::Poll])
+            .grouped_types([Type::Follow])
+            .account_id("42")
+            .expand_accounts(ExpandAccounts::PartialAvatars)
+            .include_filtered(true)
+            .max_id("9")
+            .limit(5);
+        assert_eq!(
+            request,
+            GroupedNotificationsRequest {
+                types: vec![Type::Mention],
+                exclude_types: vec![Type::Poll],
+                grouped_types: vec![Type::Follow],
+                account_id: Some("42".into()),
+                expand_accounts: Some(ExpandAccounts::PartialAvatars),
+                include_filtered: Some(true),
+                max_id: Some("9".into()),
+                since_id: None,
+                min_id: None,
+                limit: Some(5),
+            }
+        );
+        assert_eq!(
+            request.to_query_string().unwrap(),
+            "?account_id=42&expand_accounts=partial_avatars&include_filtered=true&max_id=9&limit=5\
+             &types[]=mention&exclude_types[]=poll&grouped_types[]=follow"
+        );
+    }
+}
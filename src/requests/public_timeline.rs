@@ -0,0 +1,119 @@
+use super::is_false;
+use crate::errors::Error;
+use serde::Serialize;
+use std::borrow::Cow;
+
+/// Builder for a client.public_timeline() call, choosing which statuses of
+/// the public timeline to fetch
+///
+/// // Example
+///
+/// ```
+/// use mastodon_async::requests::PublicTimelineRequest;
+/// let mut request = PublicTimelineRequest::new();
+/// request.local().only_media().limit(40);
+/// assert_eq!(
+///     &request.to_query_string().expect("Couldn't serialize qs")[..],
+///     "?local=true&only_media=true&limit=40"
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct PublicTimelineRequest<'a> {
+    #[serde(skip_serializing_if = "is_false")]
+    local: bool,
+    #[serde(skip_serializing_if = "is_false")]
+    remote: bool,
+    #[serde(skip_serializing_if = "is_false")]
+    only_media: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_id: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    since_id: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_id: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    limit: Option<usize>,
+}
+
+impl<'a> PublicTimelineRequest<'a> {
+    /// Construct a new `PublicTimelineRequest` object, for the whole
+    /// federated timeline
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only return statuses posted on this instance
+    pub fn local(&mut self) -> &mut Self {
+        self.local = true;
+        self
+    }
+
+    /// Only return statuses from other instances
+    pub fn remote(&mut self) -> &mut Self {
+        self.remote = true;
+        self
+    }
+
+    /// Only return statuses with media attachments
+    pub fn only_media(&mut self) -> &mut Self {
+        self.only_media = true;
+        self
+    }
+
+    /// Only return statuses older than this ID
+    pub fn max_id(&mut self, max_id: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.max_id = Some(max_id.into());
+        self
+    }
+
+    /// Only return statuses newer than this ID, starting from the newest
+    pub fn since_id(&mut self, since_id: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.since_id = Some(since_id.into());
+        self
+    }
+
+    /// Only return statuses immediately newer than this ID, starting from
+    /// the oldest
+    pub fn min_id(&mut self, min_id: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.min_id = Some(min_id.into());
+        self
+    }
+
+    /// Maximum number of statuses to return
+    pub fn limit(&mut self, limit: usize) -> &mut Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Serialize into a query string
+    pub fn to_query_string(&self) -> Result<String, Error> {
+        Ok(format!("?{}", serde_urlencoded::to_string(self)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        let request = PublicTimelineRequest::new();
+        assert_eq!(&request.to_query_string().expect("qs")[..], "?");
+    }
+
+    #[test]
+    fn test_all_fields() {
+        let mut request = PublicTimelineRequest::new();
+        request
+            .remote()
+            .only_media()
+            .max_id("3")
+            .since_id("1")
+            .min_id("2")
+            .limit(5);
+        assert_eq!(
+            &request.to_query_string().expect("qs")[..],
+            "?remote=true&only_media=true&max_id=3&since_id=1&min_id=2&limit=5"
+        );
+    }
+}
@@ -1,6 +1,9 @@
-use mastodon_async_entities::push::Alerts;
+use mastodon_async_entities::push::{Alerts, Policy};
 
-use crate::entities::push::{add_subscription, update_data};
+use crate::entities::{
+    push::{add_subscription, update_data},
+    SubscriptionId,
+};
 
 /// Container for the key & auth strings for an AddPushRequest
 ///
@@ -69,8 +72,10 @@ pub struct AddPushRequest {
 
     p256dh: String,
     auth: String,
+    standard: Option<bool>,
 
     alerts: Alerts,
+    policy: Option<Policy>,
 }
 
 impl AddPushRequest {
@@ -99,6 +104,22 @@ impl AddPushRequest {
         self
     }
 
+    /// Set whose notifications should be pushed, e.g. only those from
+    /// accounts the user follows.
+    pub fn policy(&mut self, policy: Policy) -> &mut Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// Set whether the notifications should be encrypted as standardized by
+    /// RFC 8291 and sent as RFC 8030 web push messages, rather than as in
+    /// the earlier draft Mastodon used before 4.4.0. Older servers ignore
+    /// it.
+    pub fn standard(&mut self, standard: bool) -> &mut Self {
+        self.standard = Some(standard);
+        self
+    }
+
     /// Build the form.
     pub fn build(&self) -> add_subscription::Form {
         use crate::entities::push::add_subscription::{Data, Form, Keys, Subscription};
@@ -109,13 +130,15 @@ impl AddPushRequest {
                     p256dh: self.p256dh.clone(),
                     auth: self.auth.clone(),
                 },
+                standard: self.standard,
             },
             data: None,
         };
 
-        if self.alerts.is_some() {
+        if self.alerts.is_some() || self.policy.is_some() {
             form.data = Some(Data {
-                alerts: Some(self.alerts),
+                alerts: self.alerts.is_some().then_some(self.alerts),
+                policy: self.policy,
             });
         }
 
@@ -150,10 +173,12 @@ impl AddPushRequest {
 ///     client.update_push_data(&request).await.unwrap();
 /// });
 /// ```
-#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct UpdatePushRequest {
-    id: String,
+    id: SubscriptionId,
     alerts: Alerts,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    policy: Option<Policy>,
 }
 
 impl UpdatePushRequest {
@@ -164,10 +189,11 @@ impl UpdatePushRequest {
     /// ```
     /// let request = mastodon_async::requests::UpdatePushRequest::new("some-id");
     /// ```
-    pub fn new(id: &str) -> UpdatePushRequest {
+    pub fn new(id: impl Into<SubscriptionId>) -> UpdatePushRequest {
         UpdatePushRequest {
-            id: id.to_string(),
-            ..Default::default()
+            id: id.into(),
+            alerts: Alerts::default(),
+            policy: None,
         }
     }
 
@@ -177,20 +203,25 @@ impl UpdatePushRequest {
         self
     }
 
+    /// Change whose notifications should be pushed.
+    pub fn policy(&mut self, policy: Policy) -> &mut Self {
+        self.policy = Some(policy);
+        self
+    }
+
     /// Build the form from the update
     pub fn build(&self) -> update_data::Form {
         use crate::entities::push::update_data::{Data, Form};
 
         let mut form = Form {
             id: self.id.clone(),
-            ..Default::default()
+            data: Data::default(),
         };
 
         if self.alerts.is_some() {
-            form.data = Data {
-                alerts: Some(self.alerts),
-            };
+            form.data.alerts = Some(self.alerts);
         }
+        form.data.policy = self.policy;
         form
     }
 }
@@ -200,7 +231,7 @@ mod tests {
     use mastodon_async_entities::push::AlertsBuilder;
 
     use super::*;
-    use crate::entities::push::{add_subscription, update_data, Alerts};
+    use crate::entities::push::{add_subscription, update_data, Alerts, Policy};
 
     #[test]
     fn test_keys_new() {
@@ -247,7 +278,8 @@ mod tests {
                         alerts: Alerts {
                             $set: Some(true),
                             ..Default::default()
-                        }
+                        },
+                        ..Default::default()
                     }
                 );
             }
@@ -261,6 +293,10 @@ mod tests {
         test_add_push_request_favourite, favourite;
         test_add_push_request_reblog, reblog;
         test_add_push_request_mention, mention;
+        test_add_push_request_status, status;
+        test_add_push_request_follow_request, follow_request;
+        test_add_push_request_poll, poll;
+        test_add_push_request_update, update;
     );
 
     #[test]
@@ -285,6 +321,7 @@ mod tests {
                         p256dh: "anetohias===".to_string(),
                         auth: "oeatssah=".to_string(),
                     },
+                    standard: None,
                 },
                 data: Some(add_subscription::Data {
                     alerts: Some(Alerts {
@@ -292,19 +329,79 @@ mod tests {
                         reblog: Some(true),
                         ..Default::default()
                     }),
+                    policy: None,
                 }),
             }
         );
     }
 
+    #[test]
+    fn test_add_push_request_build_policy() {
+        let endpoint = "https://example.com/push/endpoint";
+        let keys = Keys::new("anetohias===", "oeatssah=");
+        let mut req = AddPushRequest::new(endpoint, &keys);
+        req.policy(Policy::Follower).standard(true);
+        let form = req.build();
+        assert_eq!(form.subscription.standard, Some(true));
+        assert_eq!(
+            form.data,
+            Some(add_subscription::Data {
+                alerts: None,
+                policy: Some(Policy::Follower),
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(&form).unwrap(),
+            serde_json::json!({
+                "subscription": {
+                    "endpoint": "https://example.com/push/endpoint",
+                    "keys": {"p256dh": "anetohias===", "auth": "oeatssah="},
+                    "standard": true
+                },
+                "data": {"alerts": null, "policy": "follower"}
+            })
+        );
+    }
+
+    #[test]
+    fn test_add_push_request_admin_alerts() {
+        let endpoint = "https://example.com/push/endpoint";
+        let keys = Keys::new("anetohias===", "oeatssah=");
+        let mut req = AddPushRequest::new(endpoint, &keys);
+        req.alerts(
+            AlertsBuilder::default()
+                .sign_up(true)
+                .report(false)
+                .build()
+                .unwrap(),
+        );
+        let form = req.build();
+        assert_eq!(
+            serde_json::to_value(form.data.unwrap().alerts.unwrap()).unwrap(),
+            serde_json::json!({
+                "follow": null,
+                "favourite": null,
+                "reblog": null,
+                "mention": null,
+                "status": null,
+                "follow_request": null,
+                "poll": null,
+                "update": null,
+                "admin.sign_up": true,
+                "admin.report": false
+            })
+        );
+    }
+
     #[test]
     fn test_update_push_request_new() {
         let req = UpdatePushRequest::new("some-id");
         assert_eq!(
             req,
             UpdatePushRequest {
-                id: "some-id".to_string(),
-                ..Default::default()
+                id: SubscriptionId::new("some-id"),
+                alerts: Alerts::default(),
+                policy: None,
             }
         );
     }
@@ -318,11 +415,12 @@ mod tests {
                 assert_eq!(
                     req,
                     UpdatePushRequest {
-                        id: "some-id".to_string(),
+                        id: SubscriptionId::new("some-id"),
                         alerts: Alerts {
                             $set: Some(true),
                             ..Default::default()
-                        }
+                        },
+                        policy: None,
                     }
                 );
             }
@@ -337,6 +435,8 @@ mod tests {
         test_update_push_request_favourite, favourite;
         test_update_push_request_reblog, reblog;
         test_update_push_request_mention, mention;
+        test_update_push_request_status, status;
+        test_update_push_request_update, update;
 
     }
 
@@ -347,8 +447,11 @@ mod tests {
         assert_eq!(
             form,
             update_data::Form {
-                id: "some-id".to_string(),
-                data: update_data::Data { alerts: None },
+                id: SubscriptionId::new("some-id"),
+                data: update_data::Data {
+                    alerts: None,
+                    policy: None,
+                },
             }
         );
     }
@@ -364,14 +467,29 @@ mod tests {
         assert_eq!(
             form,
             update_data::Form {
-                id: "some-id".to_string(),
+                id: SubscriptionId::new("some-id"),
                 data: update_data::Data {
                     alerts: Some(Alerts {
                         favourite: Some(false),
                         ..Default::default()
                     }),
+                    policy: None,
                 },
             }
         );
     }
+
+    #[test]
+    fn test_update_push_request_build_policy() {
+        let mut req = UpdatePushRequest::new("some-id");
+        req.policy(Policy::None);
+        let form = req.build();
+        assert_eq!(
+            form.data,
+            update_data::Data {
+                alerts: None,
+                policy: Some(Policy::None),
+            }
+        );
+    }
 }
@@ -0,0 +1,113 @@
+use crate::entities::{report::Category, AccountId, RuleId, StatusId};
+use serde::Serialize;
+
+/// Builder for making a client.report_with() call
+///
+/// // Example
+///
+/// ```
+/// use mastodon_async::{
+///     entities::{report::Category, AccountId, RuleId},
+///     requests::ReportRequest,
+/// };
+///
+/// let mut request = ReportRequest::new(AccountId::new("42"));
+/// request
+///     .category(Category::Violation)
+///     .rule_ids([RuleId::new("1"), RuleId::new("3")])
+///     .comment("breaks the rules")
+///     .forward(true);
+/// assert_eq!(
+///     serde_json::to_string(&request).unwrap(),
+///     r#"{"account_id":"42","comment":"breaks the rules","forward":true,"category":"violation","rule_ids":["1","3"]}"#
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ReportRequest {
+    account_id: AccountId,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    status_ids: Vec<StatusId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    forward: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    category: Option<Category>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    rule_ids: Vec<RuleId>,
+}
+
+impl ReportRequest {
+    /// Construct a new `ReportRequest` against the given account
+    pub fn new(account_id: AccountId) -> Self {
+        ReportRequest {
+            account_id,
+            status_ids: vec![],
+            comment: None,
+            forward: None,
+            category: None,
+            rule_ids: vec![],
+        }
+    }
+
+    /// Attach statuses by the reported account as evidence
+    pub fn status_ids(&mut self, status_ids: impl IntoIterator<Item = StatusId>) -> &mut Self {
+        self.status_ids.extend(status_ids);
+        self
+    }
+
+    /// The reason for the report, up to 1000 characters
+    pub fn comment(&mut self, comment: impl Into<String>) -> &mut Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    /// Set whether the report is forwarded to the moderators of the reported
+    /// account's instance, if it is remote
+    pub fn forward(&mut self, forward: bool) -> &mut Self {
+        self.forward = Some(forward);
+        self
+    }
+
+    /// Set the generic reason for the report. Defaults to
+    /// [`Category::Other`] on the server, or to [`Category::Violation`] if
+    /// any rule IDs are given.
+    pub fn category(&mut self, category: Category) -> &mut Self {
+        self.category = Some(category);
+        self
+    }
+
+    /// Cite the instance rules which were violated, for reports in the
+    /// [`Category::Violation`] category
+    pub fn rule_ids(&mut self, rule_ids: impl IntoIterator<Item = RuleId>) -> &mut Self {
+        self.rule_ids.extend(rule_ids);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let request = ReportRequest::new(AccountId::new("42"));
+        assert_eq!(
+            serde_json::to_string(&request).unwrap(),
+            r#"{"account_id":"42"}"#
+        );
+    }
+
+    #[test]
+    fn test_status_ids() {
+        let mut request = ReportRequest::new(AccountId::new("42"));
+        request
+            .status_ids([StatusId::new("1")])
+            .status_ids([StatusId::new("2")])
+            .category(Category::Spam);
+        assert_eq!(
+            serde_json::to_string(&request).unwrap(),
+            r#"{"account_id":"42","status_ids":["1","2"],"category":"spam"}"#
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use crate::errors::Error;
+use serde::Serialize;
+use std::borrow::Cow;
+
+/// The kind of result a search should be limited to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchType {
+    /// Only return accounts.
+    Accounts,
+    /// Only return hashtags.
+    Hashtags,
+    /// Only return statuses.
+    Statuses,
+}
+
+/// Builder for making a client.search_with() call
+///
+/// Search results are paginated with `offset` (or `max_id`/`min_id` for
+/// statuses) rather than with `Link` headers, so the next page is requested
+/// by advancing the offset by the number of results already received.
+///
+/// // Example
+///
+/// ```
+/// use mastodon_async::requests::{SearchRequest, SearchType};
+/// let mut request = SearchRequest::new("rust");
+/// request.search_type(SearchType::Accounts).resolve(true).limit(5);
+/// assert_eq!(
+///     &request.to_query_string().expect("Couldn't serialize qs")[..],
+///     "?q=rust&type=accounts&resolve=true&limit=5"
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SearchRequest<'a> {
+    q: Cow<'a, str>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    search_type: Option<SearchType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resolve: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    following: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account_id: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exclude_unreviewed: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_id: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_id: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    limit: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    offset: Option<usize>,
+}
+
+impl<'a> SearchRequest<'a> {
+    /// Construct a new `SearchRequest` for the given query
+    pub fn new(q: impl Into<Cow<'a, str>>) -> Self {
+        SearchRequest {
+            q: q.into(),
+            search_type: None,
+            resolve: None,
+            following: None,
+            account_id: None,
+            exclude_unreviewed: None,
+            max_id: None,
+            min_id: None,
+            limit: None,
+            offset: None,
+        }
+    }
+
+    /// Only return results of the given type
+    pub fn search_type(&mut self, search_type: SearchType) -> &mut Self {
+        self.search_type = Some(search_type);
+        self
+    }
+
+    /// Attempt a WebFinger lookup of remote accounts and statuses when the
+    /// query is a URL or an account handle
+    pub fn resolve(&mut self, resolve: bool) -> &mut Self {
+        self.resolve = Some(resolve);
+        self
+    }
+
+    /// Only include accounts that the user is following
+    pub fn following(&mut self, following: bool) -> &mut Self {
+        self.following = Some(following);
+        self
+    }
+
+    /// Only return statuses authored by the given account
+    pub fn account_id(&mut self, account_id: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.account_id = Some(account_id.into());
+        self
+    }
+
+    /// Filter out unreviewed tags. Only relevant for hashtag searches.
+    pub fn exclude_unreviewed(&mut self, exclude_unreviewed: bool) -> &mut Self {
+        self.exclude_unreviewed = Some(exclude_unreviewed);
+        self
+    }
+
+    /// Only return results older than this ID
+    pub fn max_id(&mut self, max_id: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.max_id = Some(max_id.into());
+        self
+    }
+
+    /// Only return results immediately newer than this ID
+    pub fn min_id(&mut self, min_id: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.min_id = Some(min_id.into());
+        self
+    }
+
+    /// Maximum number of results to return, per type
+    pub fn limit(&mut self, limit: usize) -> &mut Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Skip the first `offset` results
+    pub fn offset(&mut self, offset: usize) -> &mut Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Advance the offset past `received` results, so that the request
+    /// fetches the following page when sent again.
+    ///
+    /// // Example
+    ///
+    /// ```
+    /// use mastodon_async::requests::SearchRequest;
+    /// let mut request = SearchRequest::new("rust");
+    /// request.limit(20).next_page(20);
+    /// assert_eq!(
+    ///     &request.to_query_string().expect("Couldn't serialize qs")[..],
+    ///     "?q=rust&limit=20&offset=20"
+    /// );
+    /// ```
+    pub fn next_page(&mut self, received: usize) -> &mut Self {
+        self.offset = Some(self.offset.unwrap_or(0) + received);
+        self
+    }
+
+    /// Serialize into a query string
+    pub fn to_query_string(&self) -> Result<String, Error> {
+        Ok(format!("?{}", serde_urlencoded::to_string(self)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let request = SearchRequest::new("foo");
+        assert_eq!(
+            request,
+            SearchRequest {
+                q: "foo".into(),
+                search_type: None,
+                resolve: None,
+                following: None,
+                account_id: None,
+                exclude_unreviewed: None,
+                max_id: None,
+                min_id: None,
+                limit: None,
+                offset: None,
+            }
+        );
+        assert_eq!(request.to_query_string().unwrap(), "?q=foo");
+    }
+
+    #[test]
+    fn test_all_fields() {
+        let mut request = SearchRequest::new("foo bar");
+        request
+            .search_type(SearchType::Statuses)
+            .resolve(false)
+            .following(true)
+            .account_id("42")
+            .exclude_unreviewed(true)
+            .max_id("100")
+            .min_id("10")
+            .limit(40)
+            .offset(80);
+        assert_eq!(
+            request,
+            SearchRequest {
+                q: "foo bar".into(),
+                search_type: Some(SearchType::Statuses),
+                resolve: Some(false),
+                following: Some(true),
+                account_id: Some("42".into()),
+                exclude_unreviewed: Some(true),
+                max_id: Some("100".into()),
+                min_id: Some("10".into()),
+                limit: Some(40),
+                offset: Some(80),
+            }
+        );
+        assert_eq!(
+            request.to_query_string().unwrap(),
+            "?q=foo+bar&type=statuses&resolve=false&following=true&account_id=42\
+             &exclude_unreviewed=true&max_id=100&min_id=10&limit=40&offset=80"
+        );
+    }
+
+    #[test]
+    fn test_next_page() {
+        let mut request = SearchRequest::new("foo");
+        request.offset(5).next_page(10);
+        assert_eq!(request.offset, Some(15));
+    }
+}
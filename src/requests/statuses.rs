@@ -1,3 +1,4 @@
+use super::is_false;
 use crate::errors::Error;
 use serde::Serialize;
 use std::{borrow::Cow, convert::Into};
@@ -5,10 +6,6 @@ use std::{borrow::Cow, convert::Into};
 mod bool_qs_serialize {
     use serde::Serializer;
 
-    pub fn is_false(b: &bool) -> bool {
-        !*b
-    }
-
     pub fn serialize<S: Serializer>(b: &bool, s: S) -> Result<S::Ok, S::Error> {
         if *b {
             s.serialize_i64(1)
@@ -30,16 +27,21 @@ mod bool_qs_serialize {
 /// ```
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
 pub struct StatusesRequest<'a> {
-    #[serde(skip_serializing_if = "bool_qs_serialize::is_false")]
+    #[serde(skip_serializing_if = "is_false")]
     #[serde(serialize_with = "bool_qs_serialize::serialize")]
     only_media: bool,
-    #[serde(skip_serializing_if = "bool_qs_serialize::is_false")]
+    #[serde(skip_serializing_if = "is_false")]
     #[serde(serialize_with = "bool_qs_serialize::serialize")]
     exclude_replies: bool,
-    #[serde(skip_serializing_if = "bool_qs_serialize::is_false")]
+    #[serde(skip_serializing_if = "is_false")]
+    #[serde(serialize_with = "bool_qs_serialize::serialize")]
+    exclude_reblogs: bool,
+    #[serde(skip_serializing_if = "is_false")]
     #[serde(serialize_with = "bool_qs_serialize::serialize")]
     pinned: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
+    tagged: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     max_id: Option<Cow<'a, str>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     since_id: Option<Cow<'a, str>>,
@@ -54,7 +56,9 @@ impl<'a> From<&'a mut StatusesRequest<'a>> for Option<StatusesRequest<'a>> {
         Some(StatusesRequest {
             only_media: sr.only_media,
             exclude_replies: sr.exclude_replies,
+            exclude_reblogs: sr.exclude_reblogs,
             pinned: sr.pinned,
+            tagged: sr.tagged.clone(),
             max_id: sr.max_id.clone(),
             since_id: sr.since_id.clone(),
             limit: sr.limit,
@@ -103,6 +107,26 @@ impl<'a> StatusesRequest<'a> {
         self
     }
 
+    /// Set the `?exclude_reblogs=1` flag for the .statuses() request
+    ///
+    /// // Example
+    ///
+    /// ```
+    /// use mastodon_async::requests::StatusesRequest;
+    /// let mut request = StatusesRequest::new();
+    /// assert_eq!(
+    ///     &request
+    ///         .exclude_reblogs()
+    ///         .to_query_string()
+    ///         .expect("Couldn't serialize qs"),
+    ///     "?exclude_reblogs=1"
+    /// );
+    /// ```
+    pub fn exclude_reblogs(&mut self) -> &mut Self {
+        self.exclude_reblogs = true;
+        self
+    }
+
     /// Set the `?pinned=1` flag for the .statuses() request
     ///
     /// // Example
@@ -123,6 +147,27 @@ impl<'a> StatusesRequest<'a> {
         self
     }
 
+    /// Set the `?tagged=:tagged` flag for the .statuses() request, to only
+    /// get statuses using a hashtag, given without the `#`
+    ///
+    /// // Example
+    ///
+    /// ```
+    /// use mastodon_async::requests::StatusesRequest;
+    /// let mut request = StatusesRequest::new();
+    /// assert_eq!(
+    ///     &request
+    ///         .tagged("rustlang")
+    ///         .to_query_string()
+    ///         .expect("Couldn't serialize qs"),
+    ///     "?tagged=rustlang"
+    /// );
+    /// ```
+    pub fn tagged<S: Into<Cow<'a, str>>>(&mut self, tagged: S) -> &mut Self {
+        self.tagged = Some(tagged.into());
+        self
+    }
+
     /// Set the `?max_id=:max_id` flag for the .statuses() request
     ///
     /// // Example
@@ -221,7 +266,9 @@ mod tests {
             StatusesRequest {
                 only_media: false,
                 exclude_replies: false,
+                exclude_reblogs: false,
                 pinned: false,
+                tagged: None,
                 max_id: None,
                 since_id: None,
                 limit: None,
@@ -239,7 +286,9 @@ mod tests {
             StatusesRequest {
                 only_media: true,
                 exclude_replies: false,
+                exclude_reblogs: false,
                 pinned: false,
+                tagged: None,
                 max_id: None,
                 since_id: None,
                 limit: None,
@@ -257,7 +306,28 @@ mod tests {
             StatusesRequest {
                 only_media: false,
                 exclude_replies: true,
+                exclude_reblogs: false,
+                pinned: false,
+                tagged: None,
+                max_id: None,
+                since_id: None,
+                limit: None,
+                min_id: None,
+            }
+        );
+    }
+    #[test]
+    fn test_exclude_reblogs() {
+        let mut request = StatusesRequest::new();
+        request.exclude_reblogs();
+        assert_eq!(
+            request,
+            StatusesRequest {
+                only_media: false,
+                exclude_replies: false,
+                exclude_reblogs: true,
                 pinned: false,
+                tagged: None,
                 max_id: None,
                 since_id: None,
                 limit: None,
@@ -274,7 +344,28 @@ mod tests {
             StatusesRequest {
                 only_media: false,
                 exclude_replies: false,
+                exclude_reblogs: false,
                 pinned: true,
+                tagged: None,
+                max_id: None,
+                since_id: None,
+                limit: None,
+                min_id: None,
+            }
+        );
+    }
+    #[test]
+    fn test_tagged() {
+        let mut request = StatusesRequest::new();
+        request.tagged("rustlang");
+        assert_eq!(
+            request,
+            StatusesRequest {
+                only_media: false,
+                exclude_replies: false,
+                exclude_reblogs: false,
+                pinned: false,
+                tagged: Some("rustlang".into()),
                 max_id: None,
                 since_id: None,
                 limit: None,
@@ -291,7 +382,9 @@ mod tests {
             StatusesRequest {
                 only_media: false,
                 exclude_replies: false,
+                exclude_reblogs: false,
                 pinned: false,
+                tagged: None,
                 max_id: Some("foo".into()),
                 since_id: None,
                 limit: None,
@@ -308,7 +401,9 @@ mod tests {
             StatusesRequest {
                 only_media: false,
                 exclude_replies: false,
+                exclude_reblogs: false,
                 pinned: false,
+                tagged: None,
                 max_id: None,
                 since_id: Some("foo".into()),
                 limit: None,
@@ -325,7 +420,9 @@ mod tests {
             StatusesRequest {
                 only_media: false,
                 exclude_replies: false,
+                exclude_reblogs: false,
                 pinned: false,
+                tagged: None,
                 max_id: None,
                 since_id: None,
                 limit: Some(42),
@@ -342,7 +439,9 @@ mod tests {
             StatusesRequest {
                 only_media: false,
                 exclude_replies: false,
+                exclude_reblogs: false,
                 pinned: false,
+                tagged: None,
                 max_id: None,
                 since_id: None,
                 limit: None,
@@ -579,5 +678,28 @@ mod tests {
             },
             "?since_id=foo&limit=42"
         );
+        qs_test!(
+            |request| {
+                request.exclude_reblogs();
+            },
+            "?exclude_reblogs=1"
+        );
+        qs_test!(
+            |request| {
+                request.tagged("rustlang");
+            },
+            "?tagged=rustlang"
+        );
+        qs_test!(
+            |request| {
+                request
+                    .limit(20)
+                    .tagged("rustlang")
+                    .only_media()
+                    .exclude_reblogs()
+                    .exclude_replies();
+            },
+            "?only_media=1&exclude_replies=1&exclude_reblogs=1&tagged=rustlang&limit=20"
+        );
     }
 }
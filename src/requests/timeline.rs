@@ -0,0 +1,87 @@
+use crate::errors::Error;
+use serde::Serialize;
+use std::borrow::Cow;
+
+/// Builder for choosing which part of a timeline to fetch, e.g. with a
+/// client.home_timeline_with() call
+///
+/// // Example
+///
+/// ```
+/// use mastodon_async::requests::TimelineRequest;
+/// let mut request = TimelineRequest::new();
+/// request.since_id("109348238547925380").limit(40);
+/// assert_eq!(
+///     &request.to_query_string().expect("Couldn't serialize qs")[..],
+///     "?since_id=109348238547925380&limit=40"
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct TimelineRequest<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_id: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    since_id: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_id: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    limit: Option<usize>,
+}
+
+impl<'a> TimelineRequest<'a> {
+    /// Construct a new `TimelineRequest` object
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only return statuses older than this ID
+    pub fn max_id(&mut self, max_id: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.max_id = Some(max_id.into());
+        self
+    }
+
+    /// Only return statuses newer than this ID, starting from the newest
+    pub fn since_id(&mut self, since_id: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.since_id = Some(since_id.into());
+        self
+    }
+
+    /// Only return statuses immediately newer than this ID, starting from
+    /// the oldest
+    pub fn min_id(&mut self, min_id: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.min_id = Some(min_id.into());
+        self
+    }
+
+    /// Maximum number of statuses to return
+    pub fn limit(&mut self, limit: usize) -> &mut Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Serialize into a query string
+    pub fn to_query_string(&self) -> Result<String, Error> {
+        Ok(format!("?{}", serde_urlencoded::to_string(self)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        let request = TimelineRequest::new();
+        assert_eq!(&request.to_query_string().expect("qs")[..], "?");
+    }
+
+    #[test]
+    fn test_all_fields() {
+        let mut request = TimelineRequest::new();
+        request.max_id("3").since_id("1").min_id("2").limit(5);
+        assert_eq!(
+            &request.to_query_string().expect("qs")[..],
+            "?max_id=3&since_id=1&min_id=2&limit=5"
+        );
+    }
+}
@@ -0,0 +1,273 @@
+use std::path::{Path, PathBuf};
+
+use isolang::Language;
+use mastodon_async_entities::{account::Credentials, visibility::Visibility};
+
+/// Builder to pass to the Mastodon::update_credentials method
+///
+/// // Example
+///
+/// ```no_run
+/// use mastodon_async::{prelude::*, requests::UpdateCredentialsRequest};
+///
+/// tokio_test::block_on(async {
+///     let client = Mastodon::from(Data::default());
+///
+///     let mut request = UpdateCredentialsRequest::new();
+///     request
+///         .display_name("Ferris")
+///         .note("I'm a crab")
+///         .bot(true)
+///         .privacy(Visibility::Unlisted)
+///         .field_attribute("Pronouns", "they/them")
+///         .avatar("ferris.png");
+///     client.update_credentials(request).await.unwrap();
+/// });
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct UpdateCredentialsRequest {
+    pub(crate) display_name: Option<String>,
+    pub(crate) note: Option<String>,
+    pub(crate) avatar: Option<PathBuf>,
+    pub(crate) header: Option<PathBuf>,
+    pub(crate) locked: Option<bool>,
+    pub(crate) bot: Option<bool>,
+    pub(crate) discoverable: Option<bool>,
+    pub(crate) privacy: Option<Visibility>,
+    pub(crate) sensitive: Option<bool>,
+    pub(crate) language: Option<Language>,
+    pub(crate) fields_attributes: Vec<(String, String)>,
+}
+
+impl UpdateCredentialsRequest {
+    /// Construct a new, empty `UpdateCredentialsRequest`. Fields which aren't
+    /// set are left unchanged on the server.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the display name to use for the profile.
+    pub fn display_name(&mut self, display_name: impl Into<String>) -> &mut Self {
+        self.display_name = Some(display_name.into());
+        self
+    }
+
+    /// Set the account bio.
+    pub fn note(&mut self, note: impl Into<String>) -> &mut Self {
+        self.note = Some(note.into());
+        self
+    }
+
+    /// Upload a new avatar image from the given path.
+    pub fn avatar(&mut self, path: impl AsRef<Path>) -> &mut Self {
+        self.avatar = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Upload a new header image from the given path.
+    pub fn header(&mut self, path: impl AsRef<Path>) -> &mut Self {
+        self.header = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Set whether follow requests must be approved manually.
+    pub fn locked(&mut self, locked: bool) -> &mut Self {
+        self.locked = Some(locked);
+        self
+    }
+
+    /// Set whether the account is flagged as a bot.
+    pub fn bot(&mut self, bot: bool) -> &mut Self {
+        self.bot = Some(bot);
+        self
+    }
+
+    /// Set whether the account should be shown in the profile directory.
+    pub fn discoverable(&mut self, discoverable: bool) -> &mut Self {
+        self.discoverable = Some(discoverable);
+        self
+    }
+
+    /// Set the default visibility of new statuses.
+    pub fn privacy(&mut self, privacy: Visibility) -> &mut Self {
+        self.privacy = Some(privacy);
+        self
+    }
+
+    /// Set whether new statuses are marked sensitive by default.
+    pub fn sensitive(&mut self, sensitive: bool) -> &mut Self {
+        self.sensitive = Some(sensitive);
+        self
+    }
+
+    /// Set the default language of new statuses.
+    pub fn language(&mut self, language: Language) -> &mut Self {
+        self.language = Some(language);
+        self
+    }
+
+    /// Add a profile metadata field. Note that when any fields are set, the
+    /// server replaces all existing fields with the ones given here.
+    ///
+    /// // Example
+    ///
+    /// ```
+    /// use mastodon_async::requests::UpdateCredentialsRequest;
+    ///
+    /// let mut request = UpdateCredentialsRequest::new();
+    /// request
+    ///     .field_attribute("Website", "https://example.com")
+    ///     .field_attribute("Pronouns", "they/them");
+    /// ```
+    pub fn field_attribute(
+        &mut self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) -> &mut Self {
+        self.fields_attributes.push((name.into(), value.into()));
+        self
+    }
+
+    /// The non-file parts of the multipart form, in the order they are sent.
+    pub(crate) fn text_fields(&self) -> Vec<(String, String)> {
+        let mut fields = vec![];
+        if let Some(display_name) = &self.display_name {
+            fields.push(("display_name".to_string(), display_name.clone()));
+        }
+        if let Some(note) = &self.note {
+            fields.push(("note".to_string(), note.clone()));
+        }
+        if let Some(locked) = self.locked {
+            fields.push(("locked".to_string(), locked.to_string()));
+        }
+        if let Some(bot) = self.bot {
+            fields.push(("bot".to_string(), bot.to_string()));
+        }
+        if let Some(discoverable) = self.discoverable {
+            fields.push(("discoverable".to_string(), discoverable.to_string()));
+        }
+        if let Some(privacy) = self.privacy {
+            fields.push(("source[privacy]".to_string(), privacy.to_string()));
+        }
+        if let Some(sensitive) = self.sensitive {
+            fields.push(("source[sensitive]".to_string(), sensitive.to_string()));
+        }
+        if let Some(language) = self.language {
+            let code = language.to_639_1().unwrap_or_else(|| language.to_639_3());
+            fields.push(("source[language]".to_string(), code.to_string()));
+        }
+        for (i, (name, value)) in self.fields_attributes.iter().enumerate() {
+            fields.push((format!("fields_attributes[{i}][name]"), name.clone()));
+            fields.push((format!("fields_attributes[{i}][value]"), value.clone()));
+        }
+        fields
+    }
+}
+
+impl From<Credentials> for UpdateCredentialsRequest {
+    fn from(credentials: Credentials) -> Self {
+        let source = credentials.source.unwrap_or_default();
+        UpdateCredentialsRequest {
+            display_name: credentials.display_name,
+            note: credentials.note,
+            avatar: credentials.avatar,
+            header: credentials.header,
+            locked: credentials.locked,
+            bot: credentials.bot,
+            discoverable: credentials.discoverable,
+            privacy: source.privacy,
+            sensitive: source.sensitive,
+            language: source.language,
+            fields_attributes: credentials
+                .fields_attributes
+                .into_iter()
+                .map(|field| (field.name, field.value))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let request = UpdateCredentialsRequest::new();
+        assert_eq!(
+            request,
+            UpdateCredentialsRequest {
+                display_name: None,
+                note: None,
+                avatar: None,
+                header: None,
+                locked: None,
+                bot: None,
+                discoverable: None,
+                privacy: None,
+                sensitive: None,
+                language: None,
+                fields_attributes: vec![],
+            }
+        );
+        assert!(request.text_fields().is_empty());
+    }
+
+    #[test]
+    fn test_all_fields() {
+        let mut request = UpdateCredentialsRequest::new();
+        request
+            .display_name("Ferris")
+            .note("a crab")
+            .avatar("/tmp/avatar.png")
+            .header("/tmp/header.png")
+            .locked(true)
+            .bot(false)
+            .discoverable(true)
+            .privacy(Visibility::Private)
+            .sensitive(true)
+            .language(Language::Eng)
+            .field_attribute("Website", "https://example.com");
+        assert_eq!(
+            request,
+            UpdateCredentialsRequest {
+                display_name: Some("Ferris".to_string()),
+                note: Some("a crab".to_string()),
+                avatar: Some(PathBuf::from("/tmp/avatar.png")),
+                header: Some(PathBuf::from("/tmp/header.png")),
+                locked: Some(true),
+                bot: Some(false),
+                discoverable: Some(true),
+                privacy: Some(Visibility::Private),
+                sensitive: Some(true),
+                language: Some(Language::Eng),
+                fields_attributes: vec![("Website".to_string(), "https://example.com".to_string())],
+            }
+        );
+    }
+
+    #[test]
+    fn test_text_fields() {
+        let mut request = UpdateCredentialsRequest::new();
+        request
+            .locked(false)
+            .privacy(Visibility::Unlisted)
+            .language(Language::Deu)
+            .field_attribute("a", "1")
+            .field_attribute("b", "2")
+            .avatar("/tmp/avatar.png");
+        let expected: Vec<(String, String)> = [
+            ("locked", "false"),
+            ("source[privacy]", "unlisted"),
+            ("source[language]", "de"),
+            ("fields_attributes[0][name]", "a"),
+            ("fields_attributes[0][value]", "1"),
+            ("fields_attributes[1][name]", "b"),
+            ("fields_attributes[1][value]", "2"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        assert_eq!(request.text_fields(), expected);
+    }
+}
@@ -0,0 +1,295 @@
+use std::time::Duration;
+
+use reqwest::{
+    header::{HeaderMap, RETRY_AFTER},
+    Method, Request, Response, StatusCode,
+};
+use time::{format_description::well_known::Rfc2822, OffsetDateTime};
+
+use crate::{errors::Result, rate_limit::RateLimit, Error};
+
+/// The header which marks a POST request as safe to repeat.
+pub(crate) const IDEMPOTENCY_KEY: &str = "Idempotency-Key";
+
+/// Options for automatically retrying requests which failed because the
+/// server was overloaded, unreachable, or rate limited the client, for use
+/// with [`MastodonBuilder::retry`](crate::builder::MastodonBuilder::retry).
+///
+/// Only GET and HEAD requests are retried, unless POST requests carrying an
+/// `Idempotency-Key` header are allowed with [`Retry::idempotent_posts`].
+///
+/// // Example
+///
+/// ```
+/// use std::time::Duration;
+/// use mastodon_async::retry::Retry;
+/// let mut retry = Retry::new();
+/// retry
+///     .max_attempts(5)
+///     .initial_delay(Duration::from_millis(250))
+///     .max_delay(Duration::from_secs(30))
+///     .idempotent_posts();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Retry {
+    max_attempts: u32,
+    initial_delay: Duration,
+    max_delay: Duration,
+    idempotent_posts: bool,
+}
+
+impl Default for Retry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Retry {
+    /// Retry up to three times, with a delay starting at half a second and
+    /// doubling up to a minute.
+    pub fn new() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(60),
+            idempotent_posts: false,
+        }
+    }
+
+    /// The number of times to retry a request before giving up and
+    /// returning the last response or error.
+    pub fn max_attempts(&mut self, attempts: u32) -> &mut Self {
+        self.max_attempts = attempts;
+        self
+    }
+
+    /// The delay before the first retry, when the server doesn't say how
+    /// long to wait. Each consecutive retry doubles it.
+    pub fn initial_delay(&mut self, delay: Duration) -> &mut Self {
+        self.initial_delay = delay;
+        self
+    }
+
+    /// The longest delay between retries. If the server asks the client to
+    /// wait longer than this, the request is not retried.
+    pub fn max_delay(&mut self, delay: Duration) -> &mut Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Also retry POST requests which carry an `Idempotency-Key` header,
+    /// which the server uses to recognize and ignore duplicates.
+    pub fn idempotent_posts(&mut self) -> &mut Self {
+        self.idempotent_posts = true;
+        self
+    }
+
+    /// Whether `request` may be sent more than once.
+    pub(crate) fn applies_to(&self, request: &Request) -> bool {
+        match *request.method() {
+            Method::GET | Method::HEAD => true,
+            Method::POST => {
+                self.idempotent_posts && request.headers().contains_key(IDEMPOTENCY_KEY)
+            }
+            _ => false,
+        }
+    }
+
+    /// How long to wait before retrying a request which produced `result`,
+    /// or `None` if it shouldn't be retried. `attempt` is the number of the
+    /// retry which would be made, starting at 1.
+    pub(crate) fn delay(&self, attempt: u32, result: &Result<Response>) -> Option<Duration> {
+        if attempt > self.max_attempts {
+            return None;
+        }
+        let requested = match result {
+            Ok(response) => {
+                let status = response.status();
+                if status != StatusCode::TOO_MANY_REQUESTS && !status.is_server_error() {
+                    return None;
+                }
+                requested_delay(status, response.headers(), OffsetDateTime::now_utc())
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            Err(Error::Http(err)) if err.is_connect() || err.is_timeout() => None,
+            #[cfg(target_arch = "wasm32")]
+            Err(Error::Http(err)) if err.is_request() || err.is_timeout() => None,
+            Err(Error::Io(_)) => None,
+            Err(_) => return None,
+        };
+        match requested {
+            Some(delay) if delay > self.max_delay => None,
+            Some(delay) => Some(delay),
+            None => Some(backoff(self.initial_delay, self.max_delay, attempt)),
+        }
+    }
+}
+
+/// How long the server asked the client to wait, with the `Retry-After`
+/// header or, for a rate-limited request, the time the rate limit resets.
+fn requested_delay(
+    status: StatusCode,
+    headers: &HeaderMap,
+    now: OffsetDateTime,
+) -> Option<Duration> {
+    if let Some(retry_after) = headers
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+    {
+        let retry_after = retry_after.trim();
+        if let Ok(seconds) = retry_after.parse() {
+            return Some(Duration::from_secs(seconds));
+        }
+        if let Ok(date) = OffsetDateTime::parse(retry_after, &Rfc2822) {
+            return Some(Duration::try_from(date - now).unwrap_or_default());
+        }
+    }
+    if status == StatusCode::TOO_MANY_REQUESTS {
+        let reset = RateLimit::from_headers(headers)?.reset?;
+        return Some(Duration::try_from(reset - now).unwrap_or_default());
+    }
+    None
+}
+
+/// Exponential backoff with "equal jitter", i.e. a random duration between
+/// half and all of `initial` doubled for each `attempt` after the first,
+/// capped at `max`.
+pub(crate) fn backoff(initial: Duration, max: Duration, attempt: u32) -> Duration {
+    let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+    let backoff = initial.saturating_mul(factor).min(max);
+    let mut random = [0u8; 4];
+    if getrandom::getrandom(&mut random).is_err() {
+        return backoff;
+    }
+    let jitter = f64::from(u32::from_ne_bytes(random)) / f64::from(u32::MAX);
+    backoff.mul_f64(0.5 + jitter / 2.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{helpers::fixtures::Server, Mastodon};
+
+    /// Respond to the next request with `status` and `body`, telling the
+    /// client to retry right away, and return the request.
+    async fn respond(server: &Server, status: &str, body: &str) -> String {
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nRetry-After: 0\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        server.respond_raw(response.as_bytes()).await
+    }
+
+    #[test]
+    fn test_applies_to() {
+        let client = reqwest::Client::new();
+        let get = client.get("https://example.com/api/v1/instance");
+        let post = client
+            .post("https://example.com/api/v1/statuses")
+            .header(IDEMPOTENCY_KEY, "abc");
+        let mut retry = Retry::new();
+        assert!(retry.applies_to(&get.build().unwrap()));
+        assert!(!retry.applies_to(&post.try_clone().unwrap().build().unwrap()));
+        retry.idempotent_posts();
+        assert!(retry.applies_to(&post.build().unwrap()));
+        let post = client.post("https://example.com/api/v1/statuses");
+        assert!(!retry.applies_to(&post.build().unwrap()));
+        let delete = client.delete("https://example.com/api/v1/statuses/1");
+        assert!(!retry.applies_to(&delete.build().unwrap()));
+    }
+
+    #[test]
+    fn test_requested_delay() {
+        let now = OffsetDateTime::parse("Wed, 21 Oct 2015 07:28:00 GMT", &Rfc2822).unwrap();
+        let mut headers = HeaderMap::new();
+        assert_eq!(
+            requested_delay(StatusCode::SERVICE_UNAVAILABLE, &headers, now),
+            None
+        );
+        headers.insert(RETRY_AFTER, "120".parse().unwrap());
+        assert_eq!(
+            requested_delay(StatusCode::SERVICE_UNAVAILABLE, &headers, now),
+            Some(Duration::from_secs(120))
+        );
+        headers.insert(
+            RETRY_AFTER,
+            "Wed, 21 Oct 2015 07:28:30 GMT".parse().unwrap(),
+        );
+        assert_eq!(
+            requested_delay(StatusCode::SERVICE_UNAVAILABLE, &headers, now),
+            Some(Duration::from_secs(30))
+        );
+        let mut headers = HeaderMap::new();
+        headers.insert("X-RateLimit-Limit", "300".parse().unwrap());
+        headers.insert("X-RateLimit-Remaining", "0".parse().unwrap());
+        headers.insert("X-RateLimit-Reset", "2015-10-21T07:29:00Z".parse().unwrap());
+        assert_eq!(
+            requested_delay(StatusCode::TOO_MANY_REQUESTS, &headers, now),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            requested_delay(StatusCode::SERVICE_UNAVAILABLE, &headers, now),
+            None
+        );
+    }
+
+    #[test]
+    fn test_backoff() {
+        let initial = Duration::from_secs(1);
+        let max = Duration::from_secs(5);
+        for (attempt, full) in [(1, 1), (2, 2), (3, 4), (4, 5), (10, 5)] {
+            let delay = backoff(initial, max, attempt);
+            let full = Duration::from_secs(full);
+            assert!(delay >= full / 2 && delay <= full, "{attempt}: {delay:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_unavailable() {
+        let server = Server::bind().await;
+        let client = Mastodon::builder(server.data())
+            .retry(Retry::new())
+            .build()
+            .unwrap();
+        let responses = async {
+            respond(&server, "503 Service Unavailable", "{}").await;
+            respond(&server, "200 OK", r#"["example.com"]"#).await;
+        };
+        let (peers, _) = tokio::join!(client.instance_peers(), responses);
+        assert_eq!(peers.unwrap(), vec!["example.com".to_string()]);
+
+        let client = Mastodon::from(server.data());
+        let (peers, _) = tokio::join!(
+            client.instance_peers(),
+            respond(&server, "503 Service Unavailable", "{}")
+        );
+        assert!(peers.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_retries_idempotent_post() {
+        let server = Server::bind().await;
+        let mut retry = Retry::new();
+        retry.idempotent_posts();
+        let client = Mastodon::builder(server.data())
+            .retry(retry)
+            .build()
+            .unwrap();
+        let status = crate::NewStatusBuilder::default()
+            .status("hello")
+            .build()
+            .unwrap();
+        let responses = async {
+            let first = respond(&server, "503 Service Unavailable", "{}").await;
+            let second = respond(&server, "422 Unprocessable Entity", r#"{"error":"nope"}"#).await;
+            (first, second)
+        };
+        let (posted, (first, second)) = tokio::join!(
+            client.new_status_with_idempotency_key(status, "key-1"),
+            responses
+        );
+        assert!(posted.is_err());
+        assert!(first.contains("idempotency-key: key-1"), "{first}");
+        assert!(second.contains("idempotency-key: key-1"), "{second}");
+    }
+}
@@ -0,0 +1,166 @@
+use reqwest::Method;
+
+use crate::entities::auth::scopes::{Admin, Read, Scope, Write};
+
+/// The scope an access token needs to be granted for a request with the
+/// given method and path, or `None` if the endpoint doesn't need one, or
+/// isn't known.
+pub(crate) fn required(method: &Method, path: &str) -> Option<Scope> {
+    let path = &path[path.find("/api/")?..];
+    let path = path.trim_end_matches('/');
+    let rest = path
+        .strip_prefix("/api/v1/")
+        .or_else(|| path.strip_prefix("/api/v2/"))
+        .or_else(|| path.strip_prefix("/api/"))?;
+    let segments: Vec<&str> = rest.split('/').collect();
+    if *method == Method::GET {
+        read(&segments)
+    } else {
+        write(&segments)
+    }
+}
+
+fn read(segments: &[&str]) -> Option<Scope> {
+    Some(match segments {
+        ["accounts", "verify_credentials"] => Scope::Profile,
+        ["accounts", "relationships" | "familiar_followers"] => Scope::Read(Some(Read::Follows)),
+        ["accounts", "search"] | ["endorsements" | "featured_tags" | "preferences", ..] => {
+            Scope::Read(Some(Read::Accounts))
+        }
+        ["accounts", _, "lists"] | ["lists", ..] => Scope::Read(Some(Read::Lists)),
+        ["timelines", "home" | "list", ..]
+        | ["conversations" | "markers" | "scheduled_statuses", ..] => {
+            Scope::Read(Some(Read::Statuses))
+        }
+        ["notifications", ..] => Scope::Read(Some(Read::Notifications)),
+        ["favourites", ..] => Scope::Read(Some(Read::Favourites)),
+        ["bookmarks", ..] => Scope::Read(Some(Read::Bookmarks)),
+        ["blocks" | "domain_blocks", ..] => Scope::Read(Some(Read::Blocks)),
+        ["mutes", ..] => Scope::Read(Some(Read::Mutes)),
+        ["follow_requests" | "followed_tags", ..] => Scope::Read(Some(Read::Follows)),
+        ["filters", ..] => Scope::Read(Some(Read::Filters)),
+        ["suggestions", ..] => Scope::Read(None),
+        ["push", ..] => Scope::Push,
+        ["admin", kind, ..] => Scope::AdminRead(admin(kind)),
+        ["pleroma", "aliases"] => Scope::Read(Some(Read::Accounts)),
+        _ => return None,
+    })
+}
+
+fn write(segments: &[&str]) -> Option<Scope> {
+    Some(match segments {
+        ["accounts", "update_credentials"]
+        | ["accounts", _, "pin" | "unpin" | "endorse" | "unendorse" | "note"]
+        | ["statuses", _, "pin" | "unpin"]
+        | ["featured_tags", ..]
+        | ["announcements", _, "dismiss"]
+        | ["pleroma", "aliases"] => Scope::Write(Some(Write::Accounts)),
+        ["accounts", _, "follow" | "unfollow" | "remove_from_followers"]
+        | ["follow_requests", ..]
+        | ["tags", _, "follow" | "unfollow"]
+        | ["notifications", "policy"] => Scope::Write(Some(Write::Follows)),
+        ["accounts", _, "block" | "unblock"] | ["domain_blocks", ..] => {
+            Scope::Write(Some(Write::Blocks))
+        }
+        ["accounts", _, "mute" | "unmute"] | ["statuses", _, "mute" | "unmute"] => {
+            Scope::Write(Some(Write::Mutes))
+        }
+        ["statuses", _, "favourite" | "unfavourite"] | ["announcements", _, "reactions", ..] => {
+            Scope::Write(Some(Write::Favourites))
+        }
+        ["statuses", _, "bookmark" | "unbookmark"] => Scope::Write(Some(Write::Bookmarks)),
+        ["statuses", _, "translate"] => return None,
+        ["statuses", ..] | ["polls", ..] | ["markers" | "scheduled_statuses", ..] => {
+            Scope::Write(Some(Write::Statuses))
+        }
+        ["media", ..] => Scope::Write(Some(Write::Media)),
+        ["lists", ..] => Scope::Write(Some(Write::Lists)),
+        ["reports", ..] => Scope::Write(Some(Write::Reports)),
+        ["filters", ..] => Scope::Write(Some(Write::Filters)),
+        ["notifications", ..] => Scope::Write(Some(Write::Notifications)),
+        ["conversations", ..] => Scope::Write(Some(Write::Conversations)),
+        ["push", ..] => Scope::Push,
+        // queries which are POSTed, but only read
+        ["admin", "measures" | "dimensions" | "retention"] => Scope::AdminRead(None),
+        ["admin", "canonical_email_blocks", "test"] => {
+            Scope::AdminRead(Some(Admin::CanonicalEmailBlocks))
+        }
+        ["admin", kind, ..] => Scope::AdminWrite(admin(kind)),
+        _ => return None,
+    })
+}
+
+/// The granular admin scope covering the admin endpoints for `kind`, or
+/// `None` for those which need the full admin scope.
+fn admin(kind: &str) -> Option<Admin> {
+    Some(match kind {
+        "accounts" => Admin::Accounts,
+        "reports" => Admin::Reports,
+        "domain_allows" => Admin::DomainAllows,
+        "domain_blocks" => Admin::DomainBlocks,
+        "ip_blocks" => Admin::IpBlocks,
+        "email_domain_blocks" => Admin::EmailDomainBlocks,
+        "canonical_email_blocks" => Admin::CanonicalEmailBlocks,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_required() {
+        assert_eq!(
+            required(&Method::GET, "/api/v1/accounts/verify_credentials"),
+            Some(Scope::Profile)
+        );
+        assert_eq!(
+            required(&Method::POST, "/api/v1/accounts/1234/follow"),
+            Some(Scope::Write(Some(Write::Follows)))
+        );
+        assert_eq!(
+            required(&Method::POST, "/api/v1/statuses"),
+            Some(Scope::Write(Some(Write::Statuses)))
+        );
+        assert_eq!(
+            required(&Method::POST, "/api/v1/statuses/1234/favourite"),
+            Some(Scope::Write(Some(Write::Favourites)))
+        );
+        assert_eq!(
+            required(&Method::GET, "/mastodon/api/v2/notifications"),
+            Some(Scope::Read(Some(Read::Notifications)))
+        );
+        assert_eq!(
+            required(&Method::POST, "/api/v1/admin/accounts/1234/action"),
+            Some(Scope::AdminWrite(Some(Admin::Accounts)))
+        );
+        assert_eq!(
+            required(&Method::POST, "/api/v1/admin/measures"),
+            Some(Scope::AdminRead(None))
+        );
+        assert_eq!(
+            required(&Method::POST, "/api/v1/admin/dimensions"),
+            Some(Scope::AdminRead(None))
+        );
+        assert_eq!(
+            required(&Method::POST, "/api/v1/admin/retention"),
+            Some(Scope::AdminRead(None))
+        );
+        assert_eq!(
+            required(&Method::POST, "/api/v1/admin/canonical_email_blocks/test"),
+            Some(Scope::AdminRead(Some(Admin::CanonicalEmailBlocks)))
+        );
+        assert_eq!(
+            required(&Method::POST, "/api/v1/admin/canonical_email_blocks"),
+            Some(Scope::AdminWrite(Some(Admin::CanonicalEmailBlocks)))
+        );
+        assert_eq!(
+            required(&Method::GET, "/api/v1/admin/trends/links"),
+            Some(Scope::AdminRead(None))
+        );
+        assert_eq!(required(&Method::GET, "/api/v1/statuses/1234"), None);
+        assert_eq!(required(&Method::GET, "/api/v1/instance"), None);
+        assert_eq!(required(&Method::GET, "/nodeinfo/2.0"), None);
+    }
+}
@@ -0,0 +1,557 @@
+use std::{collections::VecDeque, fmt, io, time::Duration};
+
+use crate::{
+    dedup::Seen,
+    entities::{event::Event, status::Status},
+    errors::Result,
+    event_stream::events_and_heartbeats,
+    helpers::read_response::read_response,
+    retry::backoff,
+    Error, Mastodon,
+};
+use futures::{stream::unfold, Stream, StreamExt, TryStreamExt};
+use log::{debug, trace, warn};
+use mastodon_async_entities::{ListId, StatusId};
+
+/// A timeline or set of events which can be streamed from the server.
+///
+/// See also [the API reference](https://docs.joinmastodon.org/methods/streaming/#streams)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Channel {
+    /// Events relevant to the authorized user, i.e. home timeline &
+    /// notifications.
+    User,
+    /// Notifications for the authorized user.
+    UserNotification,
+    /// All public posts known to the server.
+    Public,
+    /// All public posts known to the server with media attachments.
+    PublicMedia,
+    /// All public posts originating from this server.
+    PublicLocal,
+    /// All public posts originating from this server with media attachments.
+    PublicLocalMedia,
+    /// All public posts originating from other servers.
+    PublicRemote,
+    /// All public posts originating from other servers with media
+    /// attachments.
+    PublicRemoteMedia,
+    /// All public posts using the given hashtag, without the leading `#`.
+    Hashtag(String),
+    /// All public posts using the given hashtag, originating from this
+    /// server.
+    HashtagLocal(String),
+    /// Updates to the given list.
+    List(ListId),
+    /// Updates to direct conversations.
+    Direct,
+}
+
+impl Channel {
+    /// The name of the stream, as it is sent to and received from the server.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Channel::User => "user",
+            Channel::UserNotification => "user:notification",
+            Channel::Public => "public",
+            Channel::PublicMedia => "public:media",
+            Channel::PublicLocal => "public:local",
+            Channel::PublicLocalMedia => "public:local:media",
+            Channel::PublicRemote => "public:remote",
+            Channel::PublicRemoteMedia => "public:remote:media",
+            Channel::Hashtag(_) => "hashtag",
+            Channel::HashtagLocal(_) => "hashtag:local",
+            Channel::List(_) => "list",
+            Channel::Direct => "direct",
+        }
+    }
+}
+
+impl fmt::Display for Channel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Channel::Hashtag(tag) | Channel::HashtagLocal(tag) => {
+                write!(f, "{}#{}", self.name(), tag.trim_start_matches('#'))
+            }
+            Channel::List(list) => write!(f, "{}/{list}", self.name()),
+            _ => f.write_str(self.name()),
+        }
+    }
+}
+
+impl Channel {
+    /// The path and query of this channel's Server-Sent Events endpoint,
+    /// relative to `/api/v1/streaming/`.
+    fn sse_path(&self) -> String {
+        let path = self.name().replace(':', "/");
+        match self {
+            Channel::Hashtag(tag) | Channel::HashtagLocal(tag) => {
+                format!("{path}?tag={}", encode(tag.trim_start_matches('#')))
+            }
+            Channel::List(list) => format!("{path}?list={}", encode(list.as_ref())),
+            _ => path,
+        }
+    }
+
+    /// The path and query of the REST timeline which holds the same statuses
+    /// as this channel, relative to `/api/v1/timelines/`, if there is one.
+    pub(crate) fn timeline_path(&self) -> Option<String> {
+        Some(match self {
+            Channel::User => "home?".to_string(),
+            Channel::Public => "public?".to_string(),
+            Channel::PublicMedia => "public?only_media=true&".to_string(),
+            Channel::PublicLocal => "public?local=true&".to_string(),
+            Channel::PublicLocalMedia => "public?local=true&only_media=true&".to_string(),
+            Channel::PublicRemote => "public?remote=true&".to_string(),
+            Channel::PublicRemoteMedia => "public?remote=true&only_media=true&".to_string(),
+            Channel::Hashtag(tag) => format!("tag/{}?", encode(tag.trim_start_matches('#'))),
+            Channel::HashtagLocal(tag) => {
+                format!("tag/{}?local=true&", encode(tag.trim_start_matches('#')))
+            }
+            Channel::List(list) => format!("list/{}?", encode(list.as_ref())),
+            Channel::UserNotification | Channel::Direct => return None,
+        })
+    }
+}
+
+/// The most statuses the server returns from a timeline at once.
+pub(crate) const PAGE_SIZE: usize = 40;
+
+/// The most pages fetched to fill the gap left while disconnected.
+const MAX_GAP_PAGES: usize = 10;
+
+/// How many of the most recent status IDs are remembered to drop
+/// duplicates.
+pub(crate) const SEEN_CAPACITY: usize = 1000;
+
+pub(crate) fn encode(value: &str) -> String {
+    percent_encoding::utf8_percent_encode(value, percent_encoding::NON_ALPHANUMERIC).to_string()
+}
+
+/// An item from a [`Mastodon::stream_reconnecting`] stream: either an event
+/// from the server, or a change in the state of the connection.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug)]
+pub enum StreamEvent {
+    /// The connection was (re-)established.
+    Connected,
+    /// The connection was lost or couldn't be established. Contains the
+    /// error, or `None` if the server closed the stream.
+    Disconnected(Option<Error>),
+    /// Waiting `delay` before reconnection attempt number `attempt`.
+    Retrying {
+        /// The number of attempts since the last successful connection,
+        /// starting at 1.
+        attempt: u32,
+        /// How long until the attempt is made.
+        delay: Duration,
+    },
+    /// An event received from the server, or a status backfilled after
+    /// reconnecting.
+    Event(Event),
+}
+
+/// Options for [`Mastodon::stream_reconnecting`].
+///
+/// // Example
+///
+/// ```
+/// use std::time::Duration;
+/// use mastodon_async::streaming::{Channel, StreamOptions};
+/// let mut options = StreamOptions::new(Channel::User);
+/// options
+///     .initial_delay(Duration::from_millis(250))
+///     .max_delay(Duration::from_secs(30))
+///     .max_attempts(10)
+///     .backfill()
+///     .health_check()
+///     .idle_timeout(Duration::from_secs(45));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamOptions {
+    pub(crate) channel: Channel,
+    initial_delay: Duration,
+    max_delay: Duration,
+    max_attempts: Option<u32>,
+    pub(crate) backfill: bool,
+    health_check: bool,
+    idle_timeout: Option<Duration>,
+}
+
+impl StreamOptions {
+    /// Stream `channel`, retrying forever with a delay starting at one
+    /// second and doubling up to a minute, without backfilling, health checks
+    /// or an idle timeout.
+    pub fn new(channel: Channel) -> Self {
+        Self {
+            channel,
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+            max_attempts: None,
+            backfill: false,
+            health_check: false,
+            idle_timeout: None,
+        }
+    }
+
+    /// The delay before the first reconnection attempt. Each consecutive
+    /// attempt doubles it.
+    pub fn initial_delay(&mut self, delay: Duration) -> &mut Self {
+        self.initial_delay = delay;
+        self
+    }
+
+    /// The longest delay between reconnection attempts.
+    pub fn max_delay(&mut self, delay: Duration) -> &mut Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Give up, ending the stream, after this many consecutive failed
+    /// reconnection attempts.
+    pub fn max_attempts(&mut self, attempts: u32) -> &mut Self {
+        self.max_attempts = Some(attempts);
+        self
+    }
+
+    /// After reconnecting, fetch statuses posted since the last one received
+    /// from the REST timeline matching the channel, and emit them as
+    /// [`Event::Update`]s, dropping any which are then streamed again. At most
+    /// 400 statuses are backfilled. Has no effect
+    /// for the notification and direct channels, which have no matching
+    /// timeline.
+    pub fn backfill(&mut self) -> &mut Self {
+        self.backfill = true;
+        self
+    }
+
+    /// Before each connection attempt, check that the streaming API reports
+    /// itself healthy with [`Mastodon::streaming_health`], and treat the
+    /// attempt as failed if it doesn't.
+    pub fn health_check(&mut self) -> &mut Self {
+        self.health_check = true;
+        self
+    }
+
+    /// Treat the connection as dead, and reconnect, if nothing (not even a
+    /// heartbeat) is received from the server for this long. Mastodon sends
+    /// a heartbeat every 15 seconds or so, so this should be comfortably
+    /// longer than that.
+    pub fn idle_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
+    /// The delay before the given reconnection attempt: exponential backoff
+    /// with "equal jitter", i.e. a random duration between half and all of
+    /// the backoff.
+    fn delay(&self, attempt: u32) -> Duration {
+        backoff(self.initial_delay, self.max_delay, attempt)
+    }
+}
+
+/// The events and heartbeats received over a connection, or `None` for each
+/// heartbeat. The response body can't be sent between threads on
+/// WebAssembly, so neither can the stream.
+#[cfg(not(target_arch = "wasm32"))]
+type Events = futures::stream::BoxStream<'static, Result<Option<Event>>>;
+#[cfg(target_arch = "wasm32")]
+type Events = futures::stream::LocalBoxStream<'static, Result<Option<Event>>>;
+
+enum Phase {
+    Connect,
+    Streaming(Events),
+    Backoff,
+    Wait(Duration),
+}
+
+struct State<'a> {
+    client: &'a Mastodon,
+    options: StreamOptions,
+    phase: Phase,
+    attempt: u32,
+    last_status_id: Option<StatusId>,
+    backfilled: VecDeque<Status>,
+    seen: Seen<StatusId>,
+}
+
+/// Stream the channel, reconnecting whenever the connection is lost.
+pub(crate) fn reconnecting(
+    client: &Mastodon,
+    options: StreamOptions,
+) -> impl Stream<Item = StreamEvent> + '_ {
+    let state = State {
+        client,
+        options,
+        phase: Phase::Connect,
+        attempt: 0,
+        last_status_id: None,
+        backfilled: VecDeque::new(),
+        seen: Seen::new(SEEN_CAPACITY),
+    };
+    unfold(state, |mut state| async move {
+        loop {
+            if let Some(status) = state.backfilled.pop_front() {
+                state.last_status_id = Some(status.id.clone());
+                return Some((StreamEvent::Event(Event::Update(status)), state));
+            }
+            match state.phase {
+                Phase::Connect => match connect(state.client, &state.options).await {
+                    Ok(stream) => {
+                        state.phase = Phase::Streaming(stream);
+                        state.attempt = 0;
+                        if state.options.backfill {
+                            state.backfill().await;
+                        }
+                        return Some((StreamEvent::Connected, state));
+                    }
+                    Err(err) => {
+                        state.phase = Phase::Backoff;
+                        return Some((StreamEvent::Disconnected(Some(err)), state));
+                    }
+                },
+                Phase::Streaming(ref mut stream) => match next(stream, &state.options).await {
+                    Some(Ok(None)) => trace!("received heartbeat"),
+                    Some(Ok(Some(event))) => {
+                        if let Event::Update(ref status) = event {
+                            if !state.seen.insert(status.id.clone()) {
+                                trace!(id = status.id.as_ref(); "dropped backfilled status");
+                                continue;
+                            }
+                            state.last_status_id = Some(status.id.clone());
+                        }
+                        return Some((StreamEvent::Event(event), state));
+                    }
+                    Some(Err(err)) => {
+                        state.phase = Phase::Backoff;
+                        return Some((StreamEvent::Disconnected(Some(err)), state));
+                    }
+                    None => {
+                        state.phase = Phase::Backoff;
+                        return Some((StreamEvent::Disconnected(None), state));
+                    }
+                },
+                Phase::Backoff => {
+                    state.attempt += 1;
+                    if state
+                        .options
+                        .max_attempts
+                        .is_some_and(|max| state.attempt > max)
+                    {
+                        debug!(attempts = state.attempt - 1; "giving up reconnecting");
+                        return None;
+                    }
+                    let delay = state.options.delay(state.attempt);
+                    state.phase = Phase::Wait(delay);
+                    let attempt = state.attempt;
+                    return Some((StreamEvent::Retrying { attempt, delay }, state));
+                }
+                Phase::Wait(delay) => {
+                    crate::helpers::time::sleep(delay).await;
+                    state.phase = Phase::Connect;
+                }
+            }
+        }
+    })
+}
+
+impl State<'_> {
+    /// Queue up the statuses posted since the last one seen, oldest first.
+    async fn backfill(&mut self) {
+        let (Some(last), Some(path)) = (
+            self.last_status_id.clone(),
+            self.options.channel.timeline_path(),
+        ) else {
+            return;
+        };
+        for status in fetch_gap(self.client, &path, last).await {
+            if self.seen.insert(status.id.clone()) {
+                self.backfilled.push_back(status);
+            }
+        }
+    }
+}
+
+/// Fetch a page of the timeline at `path`, newest first, logging failures.
+pub(crate) async fn fetch_timeline(
+    client: &Mastodon,
+    path: &str,
+    query: &str,
+) -> Option<Vec<Status>> {
+    let url = client.route(format!("/api/v1/timelines/{path}{query}"));
+    debug!(url = url; "catching up on timeline");
+    let statuses: Result<Vec<Status>> = async {
+        let request = client
+            .authenticated(client.client.get(&url))
+            .header("Accept", "application/json");
+        let response = client.send(request).await?;
+        read_response(response).await
+    }
+    .await;
+    statuses
+        .map_err(|err| warn!(err:? = err, url = url; "failed to catch up on timeline"))
+        .ok()
+}
+
+/// Fetch the statuses of the timeline at `path` posted after `min_id`,
+/// oldest first, paging forward until there are no more, or
+/// [`MAX_GAP_PAGES`] pages were fetched.
+pub(crate) async fn fetch_gap(client: &Mastodon, path: &str, mut min_id: StatusId) -> Vec<Status> {
+    let mut gap = vec![];
+    for _ in 0..MAX_GAP_PAGES {
+        let query = format!("min_id={}&limit={PAGE_SIZE}", encode(min_id.as_ref()));
+        let Some(statuses) = fetch_timeline(client, path, &query).await else {
+            return gap;
+        };
+        let Some(page_newest) = statuses.first() else {
+            return gap;
+        };
+        min_id = page_newest.id.clone();
+        let full = statuses.len() >= PAGE_SIZE;
+        gap.extend(statuses.into_iter().rev());
+        if !full {
+            return gap;
+        }
+    }
+    warn!(min_id = min_id.as_ref(); "gave up filling the gap in the timeline");
+    gap
+}
+
+/// The next event or heartbeat, or a timeout error if the connection has
+/// been idle for too long.
+async fn next(stream: &mut Events, options: &StreamOptions) -> Option<Result<Option<Event>>> {
+    let Some(idle_timeout) = options.idle_timeout else {
+        return stream.next().await;
+    };
+    match crate::helpers::time::timeout(idle_timeout, stream.next()).await {
+        Ok(next) => next,
+        Err(_) => Some(Err(Error::Io(io::Error::new(
+            io::ErrorKind::TimedOut,
+            format!("nothing received from the server for {idle_timeout:?}"),
+        )))),
+    }
+}
+
+/// Open a Server-Sent Events stream of the channel, after checking the
+/// health of the streaming API if requested.
+async fn connect(client: &Mastodon, options: &StreamOptions) -> Result<Events> {
+    if options.health_check {
+        client.streaming_health().await?;
+    }
+    let url = client.route(format!("/api/v1/streaming/{}", options.channel.sse_path()));
+    let request = client
+        .authenticated(client.client.get(&url))
+        .header("Accept", "application/json");
+    let response = client.send(request).await?;
+    debug!(
+        status:serde = crate::helpers::log::Status::from(&response), url = &url,
+        headers:serde = crate::helpers::log::Headers::from(&response);
+        "received API response"
+    );
+    let status = response.status();
+    if status.is_success() {
+        let events = events_and_heartbeats(response, url).into_stream();
+        #[cfg(not(target_arch = "wasm32"))]
+        return Ok(events.boxed());
+        #[cfg(target_arch = "wasm32")]
+        return Ok(events.boxed_local());
+    } else {
+        Err(Error::from_error_response(response).await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sse_paths() {
+        assert_eq!(Channel::User.sse_path(), "user");
+        assert_eq!(Channel::PublicLocalMedia.sse_path(), "public/local/media");
+        assert_eq!(
+            Channel::HashtagLocal("#café".into()).sse_path(),
+            "hashtag/local?tag=caf%C3%A9"
+        );
+        assert_eq!(
+            Channel::List(ListId::new("12345")).sse_path(),
+            "list?list=12345"
+        );
+    }
+
+    #[test]
+    fn test_timeline_paths() {
+        assert_eq!(Channel::User.timeline_path().unwrap(), "home?");
+        assert_eq!(
+            Channel::PublicRemoteMedia.timeline_path().unwrap(),
+            "public?remote=true&only_media=true&"
+        );
+        assert_eq!(
+            Channel::Hashtag("bots".into()).timeline_path().unwrap(),
+            "tag/bots?"
+        );
+        assert!(Channel::Direct.timeline_path().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_idle_timeout() {
+        let mut options = StreamOptions::new(Channel::User);
+        let mut heartbeats = futures::stream::iter([Ok(None)])
+            .chain(futures::stream::pending())
+            .boxed();
+        options.idle_timeout(Duration::from_millis(10));
+        assert!(matches!(
+            next(&mut heartbeats, &options).await,
+            Some(Ok(None))
+        ));
+        let Some(Err(Error::Io(err))) = next(&mut heartbeats, &options).await else {
+            panic!("expected a timeout")
+        };
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn test_backfill() {
+        use crate::{
+            helpers::fixtures,
+            timeline::tests::{home, home_queries},
+        };
+
+        let home = home();
+        let client = fixtures::client(home.clone());
+        let mut options = StreamOptions::new(Channel::User);
+        options
+            .initial_delay(Duration::from_millis(1))
+            .max_attempts(1)
+            .backfill();
+        let events: Vec<String> = client
+            .stream_reconnecting(&options)
+            .filter_map(|event| async move {
+                match event {
+                    StreamEvent::Event(Event::Update(status)) => Some(status.id.to_string()),
+                    StreamEvent::Connected => Some("connected".to_string()),
+                    _ => None,
+                }
+            })
+            .collect()
+            .await;
+        assert_eq!(events, ["connected", "3", "4", "connected", "5", "6", "7"]);
+        assert_eq!(home_queries(&home), ["min_id=4&limit=40"]);
+    }
+
+    #[test]
+    fn test_delay() {
+        let mut options = StreamOptions::new(Channel::User);
+        options
+            .initial_delay(Duration::from_secs(1))
+            .max_delay(Duration::from_secs(10));
+        for _ in 0..100 {
+            let first = options.delay(1);
+            assert!(first >= Duration::from_millis(500) && first <= Duration::from_secs(1));
+            let third = options.delay(3);
+            assert!(third >= Duration::from_secs(2) && third <= Duration::from_secs(4));
+            let capped = options.delay(30);
+            assert!(capped >= Duration::from_secs(5) && capped <= Duration::from_secs(10));
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use reqwest::{
+    header::{HeaderMap, HeaderValue, CONTENT_TYPE},
+    Method, Request, StatusCode,
+};
+use serde::Serialize;
+use url::Url;
+
+use crate::{
+    transport::{ResponseFuture, Transport},
+    Data, Error, Mastodon,
+};
+
+/// A fake Mastodon instance for unit-testing applications built on this
+/// crate without a network.
+///
+/// [`MockMastodon::client`] creates a real [`Mastodon`] client whose
+/// requests are answered with responses queued on the mock, in the order
+/// they were queued, regardless of which endpoint is requested. Every
+/// request is recorded, so that tests can check what the application sent.
+///
+/// // Example
+///
+/// ```
+/// use mastodon_async::{prelude::*, testing::MockMastodon};
+/// use reqwest::Method;
+///
+/// tokio_test::block_on(async {
+///     let mock = MockMastodon::new();
+///     mock.respond_with(&vec!["mastodon.example"]);
+///     let client = mock.client();
+///     let peers = client.instance_peers().await.unwrap();
+///     assert_eq!(peers, vec!["mastodon.example".to_string()]);
+///     mock.assert_requested(Method::GET, "/api/v1/instance/peers");
+/// });
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MockMastodon {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    responses: VecDeque<http::Response<Vec<u8>>>,
+    requests: Vec<RecordedRequest>,
+}
+
+/// A request received by a [`MockMastodon`].
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    /// The request method
+    pub method: Method,
+    /// The full URL requested, including the query
+    pub url: Url,
+    /// The request headers
+    pub headers: HeaderMap,
+    /// The request body, unless there was none or it was streamed, as
+    /// uploads are
+    pub body: Option<Vec<u8>>,
+}
+
+impl RecordedRequest {
+    /// The request body parsed as JSON, if it was JSON.
+    pub fn json<T: serde::de::DeserializeOwned>(&self) -> Option<T> {
+        serde_json::from_slice(self.body.as_deref()?).ok()
+    }
+
+    /// The request body parsed as a url-encoded form, if it was one.
+    pub fn form<T: serde::de::DeserializeOwned>(&self) -> Option<T> {
+        serde_urlencoded::from_bytes(self.body.as_deref()?).ok()
+    }
+}
+
+impl MockMastodon {
+    /// The base URL of the fake instance.
+    pub const BASE: &'static str = "https://mastodon.example";
+    /// The access token the client created by [`MockMastodon::client`]
+    /// authenticates with.
+    pub const TOKEN: &'static str = "test-token";
+
+    /// A mock with no responses queued.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A client for the fake instance, authenticated with
+    /// [`MockMastodon::TOKEN`].
+    pub fn client(&self) -> Mastodon {
+        let data = Data {
+            base: Self::BASE.into(),
+            token: Self::TOKEN.into(),
+            ..Data::default()
+        };
+        self.client_with(data)
+    }
+
+    /// A client for the instance and access token in `data`, which sends its
+    /// requests to the mock.
+    pub fn client_with(&self, data: Data) -> Mastodon {
+        Mastodon::builder(data)
+            .transport(self.clone())
+            .build()
+            .expect("a client without a custom user agent can always be built")
+    }
+
+    /// Queue a successful response with `entity` as its JSON body.
+    pub fn respond_with<T: Serialize>(&self, entity: &T) -> &Self {
+        let body = serde_json::to_vec(entity).expect("entities can be serialized");
+        self.respond_with_status(StatusCode::OK, body)
+    }
+
+    /// Queue a response with `status` and a JSON `body`, e.g. an API error
+    /// such as `{"error":"Record not found"}`.
+    pub fn respond_with_status(&self, status: StatusCode, body: impl Into<Vec<u8>>) -> &Self {
+        let mut response = http::Response::new(body.into());
+        *response.status_mut() = status;
+        response
+            .headers_mut()
+            .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        self.respond(response)
+    }
+
+    /// Queue `response` as it is, e.g. to include a `Link` header for
+    /// pagination.
+    pub fn respond(&self, response: http::Response<Vec<u8>>) -> &Self {
+        self.lock().responses.push_back(response);
+        self
+    }
+
+    /// Every request received so far, oldest first.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.lock().requests.clone()
+    }
+
+    /// The most recent request received, if any.
+    pub fn last_request(&self) -> Option<RecordedRequest> {
+        self.lock().requests.last().cloned()
+    }
+
+    /// Panic unless a `method` request for `path` has been received, and
+    /// return the most recent one.
+    pub fn assert_requested(&self, method: Method, path: &str) -> RecordedRequest {
+        let state = self.lock();
+        let request = state
+            .requests
+            .iter()
+            .rev()
+            .find(|request| request.method == method && request.url.path() == path)
+            .cloned();
+        request.unwrap_or_else(|| {
+            let requested: Vec<_> = state
+                .requests
+                .iter()
+                .map(|request| format!("{} {}", request.method, request.url.path()))
+                .collect();
+            panic!("no {method} request for {path}, requests received: {requested:?}")
+        })
+    }
+
+    /// Whether every queued response has been used.
+    pub fn is_exhausted(&self) -> bool {
+        self.lock().responses.is_empty()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl Transport for MockMastodon {
+    fn execute(&self, request: Request) -> ResponseFuture<'_> {
+        let mut state = self.lock();
+        state.requests.push(RecordedRequest {
+            method: request.method().clone(),
+            url: request.url().clone(),
+            headers: request.headers().clone(),
+            body: request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .map(<[u8]>::to_vec),
+        });
+        let result = match state.responses.pop_front() {
+            Some(response) => Ok(response.into()),
+            None => Err(Error::Other(format!(
+                "no response queued for {} {}",
+                request.method(),
+                request.url()
+            ))),
+        };
+        Box::pin(futures::future::ready(result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NewStatusBuilder;
+
+    #[tokio::test]
+    async fn test_mock() {
+        let mock = MockMastodon::new();
+        mock.respond_with(&vec!["mastodon.example"])
+            .respond_with_status(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                r#"{"error":"Validation failed: Text can't be blank"}"#,
+            );
+        let client = mock.client();
+        let peers = client.instance_peers().await.unwrap();
+        assert_eq!(peers, vec!["mastodon.example".to_string()]);
+        let new_status = NewStatusBuilder::default().status("hello").build().unwrap();
+        assert!(client.new_status(new_status).await.is_err());
+        assert!(mock.is_exhausted());
+        assert!(client.instance_peers().await.is_err());
+
+        let request = mock.assert_requested(Method::POST, "/api/v1/statuses");
+        assert_eq!(
+            request.headers["authorization"],
+            format!("Bearer {}", MockMastodon::TOKEN)
+        );
+        let body: serde_json::Value = request.json().unwrap();
+        assert_eq!(body["status"], "hello");
+        assert_eq!(mock.requests().len(), 3);
+        assert_eq!(
+            mock.last_request().unwrap().url.path(),
+            "/api/v1/instance/peers"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "no DELETE request for /api/v1/statuses/1")]
+    fn test_assert_requested() {
+        MockMastodon::new().assert_requested(Method::DELETE, "/api/v1/statuses/1");
+    }
+}
@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+
+use crate::entities::{context::Context, status::Status, StatusId};
+
+/// A conversation, arranged as a tree of replies, as returned by
+/// [`Mastodon::resolve_thread`](crate::Mastodon::resolve_thread).
+///
+/// The thread is built around the status it was resolved for, the focus,
+/// from the statuses it replied to and the replies to it. Replies to the
+/// same status are ordered from oldest to newest.
+///
+/// // Example
+///
+/// ```no_run
+/// use mastodon_async::prelude::*;
+///
+/// tokio_test::block_on(async {
+///     let client = Mastodon::from(Data::default());
+///     let thread = client.resolve_thread("109348238547925380", false).await.unwrap();
+///     for (depth, status) in thread.walk() {
+///         println!("{}{}: {}", "  ".repeat(depth), status.account.acct, status.content);
+///     }
+/// });
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Thread {
+    focus: StatusId,
+    statuses: HashMap<StatusId, Status>,
+    /// The IDs of the replies to each status, oldest first.
+    children: HashMap<StatusId, Vec<StatusId>>,
+}
+
+impl Thread {
+    /// Arrange `focus` and its `context` into a tree.
+    pub fn new(focus: Status, context: Context) -> Self {
+        let mut thread = Self {
+            focus: focus.id.clone(),
+            statuses: HashMap::new(),
+            children: HashMap::new(),
+        };
+        for status in context
+            .ancestors
+            .into_iter()
+            .chain([focus])
+            .chain(context.descendants)
+        {
+            thread.statuses.insert(status.id.clone(), status);
+        }
+        thread.link();
+        thread
+    }
+
+    /// Add a status, e.g. one which was missing from the context, and link
+    /// it into the tree.
+    pub fn insert(&mut self, status: Status) {
+        self.statuses.insert(status.id.clone(), status);
+        self.link();
+    }
+
+    fn link(&mut self) {
+        let mut statuses: Vec<&Status> = self.statuses.values().collect();
+        statuses.sort_by(|a, b| (a.created_at, a.id.as_ref()).cmp(&(b.created_at, b.id.as_ref())));
+        self.children.clear();
+        for status in statuses {
+            if let Some(parent) = self.parent_id(status) {
+                self.children
+                    .entry(parent.clone())
+                    .or_default()
+                    .push(status.id.clone());
+            }
+        }
+    }
+
+    /// The ID of the status `status` replied to, if it's in the thread.
+    fn parent_id<'a>(&self, status: &'a Status) -> Option<&'a StatusId> {
+        status
+            .in_reply_to_id
+            .as_ref()
+            .filter(|parent| self.statuses.contains_key(*parent))
+    }
+
+    /// The status the thread was resolved for.
+    pub fn focus(&self) -> &Status {
+        &self.statuses[&self.focus]
+    }
+
+    /// The status with the ID `id`, if it's in the thread.
+    pub fn get(&self, id: &StatusId) -> Option<&Status> {
+        self.statuses.get(id)
+    }
+
+    /// The status that the status `id` replied to, if it's in the thread.
+    pub fn parent(&self, id: &StatusId) -> Option<&Status> {
+        let parent = self.parent_id(self.statuses.get(id)?)?;
+        self.statuses.get(parent)
+    }
+
+    /// The replies to the status `id`, oldest first.
+    pub fn children(&self, id: &StatusId) -> impl Iterator<Item = &Status> {
+        self.children
+            .get(id)
+            .into_iter()
+            .flatten()
+            .map(|child| &self.statuses[child])
+    }
+
+    /// The top of the thread: the earliest status the focus is a reply to,
+    /// or the focus itself if it isn't a reply.
+    pub fn root(&self) -> &Status {
+        let mut root = self.focus();
+        while let Some(parent) = self.parent(&root.id) {
+            root = parent;
+        }
+        root
+    }
+
+    /// The IDs of statuses which statuses in the thread replied to, but
+    /// which aren't in the thread themselves, e.g. because the context the
+    /// server returned was cut short.
+    pub fn missing_parents(&self) -> Vec<StatusId> {
+        let mut missing: Vec<StatusId> = self
+            .statuses
+            .values()
+            .filter_map(|status| status.in_reply_to_id.as_ref())
+            .filter(|parent| !self.statuses.contains_key(*parent))
+            .cloned()
+            .collect();
+        missing.sort_by(|a, b| a.as_ref().cmp(b.as_ref()));
+        missing.dedup();
+        missing
+    }
+
+    /// Every status in the thread, depth first from the top of each
+    /// branch, with how deeply it's nested, ready to be rendered.
+    pub fn walk(&self) -> impl Iterator<Item = (usize, &Status)> {
+        let mut tops: Vec<&Status> = self
+            .statuses
+            .values()
+            .filter(|status| self.parent_id(status).is_none())
+            .collect();
+        tops.sort_by(|a, b| (a.created_at, a.id.as_ref()).cmp(&(b.created_at, b.id.as_ref())));
+        let mut walked = Vec::with_capacity(self.statuses.len());
+        let mut stack: Vec<(usize, &Status)> = tops.into_iter().rev().map(|s| (0, s)).collect();
+        while let Some((depth, status)) = stack.pop() {
+            walked.push((depth, status));
+            let children: Vec<&Status> = self.children(&status.id).collect();
+            stack.extend(children.into_iter().rev().map(|child| (depth + 1, child)));
+        }
+        walked.into_iter()
+    }
+
+    /// How many statuses are in the thread.
+    pub fn len(&self) -> usize {
+        self.statuses.len()
+    }
+
+    /// Whether the thread is empty, which it never is, as it always
+    /// includes the focus.
+    pub fn is_empty(&self) -> bool {
+        self.statuses.is_empty()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+    use url::Url;
+
+    use super::*;
+    use crate::entities::{account::Account, AccountId};
+
+    pub(crate) fn status(id: &str, in_reply_to_id: Option<&str>, minute: u32) -> Status {
+        let alice = Account::builder()
+            .id("1")
+            .username("alice")
+            .acct("alice")
+            .display_name("Alice")
+            .build()
+            .expect("account");
+        let created_at = format!("2023-01-01T00:{minute:02}:00Z");
+        Status::builder()
+            .id(id)
+            .uri(
+                Url::parse(&format!(
+                    "https://mastodon.example/users/alice/statuses/{id}"
+                ))
+                .expect("uri"),
+            )
+            .account(alice)
+            .in_reply_to_id(in_reply_to_id.map(StatusId::new))
+            .in_reply_to_account_id(in_reply_to_id.map(|_| AccountId::new("1")))
+            .content(format!("<p>{id}</p>"))
+            .created_at(OffsetDateTime::parse(&created_at, &Rfc3339).expect("created_at"))
+            .build()
+            .expect("status")
+    }
+
+    fn ids<'a>(statuses: impl Iterator<Item = &'a Status>) -> Vec<&'a str> {
+        statuses.map(|status| status.id.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_tree() {
+        // 1 <- 2 <- 3 (focus) <- {5, 4 <- 6}, and 8 replying to a missing 7
+        let context = Context {
+            ancestors: vec![status("1", None, 1), status("2", Some("1"), 2)],
+            descendants: vec![
+                status("4", Some("3"), 4),
+                status("6", Some("4"), 6),
+                status("5", Some("3"), 5),
+                status("8", Some("7"), 8),
+            ],
+        };
+        let mut thread = Thread::new(status("3", Some("2"), 3), context);
+        assert_eq!(thread.len(), 7);
+        assert_eq!(thread.focus().id.as_ref(), "3");
+        assert_eq!(thread.root().id.as_ref(), "1");
+        assert_eq!(thread.parent(&StatusId::new("3")).unwrap().id.as_ref(), "2");
+        assert!(thread.parent(&StatusId::new("1")).is_none());
+        assert_eq!(ids(thread.children(&StatusId::new("3"))), ["4", "5"]);
+        assert_eq!(thread.missing_parents(), [StatusId::new("7")]);
+        let walked: Vec<(usize, &str)> = thread
+            .walk()
+            .map(|(depth, status)| (depth, status.id.as_ref()))
+            .collect();
+        assert_eq!(
+            walked,
+            [
+                (0, "1"),
+                (1, "2"),
+                (2, "3"),
+                (3, "4"),
+                (4, "6"),
+                (3, "5"),
+                (0, "8")
+            ]
+        );
+
+        thread.insert(status("7", Some("5"), 7));
+        assert!(thread.missing_parents().is_empty());
+        assert_eq!(ids(thread.children(&StatusId::new("5"))), ["7"]);
+        assert_eq!(thread.walk().last().map(|(depth, _)| depth), Some(5));
+    }
+}
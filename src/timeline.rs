@@ -0,0 +1,310 @@
+use std::collections::VecDeque;
+
+use futures::{stream::unfold, Stream, StreamExt};
+use log::trace;
+
+use crate::{
+    dedup::Seen,
+    entities::{event::Event, status::Status, StatusId},
+    errors::{Error, Result},
+    streaming::{fetch_gap, fetch_timeline, StreamEvent, StreamOptions, PAGE_SIZE, SEEN_CAPACITY},
+    Mastodon,
+};
+
+/// A timeline which is kept up to date, as returned by
+/// [`Mastodon::timeline`]: its recent history, fetched from the REST API,
+/// followed by the statuses posted since, as they're streamed.
+///
+/// Whenever the stream reconnects, the statuses posted while it was
+/// disconnected are fetched, oldest first, from just after the newest one
+/// seen, up to 400 of them. Statuses which were already seen, e.g. because
+/// they were both fetched and streamed, are dropped, so that each status
+/// appears once, in the order it was posted.
+///
+/// // Example
+///
+/// ```no_run
+/// use futures_util::{pin_mut, StreamExt};
+/// use mastodon_async::{
+///     prelude::*,
+///     streaming::{Channel, StreamOptions},
+///     timeline::TimelineEvent,
+/// };
+///
+/// tokio_test::block_on(async {
+///     let client = Mastodon::from(Data::default());
+///     let mut timeline = client.timeline(&StreamOptions::new(Channel::User)).unwrap();
+///     timeline.history(40);
+///     let events = timeline.stream();
+///     pin_mut!(events);
+///     while let Some(event) = events.next().await {
+///         match event {
+///             TimelineEvent::Status(status) => println!("{}", status.content),
+///             TimelineEvent::Deleted(id) => println!("{id} was deleted"),
+///             _ => {}
+///         }
+///     }
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct Timeline {
+    client: Mastodon,
+    options: StreamOptions,
+    path: String,
+    history: usize,
+}
+
+/// An item from a [`Timeline`] stream.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug)]
+pub enum TimelineEvent {
+    /// A status which wasn't in the timeline before, from its history, the
+    /// gap left while disconnected, or the stream.
+    Status(Status),
+    /// A status in the timeline was edited.
+    Edited(Status),
+    /// A status was deleted.
+    Deleted(StatusId),
+    /// The stream was (re-)connected. The statuses fetched to catch up
+    /// follow.
+    Connected,
+    /// The stream was disconnected, with the error, or `None` if the server
+    /// closed the stream. It is reconnected as configured in the
+    /// [`StreamOptions`] the timeline was made with.
+    Disconnected(Option<Error>),
+}
+
+impl Timeline {
+    /// Follow the timeline matching the channel of `options`, starting with
+    /// its 20 most recent statuses. Fails for the notification and direct
+    /// channels, which have no matching timeline.
+    ///
+    /// The timeline catches up after reconnecting by itself, so the
+    /// [`backfill`](StreamOptions::backfill) option is ignored.
+    pub fn new(client: Mastodon, options: &StreamOptions) -> Result<Self> {
+        let Some(path) = options.channel.timeline_path() else {
+            return Err(Error::Other(format!(
+                "the {} channel has no matching timeline",
+                options.channel
+            )));
+        };
+        let mut options = options.clone();
+        options.backfill = false;
+        Ok(Self {
+            client,
+            options,
+            path,
+            history: 20,
+        })
+    }
+
+    /// How many of the most recent statuses to start with, at most 40.
+    pub fn history(&mut self, limit: usize) -> &mut Self {
+        self.history = limit.min(PAGE_SIZE);
+        self
+    }
+
+    /// Stream the timeline. The stream only ends once the stream of the
+    /// channel gives up reconnecting.
+    pub fn stream(&self) -> impl Stream<Item = TimelineEvent> + '_ {
+        let state = State {
+            timeline: self,
+            events: Box::pin(crate::streaming::reconnecting(
+                &self.client,
+                self.options.clone(),
+            )),
+            queue: VecDeque::new(),
+            newest: None,
+            seen: Seen::new(SEEN_CAPACITY),
+        };
+        unfold(state, |mut state| async move {
+            loop {
+                if let Some(event) = state.queue.pop_front() {
+                    return Some((event, state));
+                }
+                let event = match state.events.next().await? {
+                    StreamEvent::Connected => {
+                        state.catch_up().await;
+                        TimelineEvent::Connected
+                    }
+                    StreamEvent::Disconnected(err) => TimelineEvent::Disconnected(err),
+                    StreamEvent::Retrying { .. } => continue,
+                    StreamEvent::Event(Event::Update(status)) => {
+                        if !state.insert(&status) {
+                            trace!(id = status.id.as_ref(); "dropped duplicate status");
+                            continue;
+                        }
+                        TimelineEvent::Status(status)
+                    }
+                    StreamEvent::Event(Event::StatusUpdate(status)) => {
+                        TimelineEvent::Edited(status)
+                    }
+                    StreamEvent::Event(Event::Delete(id)) => {
+                        TimelineEvent::Deleted(StatusId::new(id))
+                    }
+                    StreamEvent::Event(_) => continue,
+                };
+                return Some((event, state));
+            }
+        })
+    }
+}
+
+struct State<'a, S> {
+    timeline: &'a Timeline,
+    events: S,
+    queue: VecDeque<TimelineEvent>,
+    newest: Option<StatusId>,
+    seen: Seen<StatusId>,
+}
+
+impl<S> State<'_, S> {
+    /// Record `status` as the newest seen, returning whether it wasn't seen
+    /// before.
+    fn insert(&mut self, status: &Status) -> bool {
+        if !self.seen.insert(status.id.clone()) {
+            return false;
+        }
+        self.newest = Some(status.id.clone());
+        true
+    }
+
+    /// Queue up the statuses posted since the newest one seen, oldest
+    /// first, or the timeline's history if none was seen yet.
+    async fn catch_up(&mut self) {
+        let Some(newest) = self.newest.clone() else {
+            if self.timeline.history > 0 {
+                let query = format!("limit={}", self.timeline.history);
+                let client = &self.timeline.client;
+                if let Some(statuses) = fetch_timeline(client, &self.timeline.path, &query).await {
+                    self.queue_statuses(statuses.into_iter().rev());
+                }
+            }
+            return;
+        };
+        let gap = fetch_gap(&self.timeline.client, &self.timeline.path, newest).await;
+        self.queue_statuses(gap);
+    }
+
+    /// Queue statuses which weren't seen before, oldest first.
+    fn queue_statuses(&mut self, statuses: impl IntoIterator<Item = Status>) {
+        for status in statuses {
+            if self.insert(&status) {
+                self.queue.push_back(TimelineEvent::Status(status));
+            }
+        }
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+pub(crate) mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use reqwest::StatusCode;
+
+    use super::*;
+    use crate::{
+        helpers::fixtures::{self, Fake, Reply},
+        streaming::Channel,
+        thread::tests::status,
+        Data,
+    };
+
+    fn timeline(ids: &[&str]) -> String {
+        let statuses: Vec<Status> = ids.iter().map(|id| status(id, None, 0)).collect();
+        serde_json::to_string(&statuses).unwrap()
+    }
+
+    fn update(id: &str) -> String {
+        let status = serde_json::to_string(&status(id, None, 0)).unwrap();
+        format!("event: update\ndata: {status}\n\n")
+    }
+
+    /// A fake serving the home timeline and, for the first two connections,
+    /// a user stream which is closed after a few events.
+    pub(crate) fn home() -> Arc<Fake> {
+        let connections = AtomicUsize::new(0);
+        Fake::new(move |request| match request.url().path() {
+            "/api/v1/timelines/home" => match request.url().query().unwrap_or_default() {
+                "limit=3" => Reply::ok(timeline(&["3", "2", "1"])),
+                "min_id=4&limit=40" => Reply::ok(timeline(&["6", "5"])),
+                _ => Reply::ok("[]"),
+            },
+            "/api/v1/streaming/user" => match connections.fetch_add(1, Ordering::SeqCst) {
+                0 => Reply::ok(format!(
+                    "{}{}event: delete\ndata: 2\n\n:thump\n",
+                    update("3"),
+                    update("4")
+                )),
+                1 => Reply::ok(format!("{}{}", update("6"), update("7"))),
+                _ => Reply::status(StatusCode::BAD_GATEWAY, "{}"),
+            },
+            _ => Reply::not_found(),
+        })
+    }
+
+    /// The queries the home timeline was requested with from `home`.
+    pub(crate) fn home_queries(home: &Fake) -> Vec<String> {
+        home.requests()
+            .iter()
+            .filter(|request| request.url.path() == "/api/v1/timelines/home")
+            .map(|request| request.url.query().unwrap_or_default().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_backfill_stream_and_fill_gap() {
+        let home = home();
+        let client = fixtures::client(home.clone());
+        let mut options = StreamOptions::new(Channel::User);
+        options
+            .initial_delay(Duration::from_millis(1))
+            .max_attempts(1);
+        let mut timeline = client.timeline(&options).unwrap();
+        timeline.history(3);
+        let events: Vec<String> = timeline
+            .stream()
+            .map(|event| match event {
+                TimelineEvent::Status(status) => status.id.to_string(),
+                TimelineEvent::Edited(status) => format!("edited {}", status.id),
+                TimelineEvent::Deleted(id) => format!("deleted {id}"),
+                TimelineEvent::Connected => "connected".to_string(),
+                TimelineEvent::Disconnected(_) => "disconnected".to_string(),
+            })
+            .collect()
+            .await;
+        assert_eq!(
+            events,
+            [
+                "connected",
+                "1",
+                "2",
+                "3",
+                "4",
+                "deleted 2",
+                "disconnected",
+                "connected",
+                "5",
+                "6",
+                "7",
+                "disconnected",
+                "disconnected",
+            ]
+        );
+        assert_eq!(home_queries(&home), ["limit=3", "min_id=4&limit=40"]);
+    }
+
+    #[test]
+    fn test_no_matching_timeline() {
+        let client = Mastodon::from(Data::default());
+        assert!(client
+            .timeline(&StreamOptions::new(Channel::Direct))
+            .is_err());
+    }
+}
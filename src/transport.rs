@@ -0,0 +1,130 @@
+use std::fmt;
+
+use reqwest::{Client, Request, Response};
+
+use crate::errors::Result;
+
+/// The future returned by [`Transport::execute`]. It must be `Send`, except
+/// on WebAssembly, where the browser's fetch API can't be used from other
+/// threads.
+#[cfg(not(target_arch = "wasm32"))]
+pub type ResponseFuture<'a> = futures::future::BoxFuture<'a, Result<Response>>;
+/// The future returned by [`Transport::execute`]. It must be `Send`, except
+/// on WebAssembly, where the browser's fetch API can't be used from other
+/// threads.
+#[cfg(target_arch = "wasm32")]
+pub type ResponseFuture<'a> = futures::future::LocalBoxFuture<'a, Result<Response>>;
+
+/// Sends the HTTP requests made by a [`Mastodon`](crate::Mastodon) client.
+///
+/// By default requests are sent with a [`reqwest::Client`], but any other
+/// HTTP stack can be plugged in with
+/// [`MastodonBuilder::transport`](crate::builder::MastodonBuilder::transport),
+/// e.g. to use a different client library, or to answer requests without a
+/// network in tests.
+///
+/// Requests and responses are represented with reqwest's types, which can be
+/// converted from and to those of the [`http`] crate. A response body can be
+/// streamed by wrapping the stream with [`reqwest::Body::wrap_stream`].
+///
+/// // Example
+///
+/// ```
+/// use mastodon_async::{prelude::*, transport::{ResponseFuture, Transport}};
+/// use reqwest::{Request, Response};
+///
+/// #[derive(Debug)]
+/// struct NoPeers;
+///
+/// impl Transport for NoPeers {
+///     fn execute(&self, request: Request) -> ResponseFuture<'_> {
+///         Box::pin(async move {
+///             assert_eq!(request.url().path(), "/api/v1/instance/peers");
+///             Ok(http::Response::new("[]").into())
+///         })
+///     }
+/// }
+///
+/// tokio_test::block_on(async {
+///     let data = Data {
+///         base: "https://example.com".into(),
+///         ..Data::default()
+///     };
+///     let client = Mastodon::builder(data).transport(NoPeers).build().unwrap();
+///     assert!(client.instance_peers().await.unwrap().is_empty());
+/// });
+/// ```
+pub trait Transport: fmt::Debug + Send + Sync {
+    /// Send `request`, returning the response once its status and headers
+    /// have been received.
+    fn execute(&self, request: Request) -> ResponseFuture<'_>;
+}
+
+impl Transport for Client {
+    fn execute(&self, request: Request) -> ResponseFuture<'_> {
+        Box::pin(async move { Ok(Client::execute(self, request).await?) })
+    }
+}
+
+/// Sends requests to some hosts with one client, and to every other host
+/// with another.
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    any(feature = "native-tls", feature = "rustls-tls")
+))]
+#[derive(Debug)]
+pub(crate) struct PerHost {
+    pub(crate) hosts: Vec<String>,
+    pub(crate) matching: Client,
+    pub(crate) other: Client,
+}
+
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    any(feature = "native-tls", feature = "rustls-tls")
+))]
+impl PerHost {
+    fn matches(&self, url: &url::Url) -> bool {
+        url.host_str().is_some_and(|host| {
+            self.hosts
+                .iter()
+                .any(|matching| matching.eq_ignore_ascii_case(host))
+        })
+    }
+}
+
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    any(feature = "native-tls", feature = "rustls-tls")
+))]
+impl Transport for PerHost {
+    fn execute(&self, request: Request) -> ResponseFuture<'_> {
+        let client = if self.matches(request.url()) {
+            &self.matching
+        } else {
+            &self.other
+        };
+        Transport::execute(client, request)
+    }
+}
+
+#[cfg(all(
+    test,
+    not(target_arch = "wasm32"),
+    any(feature = "native-tls", feature = "rustls-tls")
+))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_per_host() {
+        let transport = PerHost {
+            hosts: vec!["Mastodon.Local".to_string()],
+            matching: Client::new(),
+            other: Client::new(),
+        };
+        assert!(transport.matches(&"https://mastodon.local/api/v1/instance".parse().unwrap()));
+        assert!(!transport.matches(&"https://mastodon.example/api/v1/instance".parse().unwrap()));
+        assert!(!transport.matches(&"https://sub.mastodon.local/".parse().unwrap()));
+    }
+}
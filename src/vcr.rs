@@ -0,0 +1,349 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use reqwest::{
+    header::{HeaderName, HeaderValue},
+    Request, Response, StatusCode,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::Result,
+    transport::{ResponseFuture, Transport},
+    Data, Error, Mastodon,
+};
+
+/// What secrets are replaced with in a cassette.
+const REDACTED: &str = "[REDACTED]";
+
+/// Records the HTTP interactions of a [`Mastodon`] client to a file, and
+/// replays them later, so that tests of applications built on this crate
+/// can run against a real instance once and then offline, with the same
+/// results every time.
+///
+/// When recording, the client's access token and client secret, and any
+/// other secrets passed to [`Cassette::redact`], are replaced with
+/// `[REDACTED]` before anything is written, and request headers aren't
+/// recorded at all, so cassettes can be committed.
+///
+/// When replaying, requests must be made in the order they were recorded,
+/// with the same method, path and query, but may be sent to a different
+/// instance and with a different access token. Bodies are recorded as text,
+/// so this isn't suitable for downloading media, nor for streaming
+/// endpoints, whose responses never end.
+///
+/// // Example
+///
+/// ```no_run
+/// use mastodon_async::{prelude::*, vcr::Cassette};
+///
+/// # async fn run() -> mastodon_async::Result<()> {
+/// let path = "tests/cassettes/peers.json";
+/// let cassette = if std::env::var("RECORD").is_ok() {
+///     Cassette::record(path, reqwest::Client::new())
+/// } else {
+///     Cassette::replay(path)?
+/// };
+/// let client = cassette.client(Data::default());
+/// let peers = client.instance_peers().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Cassette {
+    path: Arc<PathBuf>,
+    /// Sends the requests while recording, or `None` when replaying.
+    recorder: Option<Arc<dyn Transport>>,
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    interactions: Vec<Interaction>,
+    /// The index of the next interaction to replay.
+    next: usize,
+    secrets: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Recording {
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedRequest {
+    method: String,
+    url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl Cassette {
+    /// Send requests with `transport`, e.g. a [`reqwest::Client`], and
+    /// record them to the file at `path`, replacing its contents. The file
+    /// is written after every interaction.
+    pub fn record(path: impl AsRef<Path>, transport: impl Transport + 'static) -> Self {
+        Self {
+            path: Arc::new(path.as_ref().to_path_buf()),
+            recorder: Some(Arc::new(transport)),
+            state: Arc::default(),
+        }
+    }
+
+    /// Answer requests with the interactions recorded to the file at
+    /// `path`.
+    pub fn replay(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let cassette: Recording = serde_json::from_slice(&fs::read(path)?)?;
+        Ok(Self {
+            path: Arc::new(path.to_path_buf()),
+            recorder: None,
+            state: Arc::new(Mutex::new(State {
+                interactions: cassette.interactions,
+                ..State::default()
+            })),
+        })
+    }
+
+    /// Whether interactions are being recorded, rather than replayed.
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    /// Replace `secret` wherever it appears in the recorded URLs, bodies and
+    /// response headers.
+    pub fn redact(&self, secret: impl Into<String>) -> &Self {
+        let secret = secret.into();
+        if !secret.is_empty() {
+            self.lock().secrets.push(secret);
+        }
+        self
+    }
+
+    /// A client for the instance and access token in `data`, whose requests
+    /// go through the cassette. The access token and client secret are
+    /// redacted.
+    pub fn client(&self, data: Data) -> Mastodon {
+        self.redact(data.token.clone())
+            .redact(data.client_secret.clone());
+        Mastodon::builder(data)
+            .transport(self.clone())
+            .build()
+            .expect("a client without a custom user agent can always be built")
+    }
+
+    /// Whether every recorded interaction has been replayed.
+    pub fn is_exhausted(&self) -> bool {
+        let state = self.lock();
+        state.next >= state.interactions.len()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    async fn record_one(&self, recorder: &dyn Transport, request: Request) -> Result<Response> {
+        let recorded_request = RecordedRequest {
+            method: request.method().to_string(),
+            url: request.url().to_string(),
+            body: request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .map(|body| String::from_utf8_lossy(body).into_owned()),
+        };
+        let response = recorder.execute(request).await?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.bytes().await?;
+        let recorded_response = RecordedResponse {
+            status: status.as_u16(),
+            headers: headers
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some((name.to_string(), value.to_str().ok()?.to_string()))
+                })
+                .collect(),
+            body: String::from_utf8_lossy(&body).into_owned(),
+        };
+        let cassette = {
+            let mut state = self.lock();
+            let interaction = state.redacted(Interaction {
+                request: recorded_request,
+                response: recorded_response,
+            });
+            state.interactions.push(interaction);
+            Recording {
+                interactions: state.interactions.clone(),
+            }
+        };
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(self.path.as_ref(), serde_json::to_vec_pretty(&cassette)?)?;
+        let mut response = http::Response::new(body);
+        *response.status_mut() = status;
+        *response.headers_mut() = headers;
+        Ok(response.into())
+    }
+
+    fn replay_one(&self, request: &Request) -> Result<Response> {
+        let mut state = self.lock();
+        let Some(interaction) = state.interactions.get(state.next).cloned() else {
+            return Err(Error::Other(format!(
+                "no recorded interaction left for {} {}",
+                request.method(),
+                request.url()
+            )));
+        };
+        let recorded_url: url::Url = interaction.request.url.parse()?;
+        let url = state.redact(request.url().as_str());
+        let url: url::Url = url.parse()?;
+        if interaction.request.method != request.method().as_str()
+            || recorded_url.path() != url.path()
+            || recorded_url.query() != url.query()
+        {
+            return Err(Error::Other(format!(
+                "{} {} doesn't match the next recorded interaction, {} {}",
+                request.method(),
+                request.url(),
+                interaction.request.method,
+                interaction.request.url
+            )));
+        }
+        state.next += 1;
+        let recorded = interaction.response;
+        let mut response = http::Response::new(recorded.body.into_bytes());
+        *response.status_mut() = StatusCode::from_u16(recorded.status)
+            .map_err(|err| Error::Other(format!("invalid recorded status: {err}")))?;
+        for (name, value) in recorded.headers {
+            let name = HeaderName::try_from(name)
+                .map_err(|err| Error::Other(format!("invalid recorded header: {err}")))?;
+            response
+                .headers_mut()
+                .append(name, HeaderValue::try_from(value)?);
+        }
+        Ok(response.into())
+    }
+}
+
+impl State {
+    fn redact(&self, text: &str) -> String {
+        self.secrets.iter().fold(text.to_string(), |text, secret| {
+            text.replace(secret, REDACTED)
+        })
+    }
+
+    fn redacted(&self, mut interaction: Interaction) -> Interaction {
+        interaction.request.url = self.redact(&interaction.request.url);
+        interaction.request.body = interaction.request.body.map(|body| self.redact(&body));
+        for (_, value) in &mut interaction.response.headers {
+            *value = self.redact(value);
+        }
+        interaction.response.body = self.redact(&interaction.response.body);
+        interaction
+    }
+}
+
+impl Transport for Cassette {
+    fn execute(&self, request: Request) -> ResponseFuture<'_> {
+        match &self.recorder {
+            Some(recorder) => Box::pin(self.record_one(recorder.as_ref(), request)),
+            None => Box::pin(futures::future::ready(self.replay_one(&request))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::fixtures::Server;
+
+    #[tokio::test]
+    async fn test_record_replay() {
+        let server = Server::bind().await;
+        let data = Data {
+            token: "secret-token".into(),
+            ..server.data()
+        };
+        let path =
+            std::env::temp_dir().join(format!("mastodon-async-vcr-{}.json", std::process::id()));
+        let cassette = Cassette::record(&path, reqwest::Client::new());
+        assert!(cassette.is_recording());
+        let client = cassette.client(data);
+        let (peers, request) = tokio::join!(
+            client.instance_peers(),
+            server.respond("200 OK", r#"["secret-token.example"]"#)
+        );
+        assert_eq!(peers.unwrap(), vec!["secret-token.example".to_string()]);
+        assert!(
+            request.contains("authorization: Bearer secret-token"),
+            "{request}"
+        );
+
+        let recorded = fs::read_to_string(&path).unwrap();
+        assert!(!recorded.contains("secret-token"), "{recorded}");
+        assert!(recorded.contains("[REDACTED].example"), "{recorded}");
+
+        let cassette = Cassette::replay(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        let client = cassette.client(Data {
+            base: "https://mastodon.example".into(),
+            ..Data::default()
+        });
+        let peers = client.instance_peers().await.unwrap();
+        assert_eq!(peers, vec!["[REDACTED].example".to_string()]);
+        assert!(cassette.is_exhausted());
+        assert!(client.instance_peers().await.is_err());
+    }
+
+    #[test]
+    fn test_replay_mismatch() {
+        let cassette = Cassette {
+            path: Arc::new(PathBuf::new()),
+            recorder: None,
+            state: Arc::new(Mutex::new(State {
+                interactions: vec![Interaction {
+                    request: RecordedRequest {
+                        method: "GET".to_string(),
+                        url: "https://mastodon.example/api/v1/instance/peers".to_string(),
+                        body: None,
+                    },
+                    response: RecordedResponse {
+                        status: 200,
+                        headers: vec![],
+                        body: "[]".to_string(),
+                    },
+                }],
+                ..State::default()
+            })),
+        };
+        let client = reqwest::Client::new();
+        let request = client
+            .get("https://other.example/api/v1/instance/activity")
+            .build()
+            .unwrap();
+        assert!(cassette.replay_one(&request).is_err());
+        let request = client
+            .get("https://other.example/api/v1/instance/peers")
+            .build()
+            .unwrap();
+        assert!(cassette.replay_one(&request).is_ok());
+        assert!(cassette.is_exhausted());
+    }
+}
@@ -0,0 +1,388 @@
+//! Receiving Web Push notifications.
+//!
+//! The instance encrypts the notifications it pushes to a subscription with
+//! the subscription's public key and authentication secret. [`PushKeys`]
+//! generates those, provides the [`Keys`] to subscribe with, and decrypts
+//! the messages the push endpoint receives, in either the standard
+//! `aes128gcm` encoding (RFC 8291) or the older `aesgcm` one, which
+//! Mastodon used before 4.4.0.
+//!
+//! ```no_run
+//! use mastodon_async::{prelude::*, requests::AddPushRequest, web_push::PushKeys};
+//!
+//! tokio_test::block_on(async {
+//!     let client = Mastodon::from(Data::default());
+//!     let keys = PushKeys::generate().unwrap();
+//!     // save keys.private_key() and keys.auth() to decrypt the pushes later
+//!     let request = AddPushRequest::new("https://push.example/endpoint", &keys.keys());
+//!     client.add_push_subscription(&request).await.unwrap();
+//! });
+//! ```
+
+use std::fmt;
+
+use aes_gcm::{aead::Aead, Aes128Gcm, KeyInit, Nonce};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hkdf::Hkdf;
+use p256::{ecdh::diffie_hellman, elliptic_curve::sec1::ToEncodedPoint, PublicKey, SecretKey};
+use reqwest::header::{HeaderMap, CONTENT_ENCODING};
+use sha2::Sha256;
+
+use crate::{entities::push::Notification, errors::Result, requests::Keys, Error};
+
+/// The length of the authentication secret, in bytes.
+const AUTH_LEN: usize = 16;
+/// The length of the authentication tag of each encrypted record, in bytes.
+const TAG_LEN: usize = 16;
+/// The record size used by the `aesgcm` encoding unless the `Encryption`
+/// header says otherwise.
+const AESGCM_RECORD_SIZE: usize = 4096;
+
+/// The private key and authentication secret of a Web Push subscription.
+///
+/// These must be kept to decrypt the notifications pushed to the
+/// subscription; see [`PushKeys::private_key`] and [`PushKeys::auth`].
+#[derive(Clone)]
+pub struct PushKeys {
+    secret: SecretKey,
+    auth: [u8; AUTH_LEN],
+}
+
+impl fmt::Debug for PushKeys {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PushKeys")
+            .field("public_key", &self.public_key())
+            .finish_non_exhaustive()
+    }
+}
+
+impl PushKeys {
+    /// Generate a new key pair and authentication secret.
+    pub fn generate() -> Result<Self> {
+        let mut auth = [0; AUTH_LEN];
+        getrandom::getrandom(&mut auth).map_err(std::io::Error::from)?;
+        loop {
+            let mut bytes = [0; 32];
+            getrandom::getrandom(&mut bytes).map_err(std::io::Error::from)?;
+            // Fails for the very few values which aren't valid scalars
+            if let Ok(secret) = SecretKey::from_slice(&bytes) {
+                return Ok(PushKeys { secret, auth });
+            }
+        }
+    }
+
+    /// Restore the keys saved from [`PushKeys::private_key`] and
+    /// [`PushKeys::auth`].
+    pub fn from_base64(private_key: &str, auth: &str) -> Result<Self> {
+        let secret = SecretKey::from_slice(&decode(private_key)?)
+            .map_err(|_| Error::WebPush("invalid private key"))?;
+        let auth = decode(auth)?
+            .try_into()
+            .map_err(|_| Error::WebPush("the authentication secret isn't 16 bytes long"))?;
+        Ok(PushKeys { secret, auth })
+    }
+
+    /// The private key, base64url encoded.
+    pub fn private_key(&self) -> String {
+        URL_SAFE_NO_PAD.encode(self.secret.to_bytes())
+    }
+
+    /// The authentication secret, base64url encoded.
+    pub fn auth(&self) -> String {
+        URL_SAFE_NO_PAD.encode(self.auth)
+    }
+
+    /// The public key, base64url encoded, as the instance expects it.
+    pub fn public_key(&self) -> String {
+        URL_SAFE_NO_PAD.encode(self.public_key_bytes())
+    }
+
+    /// The keys to pass to [`AddPushRequest::new`](crate::AddPushRequest::new).
+    pub fn keys(&self) -> Keys {
+        Keys::new(&self.public_key(), &self.auth())
+    }
+
+    fn public_key_bytes(&self) -> Vec<u8> {
+        self.secret
+            .public_key()
+            .to_encoded_point(false)
+            .as_bytes()
+            .to_vec()
+    }
+
+    /// Decrypt a message pushed to the subscription, given the headers and
+    /// body of the request the push endpoint received.
+    pub fn decrypt(&self, headers: &HeaderMap, body: &[u8]) -> Result<Vec<u8>> {
+        let encoding = headers
+            .get(CONTENT_ENCODING)
+            .ok_or(Error::WebPush("no Content-Encoding header"))?
+            .to_str()?
+            .trim();
+        if encoding.eq_ignore_ascii_case("aes128gcm") {
+            self.decrypt_aes128gcm(body)
+        } else if encoding.eq_ignore_ascii_case("aesgcm") {
+            let header = |name| -> Result<&str> {
+                Ok(headers
+                    .get(name)
+                    .ok_or(Error::WebPush(
+                        "the aesgcm encoding requires the Crypto-Key and Encryption headers",
+                    ))?
+                    .to_str()?)
+            };
+            let dh = param(header("Crypto-Key")?, "dh")
+                .ok_or(Error::WebPush("no dh key in the Crypto-Key header"))?;
+            let encryption = header("Encryption")?;
+            let salt = param(encryption, "salt")
+                .ok_or(Error::WebPush("no salt in the Encryption header"))?;
+            let record_size = match param(encryption, "rs") {
+                Some(rs) => rs
+                    .parse()
+                    .map_err(|_| Error::WebPush("invalid record size in the Encryption header"))?,
+                None => AESGCM_RECORD_SIZE,
+            };
+            self.decrypt_aesgcm(&decode(dh)?, &decode(salt)?, record_size, body)
+        } else {
+            Err(Error::WebPush("unsupported Content-Encoding"))
+        }
+    }
+
+    /// Decrypt a notification pushed to the subscription, like
+    /// [`PushKeys::decrypt`].
+    pub fn decrypt_notification(&self, headers: &HeaderMap, body: &[u8]) -> Result<Notification> {
+        Ok(serde_json::from_slice(&self.decrypt(headers, body)?)?)
+    }
+
+    fn shared_secret(&self, server_key: &[u8]) -> Result<[u8; 32]> {
+        let server_key = PublicKey::from_sec1_bytes(server_key)
+            .map_err(|_| Error::WebPush("invalid server public key"))?;
+        let shared = diffie_hellman(self.secret.to_nonzero_scalar(), server_key.as_affine());
+        Ok((*shared.raw_secret_bytes()).into())
+    }
+
+    fn decrypt_aes128gcm(&self, body: &[u8]) -> Result<Vec<u8>> {
+        const HEADER_LEN: usize = 21;
+        if body.len() < HEADER_LEN {
+            return Err(Error::WebPush("truncated aes128gcm header"));
+        }
+        let (salt, rest) = body.split_at(16);
+        let record_size = u32::from_be_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+        let key_id_len = rest[4] as usize;
+        let rest = &rest[5..];
+        if rest.len() < key_id_len {
+            return Err(Error::WebPush("truncated aes128gcm header"));
+        }
+        if record_size <= TAG_LEN + 1 {
+            return Err(Error::WebPush("invalid record size"));
+        }
+        let (server_key, ciphertext) = rest.split_at(key_id_len);
+
+        let user_agent_key = self.public_key_bytes();
+        let ikm: [u8; 32] = hkdf(
+            &self.auth,
+            &self.shared_secret(server_key)?,
+            &[b"WebPush: info\0", &user_agent_key, server_key],
+        );
+        let key: [u8; 16] = hkdf(salt, &ikm, &[b"Content-Encoding: aes128gcm\0"]);
+        let nonce: [u8; 12] = hkdf(salt, &ikm, &[b"Content-Encoding: nonce\0"]);
+
+        let records = ciphertext.chunks(record_size);
+        let count = records.len();
+        let mut plaintext = vec![];
+        for (seq, record) in records.enumerate() {
+            let mut record = decrypt_record(&key, &nonce, seq, record)?;
+            // Records end with a delimiter, 2 for the last and 1 for the
+            // others, followed by any padding
+            let delimiter = record
+                .iter()
+                .rposition(|&b| b != 0)
+                .ok_or(Error::WebPush("record without delimiter"))?;
+            let expected = if seq + 1 == count { 2 } else { 1 };
+            if record[delimiter] != expected {
+                return Err(Error::WebPush("invalid record delimiter"));
+            }
+            record.truncate(delimiter);
+            plaintext.extend(record);
+        }
+        Ok(plaintext)
+    }
+
+    fn decrypt_aesgcm(
+        &self,
+        server_key: &[u8],
+        salt: &[u8],
+        record_size: usize,
+        body: &[u8],
+    ) -> Result<Vec<u8>> {
+        if record_size < 2 {
+            return Err(Error::WebPush("invalid record size"));
+        }
+        let user_agent_key = self.public_key_bytes();
+        let ikm: [u8; 32] = hkdf(
+            &self.auth,
+            &self.shared_secret(server_key)?,
+            &[b"Content-Encoding: auth\0"],
+        );
+        let key_len = |key: &[u8]| (key.len() as u16).to_be_bytes();
+        let context: &[&[u8]] = &[
+            b"P-256\0",
+            &key_len(&user_agent_key),
+            &user_agent_key,
+            &key_len(server_key),
+            server_key,
+        ];
+        let key: [u8; 16] = hkdf(
+            salt,
+            &ikm,
+            &[&[b"Content-Encoding: aesgcm\0".as_slice()], context].concat(),
+        );
+        let nonce: [u8; 12] = hkdf(
+            salt,
+            &ikm,
+            &[&[b"Content-Encoding: nonce\0".as_slice()], context].concat(),
+        );
+
+        let mut plaintext = vec![];
+        for (seq, record) in body.chunks(record_size + TAG_LEN).enumerate() {
+            let record = decrypt_record(&key, &nonce, seq, record)?;
+            // Records start with the length of the padding which follows
+            let padding = match record.as_slice() {
+                [high, low, ..] => u16::from_be_bytes([*high, *low]) as usize,
+                _ => return Err(Error::WebPush("truncated record")),
+            };
+            let data = record
+                .get(2 + padding..)
+                .ok_or(Error::WebPush("invalid record padding"))?;
+            plaintext.extend_from_slice(data);
+        }
+        Ok(plaintext)
+    }
+}
+
+/// Derive `N` bytes of key material with HKDF-SHA256.
+fn hkdf<const N: usize>(salt: &[u8], ikm: &[u8], info: &[&[u8]]) -> [u8; N] {
+    let mut okm = [0; N];
+    Hkdf::<Sha256>::new(Some(salt), ikm)
+        .expand_multi_info(info, &mut okm)
+        .expect("N is a valid HKDF-SHA256 output length");
+    okm
+}
+
+fn decrypt_record(key: &[u8; 16], nonce: &[u8; 12], seq: usize, record: &[u8]) -> Result<Vec<u8>> {
+    // Each record's nonce is the base nonce XORed with its sequence number
+    let mut nonce = *nonce;
+    for (n, s) in nonce[4..].iter_mut().zip((seq as u64).to_be_bytes()) {
+        *n ^= s;
+    }
+    Aes128Gcm::new(key.into())
+        .decrypt(Nonce::from_slice(&nonce), record)
+        .map_err(|_| Error::WebPush("decryption failed, the keys or message are wrong"))
+}
+
+fn decode(value: &str) -> Result<Vec<u8>> {
+    URL_SAFE_NO_PAD
+        .decode(value.trim().trim_end_matches('='))
+        .map_err(|_| Error::WebPush("invalid base64"))
+}
+
+/// The value of the parameter `name` in a header like `Crypto-Key`, e.g.
+/// `dh=...;p256ecdsa=...`.
+fn param<'a>(header: &'a str, name: &str) -> Option<&'a str> {
+    header
+        .split([',', ';'])
+        .filter_map(|param| param.split_once('='))
+        .find(|(key, _)| key.trim().eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.trim().trim_matches('"'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{notification::Type, NotificationId};
+    use reqwest::header::HeaderValue;
+
+    // The user agent's keys from RFC 8291, section 5
+    const PRIVATE_KEY: &str = "q1dXpw3UpT5VOmu_cf_v6ih07Aems3njxI-JWgLcM94";
+    const PUBLIC_KEY: &str =
+        "BCVxsr7N_eNgVRqvHtD0zTZsEc6-VV-JvLexhqUzORcxaOzi6-AYWXvTBHm4bjyPjs7Vd8pZGH6SRpkNtoIAiw4";
+    const AUTH: &str = "BTBZMqHH6r4Tts7J_aSIgg";
+
+    fn headers(headers: &[(&'static str, &'static str)]) -> HeaderMap {
+        headers
+            .iter()
+            .map(|(name, value)| (*name, HeaderValue::from_static(value)))
+            .map(|(name, value)| (reqwest::header::HeaderName::from_static(name), value))
+            .collect()
+    }
+
+    #[test]
+    fn test_keys() {
+        let keys = PushKeys::from_base64(PRIVATE_KEY, AUTH).unwrap();
+        assert_eq!(keys.public_key(), PUBLIC_KEY);
+        assert_eq!(keys.private_key(), PRIVATE_KEY);
+        assert_eq!(keys.auth(), AUTH);
+        assert_eq!(keys.keys(), Keys::new(PUBLIC_KEY, AUTH));
+
+        let generated = PushKeys::generate().unwrap();
+        let restored = PushKeys::from_base64(&generated.private_key(), &generated.auth()).unwrap();
+        assert_eq!(restored.public_key(), generated.public_key());
+        assert_ne!(generated.public_key(), PUBLIC_KEY);
+    }
+
+    #[test]
+    fn test_decrypt_aes128gcm() {
+        // The example message from RFC 8291, section 5
+        let body = decode(
+            "DGv6ra1nlYgDCS1FRnbzlwAAEABBBP4z9KsN6nGRTbVYI_c7VJSPQTBtkgcy27mlmlMoZIIgDll6e3vCYLoc\
+             InmYWAmS6TlzAC8wEqKK6PBru3jl7A_yl95bQpu6cVPTpK4Mqgkf1CXztLVBSt2Ks3oZwbuwXPXLWyouBWLV\
+             WGNWQexSgSxsj_Qulcy4a-fN",
+        )
+        .unwrap();
+        let keys = PushKeys::from_base64(PRIVATE_KEY, AUTH).unwrap();
+        let plaintext = keys
+            .decrypt(&headers(&[("content-encoding", "aes128gcm")]), &body)
+            .unwrap();
+        assert_eq!(plaintext, b"When I grow up, I want to be a watermelon");
+
+        let other = PushKeys::generate().unwrap();
+        assert!(other
+            .decrypt(&headers(&[("content-encoding", "aes128gcm")]), &body)
+            .unwrap_err()
+            .is_web_push());
+    }
+
+    #[test]
+    fn test_decrypt_aesgcm_notification() {
+        let headers = headers(&[
+            ("content-encoding", "aesgcm"),
+            (
+                "crypto-key",
+                "dh=BP4z9KsN6nGRTbVYI_c7VJSPQTBtkgcy27mlmlMoZIIgDll6e3vCYLocInmYWAmS6TlzAC8wEqKK6PBru3jl7A8;\
+                 p256ecdsa=BDd3_hVL9fZi9Ybo2UUzA284WG5FZR30_95YeZJsiApwXKpNcF1rRPF3foIiBHXRdJI2Qhumhf6_LFTeZaNndIo",
+            ),
+            ("encryption", "salt=DGv6ra1nlYgDCS1FRnbzlw"),
+        ]);
+        let body = decode(
+            "4qwiZl2y2_DnqdBb6bbRha5jO0dPh0GL7ANfs9BS3WoRfT8XVKOzopnhVGF_pCSMfVNXgCJTYwu4PIry9DIG\
+             OEwee5dcGjIh_duEvsx_je8CNNXJt9bm3QojyASnmfwSfB402pbgtuXAzZES0N4peI-ted4zif7sb1VRvRYm\
+             EXSywGz6XLYmWrAYNin1PB5egwy1TafbzBuzhe2abkGZpXMCXRG-nS386Fe3iK4FLJpMsiJamJ3ZQ9lBr--M\
+             jRix0AoV_vpS_dzSB0uyCTqFjiDtqpLhlwsemTBA8rgKpphRD7H5oD71Itpp7178B6TZrnR9qPCWCtqkorN1\
+             i9zrATrAWxmJFelcog0jsEIM",
+        )
+        .unwrap();
+        let keys = PushKeys::from_base64(PRIVATE_KEY, AUTH).unwrap();
+        let notification = keys.decrypt_notification(&headers, &body).unwrap();
+        assert_eq!(notification.notification_id, NotificationId::new("12"));
+        assert_eq!(notification.notification_type, Type::Mention);
+        assert_eq!(notification.title, "Alice mentioned you");
+        assert_eq!(notification.body, "@bob hello");
+    }
+
+    #[test]
+    fn test_param() {
+        let header = r#"keyid="p256dh";dh=abc, p256ecdsa=def"#;
+        assert_eq!(param(header, "dh"), Some("abc"));
+        assert_eq!(param(header, "keyid"), Some("p256dh"));
+        assert_eq!(param(header, "p256ecdsa"), Some("def"));
+        assert_eq!(param(header, "salt"), None);
+    }
+}
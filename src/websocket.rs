@@ -0,0 +1,301 @@
+use std::sync::Arc;
+
+pub use crate::streaming::Channel;
+use crate::{
+    entities::event::Event,
+    errors::{ApiError, Result},
+    event_stream::make_event,
+    Error, Mastodon,
+};
+use futures::{
+    lock::Mutex,
+    stream::{unfold, SplitSink, SplitStream},
+    SinkExt, StreamExt, TryStream,
+};
+use log::{debug, info, trace, warn};
+use mastodon_async_entities::ListId;
+use reqwest::{
+    header::{HeaderValue, AUTHORIZATION},
+    StatusCode,
+};
+use tokio::net::TcpStream;
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{client::IntoClientRequest, Message},
+    MaybeTlsStream, WebSocketStream,
+};
+use url::Url;
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+impl Channel {
+    /// Parse the `stream` field of a message received from the server, which
+    /// is the stream name followed by the tag or list ID, if any.
+    fn from_message(stream: &[String]) -> Option<Self> {
+        let (name, param) = match stream {
+            [name] => (name.as_str(), None),
+            [name, param] => (name.as_str(), Some(param.clone())),
+            _ => return None,
+        };
+        Some(match (name, param) {
+            ("user", None) => Channel::User,
+            ("user:notification", None) => Channel::UserNotification,
+            ("public", None) => Channel::Public,
+            ("public:media", None) => Channel::PublicMedia,
+            ("public:local", None) => Channel::PublicLocal,
+            ("public:local:media", None) => Channel::PublicLocalMedia,
+            ("public:remote", None) => Channel::PublicRemote,
+            ("public:remote:media", None) => Channel::PublicRemoteMedia,
+            ("hashtag", Some(tag)) => Channel::Hashtag(tag),
+            ("hashtag:local", Some(tag)) => Channel::HashtagLocal(tag),
+            ("list", Some(list)) => Channel::List(ListId::new(list)),
+            ("direct", None) => Channel::Direct,
+            _ => return None,
+        })
+    }
+
+    /// The frame to send to (un)subscribe from this channel.
+    fn frame(&self, kind: &str) -> String {
+        let mut frame = json!({ "type": kind, "stream": self.name() });
+        match self {
+            Channel::Hashtag(tag) | Channel::HashtagLocal(tag) => {
+                frame["tag"] = tag.trim_start_matches('#').into();
+            }
+            Channel::List(list) => {
+                frame["list"] = list.as_ref().into();
+            }
+            _ => {}
+        }
+        frame.to_string()
+    }
+}
+
+/// A handle for changing which channels a multiplexed WebSocket connection
+/// is subscribed to while its events are being consumed.
+///
+/// Cloning the handle is cheap; all clones control the same connection.
+#[derive(Debug, Clone)]
+pub struct Subscriptions {
+    sink: Arc<Mutex<SplitSink<Socket, Message>>>,
+}
+
+impl Subscriptions {
+    /// Start receiving events from `channel`.
+    pub async fn subscribe(&self, channel: &Channel) -> Result<()> {
+        self.send(channel, "subscribe").await
+    }
+
+    /// Stop receiving events from `channel`.
+    pub async fn unsubscribe(&self, channel: &Channel) -> Result<()> {
+        self.send(channel, "unsubscribe").await
+    }
+
+    /// Close the connection. The event stream ends once the server
+    /// acknowledges the close.
+    pub async fn close(&self) -> Result<()> {
+        self.sink.lock().await.close().await?;
+        Ok(())
+    }
+
+    async fn send(&self, channel: &Channel, kind: &str) -> Result<()> {
+        let frame = channel.frame(kind);
+        debug!(frame = frame; "sending websocket frame");
+        self.sink.lock().await.send(Message::Text(frame)).await?;
+        Ok(())
+    }
+}
+
+/// Open a single WebSocket connection to the streaming API.
+///
+/// Returns a [`Subscriptions`] handle for subscribing to channels, and a
+/// stream of events tagged with the channel they were received on.
+pub(crate) async fn connect(
+    client: &Mastodon,
+) -> Result<(
+    Subscriptions,
+    impl TryStream<Ok = (Channel, Event), Error = Error>,
+)> {
+    let advertised = match client.capabilities().await {
+        Ok(capabilities) => capabilities.streaming_url().cloned(),
+        Err(err) => {
+            debug!(err:? = err; "couldn't look up the streaming API's URL");
+            None
+        }
+    };
+    let url = streaming_url(&client.data.base, advertised)?;
+    let mut request = url.as_str().into_client_request()?;
+    client.add_default_headers(request.headers_mut());
+    if !client.data.token.is_empty() {
+        let auth = HeaderValue::from_str(&format!("Bearer {}", client.data.token))
+            .map_err(|err| Error::Other(format!("invalid access token: {err}")))?;
+        request.headers_mut().insert(AUTHORIZATION, auth);
+    }
+    let (socket, response) = connect_async(request).await?;
+    debug!(url = url.as_str(), status = response.status().as_u16(); "websocket connected");
+    let (sink, stream) = socket.split();
+    let subscriptions = Subscriptions {
+        sink: Arc::new(Mutex::new(sink)),
+    };
+    Ok((subscriptions, events(stream)))
+}
+
+fn events(stream: SplitStream<Socket>) -> impl TryStream<Ok = (Channel, Event), Error = Error> {
+    // Errors reported by the server in a message, e.g. for a subscription
+    // which was refused, are passed on without closing the stream; only
+    // errors of the connection itself end it.
+    unfold(Some(stream), |stream| async move {
+        let mut stream = stream?;
+        while let Some(message) = stream.next().await {
+            let text = match message {
+                Ok(Message::Text(text)) => text,
+                Ok(Message::Close(frame)) => {
+                    debug!(frame:? = frame; "websocket closed by server");
+                    return None;
+                }
+                Ok(_) => continue,
+                Err(err) => return Some((Err(err.into()), None)),
+            };
+            trace!(message = text; "received websocket message");
+            match parse_message(&text) {
+                Some(Ok(item)) => {
+                    info!(event:serde = item.1, channel = item.0.to_string(); "received event");
+                    return Some((Ok(item), Some(stream)));
+                }
+                Some(Err(err)) => return Some((Err(err), Some(stream))),
+                None => continue,
+            }
+        }
+        None
+    })
+}
+
+/// Parse a message received from the server into the event it carries,
+/// or the error it reports. Messages which aren't understood are ignored.
+fn parse_message(text: &str) -> Option<Result<(Channel, Event)>> {
+    #[derive(Deserialize)]
+    struct Message {
+        #[serde(default)]
+        stream: Vec<String>,
+        #[serde(default)]
+        error: Option<String>,
+        #[serde(default)]
+        status: Option<u16>,
+    }
+    let message = match serde_json::from_str::<Message>(text) {
+        Ok(message) => message,
+        Err(err) => {
+            debug!(err:? = err, message = text; "ignoring unparseable websocket message");
+            return None;
+        }
+    };
+    if let Some(error) = message.error {
+        warn!(error = error, status = message.status; "error from the streaming API");
+        let status = message
+            .status
+            .and_then(|status| StatusCode::from_u16(status).ok())
+            .unwrap_or(StatusCode::BAD_REQUEST);
+        let response = ApiError {
+            error,
+            error_description: None,
+            details: Default::default(),
+        };
+        return Some(Err(Error::Api { status, response }));
+    }
+    let Some(channel) = Channel::from_message(&message.stream) else {
+        debug!(stream:? = message.stream; "ignoring message from unknown stream");
+        return None;
+    };
+    match make_event(&[text.to_string()]) {
+        Ok(event) => Some(Ok((channel, event))),
+        Err(err) => {
+            debug!(err:? = err, message = text; "ignoring unrecognized event");
+            None
+        }
+    }
+}
+
+/// The WebSocket URL of the streaming API: the one `advertised` by the
+/// instance if any, and otherwise the one on the instance at `base`.
+fn streaming_url(base: &str, advertised: Option<Url>) -> Result<Url> {
+    let mut url = match advertised {
+        Some(url) => url,
+        None => base.parse()?,
+    };
+    let scheme = match url.scheme() {
+        "http" | "ws" => "ws",
+        _ => "wss",
+    };
+    url.set_scheme(scheme)
+        .map_err(|_| Error::Other(format!("can't stream from {url}")))?;
+    let path = format!("{}/api/v1/streaming", url.path().trim_end_matches('/'));
+    url.set_path(&path);
+    Ok(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_streaming_url() {
+        let url = streaming_url("https://example.com", None).expect("url");
+        assert_eq!(url.as_str(), "wss://example.com/api/v1/streaming");
+        let url = streaming_url("http://localhost:3000/", None).expect("url");
+        assert_eq!(url.as_str(), "ws://localhost:3000/api/v1/streaming");
+        let url = streaming_url("https://example.com/mastodon", None).expect("url");
+        assert_eq!(url.as_str(), "wss://example.com/mastodon/api/v1/streaming");
+        let advertised = "wss://streaming.example.com".parse().ok();
+        let url = streaming_url("https://example.com", advertised).expect("url");
+        assert_eq!(url.as_str(), "wss://streaming.example.com/api/v1/streaming");
+    }
+
+    #[test]
+    fn test_subscribe_frames() {
+        let frame: serde_json::Value =
+            serde_json::from_str(&Channel::PublicLocalMedia.frame("subscribe")).unwrap();
+        assert_eq!(
+            frame,
+            json!({"type": "subscribe", "stream": "public:local:media"})
+        );
+        let frame: serde_json::Value =
+            serde_json::from_str(&Channel::Hashtag("#bots".into()).frame("unsubscribe")).unwrap();
+        assert_eq!(
+            frame,
+            json!({"type": "unsubscribe", "stream": "hashtag", "tag": "bots"})
+        );
+        let frame: serde_json::Value =
+            serde_json::from_str(&Channel::List(ListId::new("12345")).frame("subscribe")).unwrap();
+        assert_eq!(
+            frame,
+            json!({"type": "subscribe", "stream": "list", "list": "12345"})
+        );
+    }
+
+    #[test]
+    fn test_parse_message() {
+        let (channel, event) = parse_message(
+            r#"{"stream":["hashtag","bots"],"event":"delete","payload":"103270115826048975"}"#,
+        )
+        .expect("message")
+        .expect("event");
+        assert_eq!(channel, Channel::Hashtag("bots".into()));
+        let Event::Delete(id) = event else {
+            panic!("expected a delete event, got {event:?}")
+        };
+        assert_eq!(id, "103270115826048975");
+        let (channel, event) = parse_message(r#"{"stream":["user"],"event":"filters_changed"}"#)
+            .expect("message")
+            .expect("event");
+        assert_eq!(channel, Channel::User);
+        assert!(event.is_filters_changed());
+        assert!(parse_message(r#"{"stream":["user"],"event":"unknown"}"#).is_none());
+        let err = parse_message(r#"{"error":"Missing access token","status":401}"#)
+            .expect("message")
+            .expect_err("error");
+        let Error::Api { status, response } = err else {
+            panic!("expected an API error, got {err:?}")
+        };
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(response.error, "Missing access token");
+    }
+}